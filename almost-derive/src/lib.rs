@@ -0,0 +1,180 @@
+//! `#[derive(AlmostEqual)]`, for the `almost` crate. Don't use this directly,
+//! enable `almost`'s `derive` feature instead, which re-exports it.
+
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Index, Member, Type};
+
+/// Derives `almost::AlmostEqual` for a struct, comparing it field by field.
+/// See the documentation of the re-export in `almost`.
+#[proc_macro_derive(AlmostEqual, attributes(almost))]
+pub fn derive_almost_equal(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A field which is compared.
+struct Field {
+    member: Member,
+    ty: Type,
+    tolerance: Option<Expr>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(s) => fields(&s.fields)?,
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`AlmostEqual` can only be derived for structs",
+            ))
+        }
+    };
+    let first = match fields.first() {
+        Some(f) => f.ty.clone(),
+        None => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "deriving `AlmostEqual` needs at least one field which isn't skipped",
+            ))
+        }
+    };
+    let float = quote!(<#first as ::almost::AlmostEqual>::Float);
+
+    // Bounds are only needed for fields whose types depend on the generic
+    // parameters, and bounding a concrete type's `Float` by itself overflows.
+    let params: Vec<_> = input.generics.type_params().map(|p| &p.ident).collect();
+    let generic = |ty: &Type| mentions(quote!(#ty), &params);
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    if generic(&first) {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#first: ::almost::AlmostEqual));
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#float: ::core::marker::Copy));
+    }
+    let first_tokens = quote!(#first).to_string();
+    for ty in fields.iter().map(|f| &f.ty) {
+        if quote!(#ty).to_string() != first_tokens && generic(ty) {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#ty: ::almost::AlmostEqual<Float = #float>));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let equals = fields.iter().map(|f| {
+        let member = &f.member;
+        let tol = match &f.tolerance {
+            Some(t) => quote!(#t),
+            None => quote!(tol),
+        };
+        quote!(::almost::AlmostEqual::almost_equals_with(self.#member, rhs.#member, #tol))
+    });
+    // Fields without a tolerance of their own compare with their default.
+    let default_equals = fields.iter().map(|f| {
+        let member = &f.member;
+        match &f.tolerance {
+            Some(t) => {
+                quote!(::almost::AlmostEqual::almost_equals_with(self.#member, rhs.#member, #t))
+            }
+            None => quote!(::almost::AlmostEqual::almost_equals(self.#member, rhs.#member)),
+        }
+    });
+    let zeros = fields.iter().map(|f| {
+        let member = &f.member;
+        quote!(::almost::AlmostEqual::almost_zero_with(self.#member, tol))
+    });
+    let default_zeros = fields.iter().map(|f| {
+        let member = &f.member;
+        quote!(::almost::AlmostEqual::almost_zero(self.#member))
+    });
+    let name = &input.ident;
+    Ok(quote! {
+        impl #impl_generics ::almost::AlmostEqual for #name #ty_generics #where_clause {
+            type Float = #float;
+
+            const MACHINE_EPSILON: Self::Float = <#first as ::almost::AlmostEqual>::MACHINE_EPSILON;
+
+            const DEFAULT_TOLERANCE: Self::Float = <#first as ::almost::AlmostEqual>::DEFAULT_TOLERANCE;
+
+            #[inline]
+            fn almost_equals(self, rhs: Self) -> bool {
+                true #(&& #default_equals)*
+            }
+
+            #[inline]
+            fn almost_zero(self) -> bool {
+                true #(&& #default_zeros)*
+            }
+
+            #[inline]
+            fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+                true #(&& #equals)*
+            }
+
+            #[inline]
+            fn almost_zero_with(self, tol: Self::Float) -> bool {
+                true #(&& #zeros)*
+            }
+        }
+    })
+}
+
+/// Returns true if `tokens` contain any of the identifiers in `idents`.
+fn mentions(tokens: TokenStream, idents: &[&Ident]) -> bool {
+    tokens.into_iter().any(|t| match t {
+        TokenTree::Ident(i) => idents.iter().any(|&p| *p == i),
+        TokenTree::Group(g) => mentions(g.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Returns the fields which aren't skipped, with their attributes.
+fn fields(fields: &Fields) -> syn::Result<Vec<Field>> {
+    let mut out = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index {
+                index: i as u32,
+                span: field.ty.span(),
+            }),
+        };
+        let mut skip = false;
+        let mut tolerance = None;
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("almost")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("tolerance") {
+                    tolerance = Some(meta.value()?.parse::<Expr>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip` or `tolerance = ...`"))
+                }
+            })?;
+        }
+        if skip {
+            if let Some(t) = tolerance {
+                return Err(syn::Error::new(
+                    t.span(),
+                    "a skipped field can't have a tolerance",
+                ));
+            }
+            continue;
+        }
+        out.push(Field {
+            member,
+            ty: field.ty.clone(),
+            tolerance,
+        });
+    }
+    Ok(out)
+}
@@ -0,0 +1,81 @@
+//! Measures the per-comparison cost of the default-tolerance paths in a tight
+//! loop, next to a hand-written relative comparison for reference.
+//!
+//! Run with `cargo bench`. There are no dependencies, so this just reports the
+//! fastest of several timed runs.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const N: usize = 4096;
+const RUNS: usize = 1000;
+
+/// Deterministic inputs: half of the pairs are almost equal, and the rest
+/// aren't, so that the branch predictor can't learn the answer.
+fn inputs() -> (Vec<f64>, Vec<f64>) {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    (0..N)
+        .map(|_| {
+            let a = (next() >> 11) as f64 / (1u64 << 53) as f64 * 100.0 - 50.0;
+            let b = if next() & 1 == 0 {
+                a * (1.0 + 1e-12)
+            } else {
+                a + 1.0
+            };
+            (a, b)
+        })
+        .unzip()
+}
+
+fn time(name: &str, lhs: &[f64], rhs: &[f64], f: impl Fn(f64, f64) -> bool) {
+    let mut best = Duration::MAX;
+    let mut count = 0;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        count = lhs
+            .iter()
+            .zip(rhs)
+            .map(|(&a, &b)| f(black_box(a), black_box(b)) as usize)
+            .sum();
+        best = best.min(start.elapsed());
+        black_box(count);
+    }
+    let per = best.as_secs_f64() * 1e9 / N as f64;
+    println!("{:<24} {:>6.2} ns/comparison ({} equal)", name, per, count);
+}
+
+/// Times comparing whole slices which are almost equal, so every pair is
+/// checked.
+fn time_slices(name: &str, lhs: &[f64], rhs: &[f64], f: impl Fn(&[f64], &[f64]) -> bool) {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        assert!(f(black_box(lhs), black_box(rhs)));
+        best = best.min(start.elapsed());
+    }
+    let per = best.as_secs_f64() * 1e9 / N as f64;
+    println!("{:<24} {:>6.2} ns/element", name, per);
+}
+
+fn main() {
+    let (lhs, rhs) = inputs();
+    time("almost::equal", &lhs, &rhs, almost::equal);
+    time("almost::equal_with", &lhs, &rhs, |a, b| {
+        almost::equal_with(a, b, almost::F64_TOLERANCE)
+    });
+    time("almost::zero", &lhs, &rhs, |a, b| almost::zero(a - b));
+    time("hand-written", &lhs, &rhs, |a, b| {
+        (a - b).abs() < almost::F64_TOLERANCE * a.abs().max(b.abs())
+    });
+    let close: Vec<f64> = lhs.iter().map(|a| a * (1.0 + 1e-12)).collect();
+    time_slices("almost::equal_slices", &lhs, &close, almost::equal_slices);
+    time_slices("almost::equal (slices)", &lhs, &close, |a, b| {
+        almost::equal(a, b)
+    });
+}
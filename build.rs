@@ -0,0 +1,29 @@
+// Enables the `portable-simd` feature's code only on a nightly compiler, so
+// that `--all-features` still builds on stable.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(almost_portable_simd)");
+    if env::var_os("CARGO_FEATURE_PORTABLE_SIMD").is_none() {
+        return;
+    }
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let nightly = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .map(|o| {
+            let version = String::from_utf8_lossy(&o.stdout);
+            version.contains("nightly") || version.contains("-dev")
+        })
+        .unwrap_or(false);
+    if nightly {
+        println!("cargo:rustc-cfg=almost_portable_simd");
+    } else {
+        println!(
+            "cargo:warning=the `portable-simd` feature requires a nightly compiler, and is ignored"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Comparisons and tolerant predicates for axis-aligned bounding boxes.
+
+use crate::{AlmostEqual, Float};
+
+/// An axis-aligned bounding box in `N` dimensions, given by its minimum and
+/// maximum corners.
+///
+/// The predicates in this crate assume that `min <= max` on every axis. A box
+/// may be flat (with `min == max` on some axes), and its coordinates may be
+/// infinite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb<T, const N: usize> {
+    /// The corner with the smallest coordinates.
+    pub min: [T; N],
+    /// The corner with the largest coordinates.
+    pub max: [T; N],
+}
+
+impl<T, const N: usize> Aabb<T, N> {
+    /// Creates a box from its minimum and maximum corners.
+    #[inline]
+    pub fn new(min: [T; N], max: [T; N]) -> Self {
+        Aabb { min, max }
+    }
+}
+
+/// The slack allowed by a comparison of `boxes` with the relative tolerance
+/// `tol`: `tol` times the largest finite coordinate of any of them.
+///
+/// Infinite coordinates are left out, as they'd make everything pass.
+#[inline]
+fn slack<T: Float, const N: usize>(boxes: &[&Aabb<T, N>], extra: &[T], tol: T) -> T {
+    let coords = boxes.iter().flat_map(|b| b.min.iter().chain(&b.max));
+    let scale = crate::linalg::max_magnitude(coords.chain(extra).filter(|v| v.is_finite()));
+    tol * scale
+}
+
+/// Returns true if `a <= b`, allowing `b` to be smaller by up to `slack`.
+#[inline]
+fn at_most<T: Float>(a: T, b: T, slack: T) -> bool {
+    a <= b || a - b <= slack
+}
+
+/// Returns true if the boxes `lhs` and `rhs` are almost equal, with the
+/// default tolerance.
+///
+/// ```
+/// use almost::Aabb;
+/// let a = Aabb::new([0.0, -1.0, 2.0], [0.1 + 0.2, 1.0, 4.0]);
+/// let b = Aabb::new([1e-18, -1.0, 2.0], [0.3, 1.0, 4.0]);
+/// assert!(almost::aabb_equal(&a, &b));
+/// assert!(!almost::aabb_equal(&a, &Aabb::new([0.0, -1.0, 2.0], [0.31, 1.0, 4.0])));
+/// ```
+///
+/// Each coordinate must be within the tolerance relative to the largest
+/// finite coordinate of either box, rather than to itself, so coordinates
+/// which are zero in one box and tiny in the other (which is common for faces
+/// on an axis) are compared sensibly. Infinite coordinates must be equal.
+/// Returns false if any coordinate is NaN.
+#[inline]
+pub fn aabb_equal<T: Float, const N: usize>(lhs: &Aabb<T, N>, rhs: &Aabb<T, N>) -> bool {
+    aabb_equal_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::aabb_equal`](aabb_equal), but uses the provided
+/// relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn aabb_equal_with<T: Float, const N: usize>(
+    lhs: &Aabb<T, N>,
+    rhs: &Aabb<T, N>,
+    tolerance: T,
+) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let slack = slack(&[lhs, rhs], &[], tolerance);
+    let near = |a: T, b: T| a == b || (a - b).abs() <= slack;
+    let mins = lhs.min.iter().zip(&rhs.min);
+    let maxes = lhs.max.iter().zip(&rhs.max);
+    mins.chain(maxes).all(|(&a, &b)| near(a, b))
+}
+
+/// Returns true if the box `outer` almost contains the box `inner`, allowing
+/// `inner` to extend past `outer` by up to the default tolerance.
+///
+/// ```
+/// use almost::Aabb;
+/// let parent = Aabb::new([0.0, 0.0], [0.3, 1.0]);
+/// // The union of the children, which rounded slightly differently.
+/// let child = Aabb::new([0.1, 0.0], [0.1 + 0.2, 0.5]);
+/// assert!(0.1 + 0.2 > 0.3);
+/// assert!(almost::aabb_contains(&parent, &child));
+/// assert!(!almost::aabb_contains(&child, &parent));
+/// ```
+///
+/// This is meant for checking the invariants of bounding volume hierarchies
+/// and the like, where a box computed as the union of others can't always
+/// exactly contain them. The tolerance is relative to the largest finite
+/// coordinate of either box, as in [`almost::aabb_equal`](aabb_equal).
+/// Returns false if any coordinate is NaN.
+#[inline]
+pub fn aabb_contains<T: Float, const N: usize>(outer: &Aabb<T, N>, inner: &Aabb<T, N>) -> bool {
+    aabb_contains_with(outer, inner, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::aabb_contains`](aabb_contains), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn aabb_contains_with<T: Float, const N: usize>(
+    outer: &Aabb<T, N>,
+    inner: &Aabb<T, N>,
+    tolerance: T,
+) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let slack = slack(&[outer, inner], &[], tolerance);
+    (0..N).all(|i| {
+        at_most(outer.min[i], inner.min[i], slack) && at_most(inner.max[i], outer.max[i], slack)
+    })
+}
+
+/// Returns true if the box `b` almost contains the point `p`, allowing `p` to
+/// be outside of it by up to the default tolerance.
+///
+/// ```
+/// use almost::Aabb;
+/// let b = Aabb::new([0.0, 0.0], [1.0, 0.3]);
+/// assert!(almost::aabb_contains_point(&b, &[0.5, 0.1 + 0.2]));
+/// assert!(!almost::aabb_contains_point(&b, &[0.5, 0.31]));
+/// ```
+///
+/// The tolerance is relative to the largest finite coordinate of the box or
+/// the point. Returns false if any coordinate is NaN.
+#[inline]
+pub fn aabb_contains_point<T: Float, const N: usize>(b: &Aabb<T, N>, p: &[T; N]) -> bool {
+    aabb_contains_point_with(b, p, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::aabb_contains_point`](aabb_contains_point), but
+/// uses the provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn aabb_contains_point_with<T: Float, const N: usize>(
+    b: &Aabb<T, N>,
+    p: &[T; N],
+    tolerance: T,
+) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let slack = slack(&[b], p, tolerance);
+    (0..N).all(|i| at_most(b.min[i], p[i], slack) && at_most(p[i], b.max[i], slack))
+}
+
+/// Returns true if the boxes `lhs` and `rhs` almost intersect, so that boxes
+/// which share a face (or would, but for rounding) are considered to
+/// intersect.
+///
+/// ```
+/// use almost::Aabb;
+/// let left = Aabb::new([0.0, 0.0], [0.1 + 0.2, 1.0]);
+/// let right = Aabb::new([0.3, 0.0], [1.0, 1.0]);
+/// assert!(almost::aabb_intersects(&left, &right));
+/// let apart = Aabb::new([0.31, 0.0], [1.0, 1.0]);
+/// assert!(!almost::aabb_intersects(&left, &apart));
+/// ```
+///
+/// The gap allowed between the boxes on each axis is within the tolerance
+/// relative to the largest finite coordinate of either box. For the opposite
+/// question, such as whether two boxes in a partition overlap by more than
+/// rounding error, use [`almost::aabb_overlap`](aabb_overlap). Returns false
+/// if any coordinate is NaN.
+#[inline]
+pub fn aabb_intersects<T: Float, const N: usize>(lhs: &Aabb<T, N>, rhs: &Aabb<T, N>) -> bool {
+    aabb_intersects_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::aabb_intersects`](aabb_intersects), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn aabb_intersects_with<T: Float, const N: usize>(
+    lhs: &Aabb<T, N>,
+    rhs: &Aabb<T, N>,
+    tolerance: T,
+) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let slack = slack(&[lhs, rhs], &[], tolerance);
+    (0..N).all(|i| at_most(lhs.min[i], rhs.max[i], slack) && at_most(rhs.min[i], lhs.max[i], slack))
+}
+
+/// Returns true if the boxes `lhs` and `rhs` overlap by more than the default
+/// tolerance on every axis, so that boxes which only share a face (up to
+/// rounding) don't overlap.
+///
+/// ```
+/// use almost::Aabb;
+/// let left = Aabb::new([0.0, 0.0], [0.1 + 0.2, 1.0]);
+/// let right = Aabb::new([0.3, 0.0], [1.0, 1.0]);
+/// assert!(!almost::aabb_overlap(&left, &right));
+/// let inside = Aabb::new([0.29, 0.5], [1.0, 1.0]);
+/// assert!(almost::aabb_overlap(&left, &inside));
+/// ```
+///
+/// This is the strict counterpart of [`almost::aabb_intersects`](aabb_intersects),
+/// for checking that the cells of a partition don't overlap, or for culling
+/// where touching boxes shouldn't count. The tolerance is relative to the
+/// largest finite coordinate of either box. Returns false if any coordinate is
+/// NaN.
+#[inline]
+pub fn aabb_overlap<T: Float, const N: usize>(lhs: &Aabb<T, N>, rhs: &Aabb<T, N>) -> bool {
+    aabb_overlap_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::aabb_overlap`](aabb_overlap), but uses the provided
+/// relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn aabb_overlap_with<T: Float, const N: usize>(
+    lhs: &Aabb<T, N>,
+    rhs: &Aabb<T, N>,
+    tolerance: T,
+) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let slack = slack(&[lhs, rhs], &[], tolerance);
+    (0..N).all(|i| rhs.max[i] - lhs.min[i] > slack && lhs.max[i] - rhs.min[i] > slack)
+}
+
+/// Boxes are almost equal as with [`almost::aabb_equal`](aabb_equal), and
+/// almost zero if every coordinate is.
+impl<T: Float, const N: usize> AlmostEqual for Aabb<T, N> {
+    type Float = T;
+
+    const MACHINE_EPSILON: T = T::MACHINE_EPSILON;
+
+    const DEFAULT_TOLERANCE: T = T::DEFAULT_TOLERANCE;
+
+    #[inline]
+    fn almost_equals(self, rhs: Self) -> bool {
+        self.almost_equals_with(rhs, crate::float::default_rel_tol())
+    }
+
+    #[inline]
+    fn almost_zero(self) -> bool {
+        self.almost_zero_with(crate::float::default_zero_tol())
+    }
+
+    #[inline]
+    fn almost_equals_with(self, rhs: Self, tol: T) -> bool {
+        aabb_equal_with(&self, &rhs, tol)
+    }
+
+    #[inline]
+    fn almost_zero_with(self, tol: T) -> bool {
+        self.min
+            .iter()
+            .chain(&self.max)
+            .all(|&v| v.almost_zero_with(tol))
+    }
+}
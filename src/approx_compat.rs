@@ -0,0 +1,144 @@
+//! Adapters between [`AlmostEqual`] and the traits of the `approx` crate. This
+//! requires the `approx-compat` feature.
+
+use crate::{AlmostEqual, Float};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+/// A float which implements `approx`'s `AbsDiffEq`, `RelativeEq` and `UlpsEq`
+/// with this crate's comparisons, so it can be passed to APIs which are
+/// generic over those traits.
+///
+/// ```
+/// use almost::Almost;
+/// approx::assert_relative_eq!(Almost(0.1 + 0.2), Almost(0.3));
+/// // Within the default zero tolerance, as with `almost::zero`.
+/// approx::assert_relative_eq!(Almost(1e-12), Almost(-1e-13));
+/// // The largest finite value is almost equal to infinity.
+/// approx::assert_relative_eq!(Almost(f64::MAX), Almost(f64::INFINITY));
+/// approx::assert_relative_ne!(Almost(1.0f32), Almost(1.01));
+/// ```
+///
+/// `relative_eq` is [`almost::equal_with_zero_tol`](crate::equal_with_zero_tol),
+/// with `max_relative` as the relative tolerance and `epsilon` as the zero
+/// tolerance, so values are equal if they're almost equal, or both almost
+/// zero. Their defaults are both the default tolerance for the type, as in
+/// [`almost::equal`](crate::equal) and [`almost::zero`](crate::zero).
+///
+/// `abs_diff_eq` checks that the difference is less than `epsilon`, and
+/// `ulps_eq` that the difference is less than `epsilon` or the values are at
+/// most `max_ulps` apart, as with [`almost::ulp_equal`](crate::ulp_equal).
+/// These treat equal infinities as equal, and NaN as unequal to everything.
+///
+/// Any tolerances `approx` accepts can be used: an `epsilon` of zero or less
+/// means only equal values are equal, and `max_relative` is clamped to be at
+/// least `T::EPSILON` and less than 1.0, rather than panicking in debug mode as
+/// [`almost::equal_with`](crate::equal_with) would.
+///
+/// The `PartialEq` impl required by `approx` is the exact comparison of the
+/// floats.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Almost<T>(pub T);
+
+impl<T: Float> AbsDiffEq for Almost<T> {
+    type Epsilon = T;
+
+    #[inline]
+    fn default_epsilon() -> T {
+        crate::float::default_zero_tol()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: T) -> bool {
+        let (a, b) = (self.0, other.0);
+        a == b || (epsilon > T::default() && (a - b).almost_zero_with(epsilon))
+    }
+}
+
+impl<T: Float> RelativeEq for Almost<T> {
+    #[inline]
+    fn default_max_relative() -> T {
+        crate::float::default_rel_tol()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: T, max_relative: T) -> bool {
+        let (a, b) = (self.0, other.0);
+        let max_relative = clamp_rel_tol(max_relative);
+        if epsilon > T::default() {
+            crate::equal_with_zero_tol(a, b, max_relative, epsilon)
+        } else {
+            a.almost_equals_with(b, max_relative)
+        }
+    }
+}
+
+/// Clamps `tol` to the range of relative tolerances this crate accepts.
+#[inline]
+fn clamp_rel_tol<T: Float>(tol: T) -> T {
+    let one = T::from_f64(1.0);
+    if tol < T::MACHINE_EPSILON {
+        T::MACHINE_EPSILON
+    } else if tol >= one {
+        one - T::MACHINE_EPSILON / T::from_f64(2.0)
+    } else {
+        tol
+    }
+}
+
+impl<T: Float> UlpsEq for Almost<T> {
+    /// The same as `approx` uses for floats.
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: T, max_ulps: u32) -> bool {
+        self.abs_diff_eq(other, epsilon) || self.0.ulp_equal(other.0, max_ulps.into())
+    }
+}
+
+/// A value which implements [`AlmostEqual`] using its `approx::RelativeEq`
+/// impl, so types from crates which only support `approx` can be compared with
+/// this crate's functions.
+///
+/// ```
+/// use almost::Approx;
+/// assert!(almost::equal(Approx(0.1 + 0.2), Approx(0.3)));
+/// assert!(!almost::equal(Approx(1.0f32), Approx(1.01)));
+/// assert!(almost::zero(Approx(1e-9f32)));
+/// ```
+///
+/// `almost_equals_with` is `relative_eq` with the tolerance as `max_relative`,
+/// and the type's `default_epsilon` as `epsilon`. `almost_zero_with` is
+/// `abs_diff_eq` against `T::default()` with the tolerance as `epsilon`.
+/// The default tolerance is the one of the `Epsilon` type, so for types made
+/// of `f64`s, it's [`F64_TOLERANCE`](crate::F64_TOLERANCE), rather than the
+/// `approx` default.
+///
+/// Note that this follows `approx`'s semantics, which differ from this
+/// crate's, for example at infinity, and in using `<=` rather than `<`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Approx<T>(pub T);
+
+impl<T> AlmostEqual for Approx<T>
+where
+    T: RelativeEq + Default,
+    T::Epsilon: AlmostEqual<Float = T::Epsilon>,
+{
+    type Float = T::Epsilon;
+
+    const MACHINE_EPSILON: T::Epsilon = <T::Epsilon as AlmostEqual>::MACHINE_EPSILON;
+
+    const DEFAULT_TOLERANCE: T::Epsilon = <T::Epsilon as AlmostEqual>::DEFAULT_TOLERANCE;
+
+    #[inline]
+    fn almost_equals_with(self, rhs: Self, tol: T::Epsilon) -> bool {
+        self.0.relative_eq(&rhs.0, T::default_epsilon(), tol)
+    }
+
+    #[inline]
+    fn almost_zero_with(self, tol: T::Epsilon) -> bool {
+        self.0.abs_diff_eq(&T::default(), tol)
+    }
+}
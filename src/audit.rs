@@ -0,0 +1,159 @@
+//! Instrumentation of comparisons, for finding bad tolerances. This requires
+//! the `audit` feature.
+//!
+//! When the feature is enabled, every comparison of `f32` or `f64` values
+//! records the relative error as a fraction of the tolerance used, grouped by
+//! the location of the call. This can then be used to find comparisons which
+//! pass by a tiny margin (and will start failing with the next compiler
+//! update), and ones which pass by a huge margin (and so would miss real
+//! problems).
+//!
+//! ```
+//! almost::audit::reset();
+//! for i in 1..10 {
+//!     assert!(almost::equal_with(i as f64 * 1.0001, i as f64, 1e-3));
+//! }
+//! let summary = almost::audit::summary();
+//! assert_eq!(summary.len(), 1);
+//! assert_eq!(summary[0].comparisons, 9);
+//! assert!(summary[0].largest_passing_ratio.unwrap() < 0.2);
+//! ```
+//!
+//! Locations are tracked through the functions at the crate root and the
+//! methods of [`AlmostEqual`](crate::AlmostEqual), but other APIs (such as the
+//! slice comparisons) are recorded at their location inside this crate.
+//!
+//! Recording takes a lock, so this should only be enabled while auditing a
+//! test suite, not in production. The `audit` feature requires `std`.
+
+use core::panic::Location;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::vec::Vec;
+
+#[derive(Clone, Copy)]
+struct Stats {
+    comparisons: u64,
+    failures: u64,
+    largest_passing_ratio: Option<f64>,
+    smallest_failing_ratio: Option<f64>,
+}
+
+static RECORDS: Mutex<BTreeMap<Location<'static>, Stats>> = Mutex::new(BTreeMap::new());
+
+/// The recorded comparisons from a single location.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AuditEntry {
+    /// Where the comparisons were made.
+    pub location: Location<'static>,
+    /// The number of comparisons.
+    pub comparisons: u64,
+    /// The number of comparisons which failed.
+    pub failures: u64,
+    /// The largest error of any passing comparison, as a fraction of the
+    /// tolerance (so it's less than 1.0). Values close to 1.0 suggest a
+    /// fragile test, and tiny values suggest the tolerance is too loose (unless
+    /// the comparisons were expected to be exact). `None` if nothing passed.
+    pub largest_passing_ratio: Option<f64>,
+    /// The smallest error of any failing comparison, as a fraction of the
+    /// tolerance (so it's usually at least 1.0, and NaN only if every failure
+    /// involved a NaN). Values close to 1.0 suggest the tolerance is slightly
+    /// too tight. `None` if nothing failed.
+    pub smallest_failing_ratio: Option<f64>,
+}
+
+#[inline(never)]
+pub(crate) fn record(location: &'static Location<'static>, ratio: f64, passed: bool) {
+    let mut records = RECORDS.lock().unwrap_or_else(|e| e.into_inner());
+    let stats = records.entry(*location).or_insert(Stats {
+        comparisons: 0,
+        failures: 0,
+        largest_passing_ratio: None,
+        smallest_failing_ratio: None,
+    });
+    stats.comparisons += 1;
+    if !passed {
+        stats.failures += 1;
+        if stats
+            .smallest_failing_ratio
+            .is_none_or(|r| r.is_nan() || ratio < r)
+        {
+            stats.smallest_failing_ratio = Some(ratio);
+        }
+    } else if stats.largest_passing_ratio.is_none_or(|r| ratio > r) {
+        stats.largest_passing_ratio = Some(ratio);
+    }
+}
+
+/// Returns the comparisons recorded so far, sorted by location.
+pub fn summary() -> Vec<AuditEntry> {
+    let records = RECORDS.lock().unwrap_or_else(|e| e.into_inner());
+    records
+        .iter()
+        .map(|(location, s)| AuditEntry {
+            location: *location,
+            comparisons: s.comparisons,
+            failures: s.failures,
+            largest_passing_ratio: s.largest_passing_ratio,
+            smallest_failing_ratio: s.smallest_failing_ratio,
+        })
+        .collect()
+}
+
+/// Discards the comparisons recorded so far.
+pub fn reset() {
+    RECORDS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Writes the [`summary`] to stderr, one line per location, noting locations
+/// whose tolerance looks too tight or too loose.
+pub fn print_summary() {
+    use std::io::Write;
+    let stderr = std::io::stderr();
+    let mut out = stderr.lock();
+    for e in summary() {
+        let _ = write!(
+            out,
+            "{}: {} comparisons, {} failed",
+            e.location, e.comparisons, e.failures
+        );
+        let _ = match e.largest_passing_ratio {
+            Some(r) if r > 0.5 => write!(out, ", closest pass {:.3}x tolerance (fragile?)", r),
+            Some(r) if r > 0.0 && r < 1e-4 => {
+                write!(out, ", closest pass {:.1e}x tolerance (too loose?)", r)
+            }
+            Some(r) => write!(out, ", closest pass {:.3}x tolerance", r),
+            None => Ok(()),
+        };
+        let _ = match e.smallest_failing_ratio {
+            Some(r) if r.is_nan() => writeln!(out, ", each failure involved NaN"),
+            Some(r) if r < 2.0 => {
+                writeln!(out, ", closest failure {:.3}x tolerance (too tight?)", r)
+            }
+            Some(r) => writeln!(out, ", closest failure {:.3}x tolerance", r),
+            None => writeln!(out),
+        };
+    }
+}
+
+/// Arranges for [`print_summary`] to be called when the process exits. Calling
+/// this more than once has no additional effect.
+///
+/// Note that test harnesses usually run each test file as its own process, so
+/// this will print one summary per test binary.
+pub fn print_summary_at_exit() {
+    extern "C" fn on_exit() {
+        print_summary();
+    }
+    extern "C" {
+        fn atexit(cb: extern "C" fn()) -> core::ffi::c_int;
+    }
+    static REGISTER: std::sync::Once = std::sync::Once::new();
+    REGISTER.call_once(|| {
+        // Safety: `atexit` is provided by the C runtime on every platform with
+        // `std`, and `on_exit` doesn't unwind.
+        unsafe {
+            atexit(on_exit);
+        }
+    });
+}
@@ -0,0 +1,220 @@
+//! Comparison of `bevy_math` and `bevy_transform` types, including transforms
+//! with separate tolerances for translation, rotation and scale. This requires
+//! the `bevy_math` feature.
+
+use crate::{AlmostEqual, Pose, PoseTolerance};
+use bevy_math::{DQuat, DVec2, DVec3, DVec4, Quat, Rect, Vec2, Vec3, Vec3A, Vec4};
+use bevy_transform::components::Transform;
+
+macro_rules! impl_bevy {
+    ($ty:ident, $fp:ident, $what:literal) => {
+        #[doc = concat!(
+            $what,
+            " are almost equal if the norm of their difference is almost zero relative to the",
+            " larger of their norms, as with [`almost::frobenius_equal`](crate::frobenius_equal),",
+            " so components which should be zero don't need to be exactly zero. They're almost",
+            " zero if every component is."
+        )]
+        impl AlmostEqual for $ty {
+            type Float = $fp;
+
+            const MACHINE_EPSILON: $fp = $fp::EPSILON;
+
+            const DEFAULT_TOLERANCE: $fp = <$fp as AlmostEqual>::DEFAULT_TOLERANCE;
+
+            #[inline]
+            fn almost_equals(self, rhs: Self) -> bool {
+                self.almost_equals_with(rhs, crate::float::default_rel_tol())
+            }
+
+            #[inline]
+            fn almost_zero(self) -> bool {
+                self.almost_zero_with(crate::float::default_zero_tol())
+            }
+
+            #[inline]
+            fn almost_equals_with(self, rhs: Self, tol: $fp) -> bool {
+                crate::frobenius_equal_with(&self.to_array(), &rhs.to_array(), tol)
+            }
+
+            #[inline]
+            fn almost_zero_with(self, tol: $fp) -> bool {
+                self.to_array().iter().all(|&v| v.almost_zero_with(tol))
+            }
+        }
+    };
+}
+
+impl_bevy!(Vec2, f32, "Vectors");
+impl_bevy!(Vec3, f32, "Vectors");
+impl_bevy!(Vec3A, f32, "Vectors");
+impl_bevy!(Vec4, f32, "Vectors");
+impl_bevy!(DVec2, f64, "Vectors");
+impl_bevy!(DVec3, f64, "Vectors");
+impl_bevy!(DVec4, f64, "Vectors");
+impl_bevy!(
+    Quat,
+    f32,
+    "Quaternions (compared componentwise, so `q` and `-q` differ even though \
+     they're the same rotation)"
+);
+impl_bevy!(
+    DQuat,
+    f64,
+    "Quaternions (compared componentwise, so `q` and `-q` differ even though \
+     they're the same rotation)"
+);
+
+/// Rectangles are almost equal as boxes are with
+/// [`almost::aabb_equal`](crate::aabb_equal), relative to the largest finite
+/// coordinate of either, and almost zero if every coordinate is.
+impl AlmostEqual for Rect {
+    type Float = f32;
+
+    const MACHINE_EPSILON: f32 = f32::EPSILON;
+
+    const DEFAULT_TOLERANCE: f32 = <f32 as AlmostEqual>::DEFAULT_TOLERANCE;
+
+    #[inline]
+    fn almost_equals(self, rhs: Self) -> bool {
+        self.almost_equals_with(rhs, crate::float::default_rel_tol())
+    }
+
+    #[inline]
+    fn almost_zero(self) -> bool {
+        self.almost_zero_with(crate::float::default_zero_tol())
+    }
+
+    #[inline]
+    fn almost_equals_with(self, rhs: Self, tol: f32) -> bool {
+        let aabb = |r: Rect| crate::Aabb::new(r.min.to_array(), r.max.to_array());
+        crate::aabb_equal_with(&aabb(self), &aabb(rhs), tol)
+    }
+
+    #[inline]
+    fn almost_zero_with(self, tol: f32) -> bool {
+        self.min.almost_zero_with(tol) && self.max.almost_zero_with(tol)
+    }
+}
+
+/// Transforms are almost equal if their translations, rotations and scales
+/// each are, as vectors, except that a rotation `q` is almost equal to `-q`.
+/// They're almost zero if every component is, which is rarely useful. Use
+/// [`almost::transform_equal`](transform_equal) to compare them with a
+/// tolerance for each part in its own units.
+impl AlmostEqual for Transform {
+    type Float = f32;
+
+    const MACHINE_EPSILON: f32 = f32::EPSILON;
+
+    const DEFAULT_TOLERANCE: f32 = <f32 as AlmostEqual>::DEFAULT_TOLERANCE;
+
+    #[inline]
+    fn almost_equals(self, rhs: Self) -> bool {
+        self.almost_equals_with(rhs, crate::float::default_rel_tol())
+    }
+
+    #[inline]
+    fn almost_zero(self) -> bool {
+        self.almost_zero_with(crate::float::default_zero_tol())
+    }
+
+    #[inline]
+    fn almost_equals_with(self, rhs: Self, tol: f32) -> bool {
+        let rotation = if self.rotation.dot(rhs.rotation) < 0.0 {
+            -rhs.rotation
+        } else {
+            rhs.rotation
+        };
+        self.translation.almost_equals_with(rhs.translation, tol)
+            && self.rotation.almost_equals_with(rotation, tol)
+            && self.scale.almost_equals_with(rhs.scale, tol)
+    }
+
+    #[inline]
+    fn almost_zero_with(self, tol: f32) -> bool {
+        self.translation.almost_zero_with(tol)
+            && self.rotation.almost_zero_with(tol)
+            && self.scale.almost_zero_with(tol)
+    }
+}
+
+/// Tolerances for [`almost::transform_equal`](transform_equal).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformTolerance {
+    /// The characteristic length of the scene, such as the size of the level
+    /// or the object. Translations are compared relative to this, as in
+    /// [`PoseTolerance`], rather than to their own magnitudes.
+    pub length: f32,
+    /// The largest distance allowed between the translations, as a fraction of
+    /// `length`.
+    pub translation: f32,
+    /// The largest angle allowed between the rotations, in radians.
+    pub angle: f32,
+    /// The relative tolerance for the scale on each axis.
+    pub scale: f32,
+}
+
+impl TransformTolerance {
+    /// Creates tolerances allowing the translations to differ by at most
+    /// `translation * length`, the rotations by at most `angle` radians, and
+    /// the scales by the relative tolerance `scale` on each axis.
+    #[inline]
+    pub fn new(length: f32, translation: f32, angle: f32, scale: f32) -> Self {
+        TransformTolerance {
+            length,
+            translation,
+            angle,
+            scale,
+        }
+    }
+}
+
+/// Returns true if the transforms `lhs` and `rhs` are almost equal: the
+/// distance between their translations and the angle between their rotations
+/// are within the tolerances, as in [`almost::pose_equal`](crate::pose_equal),
+/// and the scale on each axis is almost equal with the relative scale
+/// tolerance. This requires the `bevy_math` feature.
+///
+/// ```
+/// use bevy_math::{Quat, Vec3};
+/// use bevy_transform::components::Transform;
+/// use almost::TransformTolerance;
+/// let expected = Transform::from_xyz(10.0, 0.0, -2.0)
+///     .with_rotation(Quat::from_rotation_y(0.5))
+///     .with_scale(Vec3::splat(2.0));
+/// // After a few frames of integrating the same motion in smaller steps.
+/// let actual = Transform::from_xyz(10.002, 0.0, -2.0)
+///     .with_rotation(Quat::from_rotation_y(0.2) * Quat::from_rotation_y(0.3005))
+///     .with_scale(Vec3::new(2.0, 2.0, 2.001));
+/// // Within 1cm in a 100m level, 0.1°, and 0.1% of the scale.
+/// let tol = TransformTolerance::new(100.0, 1e-4, 0.1f32.to_radians(), 1e-3);
+/// assert!(almost::transform_equal(&actual, &expected, tol));
+/// assert!(!almost::transform_equal(&actual.with_scale(Vec3::ONE), &expected, tol));
+/// ```
+///
+/// These are in different units, so a single tolerance for the whole
+/// transform is either too loose for some parts or too strict for others. A
+/// rotation `q` is the same as `-q`. A scale which is exactly zero on some
+/// axis is only almost equal to another zero.
+///
+/// Returns false if either rotation is zero, or if anything in either
+/// transform is NaN or infinite.
+///
+/// # Panics
+/// This function panics in debug mode if `length` or `translation` aren't
+/// positive, if `angle` is very small or larger than `2π/3` radians (120°), or
+/// if `scale` is less than `f32::EPSILON` or greater than 1.0.
+pub fn transform_equal(lhs: &Transform, rhs: &Transform, tolerance: TransformTolerance) -> bool {
+    let pose_tolerance =
+        PoseTolerance::new(tolerance.length, tolerance.translation, tolerance.angle);
+    let pose = |t: &Transform| Pose::new(t.translation.to_array(), t.rotation.to_array());
+    let scale = crate::float::check_rel_tol(tolerance.scale);
+    crate::pose_equal(&pose(lhs), &pose(rhs), pose_tolerance)
+        && lhs
+            .scale
+            .to_array()
+            .iter()
+            .zip(&rhs.scale.to_array())
+            .all(|(&a, &b)| a.almost_equals_with(b, scale) && a.is_finite())
+}
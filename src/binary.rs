@@ -0,0 +1,440 @@
+//! Comparison of raw binary buffers of floats, such as golden files.
+
+use crate::report::{ElementwiseReport, ReportBuilder};
+use crate::Float;
+use core::fmt;
+
+/// The byte order of binary float data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+impl ByteOrder {
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: ByteOrder = ByteOrder::Little;
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: ByteOrder = ByteOrder::Big;
+}
+
+/// The reason binary data couldn't be compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryError {
+    /// The data contained a number of bytes which isn't a multiple of the size
+    /// of the element type.
+    Misaligned {
+        /// The number of bytes.
+        len: usize,
+        /// The size of the element type.
+        element_size: usize,
+    },
+    /// The buffer wasn't suitably aligned in memory to be reinterpreted as
+    /// elements of the requested type.
+    Unaligned {
+        /// The required alignment of the element type.
+        element_align: usize,
+    },
+    /// The inputs contained different numbers of elements.
+    LengthMismatch {
+        /// The number of elements on the left hand side.
+        lhs: usize,
+        /// The number of elements on the right hand side.
+        rhs: usize,
+    },
+}
+
+impl fmt::Display for BinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinaryError::Misaligned { len, element_size } => write!(
+                f,
+                "{} bytes is not a whole number of {} byte elements",
+                len, element_size
+            ),
+            BinaryError::Unaligned { element_align } => write!(
+                f,
+                "buffer is not aligned to {} bytes, as required by the element type",
+                element_align
+            ),
+            BinaryError::LengthMismatch { lhs, rhs } => {
+                write!(
+                    f,
+                    "inputs have different lengths ({} vs {} elements)",
+                    lhs, rhs
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for BinaryError {}
+
+#[inline]
+fn check_len<T: Float>(len: usize) -> Result<usize, BinaryError> {
+    if len % T::BYTES == 0 {
+        Ok(len / T::BYTES)
+    } else {
+        Err(BinaryError::Misaligned {
+            len,
+            element_size: T::BYTES,
+        })
+    }
+}
+
+/// Compares two buffers of encoded floats element-wise, without decoding them
+/// into an intermediate buffer.
+///
+/// ```
+/// let a: Vec<u8> = [1.0f64, 2.0, 3.0].iter().flat_map(|f| f.to_le_bytes()).collect();
+/// let b: Vec<u8> = [1.0f64, 2.0, 3.5].iter().flat_map(|f| f.to_le_bytes()).collect();
+/// let report = almost::compare_bytes::<f64>(&a, &b, almost::ByteOrder::Little).unwrap();
+/// assert_eq!(report.mismatches, 1);
+/// assert_eq!(report.first_mismatch.unwrap().index, 2);
+/// ```
+///
+/// The element type is given as a type parameter, and the number of elements
+/// is determined by the length of the buffers. This is intended for comparing
+/// large binary dumps: the buffers can be memory-mapped files (which
+/// dereference to `&[u8]`), so that neither needs to be read into memory up
+/// front. For reading files (or other streams) in chunks, see
+/// [`almost::compare_files`](crate::compare_files).
+///
+/// Returns an error if either buffer isn't a whole number of elements, or if
+/// the buffers contain different numbers of elements.
+#[inline]
+pub fn compare_bytes<T: Float>(
+    lhs: &[u8],
+    rhs: &[u8],
+    order: ByteOrder,
+) -> Result<ElementwiseReport<T, usize>, BinaryError> {
+    compare_bytes_with(lhs, rhs, order, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::compare_bytes`](compare_bytes), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn compare_bytes_with<T: Float>(
+    lhs: &[u8],
+    rhs: &[u8],
+    order: ByteOrder,
+    tolerance: T,
+) -> Result<ElementwiseReport<T, usize>, BinaryError> {
+    let (l, r) = (check_len::<T>(lhs.len())?, check_len::<T>(rhs.len())?);
+    if l != r {
+        return Err(BinaryError::LengthMismatch { lhs: l, rhs: r });
+    }
+    let mut builder = ReportBuilder::new();
+    accumulate(&mut builder, 0, lhs, rhs, order, tolerance, false);
+    Ok(builder.finish())
+}
+
+/// Adds the elements encoded in `lhs` and `rhs` (which must have the same
+/// length) to `builder`, starting at element index `start`. If `stop` is set,
+/// this returns early (with `true`) after the first mismatch.
+#[inline]
+pub(crate) fn accumulate<T: Float>(
+    builder: &mut ReportBuilder<T, usize>,
+    start: usize,
+    lhs: &[u8],
+    rhs: &[u8],
+    order: ByteOrder,
+    tolerance: T,
+    stop: bool,
+) -> bool {
+    let pairs = lhs.chunks_exact(T::BYTES).zip(rhs.chunks_exact(T::BYTES));
+    for (i, (a, b)) in pairs.enumerate() {
+        let (a, b) = (T::from_bytes(a, order), T::from_bytes(b, order));
+        if !builder.push(|| start + i, a, b, tolerance) && stop {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(feature = "bytemuck")]
+pub(crate) mod pod_impl {
+    use super::{check_len, BinaryError};
+    use crate::report::{ElementwiseReport, ReportBuilder};
+    use crate::Float;
+    use bytemuck::{Pod, PodCastError};
+
+    /// Reinterprets a buffer of native-endian floats as a slice, without
+    /// copying. This requires the `bytemuck` feature.
+    ///
+    /// ```
+    /// let floats = [1.0f32, 2.0, 3.0];
+    /// let bytes: &[u8] = bytemuck::cast_slice(&floats);
+    /// assert_eq!(almost::cast_floats::<f32>(bytes), Ok(&floats[..]));
+    /// assert!(almost::cast_floats::<f32>(&bytes[..5]).is_err());
+    /// ```
+    ///
+    /// Returns an error if the buffer isn't a whole number of elements, or its
+    /// address isn't aligned to the element type. Buffers from FFI or from
+    /// a memory-mapped file are usually aligned, but a buffer containing a file
+    /// header followed by its payload may not be. In that case, use
+    /// [`almost::compare_bytes`](crate::compare_bytes) with
+    /// [`ByteOrder::NATIVE`](crate::ByteOrder::NATIVE), which has no alignment
+    /// requirement.
+    #[inline]
+    pub fn cast_floats<T: Float + Pod>(bytes: &[u8]) -> Result<&[T], BinaryError> {
+        check_len::<T>(bytes.len())?;
+        bytemuck::try_cast_slice(bytes).map_err(|e| match e {
+            PodCastError::TargetAlignmentGreaterAndInputNotAligned => BinaryError::Unaligned {
+                element_align: core::mem::align_of::<T>(),
+            },
+            // Anything else would be a bug in the length check.
+            _ => unreachable!("unexpected cast error: {:?}", e),
+        })
+    }
+
+    /// Reinterprets two buffers of native-endian floats with
+    /// [`almost::cast_floats`](cast_floats), and compares them element-wise.
+    /// This requires the `bytemuck` feature.
+    ///
+    /// ```
+    /// let a = [1.0f64, 2.0, 3.0];
+    /// let b = [1.0f64, 2.0, 3.5];
+    /// let (a, b): (&[u8], &[u8]) = (bytemuck::cast_slice(&a), bytemuck::cast_slice(&b));
+    /// let report = almost::compare_native_bytes::<f64>(a, b).unwrap();
+    /// assert_eq!(report.mismatches, 1);
+    /// ```
+    ///
+    /// Returns an error if either buffer can't be reinterpreted, or if the
+    /// buffers contain different numbers of elements.
+    #[inline]
+    pub fn compare_native_bytes<T: Float + Pod>(
+        lhs: &[u8],
+        rhs: &[u8],
+    ) -> Result<ElementwiseReport<T, usize>, BinaryError> {
+        compare_native_bytes_with(lhs, rhs, crate::float::default_rel_tol())
+    }
+
+    /// Equivalent to [`almost::compare_native_bytes`](compare_native_bytes),
+    /// but uses the provided relative tolerance.
+    ///
+    /// # Panics
+    /// This function panics in debug mode if `tolerance` is less than
+    /// `T::EPSILON` or greater than 1.0, as with
+    /// [`almost::equal_with`](crate::equal_with).
+    pub fn compare_native_bytes_with<T: Float + Pod>(
+        lhs: &[u8],
+        rhs: &[u8],
+        tolerance: T,
+    ) -> Result<ElementwiseReport<T, usize>, BinaryError> {
+        let (l, r) = (cast_floats::<T>(lhs)?, cast_floats::<T>(rhs)?);
+        if l.len() != r.len() {
+            return Err(BinaryError::LengthMismatch {
+                lhs: l.len(),
+                rhs: r.len(),
+            });
+        }
+        let mut builder = ReportBuilder::new();
+        for (i, (&a, &b)) in l.iter().zip(r).enumerate() {
+            builder.push(|| i, a, b, tolerance);
+        }
+        Ok(builder.finish())
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) mod io_impl {
+    use super::{accumulate, check_len, BinaryError, ByteOrder};
+    use crate::report::{ElementDiff, ElementwiseReport, ReportBuilder};
+    use crate::Float;
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::path::Path;
+
+    const CHUNK: usize = 16 * 1024;
+
+    /// Reads into `buf` until it's full or the reader reaches EOF.
+    fn fill<R: Read>(r: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match r.read(&mut buf[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(filled)
+    }
+
+    fn invalid(e: BinaryError) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, e)
+    }
+
+    /// Compares two streams chunk by chunk. If `stop` is set, this returns
+    /// after the first mismatch.
+    pub(crate) fn compare_readers<T: Float, A: Read, B: Read>(
+        mut lhs: A,
+        mut rhs: B,
+        order: ByteOrder,
+        tolerance: T,
+        stop: bool,
+    ) -> io::Result<ElementwiseReport<T, usize>> {
+        let mut builder = ReportBuilder::new();
+        let (mut buf_l, mut buf_r) = ([0u8; CHUNK], [0u8; CHUNK]);
+        let mut done = 0;
+        loop {
+            let (nl, nr) = (fill(&mut lhs, &mut buf_l)?, fill(&mut rhs, &mut buf_r)?);
+            let n = nl.min(nr);
+            let (l, r) = (&buf_l[..n], &buf_r[..n]);
+            if accumulate(&mut builder, done, l, r, order, tolerance, stop) {
+                return Ok(builder.finish());
+            }
+            if nl != nr {
+                // Find out how long the rest of the longer input is, to give
+                // a useful error.
+                let (mut bytes_l, mut bytes_r) = (done * T::BYTES + nl, done * T::BYTES + nr);
+                if nl > nr {
+                    bytes_l += io::copy(&mut lhs, &mut io::sink())? as usize;
+                } else {
+                    bytes_r += io::copy(&mut rhs, &mut io::sink())? as usize;
+                }
+                let l = check_len::<T>(bytes_l).map_err(invalid)?;
+                let r = check_len::<T>(bytes_r).map_err(invalid)?;
+                return Err(invalid(BinaryError::LengthMismatch { lhs: l, rhs: r }));
+            }
+            if n < CHUNK {
+                check_len::<T>(done * T::BYTES + n).map_err(invalid)?;
+                return Ok(builder.finish());
+            }
+            done += n / T::BYTES;
+        }
+    }
+
+    /// Compares two binary files of encoded floats element-wise, reading them in
+    /// fixed-size chunks so that neither is ever fully loaded into memory. This
+    /// requires the `std` feature.
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// let report = almost::compare_files::<f32, _, _>(
+    ///     "output.bin",
+    ///     "golden.bin",
+    ///     almost::ByteOrder::Little,
+    /// )?;
+    /// assert!(report.passed(), "{}", report);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// This behaves the same as [`almost::compare_bytes`](crate::compare_bytes),
+    /// except that errors (including the data being misaligned, or having
+    /// different lengths) are reported as `io::Error`s. For these, the kind
+    /// will be `InvalidData`, and the inner error a [`BinaryError`].
+    #[inline]
+    pub fn compare_files<T: Float, P: AsRef<Path>, Q: AsRef<Path>>(
+        lhs: P,
+        rhs: Q,
+        order: ByteOrder,
+    ) -> io::Result<ElementwiseReport<T, usize>> {
+        compare_files_with(lhs, rhs, order, crate::float::default_rel_tol())
+    }
+
+    /// Equivalent to [`almost::compare_files`](compare_files), but uses the
+    /// provided relative tolerance.
+    ///
+    /// # Panics
+    /// This function panics in debug mode if `tolerance` is less than
+    /// `T::EPSILON` or greater than 1.0, as with
+    /// [`almost::equal_with`](crate::equal_with).
+    pub fn compare_files_with<T: Float, P: AsRef<Path>, Q: AsRef<Path>>(
+        lhs: P,
+        rhs: Q,
+        order: ByteOrder,
+        tolerance: T,
+    ) -> io::Result<ElementwiseReport<T, usize>> {
+        let (l, r) = (File::open(lhs)?, File::open(rhs)?);
+        compare_readers(l, r, order, tolerance, false)
+    }
+
+    /// The result of [`almost::compare_streams`](compare_streams).
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct StreamComparison<T> {
+        /// The number of elements compared. If there was a mismatch, this
+        /// includes the mismatched element, but nothing after it.
+        pub elements: usize,
+        /// The first pair of elements which were not almost equal, if any.
+        pub first_mismatch: Option<ElementDiff<T, usize>>,
+    }
+
+    impl<T: Float> StreamComparison<T> {
+        /// Returns true if the streams were almost equal.
+        #[inline]
+        pub fn passed(&self) -> bool {
+            self.first_mismatch.is_none()
+        }
+
+        /// The byte offset of the first mismatch in the streams, if any.
+        #[inline]
+        pub fn first_mismatch_offset(&self) -> Option<u64> {
+            let d = self.first_mismatch.as_ref()?;
+            Some(d.index as u64 * T::BYTES as u64)
+        }
+    }
+
+    /// Compares two streams of encoded floats element-wise, reading them
+    /// incrementally, and stopping at the first mismatch. This requires the
+    /// `std` feature.
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use almost::ByteOrder;
+    /// let a: Vec<u8> = [1.0f64, 2.0, 3.0].iter().flat_map(|f| f.to_be_bytes()).collect();
+    /// let b: Vec<u8> = [1.0f64, 2.5, 3.5].iter().flat_map(|f| f.to_be_bytes()).collect();
+    /// let result = almost::compare_streams::<f64, _, _>(&a[..], &b[..], ByteOrder::Big)?;
+    /// assert!(!result.passed());
+    /// assert_eq!(result.first_mismatch_offset(), Some(8));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Neither stream is buffered beyond a small fixed-size chunk, so this is
+    /// suitable for comparing large golden files (or other streams), without
+    /// reading either into memory. For full statistics over the whole input,
+    /// see [`almost::compare_files`](compare_files).
+    ///
+    /// I/O errors are returned as-is. If the streams contain different numbers
+    /// of elements (and no mismatch was found before the shorter one ended),
+    /// or aren't a whole number of elements, this returns an error of kind
+    /// `InvalidData`, where the inner error is a [`BinaryError`].
+    #[inline]
+    pub fn compare_streams<T: Float, A: Read, B: Read>(
+        lhs: A,
+        rhs: B,
+        order: ByteOrder,
+    ) -> io::Result<StreamComparison<T>> {
+        compare_streams_with(lhs, rhs, order, crate::float::default_rel_tol())
+    }
+
+    /// Equivalent to [`almost::compare_streams`](compare_streams), but uses the
+    /// provided relative tolerance.
+    ///
+    /// # Panics
+    /// This function panics in debug mode if `tolerance` is less than
+    /// `T::EPSILON` or greater than 1.0, as with
+    /// [`almost::equal_with`](crate::equal_with).
+    pub fn compare_streams_with<T: Float, A: Read, B: Read>(
+        lhs: A,
+        rhs: B,
+        order: ByteOrder,
+        tolerance: T,
+    ) -> io::Result<StreamComparison<T>> {
+        let report = compare_readers(lhs, rhs, order, tolerance, true)?;
+        Ok(StreamComparison {
+            elements: report.total,
+            first_mismatch: report.first_mismatch,
+        })
+    }
+}
@@ -0,0 +1,206 @@
+//! Empirical selection of tolerances, by perturbing inputs.
+
+use crate::Float;
+use alloc::vec::Vec;
+
+/// Finds a tolerance for a computation empirically, by running it many times
+/// with randomly perturbed inputs. This requires the `alloc` feature.
+///
+/// ```
+/// use almost::Calibration;
+/// // How precisely can we compute the variance of some data?
+/// let data = [100000.1, 100000.2, 100000.3, 100000.4];
+/// let variance = |x: &[f64]| {
+///     let n = x.len() as f64;
+///     x.iter().map(|v| v * v).sum::<f64>() / n - (x.iter().sum::<f64>() / n).powi(2)
+/// };
+/// let result = Calibration::new().jitter_ulps(2).run(&data, variance);
+/// // This formula cancels catastrophically, so the default tolerance is no
+/// // good here, even though the inputs only moved by a couple of ULPs.
+/// assert!(result.recommended_tolerance > almost::F64_TOLERANCE);
+/// ```
+///
+/// Each trial moves every input by a uniformly random number of ULPs in
+/// `-jitter..=jitter`, and computes the relative error of the result from the
+/// result for the unperturbed inputs. This models the inputs having been
+/// rounded slightly differently (as they might be on another platform, or
+/// after harmless refactoring of the code which produced them), so the
+/// tolerance found is one that the comparison should be robust to.
+///
+/// The random number generator is seeded deterministically, so the result is
+/// reproducible.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    trials: usize,
+    jitter_ulps: u32,
+    percentile: f64,
+    seed: u64,
+}
+
+impl Default for Calibration {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Calibration {
+    /// Creates a calibration with the default settings: 10000 trials, a jitter
+    /// of 1 ULP, and covering the 99.9th percentile.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            trials: 10_000,
+            jitter_ulps: 1,
+            percentile: 0.999,
+            seed: 0x853c_49e6_748f_ea9b,
+        }
+    }
+
+    /// Sets the number of times to run the computation.
+    ///
+    /// # Panics
+    /// Panics if `trials` is zero.
+    #[inline]
+    pub fn trials(mut self, trials: usize) -> Self {
+        assert!(trials > 0, "Calibration needs at least one trial");
+        self.trials = trials;
+        self
+    }
+
+    /// Sets the largest number of ULPs that an input is moved by.
+    #[inline]
+    pub fn jitter_ulps(mut self, ulps: u32) -> Self {
+        self.jitter_ulps = ulps;
+        self
+    }
+
+    /// Sets the fraction of trials that the recommended tolerance should
+    /// cover, e.g. 0.999 for the 99.9th percentile. Use 1.0 to cover the
+    /// largest error seen.
+    ///
+    /// # Panics
+    /// Panics if `p` isn't in `(0.0, 1.0]`.
+    #[inline]
+    pub fn percentile(mut self, p: f64) -> Self {
+        assert!(p > 0.0 && p <= 1.0, "Percentile must be in (0.0, 1.0]");
+        self.percentile = p;
+        self
+    }
+
+    /// Sets the seed for the random number generator.
+    #[inline]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Runs `f` on perturbed copies of `inputs`, and returns a summary of the
+    /// relative errors of the results.
+    ///
+    /// Non-finite inputs are never perturbed. Trials where the result isn't
+    /// finite are counted in [`non_finite`](CalibrationResult::non_finite),
+    /// but otherwise ignored.
+    pub fn run<T, F>(&self, inputs: &[T], mut f: F) -> CalibrationResult<T>
+    where
+        T: Float,
+        F: FnMut(&[T]) -> T,
+    {
+        let reference = f(inputs);
+        let mut rng = SplitMix64(self.seed);
+        let span = 2 * u64::from(self.jitter_ulps) + 1;
+        let mut scratch = inputs.to_vec();
+        let mut errors = Vec::with_capacity(self.trials);
+        let mut non_finite = 0;
+        for _ in 0..self.trials {
+            for (s, &x) in scratch.iter_mut().zip(inputs) {
+                let k = (rng.next() % span) as i64 - i64::from(self.jitter_ulps);
+                *s = if x.is_finite() {
+                    T::from_ordered(x.to_ordered() + k)
+                } else {
+                    x
+                };
+            }
+            let result = f(&scratch);
+            if result.is_finite() {
+                errors.push(result.rel_err(reference));
+            } else {
+                non_finite += 1;
+            }
+        }
+        // These are only NaN if the reference result was, in which case they
+        // all are, and the order doesn't matter.
+        errors.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+        let pick = |p: f64| -> T {
+            if errors.is_empty() {
+                return T::from_f64(f64::NAN);
+            }
+            // `ceil` needs `std`.
+            let rank = p * errors.len() as f64;
+            let i = rank as usize + ((rank as usize as f64) < rank) as usize;
+            errors[i.clamp(1, errors.len()) - 1]
+        };
+        let percentile_error = pick(self.percentile);
+        CalibrationResult {
+            recommended_tolerance: recommend(percentile_error),
+            percentile_error,
+            median_error: pick(0.5),
+            max_error: pick(1.0),
+            trials: self.trials,
+            non_finite,
+        }
+    }
+}
+
+/// The smallest power of two larger than `err`, and at least the machine
+/// epsilon. Rounding up leaves a little headroom, and gives a tolerance which
+/// is obviously a choice rather than a measurement.
+fn recommend<T: Float>(err: T) -> T {
+    if err.is_nan() {
+        return err;
+    }
+    let mut tol = T::MACHINE_EPSILON;
+    let two = T::from_f64(2.0);
+    while tol <= err && tol.is_finite() {
+        tol = tol * two;
+    }
+    tol
+}
+
+/// The result of [`Calibration::run`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CalibrationResult<T> {
+    /// The suggested relative tolerance for comparing the result: the
+    /// smallest power of two larger than `percentile_error` (and no smaller
+    /// than the machine epsilon).
+    ///
+    /// If this is 1.0 or more, then the computation is so sensitive to its
+    /// inputs that relative comparison isn't meaningful. NaN if no trials had
+    /// finite results.
+    pub recommended_tolerance: T,
+    /// The relative error at the requested percentile.
+    pub percentile_error: T,
+    /// The median relative error.
+    pub median_error: T,
+    /// The largest relative error seen.
+    pub max_error: T,
+    /// The number of trials run.
+    pub trials: usize,
+    /// The number of trials where the result wasn't finite.
+    pub non_finite: usize,
+}
+
+/// A small, fast generator. Quality is more than sufficient for choosing
+/// perturbations, and it avoids a dependency.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    #[inline]
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,194 @@
+//! Classification of the difference between two values.
+
+use crate::Float;
+
+/// How two values differ, as returned by
+/// [`almost::classify_diff`](classify_diff).
+///
+/// The variants are roughly in order of increasing severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffClass {
+    /// The values are exactly equal (including `0.0` and `-0.0`).
+    Identical,
+    /// The values are within [`DiffClass::MAX_ULPS`] ULPs of each other. The
+    /// payload is the number of ULPs (representable values) between them.
+    WithinUlps(u64),
+    /// The values are further apart than `MAX_ULPS`, but almost equal with the
+    /// default tolerance.
+    WithinDefaultTolerance,
+    /// The values have the same sign and are within a factor of 10 of each
+    /// other, but aren't almost equal with the default tolerance.
+    BeyondTolerance,
+    /// The values have the same sign, but differ by at least a factor of 10.
+    DifferentMagnitude,
+    /// One value is exactly zero, and the other isn't (and they aren't
+    /// almost equal). Relative comparison with zero never succeeds, see
+    /// [`almost::zero`](crate::zero).
+    ZeroVsNonzero,
+    /// The values are nonzero and have opposite signs.
+    OppositeSign,
+    /// One value is infinite, and the other is finite.
+    InfiniteVsFinite,
+    /// One value is positive infinity, and the other is negative infinity.
+    OppositeInfinities,
+    /// Exactly one of the values is NaN.
+    OneNaN,
+    /// Both values are NaN.
+    BothNaN,
+}
+
+impl DiffClass {
+    /// The largest number of ULPs between two values that are classified as
+    /// [`DiffClass::WithinUlps`]. This is about what's expected from a few
+    /// differently-rounded operations.
+    pub const MAX_ULPS: u64 = 8;
+}
+
+/// Classifies how `a` and `b` differ, so that code can branch on why values
+/// aren't equal.
+///
+/// ```
+/// use almost::{classify_diff, DiffClass};
+/// assert_eq!(classify_diff(0.1 + 0.2, 0.3), DiffClass::WithinUlps(1));
+/// assert_eq!(classify_diff(1.0, 1.0 + 1e-12), DiffClass::WithinDefaultTolerance);
+/// assert_eq!(classify_diff(1.0, 100.0), DiffClass::DifferentMagnitude);
+/// assert_eq!(classify_diff(1e-20, 0.0), DiffClass::ZeroVsNonzero);
+/// assert_eq!(classify_diff(f64::NAN, 1.0), DiffClass::OneNaN);
+/// ```
+///
+/// Checks are made in the same order as the variants of [`DiffClass`], except
+/// that NaNs and infinities are checked for first, and the first that applies
+/// is returned. For example, `-1e-300` and `1e-300` are `OppositeSign`, but
+/// the smallest positive and negative subnormals are `WithinUlps(2)`.
+pub fn classify_diff<T: Float>(a: T, b: T) -> DiffClass {
+    let zero = T::default();
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => return DiffClass::BothNaN,
+        (true, false) | (false, true) => return DiffClass::OneNaN,
+        _ => {}
+    }
+    if a == b {
+        return DiffClass::Identical;
+    }
+    match (a.is_finite(), b.is_finite()) {
+        (false, false) => return DiffClass::OppositeInfinities,
+        (true, false) | (false, true) => return DiffClass::InfiniteVsFinite,
+        _ => {}
+    }
+    let ulps = a.to_ordered().abs_diff(b.to_ordered());
+    if ulps <= DiffClass::MAX_ULPS {
+        return DiffClass::WithinUlps(ulps);
+    }
+    if a.almost_equals(b) {
+        return DiffClass::WithinDefaultTolerance;
+    }
+    if a == zero || b == zero {
+        return DiffClass::ZeroVsNonzero;
+    }
+    if (a < zero) != (b < zero) {
+        return DiffClass::OppositeSign;
+    }
+    let (small, large) = if a.abs() < b.abs() {
+        (a.abs(), b.abs())
+    } else {
+        (b.abs(), a.abs())
+    };
+    if large >= small * T::from_f64(10.0) {
+        DiffClass::DifferentMagnitude
+    } else {
+        DiffClass::BeyondTolerance
+    }
+}
+
+/// Returns the number of significant decimal digits to which `a` and `b`
+/// agree, or 0 if their signs or magnitudes don't.
+///
+/// ```
+/// assert_eq!(almost::matching_digits(3.14159, 3.14160), 5);
+/// assert_eq!(almost::matching_digits(1.0, 1.1), 1);
+/// assert_eq!(almost::matching_digits(1.0, 2.0), 0);
+/// assert_eq!(almost::matching_digits(0.1 + 0.2, 0.3), 15);
+/// assert_eq!(almost::matching_digits(0.3, 0.3), 17);
+/// ```
+///
+/// This is for logging how accurate results are, such as to track it over
+/// time, where "agrees to 9 digits" means more to a reader than a relative
+/// error or a pass. It's the largest `d` for which the relative error (as used
+/// by [`almost::equal_with`](crate::equal_with)) is less than `10^-d`, so it's
+/// a measure of the difference rather than a count of matching characters:
+/// `0.9999` and `1.0001` agree to 3 digits.
+///
+/// Values which are exactly equal agree to 17 digits for `f64` (or 9 for
+/// `f32`), which is as many as it takes to write any value exactly, and one
+/// more than any pair of different values can agree to. Subnormal values are
+/// compared as with `equal_with`, relative to `T::MIN_POSITIVE`. Returns 0 if
+/// either value is NaN, or if they're different and either is infinite.
+pub fn matching_digits<T: Float>(a: T, b: T) -> u32 {
+    let all = if T::BYTES == 4 { 9 } else { 17 };
+    if a == b {
+        return all;
+    }
+    if !(a.is_finite() && b.is_finite()) {
+        return 0;
+    }
+    let rel = a.rel_err(b).to_f64();
+    let mut digits = 0;
+    let mut bound = 1.0;
+    while digits < all - 1 {
+        bound /= 10.0;
+        if rel >= bound {
+            break;
+        }
+        digits += 1;
+    }
+    digits
+}
+
+/// Returns a score from 0 to 1 for how close `a` and `b` are: 1 if they're
+/// equal, decreasing smoothly as their relative difference grows, and 0 if
+/// they aren't almost equal with the default tolerance.
+///
+/// ```
+/// assert_eq!(almost::closeness(0.3, 0.3), 1.0);
+/// assert!(almost::closeness(0.1 + 0.2, 0.3) > 0.999);
+/// assert_eq!(almost::closeness(1.0, 1.1), 0.0);
+/// // Half way to the cutoff.
+/// let score = almost::closeness_with(1.0, 1.0 / 0.95, 0.1);
+/// assert!(0.5 < score && score < 0.6);
+/// ```
+///
+/// This is for weighing matches rather than accepting or rejecting them, such
+/// as in record linkage, where a pair of values which only just passes
+/// shouldn't count as much as a pair which is identical. It's nonzero exactly
+/// when [`almost::equal`](crate::equal) is true, and it's `(1 - r²)²`, where
+/// `r` is the relative difference as a fraction of the cutoff, so it
+/// starts and ends flat and has no jumps for small changes to the inputs.
+///
+/// Equal infinities score 1, and anything involving NaN scores 0.
+#[inline]
+pub fn closeness<T: Float>(a: T, b: T) -> f64 {
+    closeness_with(a, b, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::closeness`](closeness), but the score reaches 0 at
+/// the relative difference `cutoff`, rather than the default tolerance. It's
+/// nonzero exactly when [`almost::equal_with`](crate::equal_with) with the
+/// tolerance `cutoff` is true.
+///
+/// # Panics
+/// This function panics in debug mode if `cutoff` is less than `T::EPSILON` or
+/// greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn closeness_with<T: Float>(a: T, b: T, cutoff: T) -> f64 {
+    let cutoff = crate::float::check_rel_tol(cutoff);
+    if a == b {
+        return 1.0;
+    }
+    let r = a.rel_err(b).to_f64() / cutoff.to_f64();
+    if r < 1.0 {
+        let s = 1.0 - r * r;
+        s * s
+    } else {
+        // Including NaN.
+        0.0
+    }
+}
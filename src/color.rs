@@ -0,0 +1,162 @@
+//! Comparison of RGB colors. This requires the `std` or `libm` feature.
+
+use crate::Float;
+
+/// How the channels of a color passed to
+/// [`almost::color_equal`](crate::color_equal) are encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorEncoding {
+    /// Gamma-encoded sRGB, which is what's stored in most image files and
+    /// textures, and what's written as e.g. `#ff8000`. Values are decoded to
+    /// linear RGB before comparison.
+    Srgb,
+    /// Linear RGB with sRGB primaries, which is what shaders and renderers
+    /// usually compute in.
+    Linear,
+}
+
+/// How [`almost::color_equal`](crate::color_equal) should compare two colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorMetric {
+    /// Each channel of the linear RGB values must be within the (absolute)
+    /// tolerance of the other. This is what a renderer's error is usually
+    /// bounded by.
+    LinearRgb,
+    /// The CIE76 color difference, ΔE*, which is the distance between the
+    /// colors in CIELAB space (with a D65 white point). This approximates how
+    /// different the colors look: a ΔE* of around 2.3 is just noticeable, and
+    /// dark colors need much larger linear differences to get there.
+    DeltaE,
+}
+
+impl ColorMetric {
+    /// The tolerance used by [`almost::color_equal`](crate::color_equal) for
+    /// this metric.
+    ///
+    /// For `LinearRgb` this is `1 / 510`, half of a step in an 8-bit linear
+    /// channel. For `DeltaE` it's `1.0`, comfortably below what's noticeable.
+    #[inline]
+    pub fn default_tolerance(self) -> f64 {
+        match self {
+            ColorMetric::LinearRgb => 1.0 / 510.0,
+            ColorMetric::DeltaE => 1.0,
+        }
+    }
+}
+
+/// Decodes an sRGB channel. Negative values (from wide-gamut colors) are
+/// decoded symmetrically.
+fn srgb_to_linear(c: f64) -> f64 {
+    let m = if c < 0.0 { -c } else { c };
+    let l = if m <= 0.04045 {
+        m / 12.92
+    } else {
+        crate::math::pow((m + 0.055) / 1.055, 2.4)
+    };
+    if c < 0.0 {
+        -l
+    } else {
+        l
+    }
+}
+
+/// Converts linear RGB to CIELAB, relative to the D65 white point.
+fn linear_to_lab(rgb: [f64; 3]) -> [f64; 3] {
+    let [r, g, b] = rgb;
+    // Linear sRGB to XYZ, already divided by the white point.
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+    const DELTA: f64 = 6.0 / 29.0;
+    let f = |t: f64| {
+        if t > DELTA * DELTA * DELTA {
+            crate::math::pow(t, 1.0 / 3.0)
+        } else {
+            t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Returns true if the RGB colors `lhs` and `rhs` are almost equal under the
+/// provided metric, using its
+/// [default tolerance](ColorMetric::default_tolerance).
+///
+/// ```
+/// use almost::{ColorEncoding, ColorMetric};
+/// // One 8-bit step apart, near black.
+/// let a = [1.0 / 255.0, 0.0, 0.0];
+/// let b = [2.0 / 255.0, 0.0, 0.0];
+/// assert!(almost::color_equal(a, b, ColorEncoding::Srgb, ColorMetric::LinearRgb));
+/// assert!(almost::color_equal(a, b, ColorEncoding::Srgb, ColorMetric::DeltaE));
+/// // A visibly different shade of grey.
+/// let (c, d) = ([0.5f32; 3], [0.52f32; 3]);
+/// assert!(!almost::color_equal(c, d, ColorEncoding::Srgb, ColorMetric::DeltaE));
+/// ```
+///
+/// Colors in sRGB should almost never be compared without decoding them
+/// first: an sRGB channel has much less precision in the highlights than the
+/// shadows, so no single tolerance on the encoded values makes sense.
+///
+/// Alpha (if present) should be compared separately, usually with
+/// [`almost::zero_with`](crate::zero_with) on the difference. Returns false if
+/// any channel is NaN.
+#[inline]
+pub fn color_equal<T: Float>(
+    lhs: [T; 3],
+    rhs: [T; 3],
+    encoding: ColorEncoding,
+    metric: ColorMetric,
+) -> bool {
+    color_equal_with(
+        lhs,
+        rhs,
+        encoding,
+        metric,
+        T::from_f64(metric.default_tolerance()),
+    )
+}
+
+/// Equivalent to [`almost::color_equal`](color_equal), but uses the provided
+/// tolerance. This is an absolute tolerance for each linear channel with
+/// `LinearRgb`, and the largest ΔE* with `DeltaE`.
+///
+/// ```
+/// use almost::{ColorEncoding, ColorMetric};
+/// let (a, b) = ([0.5, 0.25, 0.1], [0.51, 0.25, 0.1]);
+/// assert!(almost::color_equal_with(a, b, ColorEncoding::Linear, ColorMetric::LinearRgb, 0.02));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is not greater than zero,
+/// as with [`almost::zero_with`](crate::zero_with).
+pub fn color_equal_with<T: Float>(
+    lhs: [T; 3],
+    rhs: [T; 3],
+    encoding: ColorEncoding,
+    metric: ColorMetric,
+    tolerance: T,
+) -> bool {
+    crate::float::check_zero_tol(tolerance);
+    let linear = |c: [T; 3]| {
+        let c = c.map(|v| v.to_f64());
+        match encoding {
+            ColorEncoding::Srgb => c.map(srgb_to_linear),
+            ColorEncoding::Linear => c,
+        }
+    };
+    let (a, b) = (linear(lhs), linear(rhs));
+    let tol = tolerance.to_f64();
+    match metric {
+        ColorMetric::LinearRgb => a.iter().zip(&b).all(|(&x, &y)| {
+            let d = x - y;
+            d < tol && -d < tol
+        }),
+        ColorMetric::DeltaE => {
+            let (a, b) = (linear_to_lab(a), linear_to_lab(b));
+            let dist_sq: f64 = a.iter().zip(&b).map(|(&x, &y)| (x - y) * (x - y)).sum();
+            dist_sq < tol * tol
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! The [`Comparator`] type, which bundles the settings for a comparison so
+//! they can be validated once and reused.
+
+use crate::float::{default_rel_tol, default_zero_tol};
+use crate::{Float, Tolerance, ToleranceError};
+use core::fmt;
+
+/// How a [`Comparator`] treats NaN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NanPolicy {
+    /// NaN is never equal to anything, including NaN, as with
+    /// [`almost::equal`](crate::equal).
+    #[default]
+    Unequal,
+    /// NaN is equal to NaN (of any sign or payload), but to nothing else. This
+    /// is for comparing outputs where NaN marks missing data, and should be in
+    /// the same places in both.
+    EqualToNan,
+}
+
+/// A set of validated settings for comparing values, which can be reused for
+/// any number of comparisons.
+///
+/// ```
+/// use almost::{Comparator, NanPolicy};
+/// let cmp = Comparator::builder()
+///     .tolerance(1e-9)
+///     .zero_tolerance(1e-12)
+///     .nan_policy(NanPolicy::EqualToNan)
+///     .build()
+///     .unwrap();
+/// assert!(cmp.equal(0.1 + 0.2, 0.3));
+/// assert!(cmp.equal(1e-15, -1e-14));
+/// assert!(cmp.equal(f64::NAN, f64::NAN));
+/// assert!(cmp.zero(0.1 + 0.2 - 0.3));
+/// assert!(!cmp.equal(1.0, 1.0 + 1e-8));
+/// ```
+///
+/// Threading a relative tolerance, an absolute tolerance and a policy for NaN
+/// through every call site of a large test harness is error-prone, as it's
+/// easy to pass the wrong one or forget one. A comparator is configured once,
+/// and validated as a whole when it's built, with an error rather than a
+/// debug assertion, so its comparisons never panic.
+///
+/// The `Default` (also [`Comparator::new`]) compares as
+/// [`almost::equal`](crate::equal) and [`almost::zero`](crate::zero) do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparator<T> {
+    tolerance: Tolerance<T>,
+    zero_tolerance: Option<T>,
+    nan_policy: NanPolicy,
+}
+
+/// Builds a [`Comparator`], see [`Comparator::builder`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComparatorBuilder<T> {
+    tolerance: T,
+    zero_tolerance: Option<T>,
+    nan_policy: NanPolicy,
+}
+
+/// The reason a [`ComparatorBuilder`] couldn't build a [`Comparator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComparatorError {
+    /// The relative tolerance wasn't valid, as for [`Tolerance::new`].
+    Tolerance(ToleranceError),
+    /// The zero tolerance was zero, negative, NaN or infinite.
+    ZeroTolerance,
+}
+
+impl fmt::Display for ComparatorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComparatorError::Tolerance(e) => fmt::Display::fmt(e, f),
+            ComparatorError::ZeroTolerance => {
+                f.write_str("zero tolerance must be positive and finite")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ComparatorError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ComparatorError::Tolerance(e) => Some(e),
+            ComparatorError::ZeroTolerance => None,
+        }
+    }
+}
+
+impl From<ToleranceError> for ComparatorError {
+    #[inline]
+    fn from(e: ToleranceError) -> Self {
+        ComparatorError::Tolerance(e)
+    }
+}
+
+impl<T: Float> Default for Comparator<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> Comparator<T> {
+    /// Creates a comparator with the default settings: the default tolerance
+    /// for the type, no zero tolerance (so values are only equal to zero if
+    /// they're exactly zero), and NaN unequal to everything.
+    #[inline]
+    pub fn new() -> Self {
+        Comparator {
+            tolerance: Tolerance::default(),
+            zero_tolerance: None,
+            nan_policy: NanPolicy::Unequal,
+        }
+    }
+
+    /// Returns a builder, which starts with the same settings as
+    /// [`Comparator::new`].
+    #[inline]
+    pub fn builder() -> ComparatorBuilder<T> {
+        ComparatorBuilder {
+            tolerance: default_rel_tol(),
+            zero_tolerance: None,
+            nan_policy: NanPolicy::Unequal,
+        }
+    }
+
+    /// The relative tolerance.
+    #[inline]
+    pub fn tolerance(&self) -> T {
+        self.tolerance.get()
+    }
+
+    /// The absolute tolerance for values near zero, if one was set.
+    #[inline]
+    pub fn zero_tolerance(&self) -> Option<T> {
+        self.zero_tolerance
+    }
+
+    /// How NaN is treated.
+    #[inline]
+    pub fn nan_policy(&self) -> NanPolicy {
+        self.nan_policy
+    }
+
+    /// Returns true if `lhs` and `rhs` are almost equal.
+    ///
+    /// This is [`almost::equal_with`](crate::equal_with) with the relative
+    /// tolerance. If there's a zero tolerance, values which are both almost
+    /// zero are equal too, as with
+    /// [`almost::equal_with_zero_tol`](crate::equal_with_zero_tol). With
+    /// [`NanPolicy::EqualToNan`], NaN is equal to NaN.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn equal(&self, lhs: T, rhs: T) -> bool {
+        if lhs.is_nan() || rhs.is_nan() {
+            return self.nan_policy == NanPolicy::EqualToNan && lhs.is_nan() && rhs.is_nan();
+        }
+        match self.zero_tolerance {
+            Some(z) => crate::equal_with_zero_tol(lhs, rhs, self.tolerance.get(), z),
+            None => self.tolerance.equal(lhs, rhs),
+        }
+    }
+
+    /// Returns true if `v` is almost zero, with the zero tolerance, or with
+    /// the default tolerance for the type (as in
+    /// [`almost::zero`](crate::zero)) if there isn't one. NaN is never almost
+    /// zero.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn zero(&self, v: T) -> bool {
+        v.almost_zero_with(self.zero_tolerance.unwrap_or_else(default_zero_tol))
+    }
+}
+
+impl<T: Float> ComparatorBuilder<T> {
+    /// Sets the relative tolerance, as in
+    /// [`almost::equal_with`](crate::equal_with).
+    #[inline]
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the absolute tolerance for values near zero, as in
+    /// [`almost::zero_with`](crate::zero_with). Values which are both within
+    /// it are considered equal.
+    #[inline]
+    pub fn zero_tolerance(mut self, zero_tolerance: T) -> Self {
+        self.zero_tolerance = Some(zero_tolerance);
+        self
+    }
+
+    /// Sets how NaN is treated.
+    #[inline]
+    pub fn nan_policy(mut self, nan_policy: NanPolicy) -> Self {
+        self.nan_policy = nan_policy;
+        self
+    }
+
+    /// Validates the settings and builds the comparator.
+    ///
+    /// ```
+    /// use almost::{Comparator, ComparatorError, ToleranceError};
+    /// let err = Comparator::builder().tolerance(2.0).build().unwrap_err();
+    /// assert_eq!(err, ComparatorError::Tolerance(ToleranceError::TooLarge));
+    /// let err = Comparator::builder().zero_tolerance(0.0f32).build().unwrap_err();
+    /// assert_eq!(err, ComparatorError::ZeroTolerance);
+    /// ```
+    ///
+    /// Returns an error if the relative tolerance isn't valid, as for
+    /// [`Tolerance::new`], or if the zero tolerance isn't positive and
+    /// finite.
+    pub fn build(self) -> Result<Comparator<T>, ComparatorError> {
+        let tolerance = Tolerance::new(self.tolerance)?;
+        if let Some(z) = self.zero_tolerance {
+            if !z.is_finite() || z <= T::default() {
+                return Err(ComparatorError::ZeroTolerance);
+            }
+        }
+        Ok(Comparator {
+            tolerance,
+            zero_tolerance: self.zero_tolerance,
+            nan_policy: self.nan_policy,
+        })
+    }
+}
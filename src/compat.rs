@@ -0,0 +1,357 @@
+//! Comparisons which match the semantics of other languages and libraries
+//! exactly, for validating ports of code which relies on them.
+//!
+//! These are intentionally faithful rather than good: they're for checking
+//! that a port makes the same decisions as the original, before (or instead
+//! of) moving it to this crate's own comparisons.
+
+use crate::Float;
+use core::fmt;
+
+/// R's default tolerance for `all.equal`, which is `sqrt(.Machine$double.eps)`
+/// rounded to `1.5e-8`.
+pub const R_TOLERANCE: f64 = 1.5e-8;
+
+/// What [`all_equal`]'s mean difference was measured relative to, as in R's
+/// "Mean relative difference" message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeanDifference {
+    /// Relative to the mean magnitude of the target, which is the usual case.
+    Relative,
+    /// Not scaled, because the mean magnitude of the target was no larger
+    /// than the tolerance (or wasn't finite), or because the explicit scale
+    /// was 1.
+    Absolute,
+    /// Relative to an explicit scale.
+    Scaled,
+}
+
+/// The reason [`all_equal`] failed. The `Display` impl matches the message R
+/// would return, including R's formatting of the mean difference to 7
+/// significant digits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AllEqualMismatch<T> {
+    /// The lengths differ.
+    Lengths {
+        /// The length of the target.
+        target: usize,
+        /// The length of the current value.
+        current: usize,
+    },
+    /// The values are NaN (R's `NA` or `NaN`) in different places.
+    NaMismatch {
+        /// The number of NaNs in the target.
+        target: usize,
+        /// The number of NaNs in the current value.
+        current: usize,
+    },
+    /// The mean difference was larger than the tolerance, or NaN.
+    MeanDifference {
+        /// How the difference was scaled.
+        kind: MeanDifference,
+        /// The mean difference.
+        value: T,
+    },
+}
+
+impl<T: Float> fmt::Display for AllEqualMismatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AllEqualMismatch::Lengths { target, current } => {
+                write!(f, "Lengths ({}, {}) differ", target, current)
+            }
+            AllEqualMismatch::NaMismatch { target, current } => write!(
+                f,
+                "'is.NA' value mismatch: {} in current {} in target",
+                current, target
+            ),
+            AllEqualMismatch::MeanDifference { kind, value } => {
+                let kind = match kind {
+                    MeanDifference::Relative => "relative",
+                    MeanDifference::Absolute => "absolute",
+                    MeanDifference::Scaled => "scaled",
+                };
+                write!(f, "Mean {} difference: {}", kind, RNumber(value.to_f64()))
+            }
+        }
+    }
+}
+
+impl<T: Float> core::error::Error for AllEqualMismatch<T> {}
+
+/// Formats a number as R's `format` does by default: with up to 7 significant
+/// digits, and in scientific notation if that's narrower.
+struct RNumber(f64);
+
+impl fmt::Display for RNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+        let x = self.0;
+        if x.is_nan() {
+            return f.write_str("NaN");
+        } else if x.is_infinite() {
+            return f.write_str(if x > 0.0 { "Inf" } else { "-Inf" });
+        } else if x == 0.0 {
+            return f.write_str("0");
+        }
+        // Round to 7 digits, and only show the ones before the trailing zeros.
+        let mut buf = crate::uncertain::Buf::new();
+        write!(buf, "{:.6e}", x)?;
+        let (mantissa, exp) = buf
+            .as_str()
+            .split_at(buf.as_str().find('e').ok_or(fmt::Error)?);
+        let exp: i32 = exp[1..].parse().map_err(|_| fmt::Error)?;
+        let sig = mantissa
+            .trim_end_matches('0')
+            .bytes()
+            .filter(u8::is_ascii_digit)
+            .count() as i32;
+        // As in R's `formatReal`, fixed notation is used unless it's wider.
+        let right = (sig - 1 - exp).max(0);
+        let fixed = exp.max(0) + 1 + if right > 0 { right + 1 } else { 0 };
+        let sci = sig + (sig > 1) as i32 + if exp.abs() >= 100 { 5 } else { 4 };
+        if fixed <= sci {
+            write!(f, "{:.*}", right as usize, x)
+        } else {
+            let mut buf = crate::uncertain::Buf::new();
+            write!(buf, "{:.*e}", (sig - 1) as usize, x)?;
+            let mantissa = buf.as_str().split('e').next().unwrap_or_default();
+            let sign = if exp < 0 { '-' } else { '+' };
+            write!(f, "{}e{}{:02}", mantissa, sign, exp.unsigned_abs())
+        }
+    }
+}
+
+/// Compares `target` and `current` as R's `all.equal(target, current)` does
+/// for numeric vectors, with the default tolerance of `1.5e-8`.
+///
+/// ```
+/// use almost::compat::{all_equal, AllEqualMismatch, MeanDifference};
+/// assert!(all_equal(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0 + 1e-8]).is_ok());
+/// // A larger error in one element is averaged with the others.
+/// let e = 1e-9;
+/// assert!(all_equal(&[1.0; 4], &[1.0 + e, 1.0 + e, 1.0 + e, 1.0 + 50.0 * e]).is_ok());
+/// // But elements which are exactly equal are left out.
+/// assert!(all_equal(&[1.0; 4], &[1.0, 1.0, 1.0, 1.0 + 50.0 * e]).is_err());
+/// let err = all_equal(&[1.0, 2.0], &[1.0, 2.2]).unwrap_err();
+/// assert_eq!(err.to_string(), "Mean relative difference: 0.1");
+/// ```
+///
+/// Unlike this crate's element-wise comparisons, R compares the mean absolute
+/// difference of the elements which aren't exactly equal to the mean
+/// magnitude of the corresponding elements of `target`. Because of that, the
+/// result depends on which argument is the target, and a few large errors can
+/// be hidden by many small ones. If the mean magnitude isn't larger than the
+/// tolerance, the mean absolute difference is used directly.
+///
+/// NaNs must be in the same places in both (R doesn't distinguish `NA` from
+/// `NaN` here), and are then ignored. Infinities are only equal to
+/// themselves. R's `countEQ` argument isn't supported, and is always false.
+#[inline]
+pub fn all_equal<T: Float>(target: &[T], current: &[T]) -> Result<(), AllEqualMismatch<T>> {
+    all_equal_with(target, current, T::from_f64(R_TOLERANCE), None)
+}
+
+/// Equivalent to [`all_equal`], but with R's `tolerance` and `scale`
+/// arguments. If `scale` is provided, the mean absolute difference is divided
+/// by it, rather than by the mean magnitude of `target`.
+///
+/// ```
+/// use almost::compat::all_equal_with;
+/// assert!(all_equal_with(&[100.0], &[101.0], 0.02, None).is_ok());
+/// assert!(all_equal_with(&[100.0], &[101.0], 0.02, Some(1.0)).is_err());
+/// ```
+pub fn all_equal_with<T: Float>(
+    target: &[T],
+    current: &[T],
+    tolerance: T,
+    scale: Option<T>,
+) -> Result<(), AllEqualMismatch<T>> {
+    if target.len() != current.len() {
+        return Err(AllEqualMismatch::Lengths {
+            target: target.len(),
+            current: current.len(),
+        });
+    }
+    if target
+        .iter()
+        .zip(current)
+        .any(|(a, b)| a.is_nan() != b.is_nan())
+    {
+        return Err(AllEqualMismatch::NaMismatch {
+            target: target.iter().filter(|v| v.is_nan()).count(),
+            current: current.iter().filter(|v| v.is_nan()).count(),
+        });
+    }
+    let zero = T::default();
+    let (mut n, mut diff, mut magnitude) = (0usize, zero, zero);
+    // Only elements which aren't NaN or exactly equal are counted.
+    for (&a, &b) in target.iter().zip(current) {
+        if !a.is_nan() && a != b {
+            n += 1;
+            diff = diff + (a - b).abs();
+            magnitude = magnitude + a.abs();
+        }
+    }
+    if n == 0 {
+        return Ok(());
+    }
+    let n = T::from_f64(n as f64);
+    let mut value = diff / n;
+    let kind = match scale {
+        Some(scale) => {
+            value = value / scale;
+            // R says the difference is absolute if the scale is 1.
+            if (scale - T::from_f64(1.0)).abs() < T::from_f64(1e-7) {
+                MeanDifference::Absolute
+            } else {
+                MeanDifference::Scaled
+            }
+        }
+        None => {
+            let mean = magnitude / n;
+            if mean.is_finite() && mean > tolerance {
+                value = value / mean;
+                MeanDifference::Relative
+            } else {
+                MeanDifference::Absolute
+            }
+        }
+    };
+    if value.is_nan() || value > tolerance {
+        Err(AllEqualMismatch::MeanDifference { kind, value })
+    } else {
+        Ok(())
+    }
+}
+
+/// MATLAB's default tolerance for `uniquetol` and `ismembertol` with `double`
+/// data.
+pub const MATLAB_DOUBLE_TOLERANCE: f64 = 1e-6;
+
+/// MATLAB's default tolerance for `uniquetol` and `ismembertol` with `single`
+/// data.
+pub const MATLAB_SINGLE_TOLERANCE: f64 = 1e-4;
+
+/// MATLAB's default tolerance for `T`, which depends on its precision.
+#[cfg(feature = "alloc")]
+#[inline]
+fn matlab_tolerance<T: Float>() -> T {
+    if T::BYTES == 4 {
+        T::from_f64(MATLAB_SINGLE_TOLERANCE)
+    } else {
+        T::from_f64(MATLAB_DOUBLE_TOLERANCE)
+    }
+}
+
+/// MATLAB's `DataScale`: the largest finite magnitude, or 1 if there are none.
+#[cfg(feature = "alloc")]
+fn data_scale<T: Float>(values: impl Iterator<Item = T>) -> T {
+    values
+        .filter(|v| v.is_finite())
+        .map(|v| v.abs())
+        .fold(None, |max: Option<T>, v| match max {
+            Some(m) if m >= v => Some(m),
+            _ => Some(v),
+        })
+        .unwrap_or_else(|| T::from_f64(1.0))
+}
+
+/// Returns true if `a` and `b` are within `margin` of each other, where
+/// infinities are only within any margin of themselves.
+#[cfg(feature = "alloc")]
+#[inline]
+fn within<T: Float>(a: T, b: T, margin: T) -> bool {
+    a == b || (a - b).abs() <= margin
+}
+
+/// Returns the unique values in `values` as MATLAB's `uniquetol(values)` does,
+/// with its default tolerance of `1e-6` (or `1e-4` for `f32`). Requires the
+/// `alloc` feature.
+///
+/// ```
+/// use almost::compat::uniquetol;
+/// let values = [0.3, 1.0, 0.1 + 0.2, 1.0 + 1e-7, 2.0];
+/// assert_eq!(uniquetol(&values), [0.3, 1.0, 2.0]);
+/// ```
+///
+/// As in MATLAB, two values are within tolerance if they differ by at most
+/// `tolerance * max(abs(values))`, so the tolerance is relative to the
+/// largest value rather than to the values being compared. The result is
+/// sorted, and each value in it is the smallest of a run of values which are
+/// within tolerance of it, so a long run of values which are each close to
+/// the last can still produce several results.
+///
+/// Infinities are only equal to themselves, and don't contribute to the
+/// scale. Each NaN is distinct, and they're all kept at the end, as MATLAB's
+/// `unique` does.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn uniquetol<T: Float>(values: &[T]) -> alloc::vec::Vec<T> {
+    uniquetol_with(values, matlab_tolerance())
+}
+
+/// Equivalent to [`uniquetol`], but with the provided tolerance, as in
+/// `uniquetol(values, tolerance)`. Requires the `alloc` feature.
+///
+/// ```
+/// let values = [1.0, 1.05, 1.09, 2.0, 10.0];
+/// assert_eq!(almost::compat::uniquetol_with(&values, 0.01), [1.0, 2.0, 10.0]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn uniquetol_with<T: Float>(values: &[T], tolerance: T) -> alloc::vec::Vec<T> {
+    let margin = tolerance * data_scale(values.iter().copied());
+    let mut sorted: alloc::vec::Vec<T> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+    let mut unique = alloc::vec::Vec::with_capacity(sorted.len());
+    for v in sorted {
+        match unique.last() {
+            Some(&rep) if within(v, rep, margin) => {}
+            _ => unique.push(v),
+        }
+    }
+    unique.extend(values.iter().copied().filter(|v| v.is_nan()));
+    unique
+}
+
+/// For each value in `a`, returns the index of the first value in `b` which
+/// is within tolerance of it, as MATLAB's `[Lia, LocB] = ismembertol(a, b)`
+/// does, with its default tolerance of `1e-6` (or `1e-4` for `f32`). Requires
+/// the `alloc` feature.
+///
+/// ```
+/// use almost::compat::ismembertol;
+/// let found = ismembertol(&[0.1 + 0.2, 0.5, 4.0], &[4.0, 0.3, 0.7]);
+/// assert_eq!(found, [Some(1), None, Some(0)]);
+/// ```
+///
+/// `Some` corresponds to `Lia` being true, and the index is MATLAB's `LocB`,
+/// but from 0. The tolerance is relative to the largest magnitude in either
+/// `a` or `b`, as with [`uniquetol`]: two values are within tolerance if they
+/// differ by at most `tolerance * max(abs([a(:); b(:)]))`. Infinities are only
+/// members if the same infinity is in `b`, and NaN is never a member.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn ismembertol<T: Float>(a: &[T], b: &[T]) -> alloc::vec::Vec<Option<usize>> {
+    ismembertol_with(a, b, matlab_tolerance())
+}
+
+/// Equivalent to [`ismembertol`], but with the provided tolerance, as in
+/// `ismembertol(a, b, tolerance)`. Requires the `alloc` feature.
+///
+/// ```
+/// // The largest magnitude is 100, so values within 1.0 are members.
+/// let found = almost::compat::ismembertol_with(&[1.5, 3.5], &[1.0, 2.0, 100.0], 0.01);
+/// assert_eq!(found, [Some(0), None]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn ismembertol_with<T: Float>(
+    a: &[T],
+    b: &[T],
+    tolerance: T,
+) -> alloc::vec::Vec<Option<usize>> {
+    let margin = tolerance * data_scale(a.iter().chain(b).copied());
+    a.iter()
+        .map(|&x| b.iter().position(|&y| within(x, y, margin)))
+        .collect()
+}
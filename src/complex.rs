@@ -0,0 +1,211 @@
+//! Comparison of complex numbers.
+
+use crate::Float;
+
+/// Types which can be viewed as a complex number, for use with
+/// [`almost::complex_equal`](crate::complex_equal).
+///
+/// This is implemented for `(re, im)` tuples, `[re, im]` arrays, and (with the
+/// `num-complex` feature) `num_complex::Complex`.
+pub trait ComplexParts<T> {
+    /// Returns the real and imaginary parts.
+    fn parts(&self) -> (T, T);
+}
+
+impl<T: Float> ComplexParts<T> for (T, T) {
+    #[inline]
+    fn parts(&self) -> (T, T) {
+        *self
+    }
+}
+
+impl<T: Float> ComplexParts<T> for [T; 2] {
+    #[inline]
+    fn parts(&self) -> (T, T) {
+        (self[0], self[1])
+    }
+}
+
+#[cfg(feature = "num-complex")]
+impl<T: Float> ComplexParts<T> for num_complex::Complex<T> {
+    #[inline]
+    fn parts(&self) -> (T, T) {
+        (self.re, self.im)
+    }
+}
+
+impl<C: ComplexParts<T>, T> ComplexParts<T> for &C {
+    #[inline]
+    fn parts(&self) -> (T, T) {
+        (**self).parts()
+    }
+}
+
+/// How [`almost::complex_equal`](crate::complex_equal) should compare two
+/// complex numbers.
+///
+/// Neither choice is right for every application, so there is intentionally no
+/// default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComplexMetric {
+    /// The real parts must be almost equal, and the imaginary parts must be
+    /// almost equal, each using a relative comparison.
+    ///
+    /// This is strict about phase: values near the real or imaginary axis
+    /// must agree about which side of it they're on. As with
+    /// [`almost::equal`](crate::equal), a component which is exactly zero is
+    /// only equal to another zero, so values which should be purely real (or
+    /// imaginary) need to be exactly so.
+    Componentwise,
+
+    /// The magnitude of the difference must be small relative to the magnitude
+    /// of the larger input: `|a - b| < tol * max(|a|, |b|)`.
+    ///
+    /// This treats the complex number as a point in the plane, and doesn't
+    /// care how the error is distributed between the components. It is usually
+    /// the right choice when a small component is just noise.
+    Magnitude,
+}
+
+/// Returns true if the complex numbers `lhs` and `rhs` are almost equal under
+/// the provided metric.
+///
+/// ```
+/// use almost::ComplexMetric;
+/// let (a, b) = ((1.0, 1e-20), (1.0, -1e-20));
+/// assert!(almost::complex_equal(a, b, ComplexMetric::Magnitude));
+/// assert!(!almost::complex_equal(a, b, ComplexMetric::Componentwise));
+/// ```
+///
+/// Note that this returns false if any component of either value is NaN.
+#[inline]
+pub fn complex_equal<T: Float, C: ComplexParts<T>>(lhs: C, rhs: C, metric: ComplexMetric) -> bool {
+    complex_equal_with(lhs, rhs, metric, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::complex_equal`](complex_equal), but uses the
+/// provided relative tolerance.
+///
+/// ```
+/// use almost::ComplexMetric;
+/// let (a, b) = ([3.0f32, 4.0], [3.0, 4.1]);
+/// assert!(almost::complex_equal_with(a, b, ComplexMetric::Magnitude, 0.05));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+pub fn complex_equal_with<T: Float, C: ComplexParts<T>>(
+    lhs: C,
+    rhs: C,
+    metric: ComplexMetric,
+    tolerance: T,
+) -> bool {
+    let (ar, ai) = lhs.parts();
+    let (br, bi) = rhs.parts();
+    match metric {
+        ComplexMetric::Componentwise => {
+            ar.almost_equals_with(br, tolerance) && ai.almost_equals_with(bi, tolerance)
+        }
+        ComplexMetric::Magnitude => {
+            let tolerance = crate::float::check_rel_tol(tolerance);
+            magnitude_equal(ar, ai, br, bi, tolerance)
+        }
+    }
+}
+
+fn magnitude_equal<T: Float>(ar: T, ai: T, br: T, bi: T, tol: T) -> bool {
+    if !(ar.is_finite() && ai.is_finite() && br.is_finite() && bi.is_finite()) {
+        // Magnitudes aren't meaningful here, but componentwise comparison
+        // handles both NaN and infinity correctly.
+        return ar.almost_equals_with(br, tol) && ai.almost_equals_with(bi, tol);
+    }
+    // Rescale so that the largest component has magnitude 1, which avoids
+    // both overflow and underflow when squaring.
+    let mut scale = T::MIN_POSITIVE;
+    for c in [ar.abs(), ai.abs(), br.abs(), bi.abs()] {
+        if c > scale {
+            scale = c;
+        }
+    }
+    let (ar, ai, br, bi) = (ar / scale, ai / scale, br / scale, bi / scale);
+    let (dr, di) = (ar - br, ai - bi);
+    let diff_sq = dr * dr + di * di;
+    let a_sq = ar * ar + ai * ai;
+    let b_sq = br * br + bi * bi;
+    let mag_sq = if a_sq > b_sq { a_sq } else { b_sq };
+    // Match `almost::equal` for exactly equal values, including zero.
+    (ar == br && ai == bi) || diff_sq < tol * tol * mag_sq
+}
+
+/// Returns true if the complex numbers `lhs` and `rhs` have almost equal
+/// magnitudes, with the relative tolerance `magnitude_tolerance`, and phases
+/// which differ by at most `phase_tolerance` radians. This requires the `std`
+/// or `libm` feature.
+///
+/// ```
+/// // A phasor near the negative real axis, within 1% and 1° of the expected
+/// // one, even though their phases are reported as about ±180°.
+/// let polar = |m: f64, deg: f64| (m * deg.to_radians().cos(), m * deg.to_radians().sin());
+/// let expected = polar(1.0, 179.5);
+/// let measured = polar(1.005, -179.8);
+/// assert!(almost::complex_polar_equal(expected, measured, 0.01, 1f64.to_radians()));
+/// assert!(!almost::complex_polar_equal(expected, measured, 0.001, 1f64.to_radians()));
+/// assert!(!almost::complex_polar_equal(expected, polar(1.0, 178.0), 0.01, 1f64.to_radians()));
+/// ```
+///
+/// This is how gain and phase are usually specified, e.g. by RF and control
+/// engineers, and neither [`ComplexMetric`] can express it: the allowed phase
+/// error doesn't depend on the magnitude, and the allowed magnitude error
+/// doesn't depend on the phase. The phase difference is measured the short way
+/// around the circle, so phases near `±π` are compared sensibly, and a
+/// `phase_tolerance` of `π` or more allows any phase.
+///
+/// The phase of zero is meaningless, so zero is only almost equal to another
+/// zero. Returns false if any component of either value is NaN or infinite.
+///
+/// # Panics
+/// This function panics in debug mode if `magnitude_tolerance` is less than
+/// `T::EPSILON` or greater than 1.0, or if `phase_tolerance` isn't positive.
+#[cfg(any(feature = "std", feature = "libm"))]
+pub fn complex_polar_equal<T: Float, C: ComplexParts<T>>(
+    lhs: C,
+    rhs: C,
+    magnitude_tolerance: T,
+    phase_tolerance: T,
+) -> bool {
+    let magnitude_tolerance = crate::float::check_rel_tol(magnitude_tolerance);
+    crate::float::check_zero_tol(phase_tolerance);
+    let (ar, ai) = lhs.parts();
+    let (br, bi) = rhs.parts();
+    if phase_tolerance.is_nan() || phase_tolerance <= T::default() {
+        return false;
+    }
+    if !(ar.is_finite() && ai.is_finite() && br.is_finite() && bi.is_finite()) {
+        return false;
+    }
+    if ar == br && ai == bi {
+        return true;
+    }
+    // Rescale as in `magnitude_equal`, which doesn't affect either the ratio
+    // of the magnitudes or the phases.
+    let mut scale = T::MIN_POSITIVE;
+    for c in [ar.abs(), ai.abs(), br.abs(), bi.abs()] {
+        if c > scale {
+            scale = c;
+        }
+    }
+    let (ar, ai, br, bi) = (ar / scale, ai / scale, br / scale, bi / scale);
+    let magnitude = |re: T, im: T| T::from_f64(crate::math::sqrt((re * re + im * im).to_f64()));
+    let (a, b) = (magnitude(ar, ai), magnitude(br, bi));
+    if !a.almost_equals_with(b, magnitude_tolerance) {
+        return false;
+    }
+    // For phases `θ` apart, the chord between the points on the unit circle
+    // is `2 sin(θ / 2)`, which unlike the cosine stays accurate for small
+    // angles.
+    let half = phase_tolerance.to_f64().min(core::f64::consts::PI) / 2.0;
+    let chord = T::from_f64(2.0 * crate::math::sin(half));
+    let (dr, di) = (ar / a - br / b, ai / a - bi / b);
+    dr * dr + di * di <= chord * chord
+}
@@ -0,0 +1,60 @@
+//! Comparisons which can be evaluated at compile time.
+
+macro_rules! impl_const_equal {
+    ($fp:ident, $equal:ident, $equal_with:ident, $TOLERANCE:ident) => {
+        #[doc = concat!(
+            "Equivalent to [`almost::equal`](crate::equal) for `", stringify!($fp), "`, but is a `const fn`."
+        )]
+        ///
+        /// ```
+        #[doc = concat!("const HALF: ", stringify!($fp), " = 0.1 + 0.2 + 0.2;")]
+        #[doc = concat!("const CHECK: bool = almost::", stringify!($equal), "(HALF, 0.5);")]
+        /// assert!(CHECK);
+        /// ```
+        ///
+        /// See also [`const_assert_almost_eq!`](crate::const_assert_almost_eq),
+        /// which fails compilation when constants aren't almost equal.
+        #[inline]
+        pub const fn $equal(lhs: $fp, rhs: $fp) -> bool {
+            crate::imp::$fp::eq_with_tol_impl(lhs, rhs, crate::$TOLERANCE)
+        }
+
+        #[doc = concat!(
+            "Equivalent to [`almost::equal_with`](crate::equal_with) for `", stringify!($fp), "`, but is a `const fn`."
+        )]
+        ///
+        /// # Panics
+        /// This function panics in debug mode (or fails to compile, when
+        /// evaluated at compile time) if `tolerance` is less than the machine
+        /// epsilon or greater than 1.0, as with `equal_with`.
+        #[inline]
+        pub const fn $equal_with(lhs: $fp, rhs: $fp, tolerance: $fp) -> bool {
+            #[cfg(not(feature = "no-panic"))]
+            debug_assert!(
+                tolerance >= $fp::EPSILON && tolerance <= 1.0,
+                "Tolerance must be between the machine epsilon and 1.0"
+            );
+            crate::imp::$fp::eq_with_tol_impl(lhs, rhs, tolerance)
+        }
+
+        impl __ConstPair<$fp> {
+            #[inline]
+            pub const fn equal(&self) -> bool {
+                $equal(self.0, self.1)
+            }
+
+            #[inline]
+            pub const fn equal_with(&self, tolerance: $fp) -> bool {
+                $equal_with(self.0, self.1, tolerance)
+            }
+        }
+    };
+}
+
+/// Used by `const_assert_almost_eq!` to pick the function for the type of its
+/// arguments, as generic functions can't call trait methods in a `const fn`.
+#[doc(hidden)]
+pub struct __ConstPair<T>(pub T, pub T);
+
+impl_const_equal!(f32, const_equal_f32, const_equal_with_f32, F32_TOLERANCE);
+impl_const_equal!(f64, const_equal_f64, const_equal_with_f64, F64_TOLERANCE);
@@ -0,0 +1,315 @@
+//! Comparison of curves and functions by adaptive sampling. This requires the
+//! `alloc` feature.
+
+use crate::Float;
+use alloc::collections::BinaryHeap;
+use core::cmp::Ordering;
+use core::fmt;
+
+/// Values of a curve at a parameter, as compared by
+/// [`almost::curves_equal`](curves_equal).
+///
+/// This is implemented for floats, for functions of one variable, and for
+/// arrays of them, for parametric curves.
+pub trait CurvePoint<T>: Copy {
+    /// The largest magnitude of any coordinate.
+    fn magnitude(&self) -> T;
+    /// The largest difference between corresponding coordinates.
+    fn distance(&self, other: &Self) -> T;
+    /// The point halfway between `self` and `other`.
+    fn midpoint(&self, other: &Self) -> Self;
+}
+
+impl<T: Float> CurvePoint<T> for T {
+    #[inline]
+    fn magnitude(&self) -> T {
+        self.abs()
+    }
+
+    #[inline]
+    fn distance(&self, other: &Self) -> T {
+        (*self - *other).abs()
+    }
+
+    #[inline]
+    fn midpoint(&self, other: &Self) -> Self {
+        *self / T::from_f64(2.0) + *other / T::from_f64(2.0)
+    }
+}
+
+impl<T: Float, const N: usize> CurvePoint<T> for [T; N] {
+    #[inline]
+    fn magnitude(&self) -> T {
+        self.iter().fold(T::default(), |m, v| max(m, v.magnitude()))
+    }
+
+    #[inline]
+    fn distance(&self, other: &Self) -> T {
+        let pairs = self.iter().zip(other);
+        pairs.fold(T::default(), |m, (a, b)| max(m, a.distance(b)))
+    }
+
+    #[inline]
+    fn midpoint(&self, other: &Self) -> Self {
+        let mut mid = *self;
+        for (m, b) in mid.iter_mut().zip(other) {
+            *m = m.midpoint(b);
+        }
+        mid
+    }
+}
+
+/// The larger of `a` and `b`, or NaN if either is.
+#[inline]
+fn max<T: Float>(a: T, b: T) -> T {
+    if a.is_nan() || b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// The default limit on the number of parameters at which
+/// [`almost::curves_equal`](curves_equal) evaluates the curves.
+pub const DEFAULT_CURVE_SAMPLES: usize = 2048;
+
+/// The number of equal intervals sampled before refining.
+const INITIAL_INTERVALS: usize = 32;
+
+/// The results of comparing two curves, from
+/// [`almost::curves_equal`](curves_equal).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveReport<T> {
+    /// The parameter where the curves differed the most.
+    pub parameter: T,
+    /// The largest difference found between the curves, relative to `scale`.
+    /// This is NaN if either curve was NaN at `parameter`.
+    pub deviation: T,
+    /// The largest magnitude of any coordinate of either curve, at any of the
+    /// parameters sampled.
+    pub scale: T,
+    /// The relative tolerance which was used.
+    pub tolerance: T,
+    /// The number of parameters at which the curves were evaluated.
+    pub samples: usize,
+}
+
+impl<T: Float> CurveReport<T> {
+    /// Returns true if the curves were almost equal at every parameter which
+    /// was sampled.
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.deviation < self.tolerance
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for CurveReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "largest relative deviation {} at parameter {} (tolerance {}, {} samples)",
+            self.deviation, self.parameter, self.tolerance, self.samples
+        )
+    }
+}
+
+/// A sample of both curves: the parameter, each curve's value, and the
+/// distance between them.
+type Sample<T, P> = (T, P, P, T);
+
+/// An interval of the parameter which may be refined.
+struct Interval<T, P> {
+    priority: f64,
+    a: Sample<T, P>,
+    b: Sample<T, P>,
+}
+
+impl<T, P> PartialEq for Interval<T, P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T, P> Eq for Interval<T, P> {}
+
+impl<T, P> PartialOrd for Interval<T, P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, P> Ord for Interval<T, P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.total_cmp(&other.priority)
+    }
+}
+
+/// Returns the results of comparing the curves `lhs` and `rhs` for parameters
+/// from `start` to `end`, by sampling them adaptively, with the default
+/// tolerance and sample limit.
+///
+/// ```
+/// // A function and a (bad) approximation of it, which only differs much
+/// // in a narrow region.
+/// let exact = |x: f64| x * x;
+/// let approx = |x: f64| x * x + 1e-3 * (-((x - 0.637) * 200.0).powi(2)).exp();
+/// let r = almost::curves_equal(exact, approx, 0.0, 1.0);
+/// assert!(!r.passed());
+/// assert!((r.parameter - 0.637).abs() < 1e-3);
+///
+/// // A parametric curve (a circle), computed in two ways.
+/// let circle = |t: f64| [t.cos(), t.sin()];
+/// let (c, s) = (|t: f64| (t / 2.0).cos(), |t: f64| (t / 2.0).sin());
+/// let doubled = |t: f64| [c(t) * c(t) - s(t) * s(t), 2.0 * s(t) * c(t)];
+/// let r = almost::curves_equal_with(circle, doubled, 0.0, 6.0, 1e-12, 500);
+/// assert!(r.passed());
+/// assert_eq!(r.samples, 500);
+/// ```
+///
+/// This is for comparing functions whose representations can't sensibly be
+/// compared directly, such as splines fitted with different knots, or a
+/// piecewise-polynomial approximation against the function it approximates.
+/// The curves are evaluated at evenly spaced parameters, and then more
+/// densely wherever either curve or the difference between them isn't close
+/// to linear, or where they're close to failing, so that narrow features are
+/// found without sampling everywhere densely.
+///
+/// Differences are relative to the scale of the curves as a whole, rather than
+/// to their values at each parameter, so a function which crosses zero isn't
+/// held to an impossible standard there. For each parameter, the difference is
+/// the largest difference of any coordinate, and the scale is the largest
+/// magnitude of any coordinate of either curve over all the parameters
+/// sampled.
+///
+/// This is sampling, so it can't prove the curves are almost equal: features
+/// much narrower than the spacing of the initial samples can be missed. The
+/// report gives the parameter of the largest deviation found, and sampling
+/// stops early if either curve is NaN.
+///
+/// # Panics
+/// This function panics if `start` or `end` aren't finite, or if `start` is
+/// greater than `end`.
+#[inline]
+pub fn curves_equal<T, P, F, G>(lhs: F, rhs: G, start: T, end: T) -> CurveReport<T>
+where
+    T: Float,
+    P: CurvePoint<T>,
+    F: FnMut(T) -> P,
+    G: FnMut(T) -> P,
+{
+    curves_equal_with(
+        lhs,
+        rhs,
+        start,
+        end,
+        crate::float::default_rel_tol(),
+        DEFAULT_CURVE_SAMPLES,
+    )
+}
+
+/// Equivalent to [`almost::curves_equal`](curves_equal), but uses the provided
+/// relative tolerance, and evaluates the curves at no more than `max_samples`
+/// parameters (or two, if it's less than that).
+///
+/// # Panics
+/// This function panics if `start` or `end` aren't finite, or if `start` is
+/// greater than `end`.
+///
+/// It panics in debug mode if `tolerance` is less than `T::EPSILON` or greater
+/// than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn curves_equal_with<T, P, F, G>(
+    mut lhs: F,
+    mut rhs: G,
+    start: T,
+    end: T,
+    tolerance: T,
+    max_samples: usize,
+) -> CurveReport<T>
+where
+    T: Float,
+    P: CurvePoint<T>,
+    F: FnMut(T) -> P,
+    G: FnMut(T) -> P,
+{
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    assert!(
+        start.is_finite() && end.is_finite() && start <= end,
+        "The parameters of a curve must be a finite, non-empty range"
+    );
+    let zero = T::default();
+    let mut report = CurveReport {
+        parameter: start,
+        deviation: zero,
+        scale: T::MIN_POSITIVE,
+        tolerance,
+        samples: 0,
+    };
+    let mut sample = |t: T, report: &mut CurveReport<T>| -> Sample<T, P> {
+        let (f, g) = (lhs(t), rhs(t));
+        let d = f.distance(&g);
+        report.samples += 1;
+        report.scale = max(report.scale, max(f.magnitude(), g.magnitude()));
+        // `deviation` holds the absolute distance until the end.
+        if d.is_nan() || d > report.deviation {
+            report.deviation = d;
+            report.parameter = t;
+        }
+        (t, f, g, d)
+    };
+    let done = |report: &CurveReport<T>| report.deviation.is_nan() || report.scale.is_nan();
+    let mut heap = BinaryHeap::new();
+    let n = INITIAL_INTERVALS.min(max_samples.saturating_sub(1)).max(1);
+    let mut prev = sample(start, &mut report);
+    for i in 1..=n {
+        if start == end || done(&report) {
+            break;
+        }
+        let t = if i == n {
+            end
+        } else {
+            start + (end - start) * T::from_f64(i as f64 / n as f64)
+        };
+        let next = sample(t, &mut report);
+        // Each initial interval is refined once before anything else.
+        heap.push(Interval {
+            priority: f64::INFINITY,
+            a: prev,
+            b: next,
+        });
+        prev = next;
+    }
+    while report.samples < max_samples && !done(&report) {
+        let Interval { a, b, .. } = match heap.pop() {
+            Some(interval) => interval,
+            None => break,
+        };
+        let t = a.0.midpoint(&b.0);
+        if !(a.0 < t && t < b.0) {
+            continue;
+        }
+        let m = sample(t, &mut report);
+        // How far the difference and each curve are from linear here, which
+        // suggests there's more to find between these points.
+        let bump = max(
+            m.3.distance(&a.3.midpoint(&b.3)),
+            max(
+                m.1.distance(&a.1.midpoint(&b.1)),
+                m.2.distance(&a.2.midpoint(&b.2)),
+            ),
+        ) / report.scale;
+        let refine =
+            |d: T| bump > tolerance / T::from_f64(4.0) || d >= tolerance / T::from_f64(2.0);
+        for &(a, b) in &[(a, m), (m, b)] {
+            let d = max(a.3, b.3) / report.scale;
+            if refine(d) {
+                heap.push(Interval {
+                    priority: (d + bump).to_f64(),
+                    a,
+                    b,
+                });
+            }
+        }
+    }
+    report.deviation = report.deviation / report.scale;
+    report
+}
@@ -0,0 +1,124 @@
+//! A comparison which is computed exactly, with integer arithmetic.
+
+use crate::Float;
+
+/// The magnitude of `f` as `m · 2^e`, where `m` is the significand as an
+/// integer (including the implicit bit). Infinity is treated as the power of
+/// two after the largest finite value, which is how `eq_with_tol_impl` treats
+/// it. Must not be NaN.
+fn parts<T: Float>(f: T) -> (u64, i32) {
+    let sig_bits = T::SIGNIFICAND_BITS;
+    let exp_bits = T::BYTES as u32 * 8 - sig_bits - 1;
+    let bias = (1i32 << (exp_bits - 1)) - 1;
+    let mag = f.abs().to_ordered() as u64;
+    let raw_exp = (mag >> sig_bits) as i32;
+    let frac = mag & ((1 << sig_bits) - 1);
+    if raw_exp == 0 {
+        (frac, 1 - bias - sig_bits as i32)
+    } else if raw_exp == (1 << exp_bits) - 1 {
+        (1 << sig_bits, raw_exp - bias - sig_bits as i32)
+    } else {
+        (frac | (1 << sig_bits), raw_exp - bias - sig_bits as i32)
+    }
+}
+
+/// Returns true if `x · 2^ex < y · 2^ey`, exactly.
+fn lt_scaled(x: u128, ex: i32, y: u128, ey: i32) -> bool {
+    if x == 0 || y == 0 {
+        return x < y;
+    }
+    // Compare the positions of the top bits first, and if they match, then
+    // shifting the one with the larger exponent to line them up can't overflow.
+    let top_x = ex + (128 - x.leading_zeros() as i32);
+    let top_y = ey + (128 - y.leading_zeros() as i32);
+    if top_x != top_y {
+        top_x < top_y
+    } else if ex >= ey {
+        (x << (ex - ey)) < y
+    } else {
+        x < (y << (ey - ex))
+    }
+}
+
+/// Returns true if `lhs` and `rhs` are almost equal, as with
+/// [`almost::equal`](crate::equal), but computed exactly using integer
+/// arithmetic on the bit patterns.
+///
+/// ```
+/// assert!(almost::deterministic_equal(0.1 + 0.2, 0.3));
+/// assert!(!almost::deterministic_equal(1.0f32, 1.01));
+/// ```
+///
+/// The result only depends on the bits of the inputs, so it's identical on
+/// every platform, at every optimization level, and regardless of excess
+/// precision (as on x87) or contraction into fused multiply-adds, as needed to
+/// keep a lockstep simulation from diverging, or for reproducible results.
+///
+/// This computes `|lhs - rhs| < tol · max(|lhs|, |rhs|, MIN_POSITIVE)` without
+/// rounding, so it can differ from [`almost::equal`](crate::equal) (which
+/// rounds the subtraction and the multiplication) for values right on the
+/// boundary. It's also somewhat slower.
+#[inline]
+pub fn deterministic_equal<T: Float>(lhs: T, rhs: T) -> bool {
+    deterministic_equal_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::deterministic_equal`](deterministic_equal), but uses
+/// the provided relative tolerance.
+///
+/// ```
+/// // Exactly on the boundary, so not equal.
+/// assert!(!almost::deterministic_equal_with(1.0, 0.75, 0.25));
+/// assert!(almost::deterministic_equal_with(1.0, 0.75, 0.2500001));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with). The
+/// result is only exact for tolerances in that range, although it's
+/// deterministic either way.
+pub fn deterministic_equal_with<T: Float>(lhs: T, rhs: T, tolerance: T) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    // Comparisons are exact, so these are fine.
+    if lhs.is_nan() || rhs.is_nan() || tolerance.is_nan() || tolerance <= T::default() {
+        return false;
+    }
+    // `a` is the larger magnitude.
+    let (a, b) = if lhs.abs().to_ordered() >= rhs.abs().to_ordered() {
+        (lhs, rhs)
+    } else {
+        (rhs, lhs)
+    };
+    let (am, ae) = parts(a);
+    let (bm, be) = parts(b);
+    if am == 0 {
+        // Both are zero.
+        return true;
+    }
+    let same_sign = bm == 0 || (a < T::default()) == (b < T::default());
+    // The difference, as `diff · 2^diff_exp`. When `b` is far smaller than `a`,
+    // it only matters as a tiny adjustment, so it's replaced by a single unit
+    // far below `a`. That's exact for any tolerance below 1.0, as the result
+    // is then nowhere near the tolerance.
+    let (bm, shift) = match (ae - be) as u32 {
+        _ if bm == 0 => (0, 0),
+        k if k <= 64 => (bm, k),
+        _ => (1, 64),
+    };
+    let big = (am as u128) << shift;
+    let diff = if same_sign {
+        big - bm as u128
+    } else {
+        big + bm as u128
+    };
+    let diff_exp = ae - shift as i32;
+    // The allowed difference, `tol · max(|a|, MIN_POSITIVE)`, which is exact
+    // as both significands fit in 64 bits.
+    let (sm, se) = if a.abs() < T::MIN_POSITIVE {
+        parts(T::MIN_POSITIVE)
+    } else {
+        (am, ae)
+    };
+    let (tm, te) = parts(tolerance);
+    lt_scaled(diff, diff_exp, tm as u128 * sm as u128, te + se)
+}
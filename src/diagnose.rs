@@ -0,0 +1,131 @@
+//! Explaining the result of a comparison.
+
+use crate::{Float, Hint};
+use core::fmt;
+
+/// Everything about a comparison which is useful for understanding why it
+/// passed or failed, as returned by [`almost::diagnose`](diagnose).
+///
+/// Its `Display` output lists each of these on its own line, in the same
+/// format as the panic message of
+/// [`assert_almost_eq!`](crate::assert_almost_eq), so it can be printed
+/// directly in a test failure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison<T> {
+    /// The left hand side.
+    pub lhs: T,
+    /// The right hand side.
+    pub rhs: T,
+    /// The absolute difference, `|lhs - rhs|`. This is 0 for equal values
+    /// (including equal infinities), and NaN if either value is NaN.
+    pub abs_diff: T,
+    /// The relative difference, `abs_diff / scale`, computed the same way as
+    /// the comparison itself, so that (up to rounding) it's less than
+    /// `tolerance` exactly when the comparison passes. NaN if either value is
+    /// NaN.
+    pub rel_diff: T,
+    /// The distance between the values in units in the last place, as with
+    /// [`almost::ulp_distance`](crate::ulp_distance), or `None` if either
+    /// value is NaN.
+    pub ulps: Option<u64>,
+    /// The magnitude the difference is relative to, `max(|lhs|, |rhs|,
+    /// MIN_POSITIVE)`.
+    pub scale: T,
+    /// The relative tolerance the values were compared with.
+    pub tolerance: T,
+    /// Whether the values were almost equal.
+    pub passed: bool,
+    /// A likely mistake, if the comparison failed and one was detected, as
+    /// with [`Hint::detect`].
+    pub hint: Option<Hint>,
+}
+
+/// Compares `lhs` and `rhs` as [`almost::equal`](crate::equal) does, returning
+/// the details of the comparison rather than just whether it passed.
+///
+/// ```
+/// let c = almost::diagnose(1.0f64, 1.0 + 1e-4);
+/// assert!(!c.passed);
+/// assert!((c.rel_diff - 1e-4).abs() < 1e-7);
+/// assert_eq!(c.ulps, Some(450359962737));
+/// assert_eq!(c.scale, 1.0 + 1e-4);
+/// ```
+///
+/// This is for understanding failures, such as ones which only happen in CI,
+/// without recomputing the relative difference by hand. The result prints as:
+///
+/// ```text
+///      left: 1.0
+///     right: 1.0001
+///  abs diff: 9.999999999998899e-5
+///  rel diff: 9.999000099991662e-5
+///      ulps: 450359962737
+///     scale: 1.0001
+/// tolerance: 1.4901161193847656e-8
+///    passed: false
+/// ```
+#[inline]
+pub fn diagnose<T: Float>(lhs: T, rhs: T) -> Comparison<T> {
+    diagnose_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::diagnose`](diagnose), but compares using the
+/// provided relative tolerance, as with [`almost::equal_with`](crate::equal_with).
+///
+/// ```
+/// let c = almost::diagnose_with(0.0f32, 1e-20, 1e-3);
+/// assert!(!c.passed);
+/// assert_eq!(c.hint, Some(almost::Hint::ZeroOperand));
+/// let c = almost::diagnose_with(f64::INFINITY, f64::INFINITY, 1e-3);
+/// assert!(c.passed && c.abs_diff == 0.0 && c.ulps == Some(0));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn diagnose_with<T: Float>(lhs: T, rhs: T, tolerance: T) -> Comparison<T> {
+    let passed = lhs.almost_equals_with(rhs, tolerance);
+    let max = |a: T, b: T| if b > a { b } else { a };
+    Comparison {
+        lhs,
+        rhs,
+        abs_diff: if lhs == rhs {
+            T::default()
+        } else {
+            (lhs - rhs).abs()
+        },
+        rel_diff: lhs.rel_err(rhs),
+        ulps: lhs.ulp_distance(rhs),
+        scale: max(max(lhs.abs(), rhs.abs()), T::MIN_POSITIVE),
+        tolerance,
+        passed,
+        hint: if passed {
+            None
+        } else {
+            Hint::detect(lhs, rhs, tolerance)
+        },
+    }
+}
+
+impl<T: Float> fmt::Display for Comparison<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "     left: {:?}\n    right: {:?}\n abs diff: {:?}\n rel diff: {:?}\n",
+            self.lhs, self.rhs, self.abs_diff, self.rel_diff
+        )?;
+        match self.ulps {
+            Some(ulps) => writeln!(f, "     ulps: {}", ulps)?,
+            None => f.write_str("     ulps: none\n")?,
+        }
+        write!(
+            f,
+            "    scale: {:?}\ntolerance: {:?}\n   passed: {}",
+            self.scale, self.tolerance, self.passed
+        )?;
+        if let Some(hint) = &self.hint {
+            write!(f, "\n     hint: {}", hint)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+//! Comparison of `num-dual` dual numbers, with separate tolerances for the
+//! value and the derivatives. This requires the `num-dual` feature.
+
+use crate::AlmostEqual;
+use num_dual::{Dual, Dual2, HyperDual};
+
+/// Dual numbers which can be compared by
+/// [`almost::dual_equal_with`](dual_equal_with).
+///
+/// This is implemented for `num_dual`'s `Dual`, `Dual2` and `HyperDual`.
+pub trait DualParts<T>: Copy {
+    /// Returns the value (or real part).
+    fn value(self) -> T;
+
+    /// Returns true if `f` returns true for each pair of corresponding
+    /// derivative parts of `self` and `rhs`.
+    fn all_derivatives<F: FnMut(T, T) -> bool>(self, rhs: Self, f: F) -> bool;
+}
+
+macro_rules! impl_dual {
+    ($ty:ident, [$($derivative:ident),*]) => {
+        impl<T: Copy> DualParts<T> for $ty<T> {
+            #[inline]
+            fn value(self) -> T {
+                self.re
+            }
+
+            #[inline]
+            fn all_derivatives<F: FnMut(T, T) -> bool>(self, rhs: Self, mut f: F) -> bool {
+                true $(&& f(self.$derivative, rhs.$derivative))*
+            }
+        }
+
+        /// Dual numbers are almost equal if the value and every derivative
+        /// part are almost equal, and almost zero if they're all almost zero.
+        /// Use [`almost::dual_equal_with`](dual_equal_with) to allow a
+        /// different tolerance for the derivatives.
+        impl<T> AlmostEqual for $ty<T>
+        where
+            T: AlmostEqual + Copy,
+            T::Float: Copy,
+        {
+            type Float = T::Float;
+
+            const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+            const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+            #[inline]
+            fn almost_equals(self, rhs: Self) -> bool {
+                self.re.almost_equals(rhs.re) $(&& self.$derivative.almost_equals(rhs.$derivative))*
+            }
+
+            #[inline]
+            fn almost_zero(self) -> bool {
+                self.re.almost_zero() $(&& self.$derivative.almost_zero())*
+            }
+
+            #[inline]
+            fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+                dual_equal_with(self, rhs, tol, tol)
+            }
+
+            #[inline]
+            fn almost_zero_with(self, tol: Self::Float) -> bool {
+                self.re.almost_zero_with(tol) $(&& self.$derivative.almost_zero_with(tol))*
+            }
+        }
+    };
+}
+
+impl_dual!(Dual, [eps]);
+impl_dual!(Dual2, [v1, v2]);
+impl_dual!(HyperDual, [eps1, eps2, eps1eps2]);
+
+/// Returns true if the values of the dual numbers `lhs` and `rhs` are almost
+/// equal with the relative tolerance `value_tolerance`, and each of their
+/// derivatives are almost equal with `derivative_tolerance`. This requires the
+/// `num-dual` feature.
+///
+/// ```
+/// use num_dual::Dual64;
+/// // d/dx x^3 at x = 2, from forward-mode autodiff and a reference.
+/// let x = Dual64 { re: 2.0, eps: 1.0 };
+/// let computed = x * x * x;
+/// let expected = Dual64 { re: 8.0, eps: 12.000001 };
+/// assert!(!almost::equal(computed, expected));
+/// assert!(almost::dual_equal_with(computed, expected, almost::F64_TOLERANCE, 1e-6));
+/// ```
+///
+/// Derivatives computed by automatic differentiation usually lose more
+/// precision than the values do, and references for them often come from
+/// finite differences, so they tend to need a looser tolerance. As with
+/// [`almost::equal`](crate::equal), a part which is exactly zero is only
+/// almost equal to another zero, so a derivative which should vanish needs to
+/// be exactly zero in both.
+///
+/// # Panics
+/// This function panics in debug mode if either tolerance is less than
+/// `T::MACHINE_EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn dual_equal_with<D, T>(
+    lhs: D,
+    rhs: D,
+    value_tolerance: T::Float,
+    derivative_tolerance: T::Float,
+) -> bool
+where
+    D: DualParts<T>,
+    T: AlmostEqual,
+    T::Float: Copy,
+{
+    lhs.value().almost_equals_with(rhs.value(), value_tolerance)
+        && lhs.all_derivatives(rhs, |a, b| a.almost_equals_with(b, derivative_tolerance))
+}
@@ -0,0 +1,119 @@
+//! Overriding the default tolerances from the environment, for investigating
+//! failures. This requires the `env-tolerance` feature.
+//!
+//! When the feature is enabled, comparisons of `f32` and `f64` values which
+//! would use the default tolerance instead use the one given by an environment
+//! variable, if it's set:
+//!
+//! - `ALMOST_REL_TOL` replaces the default relative tolerance, as used by
+//!   [`almost::equal`](crate::equal). This is parsed as a [`Tolerance`], so
+//!   `1e-6`, `6digits`, `4ulps` and `0.1%` all work, and ulps are relative to
+//!   each type's epsilon.
+//! - `ALMOST_ZERO_TOL` replaces the default absolute tolerance, as used by
+//!   [`almost::zero`](crate::zero). This is a positive number.
+//!
+//! This is so that CI can loosen or tighten a whole test suite temporarily,
+//! such as to see how far off the results are on a new platform, without
+//! changing any code. It's not meant for production, where tolerances should be
+//! chosen in the code.
+//!
+//! Each variable is read the first time it's needed (for each type), and not
+//! again, so changing them while the program runs has no effect. An invalid
+//! value panics at that point, rather than being silently ignored, and an
+//! empty one is treated as unset.
+//!
+//! The override applies wherever the default tolerance for `f32` or `f64` is
+//! chosen, such as in [`almost::equal`](crate::equal),
+//! [`almost::zero`](crate::zero),
+//! [`assert_almost_eq!`](crate::assert_almost_eq) and
+//! [`Tolerance::default`](crate::Tolerance). Functions which are generic over
+//! `AlmostEqual` (such as [`almost::le`](crate::le)) choose the default of the
+//! values they're given, as do the impls in this crate for containers and
+//! vectors of `f32` or `f64`, and those from `#[derive(AlmostEqual)]`. Other
+//! impls see it only if they override
+//! [`AlmostEqual::almost_equals`](crate::AlmostEqual::almost_equals) and
+//! [`AlmostEqual::almost_zero`](crate::AlmostEqual::almost_zero) to do so.
+//!
+//! A tolerance which is passed explicitly, such as to
+//! [`almost::equal_with`](crate::equal_with), is always used as is, even if
+//! it's equal to [`F64_TOLERANCE`](crate::F64_TOLERANCE). The `const`
+//! comparisons, such as [`almost::const_equal_f64`](crate::const_equal_f64),
+//! don't see the override either.
+
+use crate::{Float, Tolerance};
+use std::string::{String, ToString};
+use std::sync::OnceLock;
+
+const REL_VAR: &str = "ALMOST_REL_TOL";
+const ZERO_VAR: &str = "ALMOST_ZERO_TOL";
+
+/// Parses a variable for a specific type, returning the tolerance as `f64`.
+type Parse = fn(&str) -> Result<f64, String>;
+
+/// The parsed overrides, for `f32` and `f64`.
+static REL: [OnceLock<Option<f64>>; 2] = [OnceLock::new(), OnceLock::new()];
+static ZERO: [OnceLock<Option<f64>>; 2] = [OnceLock::new(), OnceLock::new()];
+
+/// Returns the relative tolerance set by `ALMOST_REL_TOL` for `T`, or `None` if
+/// it's not set.
+///
+/// # Panics
+/// Panics if the variable isn't a valid tolerance for `T`.
+pub fn rel_tolerance<T: Float>() -> Option<T> {
+    let (cell, parse): (_, Parse) = if T::BYTES == 4 {
+        (&REL[0], |s| {
+            let t = s.parse::<Tolerance<f32>>().map_err(|e| e.to_string())?;
+            Ok(t.get() as f64)
+        })
+    } else {
+        (&REL[1], |s| {
+            let t = s.parse::<Tolerance<f64>>().map_err(|e| e.to_string())?;
+            Ok(t.get())
+        })
+    };
+    read(cell, REL_VAR, parse).map(T::from_f64)
+}
+
+/// Returns the absolute tolerance set by `ALMOST_ZERO_TOL` for `T`, or `None`
+/// if it's not set.
+///
+/// # Panics
+/// Panics if the variable isn't a positive number, or isn't representable as
+/// a positive `T`.
+pub fn zero_tolerance<T: Float>() -> Option<T> {
+    let (cell, parse): (_, Parse) = if T::BYTES == 4 {
+        (&ZERO[0], |s| {
+            let t = s.trim().parse::<f32>().map_err(|e| e.to_string())?;
+            positive(t).map(f64::from)
+        })
+    } else {
+        (&ZERO[1], |s| {
+            positive(s.trim().parse::<f64>().map_err(|e| e.to_string())?)
+        })
+    };
+    read(cell, ZERO_VAR, parse).map(T::from_f64)
+}
+
+fn positive<T: Float>(t: T) -> Result<T, String> {
+    if t.is_finite() && t > T::default() {
+        Ok(t)
+    } else {
+        Err("tolerance must be finite and greater than zero".to_string())
+    }
+}
+
+fn read(cell: &OnceLock<Option<f64>>, var: &str, parse: Parse) -> Option<f64> {
+    *cell.get_or_init(|| {
+        let value = std::env::var_os(var)?;
+        let value = value
+            .to_str()
+            .unwrap_or_else(|| panic!("{} isn't valid unicode", var));
+        if value.trim().is_empty() {
+            return None;
+        }
+        match parse(value) {
+            Ok(v) => Some(v),
+            Err(e) => panic!("invalid {}={:?}: {}", var, value, e),
+        }
+    })
+}
@@ -0,0 +1,265 @@
+//! The [`Float`] trait, for APIs which only make sense for primitive floats.
+
+use crate::AlmostEqual;
+
+/// A primitive floating point type, such as `f32` or `f64`.
+///
+/// Some APIs in this crate need to look at the representation of the values,
+/// and so can't be implemented on top of [`AlmostEqual`] alone. This trait is
+/// sealed, and can't be implemented outside of this crate.
+pub trait Float:
+    AlmostEqual<Float = Self>
+    + Copy
+    + PartialOrd
+    + Default
+    + core::fmt::Debug
+    + core::ops::Add<Output = Self>
+    + core::ops::Sub<Output = Self>
+    + core::ops::Mul<Output = Self>
+    + core::ops::Div<Output = Self>
+    + core::ops::Rem<Output = Self>
+    + core::ops::Neg<Output = Self>
+    + sealed::Sealed
+{
+    /// Equivalent to [`almost::ulp_distance`](crate::ulp_distance).
+    /// ```
+    /// use almost::Float;
+    /// assert_eq!(1.0f32.ulp_distance(1.0 + f32::EPSILON), Some(1));
+    /// ```
+    #[inline]
+    fn ulp_distance(self, rhs: Self) -> Option<u64> {
+        if self.is_nan() || rhs.is_nan() {
+            None
+        } else {
+            Some(self.to_ordered().abs_diff(rhs.to_ordered()))
+        }
+    }
+
+    /// Equivalent to [`almost::ulp_equal`](crate::ulp_equal).
+    /// ```
+    /// use almost::Float;
+    /// assert!((0.1f64 + 0.2).ulp_equal(0.3, 1));
+    /// ```
+    #[inline]
+    fn ulp_equal(self, rhs: Self, max_ulps: u64) -> bool {
+        self.ulp_distance(rhs).is_some_and(|d| d <= max_ulps)
+    }
+}
+
+pub(crate) mod sealed {
+    /// The operations we need from the underlying type. Lives in a private
+    /// module so that it can't be implemented or called externally.
+    pub trait Sealed: Copy {
+        /// The number of explicitly stored significand bits.
+        const SIGNIFICAND_BITS: u32;
+
+        const MIN_POSITIVE: Self;
+
+        fn abs(self) -> Self;
+
+        fn is_nan(self) -> bool;
+
+        fn is_finite(self) -> bool;
+
+        /// See `imp::to_ordered`.
+        fn to_ordered(self) -> i64;
+
+        /// See `imp::from_ordered`.
+        fn from_ordered(o: i64) -> Self;
+
+        /// See `imp::exponent`.
+        fn exponent(self) -> i32;
+
+        /// See `imp::rel_err`.
+        fn rel_err(self, rhs: Self) -> Self;
+
+        /// The size of the type in bytes.
+        const BYTES: usize;
+
+        /// Decodes a value from exactly `Self::BYTES` bytes.
+        fn from_bytes(bytes: &[u8], order: crate::ByteOrder) -> Self;
+
+        fn to_f64(self) -> f64;
+
+        fn from_f64(f: f64) -> Self;
+    }
+}
+
+macro_rules! impl_float {
+    ($fp:ident) => {
+        impl sealed::Sealed for $fp {
+            const SIGNIFICAND_BITS: u32 = crate::imp::$fp::SIGNIFICAND_SIZE as u32;
+
+            const MIN_POSITIVE: Self = $fp::MIN_POSITIVE;
+
+            #[inline]
+            fn abs(self) -> Self {
+                crate::imp::$fp::abs(self)
+            }
+
+            #[inline]
+            fn is_nan(self) -> bool {
+                self.is_nan()
+            }
+
+            #[inline]
+            fn is_finite(self) -> bool {
+                self.is_finite()
+            }
+
+            #[inline]
+            fn to_ordered(self) -> i64 {
+                crate::imp::$fp::to_ordered(self)
+            }
+
+            #[inline]
+            fn from_ordered(o: i64) -> Self {
+                crate::imp::$fp::from_ordered(o)
+            }
+
+            #[inline]
+            fn exponent(self) -> i32 {
+                crate::imp::$fp::exponent(self)
+            }
+
+            #[inline]
+            fn rel_err(self, rhs: Self) -> Self {
+                crate::imp::$fp::rel_err(self, rhs)
+            }
+
+            const BYTES: usize = core::mem::size_of::<$fp>();
+
+            #[inline]
+            fn from_bytes(bytes: &[u8], order: crate::ByteOrder) -> Self {
+                let mut buf = [0u8; core::mem::size_of::<$fp>()];
+                buf.copy_from_slice(bytes);
+                match order {
+                    crate::ByteOrder::Little => $fp::from_le_bytes(buf),
+                    crate::ByteOrder::Big => $fp::from_be_bytes(buf),
+                }
+            }
+
+            #[inline]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            #[inline]
+            fn from_f64(f: f64) -> Self {
+                f as $fp
+            }
+        }
+
+        impl Float for $fp {}
+    };
+}
+
+impl_float!(f32);
+impl_float!(f64);
+
+/// Checks that `tol` is a sensible relative tolerance: at least the machine
+/// epsilon, and less than 1.0.
+///
+/// Normally this is a `debug_assert!`. With the `no-panic` feature, it instead
+/// clamps `tol` into that range, and NaN is returned unchanged (so that
+/// nothing compares equal, aside from equal infinities).
+#[inline]
+pub(crate) fn check_rel_tol<T: Float>(tol: T) -> T {
+    check_rel_tol_eps(tol, T::MACHINE_EPSILON)
+}
+
+/// As [`check_rel_tol`], but with the machine epsilon of a narrower type,
+/// for the `half` types, whose tolerances are checked after widening.
+#[inline]
+pub(crate) fn check_rel_tol_eps<T: Float>(tol: T, epsilon: T) -> T {
+    #[cfg(not(feature = "no-panic"))]
+    {
+        debug_assert!(
+            tol < T::from_f64(1.0),
+            "Tolerance should not be greater than 1.0"
+        );
+        debug_assert!(
+            tol >= epsilon,
+            "Tolerance should not be smaller than the machine epsilon"
+        );
+        tol
+    }
+    #[cfg(feature = "no-panic")]
+    {
+        if tol >= T::from_f64(1.0) {
+            // The largest value below 1.0.
+            T::from_f64(1.0) - epsilon / T::from_f64(2.0)
+        } else if tol < epsilon {
+            epsilon
+        } else {
+            tol
+        }
+    }
+}
+
+/// Checks that `tol` is a sensible tolerance for a ratio: at least the machine
+/// epsilon. Unlike relative tolerances, large values are fine.
+///
+/// With the `no-panic` feature, this clamps `tol` up to the machine epsilon
+/// instead, and NaN is returned unchanged.
+#[inline]
+pub(crate) fn check_ratio_tol<T: Float>(tol: T) -> T {
+    #[cfg(not(feature = "no-panic"))]
+    {
+        debug_assert!(
+            tol >= T::MACHINE_EPSILON,
+            "Tolerance should not be smaller than the machine epsilon"
+        );
+        tol
+    }
+    #[cfg(feature = "no-panic")]
+    {
+        if tol < T::MACHINE_EPSILON {
+            T::MACHINE_EPSILON
+        } else {
+            tol
+        }
+    }
+}
+
+/// Checks that `tol` is a sensible absolute tolerance, that is, greater than
+/// zero.
+///
+/// This is a `debug_assert!`, unless the `no-panic` feature is enabled. Either
+/// way, nothing is almost zero when compared with a tolerance which isn't
+/// greater than zero, so there's nothing to clamp.
+#[inline]
+pub(crate) fn check_zero_tol<T: Float>(tol: T) {
+    #[cfg(not(feature = "no-panic"))]
+    debug_assert!(tol > T::default());
+    #[cfg(feature = "no-panic")]
+    let _ = tol;
+}
+
+/// The relative tolerance to use for `T` when none is given. This is
+/// `T::DEFAULT_TOLERANCE`, unless the `env-tolerance` feature overrides it.
+#[inline]
+pub(crate) fn default_rel_tol<T: Float>() -> T {
+    #[cfg(feature = "env-tolerance")]
+    {
+        crate::env::rel_tolerance().unwrap_or(T::DEFAULT_TOLERANCE)
+    }
+    #[cfg(not(feature = "env-tolerance"))]
+    {
+        T::DEFAULT_TOLERANCE
+    }
+}
+
+/// As [`default_rel_tol`], but for the absolute tolerance used to check for
+/// zero.
+#[inline]
+pub(crate) fn default_zero_tol<T: Float>() -> T {
+    #[cfg(feature = "env-tolerance")]
+    {
+        crate::env::zero_tolerance().unwrap_or(T::DEFAULT_TOLERANCE)
+    }
+    #[cfg(not(feature = "env-tolerance"))]
+    {
+        T::DEFAULT_TOLERANCE
+    }
+}
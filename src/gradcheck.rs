@@ -0,0 +1,171 @@
+//! Checking analytic gradients against finite differences. This requires the
+//! `alloc` feature.
+//!
+//! ```
+//! // f(x, y) = x² y + sin(y)
+//! let f = |v: &[f64]| v[0] * v[0] * v[1] + v[1].sin();
+//! let grad = |v: &[f64]| [2.0 * v[0] * v[1], v[0] * v[0] + v[1].cos()];
+//!
+//! let x = [1.5, -0.5];
+//! let result = almost::gradcheck::check(f, &x, &grad(&x));
+//! assert!(result.passed(), "{:?}", result);
+//!
+//! // A typo in the derivative is caught.
+//! let wrong = [2.0 * x[0] * x[1], x[0] * x[0] - x[1].cos()];
+//! let result = almost::gradcheck::check(f, &x, &wrong);
+//! assert!(!result.passed());
+//! assert_eq!(result.worst().unwrap(), 1);
+//! ```
+//!
+//! Each component of the gradient is estimated with a central difference,
+//! `(f(x + h) - f(x - h)) / 2h`. The step `h` is `∛ε · max(|xᵢ|, 1)`, which
+//! balances the truncation error of the difference against the rounding error
+//! in evaluating `f`, and gives an estimate accurate to roughly `ε^⅔` (relative
+//! to the size of `f` and its derivatives).
+//!
+//! Components are compared relatively, with the [`TOLERANCE`]. Additionally, a
+//! component passes if the difference is within the error expected from the
+//! finite difference itself, which is what allows components which should be
+//! exactly zero to pass. The truncation error is estimated by repeating the
+//! difference with twice the step, and the rounding error assumes that `f` is
+//! accurate to within a few ULPs; if it isn't, use [`check_with`] with a looser
+//! tolerance.
+
+use crate::imp::f64::abs;
+use alloc::vec::Vec;
+
+/// The default relative tolerance for a gradient check, `∛ε` (about `6e-6`).
+///
+/// This is much looser than [`F64_TOLERANCE`](crate::F64_TOLERANCE), since
+/// even the best step size for a finite difference loses about a third of the
+/// precision, and functions with large higher derivatives lose more.
+pub const TOLERANCE: f64 = 6.0554544523933395e-6;
+
+/// The step used for finite differences, relative to the size of the input.
+/// Happens to be the same value as the tolerance.
+const STEP: f64 = 6.0554544523933395e-6;
+
+/// One component of a [`GradCheck`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Component {
+    /// The finite difference estimate of the derivative.
+    pub numeric: f64,
+    /// The derivative which was provided.
+    pub analytic: f64,
+    /// The difference between `numeric` and `analytic`, relative to the larger
+    /// of their magnitudes. Zero if they're equal, NaN if either is NaN.
+    pub relative_error: f64,
+    /// Whether this component passed the check.
+    pub passed: bool,
+}
+
+/// The result of a gradient check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradCheck {
+    /// The result for each component of the gradient, in order.
+    pub components: Vec<Component>,
+    /// The relative tolerance that was used.
+    pub tolerance: f64,
+}
+
+impl GradCheck {
+    /// Returns true if every component passed.
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.components.iter().all(|c| c.passed)
+    }
+
+    /// The index of the failing component with the largest relative error, or
+    /// `None` if every component passed. NaN is considered larger than
+    /// everything.
+    pub fn worst(&self) -> Option<usize> {
+        let mut worst: Option<(usize, f64)> = None;
+        for (i, c) in self
+            .components
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.passed)
+        {
+            let worse = match worst {
+                None => true,
+                Some((_, e)) => c.relative_error > e || (c.relative_error.is_nan() && !e.is_nan()),
+            };
+            if worse {
+                worst = Some((i, c.relative_error));
+            }
+        }
+        worst.map(|(i, _)| i)
+    }
+}
+
+/// Compares `gradient` with the gradient of `f` at `x`, estimated with central
+/// differences, using the default [`TOLERANCE`]. This evaluates `f` four times
+/// for each component.
+///
+/// # Panics
+/// Panics if `x` and `gradient` have different lengths.
+#[inline]
+pub fn check<F: FnMut(&[f64]) -> f64>(f: F, x: &[f64], gradient: &[f64]) -> GradCheck {
+    check_with(f, x, gradient, TOLERANCE)
+}
+
+/// Equivalent to [`check`], but uses the provided relative tolerance.
+///
+/// # Panics
+/// Panics if `x` and `gradient` have different lengths. Also panics in debug
+/// mode if `tolerance` is less than `f64::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+pub fn check_with<F>(mut f: F, x: &[f64], gradient: &[f64], tolerance: f64) -> GradCheck
+where
+    F: FnMut(&[f64]) -> f64,
+{
+    assert_eq!(
+        x.len(),
+        gradient.len(),
+        "gradient must have one component per input"
+    );
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let mut xs = x.to_vec();
+    let mut diff_at = |i: usize, step: f64| {
+        // Use the actual distance between the points, rather than `2 * step`,
+        // as `x ± step` is rounded.
+        let (hi, lo) = (x[i] + step, x[i] - step);
+        xs[i] = hi;
+        let f_hi = f(&xs);
+        xs[i] = lo;
+        let f_lo = f(&xs);
+        xs[i] = x[i];
+        let width = hi - lo;
+        // The rounding error in the difference, assuming `f` is accurate to a
+        // few ULPs.
+        let noise = 8.0 * f64::EPSILON * abs(f_hi).max(abs(f_lo)) / width;
+        ((f_hi - f_lo) / width, noise)
+    };
+    let components = gradient
+        .iter()
+        .enumerate()
+        .map(|(i, &analytic)| {
+            let step = STEP * abs(x[i]).max(1.0);
+            let (numeric, noise) = diff_at(i, step);
+            // The truncation error is proportional to the square of the step,
+            // so comparing with a doubled step estimates it.
+            let (coarse, coarse_noise) = diff_at(i, 2.0 * step);
+            let truncation = abs(coarse - numeric) / 3.0;
+            let allowance = 2.0 * (truncation + noise) + coarse_noise;
+
+            let diff = abs(numeric - analytic);
+            let scale = abs(numeric).max(abs(analytic));
+            let relative_error = if diff == 0.0 { 0.0 } else { diff / scale };
+            Component {
+                numeric,
+                analytic,
+                relative_error,
+                passed: diff == 0.0 || diff < tolerance * scale + allowance,
+            }
+        })
+        .collect();
+    GradCheck {
+        components,
+        tolerance,
+    }
+}
@@ -0,0 +1,153 @@
+//! Comparison of 2-D grids of values, such as images, heightmaps, or
+//! simulation fields.
+
+use crate::report::{ElementwiseReport, ReportBuilder};
+use crate::Float;
+use core::fmt;
+use core::ops::Range;
+
+/// A rectangular region of a grid.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GridRegion {
+    /// The rows in the region.
+    pub rows: Range<usize>,
+    /// The columns in the region.
+    pub cols: Range<usize>,
+}
+
+/// The result of [`almost::compare_grid`](compare_grid).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridReport<T> {
+    /// The number of columns in the grid.
+    pub width: usize,
+    /// The number of rows in the grid.
+    pub height: usize,
+    /// Statistics about the cells. Indices are `(row, column)`.
+    pub values: ElementwiseReport<T, (usize, usize)>,
+    /// The smallest region containing every cell which differed, or `None` if
+    /// none did.
+    pub bounding_box: Option<GridRegion>,
+}
+
+impl<T> GridReport<T> {
+    /// Returns true if every cell was almost equal.
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.values.passed()
+    }
+
+    /// The fraction of the cells which differed, from 0.0 to 1.0. This is 0.0
+    /// for an empty grid.
+    #[inline]
+    pub fn fraction_differing(&self) -> f64 {
+        if self.values.total == 0 {
+            0.0
+        } else {
+            self.values.mismatches as f64 / self.values.total as f64
+        }
+    }
+
+    /// Returns true if at most `max_fraction` of the cells differed, which
+    /// allows for a few differences from e.g. anti-aliasing or a different
+    /// rasterizer.
+    ///
+    /// This uses the count of differing cells, so `0.0` is the same as
+    /// [`passed`](GridReport::passed).
+    #[inline]
+    pub fn passed_with_fraction(&self, max_fraction: f64) -> bool {
+        self.values.mismatches as f64 <= max_fraction * self.values.total as f64
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for GridReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{} grid: {}", self.width, self.height, self.values)?;
+        if let Some(b) = &self.bounding_box {
+            write!(
+                f,
+                "; differences in rows {:?}, columns {:?}",
+                b.rows, b.cols
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two 2-D grids, given as row-major slices with `width` columns,
+/// reporting how many cells differ and where.
+///
+/// ```
+/// let width = 4;
+/// let mut actual = vec![1.0; 4 * 3];
+/// let expected = actual.clone();
+/// actual[1 * width + 2] = 2.0;
+/// actual[2 * width + 3] = 0.5;
+/// let report = almost::compare_grid(&actual, &expected, width);
+/// assert_eq!(report.values.mismatches, 2);
+/// let bounds = report.bounding_box.clone().unwrap();
+/// assert_eq!((bounds.rows, bounds.cols), (1..3, 2..4));
+/// assert!(!report.passed());
+/// assert!(report.passed_with_fraction(0.2));
+/// ```
+///
+/// Cells are compared as with [`almost::equal`](crate::equal), and the report
+/// uses `(row, column)` indices. Its `Display` output includes the bounding box
+/// of the differences, which is usually enough to find the problem.
+///
+/// # Panics
+/// This function panics if the slices have different lengths, or if the length
+/// isn't a multiple of `width` (unless both are empty).
+#[inline]
+pub fn compare_grid<T: Float>(lhs: &[T], rhs: &[T], width: usize) -> GridReport<T> {
+    compare_grid_with(lhs, rhs, width, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::compare_grid`](compare_grid), but uses the provided
+/// relative tolerance.
+///
+/// # Panics
+/// This function panics if the slices have different lengths, or if the length
+/// isn't a multiple of `width`. It also panics in debug mode if `tolerance` is
+/// less than `T::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+pub fn compare_grid_with<T: Float>(
+    lhs: &[T],
+    rhs: &[T],
+    width: usize,
+    tolerance: T,
+) -> GridReport<T> {
+    assert_eq!(
+        lhs.len(),
+        rhs.len(),
+        "compare_grid requires grids of the same size"
+    );
+    assert!(
+        lhs.is_empty() || (width != 0 && lhs.len() % width == 0),
+        "grid length must be a multiple of the width"
+    );
+    let height = lhs.len().checked_div(width).unwrap_or(0);
+    let mut builder = ReportBuilder::new();
+    let mut bounds: Option<GridRegion> = None;
+    for (i, (&a, &b)) in lhs.iter().zip(rhs).enumerate() {
+        let (row, col) = (i / width, i % width);
+        if !builder.push(|| (row, col), a, b, tolerance) {
+            bounds = Some(match bounds {
+                None => GridRegion {
+                    rows: row..row + 1,
+                    cols: col..col + 1,
+                },
+                // Rows are visited in order, so only the end can grow.
+                Some(r) => GridRegion {
+                    rows: r.rows.start..row + 1,
+                    cols: r.cols.start.min(col)..r.cols.end.max(col + 1),
+                },
+            });
+        }
+    }
+    GridReport {
+        width,
+        height,
+        values: builder.finish(),
+        bounding_box: bounds,
+    }
+}
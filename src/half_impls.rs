@@ -0,0 +1,114 @@
+//! Implementations of [`AlmostEqual`] for `half`'s `f16` and `bf16`. This
+//! requires the `half` feature.
+
+use crate::imp::half::{eq_with_tol_impl, Format, BF16, F16};
+use crate::AlmostEqual;
+use half::{bf16, f16};
+
+/// The default tolerance used for `f16`. Equivalent to `f16::EPSILON.sqrt()`
+/// (or `0.03125`), as we assume that around half of the precision bits of any
+/// arbitrary value have been rounded away.
+///
+/// This can be changed crate-wide with the `strict-defaults` and
+/// `loose-defaults` features, as with [`F32_TOLERANCE`](crate::F32_TOLERANCE).
+#[cfg(not(any(feature = "strict-defaults", feature = "loose-defaults")))]
+pub const F16_TOLERANCE: f16 = f16::from_f32_const(0.03125);
+/// The default tolerance used for `bf16`. Equivalent to `bf16::EPSILON.sqrt()`
+/// (or about `0.0884`), as we assume that around half of the precision bits of
+/// any arbitrary value have been rounded away.
+///
+/// This can be changed crate-wide with the `strict-defaults` and
+/// `loose-defaults` features, as with [`F32_TOLERANCE`](crate::F32_TOLERANCE).
+#[cfg(not(any(feature = "strict-defaults", feature = "loose-defaults")))]
+pub const BF16_TOLERANCE: bf16 = bf16::from_f32_const(0.088388346);
+
+/// The default tolerance used for `f16`. With the `strict-defaults` feature,
+/// this is `f16::EPSILON.powf(2.0 / 3.0)` (or about `0.00984`).
+#[cfg(feature = "strict-defaults")]
+pub const F16_TOLERANCE: f16 = f16::from_f32_const(0.009843133);
+/// The default tolerance used for `bf16`. With the `strict-defaults` feature,
+/// this is `bf16::EPSILON.powf(2.0 / 3.0)` (or about `0.0394`).
+#[cfg(feature = "strict-defaults")]
+pub const BF16_TOLERANCE: bf16 = bf16::from_f32_const(0.039372534);
+
+/// The default tolerance used for `f16`. With the `loose-defaults` feature,
+/// this is `f16::EPSILON.cbrt()` (or about `0.0992`).
+#[cfg(all(feature = "loose-defaults", not(feature = "strict-defaults")))]
+pub const F16_TOLERANCE: f16 = f16::from_f32_const(0.099212565);
+/// The default tolerance used for `bf16`. With the `loose-defaults` feature,
+/// this is `bf16::EPSILON.cbrt()` (or about `0.198`).
+#[cfg(all(feature = "loose-defaults", not(feature = "strict-defaults")))]
+pub const BF16_TOLERANCE: bf16 = bf16::from_f32_const(0.19842513);
+
+#[inline]
+fn equal(lhs: f64, rhs: f64, tol: f64, epsilon: f64, format: &Format) -> bool {
+    let tol = crate::float::check_rel_tol_eps(tol, epsilon);
+    eq_with_tol_impl(lhs, rhs, tol, format)
+}
+
+#[inline]
+fn zero(v: f64, tol: f64) -> bool {
+    crate::float::check_zero_tol(tol);
+    crate::imp::f64::abs(v) < tol
+}
+
+/// Compares as `f32` does, but with the limits of `f16`: values below
+/// `f16::MIN_POSITIVE` are compared relative to it, and infinities are almost
+/// equal to values near `f16::MAX`.
+///
+/// ```
+/// use half::f16;
+/// let a = f16::from_f32(0.1) + f16::from_f32(0.2);
+/// assert!(almost::equal(a, f16::from_f32(0.3)));
+/// assert!(!almost::equal(f16::from_f32(1.0), f16::from_f32(1.25)));
+/// assert!(almost::equal(f16::MAX, f16::INFINITY));
+/// assert!(almost::zero(f16::from_f32(1e-3)));
+/// ```
+impl AlmostEqual for f16 {
+    type Float = f16;
+
+    const MACHINE_EPSILON: f16 = f16::EPSILON;
+
+    const DEFAULT_TOLERANCE: f16 = F16_TOLERANCE;
+
+    #[inline]
+    fn almost_equals_with(self, rhs: Self, tol: f16) -> bool {
+        let epsilon = f16::EPSILON.to_f64();
+        equal(self.to_f64(), rhs.to_f64(), tol.to_f64(), epsilon, &F16)
+    }
+
+    #[inline]
+    fn almost_zero_with(self, tol: f16) -> bool {
+        zero(self.to_f64(), tol.to_f64())
+    }
+}
+
+/// Compares as `f32` does, but with the precision of `bf16`, which has the
+/// same range as `f32`.
+///
+/// ```
+/// use half::bf16;
+/// let a = bf16::from_f32(0.1) + bf16::from_f32(0.2);
+/// assert!(almost::equal(a, bf16::from_f32(0.3)));
+/// assert!(!almost::equal(bf16::from_f32(1.0), bf16::from_f32(1.5)));
+/// assert!(almost::equal(bf16::MAX, bf16::INFINITY));
+/// assert!(almost::equal_with(bf16::from_f32(1.0), bf16::from_f32(1.2), bf16::from_f32(0.25)));
+/// ```
+impl AlmostEqual for bf16 {
+    type Float = bf16;
+
+    const MACHINE_EPSILON: bf16 = bf16::EPSILON;
+
+    const DEFAULT_TOLERANCE: bf16 = BF16_TOLERANCE;
+
+    #[inline]
+    fn almost_equals_with(self, rhs: Self, tol: bf16) -> bool {
+        let epsilon = bf16::EPSILON.to_f64();
+        equal(self.to_f64(), rhs.to_f64(), tol.to_f64(), epsilon, &BF16)
+    }
+
+    #[inline]
+    fn almost_zero_with(self, tol: bf16) -> bool {
+        zero(self.to_f64(), tol.to_f64())
+    }
+}
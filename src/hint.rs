@@ -0,0 +1,64 @@
+//! Detection of common mistakes when comparing floats.
+
+use crate::Float;
+use core::fmt;
+
+/// A likely mistake detected in a failed comparison, along with a suggestion
+/// for fixing it.
+///
+/// ```
+/// use almost::Hint;
+/// assert_eq!(Hint::detect(1e-9, 0.0, almost::F64_TOLERANCE), Some(Hint::ZeroOperand));
+/// assert_eq!(Hint::detect(1.0, 1.0 + 2e-16, f64::EPSILON), Some(Hint::EpsilonTolerance));
+/// assert_eq!(Hint::detect(1.0, 2.0, almost::F64_TOLERANCE), None);
+/// ```
+///
+/// These are reported in an [`ElementwiseReport`](crate::ElementwiseReport),
+/// and shown in its `Display` output, so that the problem is pointed out where
+/// the test failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hint {
+    /// One of the values was NaN, which is never almost equal to anything
+    /// (including NaN).
+    NanOperand,
+    /// One of the values was exactly zero, and the other wasn't. A relative
+    /// comparison between these always fails, and
+    /// [`almost::zero`](crate::zero) should have been used instead.
+    ZeroOperand,
+    /// The tolerance was the machine epsilon, which is far too strict for the
+    /// result of all but the most trivial computations.
+    EpsilonTolerance,
+}
+
+impl Hint {
+    /// Checks a pair of values which were not almost equal (with a relative
+    /// tolerance of `tolerance`) for a likely mistake.
+    pub fn detect<T: Float>(lhs: T, rhs: T, tolerance: T) -> Option<Hint> {
+        let zero = T::default();
+        if lhs.is_nan() || rhs.is_nan() {
+            Some(Hint::NanOperand)
+        } else if (lhs == zero) != (rhs == zero) {
+            Some(Hint::ZeroOperand)
+        } else if tolerance <= T::MACHINE_EPSILON {
+            Some(Hint::EpsilonTolerance)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Hint::NanOperand => "a value is NaN, which is never equal to anything (even NaN)",
+            Hint::ZeroOperand => {
+                "a value is exactly zero, so a relative comparison can't succeed; \
+                 use `almost::zero` to compare with zero"
+            }
+            Hint::EpsilonTolerance => {
+                "the tolerance is the machine epsilon, which is too strict for most \
+                 computations; try the default tolerance instead"
+            }
+        })
+    }
+}
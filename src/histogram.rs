@@ -0,0 +1,176 @@
+//! Comparison of histograms, such as binned event counts. This requires the
+//! `std` or `libm` feature.
+
+use crate::report::{ElementwiseReport, ReportBuilder};
+use crate::Float;
+use core::fmt;
+
+/// A histogram with `contents.len()` bins, where bin `i` covers
+/// `edges[i]..edges[i + 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Histogram<'a, T> {
+    /// The bin edges, in order. There's one more edge than there are bins.
+    pub edges: &'a [T],
+    /// The count or total weight in each bin.
+    pub contents: &'a [T],
+}
+
+impl<'a, T> Histogram<'a, T> {
+    /// Creates a histogram from its bin edges and contents.
+    ///
+    /// # Panics
+    /// Panics if `edges.len()` isn't `contents.len() + 1`.
+    #[inline]
+    pub fn new(edges: &'a [T], contents: &'a [T]) -> Self {
+        assert_eq!(
+            edges.len(),
+            contents.len() + 1,
+            "A histogram needs one more edge than it has bins"
+        );
+        Histogram { edges, contents }
+    }
+}
+
+/// How the contents of each pair of bins are compared by
+/// [`almost::histogram_equal_with`](histogram_equal_with).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinTolerance<T> {
+    /// The contents must be almost equal with this relative tolerance, as with
+    /// [`almost::equal_with`](crate::equal_with).
+    Relative(T),
+    /// The contents are independent Poisson counts, and must differ by at most
+    /// this many standard deviations. The standard deviation of the difference
+    /// of counts `a` and `b` is estimated as `sqrt(a + b)`.
+    ///
+    /// This is for unweighted counts: for weighted contents the variance
+    /// depends on the squares of the weights, which a histogram doesn't record.
+    Poisson(T),
+}
+
+/// The reason a histogram comparison failed, as returned by
+/// [`almost::histogram_equal`](histogram_equal).
+#[derive(Debug, Clone, PartialEq)]
+pub enum HistogramMismatch<T> {
+    /// The histograms have different numbers of bins.
+    BinCount {
+        /// The number of bins on the left hand side.
+        lhs: usize,
+        /// The number of bins on the right hand side.
+        rhs: usize,
+    },
+    /// The bin edges weren't almost equal. Indices in the report are those of
+    /// the edges.
+    Edges(ElementwiseReport<T, usize>),
+    /// The edges matched, but the contents of some bins didn't. Indices in the
+    /// report are those of the bins.
+    Contents(ElementwiseReport<T, usize>),
+}
+
+impl<T: fmt::Display> fmt::Display for HistogramMismatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistogramMismatch::BinCount { lhs, rhs } => write!(
+                f,
+                "histograms have different numbers of bins ({} vs {})",
+                lhs, rhs
+            ),
+            HistogramMismatch::Edges(report) => write!(f, "bin edges differ: {}", report),
+            HistogramMismatch::Contents(report) => write!(f, "bin contents differ: {}", report),
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> core::error::Error for HistogramMismatch<T> {}
+
+/// Returns `Ok(())` if the histograms `lhs` and `rhs` have almost equal bin
+/// edges and contents, using the default tolerance for both.
+///
+/// ```
+/// use almost::{BinTolerance, Histogram};
+/// let edges = [0.0, 0.5, 1.0, 1.5];
+/// let old = Histogram::new(&edges, &[100.0, 400.0, 90.0]);
+/// let new = Histogram::new(&edges, &[108.0, 391.0, 96.0]);
+/// assert!(almost::histogram_equal(old, new).is_err());
+/// // Within three standard deviations of the Poisson noise.
+/// let tol = BinTolerance::Poisson(3.0);
+/// assert!(almost::histogram_equal_with(old, new, almost::F64_TOLERANCE, tol).is_ok());
+/// ```
+///
+/// The edges are checked before the contents, and only compared relatively,
+/// so an edge which is exactly zero must be exactly zero in both.
+#[inline]
+pub fn histogram_equal<T: Float>(
+    lhs: Histogram<'_, T>,
+    rhs: Histogram<'_, T>,
+) -> Result<(), HistogramMismatch<T>> {
+    let tolerance = crate::float::default_rel_tol();
+    histogram_equal_with(lhs, rhs, tolerance, BinTolerance::Relative(tolerance))
+}
+
+/// Equivalent to [`almost::histogram_equal`](histogram_equal), but uses the
+/// provided relative tolerance for the edges, and compares the contents as
+/// described by `contents`.
+///
+/// # Panics
+/// This function panics if either histogram doesn't have one more edge than
+/// it has bins.
+///
+/// It panics in debug mode if `edge_tolerance` (or a relative tolerance for
+/// the contents) is less than `T::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with), or if a number of standard
+/// deviations for the contents isn't positive.
+pub fn histogram_equal_with<T: Float>(
+    lhs: Histogram<'_, T>,
+    rhs: Histogram<'_, T>,
+    edge_tolerance: T,
+    contents: BinTolerance<T>,
+) -> Result<(), HistogramMismatch<T>> {
+    let (lhs, rhs) = (
+        Histogram::new(lhs.edges, lhs.contents),
+        Histogram::new(rhs.edges, rhs.contents),
+    );
+    if lhs.contents.len() != rhs.contents.len() {
+        return Err(HistogramMismatch::BinCount {
+            lhs: lhs.contents.len(),
+            rhs: rhs.contents.len(),
+        });
+    }
+    let mut edges = ReportBuilder::new();
+    for (i, (&a, &b)) in lhs.edges.iter().zip(rhs.edges).enumerate() {
+        edges.push(|| i, a, b, edge_tolerance);
+    }
+    let edges = edges.finish();
+    if !edges.passed() {
+        return Err(HistogramMismatch::Edges(edges));
+    }
+    let mut report = ReportBuilder::new();
+    for (i, (&a, &b)) in lhs.contents.iter().zip(rhs.contents).enumerate() {
+        match contents {
+            BinTolerance::Relative(tolerance) => {
+                report.push(|| i, a, b, tolerance);
+            }
+            BinTolerance::Poisson(sigmas) => {
+                report.record(|| i, a, b, poisson_equal(a, b, sigmas));
+            }
+        }
+    }
+    let report = report.finish();
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(HistogramMismatch::Contents(report))
+    }
+}
+
+/// Returns true if the counts `a` and `b` differ by at most `sigmas` standard
+/// deviations.
+fn poisson_equal<T: Float>(a: T, b: T, sigmas: T) -> bool {
+    crate::float::check_zero_tol(sigmas);
+    if a == b {
+        return true;
+    }
+    let (a, b) = (a.to_f64(), b.to_f64());
+    let abs = crate::imp::f64::abs;
+    let sd = crate::math::sqrt(abs(a) + abs(b));
+    abs(a - b) <= sigmas.to_f64() * sd
+}
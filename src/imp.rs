@@ -2,7 +2,7 @@
 
 macro_rules! impl_equals {
     ($fp:ident, $bits:ident, $SIGNIFICAND_SIZE:expr) => {
-        const SIGNIFICAND_SIZE: $bits = $SIGNIFICAND_SIZE;
+        pub(crate) const SIGNIFICAND_SIZE: $bits = $SIGNIFICAND_SIZE;
         const EXPONENT_SIZE: $bits =
             (core::mem::size_of::<$fp>() as $bits) * 8 - SIGNIFICAND_SIZE - 1;
         const EXPONENT_MASK: $bits = ((1 << EXPONENT_SIZE) - 1) << SIGNIFICAND_SIZE;
@@ -12,37 +12,98 @@ macro_rules! impl_equals {
 
         // abs requires std? ugh.
         #[inline]
-        pub(crate) fn abs(f: $fp) -> $fp {
+        pub(crate) const fn abs(f: $fp) -> $fp {
             $fp::from_bits(f.to_bits() & !SIGN_BIT)
         }
 
+        /// Maps `f` to an integer such that the ordering of the integers matches
+        /// the ordering of the values, with both zeros mapping to 0, and
+        /// adjacent floats mapping to adjacent integers.
         #[inline]
-        pub(crate) fn eq_with_tol_impl(lhs: $fp, rhs: $fp, tol: $fp) -> bool {
+        pub(crate) fn to_ordered(f: $fp) -> i64 {
+            let bits = f.to_bits();
+            let mag = (bits & !SIGN_BIT) as i64;
+            if (bits & SIGN_BIT) != 0 {
+                -mag
+            } else {
+                mag
+            }
+        }
+
+        /// The inverse of `to_ordered`. Zero maps to positive zero, and values
+        /// beyond the range of the type map to NaN.
+        #[inline]
+        pub(crate) fn from_ordered(o: i64) -> $fp {
+            let mag = o.unsigned_abs();
+            if mag > (!SIGN_BIT) as u64 {
+                $fp::NAN
+            } else if o < 0 {
+                $fp::from_bits(mag as $bits | SIGN_BIT)
+            } else {
+                $fp::from_bits(mag as $bits)
+            }
+        }
+
+        /// The unbiased exponent of `f`. Meaningless for zero, subnormals and
+        /// non-finite values.
+        #[inline]
+        pub(crate) fn exponent(f: $fp) -> i32 {
+            ((f.to_bits() & EXPONENT_MASK) >> SIGNIFICAND_SIZE) as i32 - EXPONENT_BIAS as i32
+        }
+
+        #[inline]
+        pub(crate) const fn eq_with_tol_impl(lhs: $fp, rhs: $fp, tol: $fp) -> bool {
             let left_mag = abs(lhs);
             let right_mag = abs(rhs);
-            if !((left_mag < core::$fp::INFINITY) & (right_mag < core::$fp::INFINITY)) {
+            if !((left_mag < $fp::INFINITY) & (right_mag < $fp::INFINITY)) {
                 handle_not_finite(lhs, rhs, tol)
             } else {
-                let scale = if left_mag > right_mag {
-                    left_mag
-                } else {
-                    right_mag
-                };
-                // If both left_mag and right_mag are subnormal, rescale to
-                // MIN_POSITIVE instead, which is what they round against anyway.
-                let scale = if scale > core::$fp::MIN_POSITIVE {
-                    scale
-                } else {
-                    core::$fp::MIN_POSITIVE
-                };
-                let abs_tol = tol * scale;
-                abs(lhs - rhs) < abs_tol
+                eq_finite(lhs, rhs, tol)
+            }
+        }
+
+        /// `eq_with_tol_impl` for finite values. Split out so that
+        /// `handle_not_finite` isn't recursive, as recursion gets in the way of
+        /// inlining, which the `no-panic` check relies on.
+        #[inline]
+        const fn eq_finite(lhs: $fp, rhs: $fp, tol: $fp) -> bool {
+            let left_mag = abs(lhs);
+            let right_mag = abs(rhs);
+            let scale = if left_mag > right_mag {
+                left_mag
+            } else {
+                right_mag
+            };
+            // If both left_mag and right_mag are subnormal, rescale to
+            // MIN_POSITIVE instead, which is what they round against anyway.
+            let scale = if scale > $fp::MIN_POSITIVE {
+                scale
+            } else {
+                $fp::MIN_POSITIVE
+            };
+            if scale < SMALL_SCALE {
+                // `tol * scale` could be subnormal, and so only have a few bits
+                // of precision. Scaling by a power of two is exact here.
+                return abs(lhs * SMALL_RESCALE - rhs * SMALL_RESCALE)
+                    < tol * (scale * SMALL_RESCALE);
             }
+            let abs_tol = tol * scale;
+            abs(lhs - rhs) < abs_tol
         }
 
+        /// `2^(SIGNIFICAND_SIZE + 1)`, which is enough to make `tol * scale`
+        /// normal for any `tol` of at least the machine epsilon.
+        const SMALL_RESCALE: $fp =
+            $fp::from_bits((EXPONENT_BIAS + SIGNIFICAND_SIZE + 1) << SIGNIFICAND_SIZE);
+
+        /// Scales below which `eq_finite` rescales, `MIN_POSITIVE * SMALL_RESCALE`.
+        const SMALL_SCALE: $fp = $fp::from_bits((SIGNIFICAND_SIZE + 2) << SIGNIFICAND_SIZE);
+
         #[cold]
-        #[inline(never)]
-        fn handle_not_finite(lhs: $fp, rhs: $fp, tol: $fp) -> bool {
+        // The `no-panic` check can't see through calls which aren't inlined.
+        #[cfg_attr(not(feature = "no-panic"), inline(never))]
+        #[cfg_attr(feature = "no-panic", inline)]
+        const fn handle_not_finite(lhs: $fp, rhs: $fp, tol: $fp) -> bool {
             if lhs.is_nan() || rhs.is_nan() {
                 false
             } else if lhs.is_infinite() && rhs.is_infinite() {
@@ -58,14 +119,9 @@ macro_rules! impl_equals {
                 } else {
                     (rhs, lhs)
                 };
-                debug_assert!(
-                    rhs.is_finite() && lhs.is_infinite(),
-                    "logic bug {} {} {:x} {:x}",
-                    lhs,
-                    rhs,
-                    lhs.to_bits(),
-                    rhs.to_bits()
-                );
+                // No formatting, as this is a `const fn`.
+                #[cfg(not(feature = "no-panic"))]
+                debug_assert!(rhs.is_finite() && lhs.is_infinite(), "logic bug");
                 let rbits = rhs.to_bits();
                 if (rbits & EXPONENT_MASK) == 0 {
                     // subnormal, so clearly not equal to infinity, and would
@@ -73,14 +129,62 @@ macro_rules! impl_equals {
                     return false;
                 }
                 // XXX: does rust turn this into a constant like it should?
-                let max_float_binade_bits = core::$fp::MAX.to_bits() & EXPONENT_MASK;
+                let max_float_binade_bits = $fp::MAX.to_bits() & EXPONENT_MASK;
                 // copysign requires std, so just build directly.
                 let new_lhs = $fp::from_bits(max_float_binade_bits | (lhs.to_bits() & SIGN_BIT));
 
                 let rhs_rescale = $fp::from_bits((EXPONENT_BIAS - 1) << SIGNIFICAND_SIZE);
                 let new_rhs = rhs * rhs_rescale;
 
-                eq_with_tol_impl(new_lhs, new_rhs, tol)
+                eq_finite(new_lhs, new_rhs, tol)
+            }
+        }
+
+        /// The relative difference between `lhs` and `rhs`, defined so that
+        /// `eq_with_tol_impl(lhs, rhs, tol)` is (up to rounding) the same as
+        /// `rel_err(lhs, rhs) < tol`. NaN if either is NaN.
+        #[inline]
+        pub(crate) fn rel_err(lhs: $fp, rhs: $fp) -> $fp {
+            let left_mag = abs(lhs);
+            let right_mag = abs(rhs);
+            if !((left_mag < $fp::INFINITY) & (right_mag < $fp::INFINITY)) {
+                rel_err_not_finite(lhs, rhs)
+            } else {
+                let scale = if left_mag > right_mag {
+                    left_mag
+                } else {
+                    right_mag
+                };
+                let scale = if scale > $fp::MIN_POSITIVE {
+                    scale
+                } else {
+                    $fp::MIN_POSITIVE
+                };
+                // Divide first, so that this can't overflow.
+                abs(lhs / scale - rhs / scale)
+            }
+        }
+
+        #[cold]
+        #[inline(never)]
+        fn rel_err_not_finite(lhs: $fp, rhs: $fp) -> $fp {
+            if lhs.is_nan() || rhs.is_nan() {
+                $fp::NAN
+            } else if lhs == rhs {
+                0.0
+            } else {
+                // Same rescaling as `handle_not_finite`: infinities move to the
+                // largest binade, and finite values are halved.
+                let max_float_binade_bits = $fp::MAX.to_bits() & EXPONENT_MASK;
+                let rescale = $fp::from_bits((EXPONENT_BIAS - 1) << SIGNIFICAND_SIZE);
+                let fix = |f: $fp| {
+                    if f.is_infinite() {
+                        $fp::from_bits(max_float_binade_bits | (f.to_bits() & SIGN_BIT))
+                    } else {
+                        f * rescale
+                    }
+                };
+                rel_err(fix(lhs), fix(rhs))
             }
         }
     };
@@ -93,3 +197,63 @@ pub(crate) mod f32 {
 pub(crate) mod f64 {
     impl_equals!(f64, u64, 52);
 }
+
+/// Comparison of the `half` crate's types, which is done in `f64`. Every `f16`
+/// and `bf16` value converts to `f64` exactly, and their differences are far
+/// more precise there than any tolerance they can be compared with needs.
+#[cfg(feature = "half")]
+pub(crate) mod half {
+    use super::f64::abs;
+
+    /// The limits of a half precision format, as `f64`.
+    pub(crate) struct Format {
+        /// The smallest normal value, which subnormals are compared relative
+        /// to, as they round against it.
+        pub(crate) min_positive: f64,
+        /// The power of two just past the largest finite value, which
+        /// infinities are treated as, as for `f32` and `f64`.
+        pub(crate) infinity: f64,
+    }
+
+    pub(crate) const F16: Format = Format {
+        // 2^-14 and 2^16.
+        min_positive: 6.103515625e-5,
+        infinity: 65536.0,
+    };
+
+    pub(crate) const BF16: Format = Format {
+        // 2^-126 and 2^128, as for `f32`.
+        min_positive: f32::MIN_POSITIVE as f64,
+        infinity: 3.402823669209385e38,
+    };
+
+    #[inline]
+    pub(crate) fn eq_with_tol_impl(lhs: f64, rhs: f64, tol: f64, format: &Format) -> bool {
+        if lhs.is_nan() || rhs.is_nan() {
+            return false;
+        }
+        if lhs == rhs {
+            // Including equal infinities.
+            return true;
+        }
+        let finite = |f: f64| {
+            if f.is_infinite() {
+                if f < 0.0 {
+                    -format.infinity
+                } else {
+                    format.infinity
+                }
+            } else {
+                f
+            }
+        };
+        let (lhs, rhs) = (finite(lhs), finite(rhs));
+        let mut scale = format.min_positive;
+        for mag in [abs(lhs), abs(rhs)] {
+            if mag > scale {
+                scale = mag;
+            }
+        }
+        abs(lhs - rhs) < tol * scale
+    }
+}
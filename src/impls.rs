@@ -0,0 +1,388 @@
+//! Implementations of [`AlmostEqual`](crate::AlmostEqual) for types from
+//! `core` and `alloc`.
+//!
+//! These forward `almost_equals` and `almost_zero` to the same methods on
+//! their contents, rather than passing `DEFAULT_TOLERANCE` down, so that the
+//! contents choose their own default (which the `env-tolerance` feature can
+//! override).
+
+use crate::AlmostEqual;
+use core::ops::{Range, RangeInclusive};
+
+/// Results are almost equal if both are `Ok` with almost equal values, or both
+/// are `Err` with equal errors (compared with `PartialEq`). A result is almost
+/// zero if it's `Ok` with a value that's almost zero.
+impl<T: AlmostEqual, E: PartialEq> AlmostEqual for Result<T, E> {
+    type Float = T::Float;
+
+    const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+    const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_equals(self, rhs: Self) -> bool {
+        match (self, rhs) {
+            (Ok(a), Ok(b)) => a.almost_equals(b),
+            (Err(a), Err(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_zero(self) -> bool {
+        match self {
+            Ok(v) => v.almost_zero(),
+            Err(_) => false,
+        }
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+        match (self, rhs) {
+            (Ok(a), Ok(b)) => a.almost_equals_with(b, tol),
+            (Err(a), Err(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_zero_with(self, tol: Self::Float) -> bool {
+        match self {
+            Ok(v) => v.almost_zero_with(tol),
+            Err(_) => false,
+        }
+    }
+}
+
+macro_rules! impl_for_range {
+    ($range:ident, $parts:expr) => {
+        /// Ranges are almost equal if both endpoints are, and almost zero if
+        /// both endpoints are.
+        impl<T> AlmostEqual for $range<T>
+        where
+            T: AlmostEqual,
+            T::Float: Copy,
+        {
+            type Float = T::Float;
+
+            const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+            const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+            #[inline]
+            #[cfg_attr(feature = "audit", track_caller)]
+            fn almost_equals(self, rhs: Self) -> bool {
+                let parts = $parts;
+                let ((a0, a1), (b0, b1)) = (parts(self), parts(rhs));
+                a0.almost_equals(b0) && a1.almost_equals(b1)
+            }
+
+            #[inline]
+            #[cfg_attr(feature = "audit", track_caller)]
+            fn almost_zero(self) -> bool {
+                let (a0, a1) = $parts(self);
+                a0.almost_zero() && a1.almost_zero()
+            }
+
+            #[inline]
+            #[cfg_attr(feature = "audit", track_caller)]
+            fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+                let parts = $parts;
+                let ((a0, a1), (b0, b1)) = (parts(self), parts(rhs));
+                a0.almost_equals_with(b0, tol) && a1.almost_equals_with(b1, tol)
+            }
+
+            #[inline]
+            #[cfg_attr(feature = "audit", track_caller)]
+            fn almost_zero_with(self, tol: Self::Float) -> bool {
+                let (a0, a1) = $parts(self);
+                a0.almost_zero_with(tol) && a1.almost_zero_with(tol)
+            }
+        }
+    };
+}
+
+impl_for_range!(Range, |r: Range<T>| (r.start, r.end));
+impl_for_range!(RangeInclusive, RangeInclusive::into_inner);
+
+/// Slices are almost equal if they have the same length, and each pair of
+/// elements is almost equal. They're almost zero if every element is.
+impl<T> AlmostEqual for &[T]
+where
+    T: AlmostEqual + Copy,
+    T::Float: Copy,
+{
+    type Float = T::Float;
+
+    const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+    const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+    // These loop rather than using `Iterator::all`, as `track_caller` can't see
+    // through closures.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_equals(self, rhs: Self) -> bool {
+        if self.len() != rhs.len() {
+            return false;
+        }
+        for (&a, &b) in self.iter().zip(rhs) {
+            if !a.almost_equals(b) {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_zero(self) -> bool {
+        for &a in self {
+            if !a.almost_zero() {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+        if self.len() != rhs.len() {
+            return false;
+        }
+        for (&a, &b) in self.iter().zip(rhs) {
+            if !a.almost_equals_with(b, tol) {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_zero_with(self, tol: Self::Float) -> bool {
+        for &a in self {
+            if !a.almost_zero_with(tol) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Arrays are almost equal if each pair of elements is almost equal, and
+/// almost zero if every element is.
+impl<T, const N: usize> AlmostEqual for [T; N]
+where
+    T: AlmostEqual,
+    T::Float: Copy,
+{
+    type Float = T::Float;
+
+    const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+    const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+    // `IntoIterator` is called explicitly for arrays before edition 2021.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_equals(self, rhs: Self) -> bool {
+        for (a, b) in IntoIterator::into_iter(self).zip(rhs) {
+            if !a.almost_equals(b) {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_zero(self) -> bool {
+        for a in IntoIterator::into_iter(self) {
+            if !a.almost_zero() {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+        for (a, b) in IntoIterator::into_iter(self).zip(rhs) {
+            if !a.almost_equals_with(b, tol) {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_zero_with(self, tol: Self::Float) -> bool {
+        for a in IntoIterator::into_iter(self) {
+            if !a.almost_zero_with(tol) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use crate::AlmostEqual;
+    use alloc::borrow::{Cow, ToOwned};
+    use alloc::rc::Rc;
+    #[cfg(target_has_atomic = "ptr")]
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+
+    macro_rules! impl_for_pointer {
+        ($ptr:ident) => {
+            impl<T: AlmostEqual + Copy> AlmostEqual for $ptr<T> {
+                type Float = T::Float;
+
+                const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+                const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+                #[inline]
+                #[cfg_attr(feature = "audit", track_caller)]
+                fn almost_equals(self, rhs: Self) -> bool {
+                    (*self).almost_equals(*rhs)
+                }
+
+                #[inline]
+                #[cfg_attr(feature = "audit", track_caller)]
+                fn almost_zero(self) -> bool {
+                    (*self).almost_zero()
+                }
+
+                #[inline]
+                #[cfg_attr(feature = "audit", track_caller)]
+                fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+                    (*self).almost_equals_with(*rhs, tol)
+                }
+
+                #[inline]
+                #[cfg_attr(feature = "audit", track_caller)]
+                fn almost_zero_with(self, tol: Self::Float) -> bool {
+                    (*self).almost_zero_with(tol)
+                }
+            }
+        };
+    }
+
+    impl_for_pointer!(Rc);
+    #[cfg(target_has_atomic = "ptr")]
+    impl_for_pointer!(Arc);
+
+    /// Vectors are almost equal if they have the same length, and each pair of
+    /// elements is almost equal. They're almost zero if every element is.
+    impl<T> AlmostEqual for Vec<T>
+    where
+        T: AlmostEqual,
+        T::Float: Copy,
+    {
+        type Float = T::Float;
+
+        const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+        const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+        #[inline]
+        #[cfg_attr(feature = "audit", track_caller)]
+        fn almost_equals(self, rhs: Self) -> bool {
+            if self.len() != rhs.len() {
+                return false;
+            }
+            for (a, b) in self.into_iter().zip(rhs) {
+                if !a.almost_equals(b) {
+                    return false;
+                }
+            }
+            true
+        }
+
+        #[inline]
+        #[cfg_attr(feature = "audit", track_caller)]
+        fn almost_zero(self) -> bool {
+            for a in self {
+                if !a.almost_zero() {
+                    return false;
+                }
+            }
+            true
+        }
+
+        #[inline]
+        #[cfg_attr(feature = "audit", track_caller)]
+        fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+            if self.len() != rhs.len() {
+                return false;
+            }
+            for (a, b) in self.into_iter().zip(rhs) {
+                if !a.almost_equals_with(b, tol) {
+                    return false;
+                }
+            }
+            true
+        }
+
+        #[inline]
+        #[cfg_attr(feature = "audit", track_caller)]
+        fn almost_zero_with(self, tol: Self::Float) -> bool {
+            for a in self {
+                if !a.almost_zero_with(tol) {
+                    return false;
+                }
+            }
+            true
+        }
+    }
+
+    /// Slices are almost equal if they have the same length, and each pair of
+    /// elements is almost equal. They're almost zero if every element is.
+    impl<T> AlmostEqual for Cow<'_, [T]>
+    where
+        T: AlmostEqual + Copy,
+        T::Float: Copy,
+        [T]: ToOwned,
+    {
+        type Float = T::Float;
+
+        const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+        const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+        #[inline]
+        #[cfg_attr(feature = "audit", track_caller)]
+        fn almost_equals(self, rhs: Self) -> bool {
+            (&*self).almost_equals(&*rhs)
+        }
+
+        #[inline]
+        #[cfg_attr(feature = "audit", track_caller)]
+        fn almost_zero(self) -> bool {
+            (&*self).almost_zero()
+        }
+
+        #[inline]
+        #[cfg_attr(feature = "audit", track_caller)]
+        fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+            (&*self).almost_equals_with(&*rhs, tol)
+        }
+
+        #[inline]
+        #[cfg_attr(feature = "audit", track_caller)]
+        fn almost_zero_with(self, tol: Self::Float) -> bool {
+            (&*self).almost_zero_with(tol)
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! A sorted index over a set of floats, for tolerant range queries.
+
+use crate::Float;
+use alloc::vec::Vec;
+
+/// A sorted set of values supporting `O(log n)` queries for every value almost
+/// equal to some `x`, and for the value nearest to `x`.
+///
+/// ```
+/// let index = almost::SortedIndex::new(vec![0.5, 0.3, 0.1 + 0.2, 0.7]);
+/// // Each entry is the value, along with its position in the input.
+/// assert_eq!(index.within(0.3), &[(0.3, 1), (0.1 + 0.2, 2)]);
+/// assert_eq!(index.nearest(0.55), Some((0.5, 0)));
+/// ```
+///
+/// This is intended as a building block for deduplicating, snapping, or
+/// cross-referencing large sets of coordinates: each entry remembers its
+/// position in the original input, so results can be related back to it.
+///
+/// NaN values are dropped when the index is built, as they can never be found.
+#[derive(Debug, Clone)]
+pub struct SortedIndex<T> {
+    entries: Vec<(T, usize)>,
+    tol: T,
+}
+
+impl<T: Float> SortedIndex<T> {
+    /// Builds an index over `values`, comparing with the default tolerance (as
+    /// in [`almost::equal`](crate::equal)).
+    #[inline]
+    pub fn new<I: IntoIterator<Item = T>>(values: I) -> Self {
+        Self::with_tolerance(values, crate::float::default_rel_tol())
+    }
+
+    /// Builds an index over `values`, comparing with the provided relative
+    /// tolerance (as in [`almost::equal_with`](crate::equal_with)).
+    ///
+    /// ```
+    /// let index = almost::SortedIndex::with_tolerance(vec![1.0f32, 1.05, 1.2], 0.1);
+    /// assert_eq!(index.within(1.0).len(), 2);
+    /// ```
+    ///
+    /// # Panics
+    /// Queries panic in debug mode if `tolerance` is less than `T::EPSILON` or
+    /// greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+    pub fn with_tolerance<I: IntoIterator<Item = T>>(values: I, tolerance: T) -> Self {
+        let mut entries: Vec<(T, usize)> = values
+            .into_iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_nan())
+            .map(|(i, v)| (v, i))
+            .collect();
+        // Stable, so equal values stay in input order. There are no NaNs, so
+        // the `unwrap_or` is never used.
+        entries.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+        Self {
+            entries,
+            tol: tolerance,
+        }
+    }
+
+    /// The tolerance used for queries.
+    #[inline]
+    pub fn tolerance(&self) -> T {
+        self.tol
+    }
+
+    /// The number of values in the index.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the index holds no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Every entry in the index, sorted by value. Each entry is a value along
+    /// with its position in the input.
+    #[inline]
+    pub fn as_slice(&self) -> &[(T, usize)] {
+        &self.entries
+    }
+
+    /// Returns every entry whose value is almost equal to `x`, in ascending
+    /// order.
+    ///
+    /// Because relative comparison with a tolerance below 1.0 accepts a
+    /// contiguous interval around `x`, these are always adjacent in the index,
+    /// and can be found with a pair of binary searches.
+    pub fn within(&self, x: T) -> &[(T, usize)] {
+        let tol = self.tol;
+        let lo = self
+            .entries
+            .partition_point(|e| e.0 < x && !e.0.almost_equals_with(x, tol));
+        let hi = self
+            .entries
+            .partition_point(|e| e.0 <= x || e.0.almost_equals_with(x, tol));
+        // `hi < lo` is only possible if `x` is NaN.
+        if hi > lo {
+            &self.entries[lo..hi]
+        } else {
+            &[]
+        }
+    }
+
+    /// Returns true if any value in the index is almost equal to `x`.
+    #[inline]
+    pub fn contains(&self, x: T) -> bool {
+        !self.within(x).is_empty()
+    }
+
+    /// Returns the entry whose value is nearest to `x` by relative error,
+    /// whether or not it's almost equal. Returns `None` if the index is empty,
+    /// or `x` is NaN.
+    ///
+    /// If two values are equally near, the smaller one is returned.
+    pub fn nearest(&self, x: T) -> Option<(T, usize)> {
+        if x.is_nan() {
+            return None;
+        }
+        let p = self.entries.partition_point(|e| e.0 < x);
+        let above = self.entries.get(p).copied();
+        let below = p.checked_sub(1).map(|i| self.entries[i]);
+        match (below, above) {
+            (Some(b), Some(a)) if a.0.rel_err(x) < b.0.rel_err(x) => Some(a),
+            (Some(b), _) => Some(b),
+            (None, a) => a,
+        }
+    }
+}
+
+impl<T: Float> core::iter::FromIterator<T> for SortedIndex<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
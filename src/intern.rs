@@ -0,0 +1,117 @@
+//! A pool of canonical values, for merging values which are almost equal.
+
+use crate::{Float, TolerantMap};
+use alloc::vec::Vec;
+
+/// A pool which maps each value to a canonical representative: the first
+/// value added to the pool which is almost equal to it.
+///
+/// ```
+/// let mut pool = almost::InternPool::new();
+/// // Timestamps from two datasets, which computed them slightly differently.
+/// let a: Vec<f64> = [0.1, 0.2, 0.3].iter().map(|&t| pool.intern(t)).collect();
+/// let b: Vec<f64> = [0.1, 0.1 + 0.1, 0.1 + 0.2, 0.4].iter().map(|&t| pool.intern(t)).collect();
+/// assert_eq!(&a[..], &b[..3]);
+/// assert_eq!(pool.values(), &[0.1, 0.2, 0.3, 0.4]);
+/// ```
+///
+/// This is for merging near-duplicate coordinates or timestamps, such as when
+/// joining several datasets, so that values which are almost equal become
+/// exactly equal, and can then be hashed, sorted or compared with `==`. Every
+/// value interned into the same pool is merged consistently, however many
+/// datasets it came from.
+///
+/// Lookups use a [`TolerantMap`], so they're `O(log n)` in the number of
+/// distinct values, and have the same resolution limits: if a value is almost
+/// equal to several canonical values, the closest is used, and as approximate
+/// equality isn't transitive, which values are canonical can depend on the
+/// order they're interned in when they're about a tolerance apart. NaN is
+/// never registered, and interns to itself.
+#[derive(Debug, Clone)]
+pub struct InternPool<T> {
+    map: TolerantMap<T, usize>,
+    values: Vec<T>,
+}
+
+impl<T: Float> Default for InternPool<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> InternPool<T> {
+    /// Creates an empty pool which compares values using the default
+    /// tolerance (as in [`almost::equal`](crate::equal)).
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_tolerance(crate::float::default_rel_tol())
+    }
+
+    /// Creates an empty pool which compares values using the provided
+    /// relative tolerance (as in [`almost::equal_with`](crate::equal_with)).
+    ///
+    /// ```
+    /// let mut pool = almost::InternPool::with_tolerance(0.01f32);
+    /// assert_eq!(pool.intern(1.0), 1.0);
+    /// assert_eq!(pool.intern(1.005), 1.0);
+    /// assert_eq!(pool.intern(1.05), 1.05);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `tolerance` is less than the machine epsilon, or not less than
+    /// 0.5, as with [`TolerantMap::with_tolerance`].
+    #[inline]
+    pub fn with_tolerance(tolerance: T) -> Self {
+        Self {
+            map: TolerantMap::with_tolerance(tolerance),
+            values: Vec::new(),
+        }
+    }
+
+    /// The tolerance used to compare values.
+    #[inline]
+    pub fn tolerance(&self) -> T {
+        self.map.tolerance()
+    }
+
+    /// Returns the number of canonical values in the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if the pool contains no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the canonical value almost equal to `value`, registering
+    /// `value` as a new canonical value if there isn't one.
+    #[inline]
+    pub fn intern(&mut self, value: T) -> T {
+        if value.is_nan() {
+            return value;
+        }
+        let next = self.values.len();
+        let i = *self.map.get_or_insert_with(value, || next);
+        if i == next {
+            self.values.push(value);
+        }
+        self.values[i]
+    }
+
+    /// Returns the canonical value almost equal to `value`, if there is one,
+    /// without registering anything.
+    #[inline]
+    pub fn get(&self, value: T) -> Option<T> {
+        self.map.get(value).map(|&i| self.values[i])
+    }
+
+    /// Returns the canonical values, in the order they were registered.
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
@@ -0,0 +1,58 @@
+//! The lane-wise comparison shared by the `core::simd` and `simba` impls.
+
+use crate::Float;
+
+/// The operations on a SIMD vector of floats which [`equal_lanes`] needs.
+pub(crate) trait Lanes: Copy {
+    type Float: Float;
+
+    type Mask: Copy + core::ops::BitAnd<Output = Self::Mask>;
+
+    const LANES: usize;
+
+    fn splat(v: Self::Float) -> Self;
+
+    fn abs(self) -> Self;
+
+    fn max(self, rhs: Self) -> Self;
+
+    fn sub(self, rhs: Self) -> Self;
+
+    fn mul(self, rhs: Self) -> Self;
+
+    fn lt(self, rhs: Self) -> Self::Mask;
+
+    fn extract(self, i: usize) -> Self::Float;
+
+    fn all(mask: Self::Mask) -> bool;
+
+    fn test(mask: Self::Mask, i: usize) -> bool;
+
+    fn set(mask: &mut Self::Mask, i: usize, value: bool);
+}
+
+/// Compares each lane of `lhs` and `rhs`, as in
+/// [`almost::equal_with`](crate::equal_with). Finite lanes which aren't tiny
+/// are compared all at once, and the rest with the scalar code.
+#[inline]
+pub(crate) fn equal_lanes<V: Lanes<Float = T>, T: Float>(lhs: V, rhs: V, tol: T) -> V::Mask {
+    let tol = crate::float::check_rel_tol(tol);
+    // Below this, `tol * scale` can be subnormal, and the scalar comparison
+    // rescales first. It's `MIN_POSITIVE * 2^(SIGNIFICAND_BITS + 1)`.
+    let min_scale = T::MIN_POSITIVE * T::from_f64((1u64 << (T::SIGNIFICAND_BITS + 1)) as f64);
+    let inf = V::splat(T::from_f64(f64::INFINITY));
+    let finite = lhs.abs().lt(inf) & rhs.abs().lt(inf);
+    let scale = lhs.abs().max(rhs.abs());
+    let simple = finite & V::splat(min_scale).lt(scale);
+    let mut eq = simple & lhs.sub(rhs).abs().lt(scale.mul(V::splat(tol)));
+    if !V::all(simple) {
+        // Rare, so just use the scalar code for these lanes.
+        for i in 0..V::LANES {
+            if !V::test(simple, i) {
+                let (a, b) = (lhs.extract(i), rhs.extract(i));
+                V::set(&mut eq, i, a.almost_equals_with(b, tol));
+            }
+        }
+    }
+    eq
+}
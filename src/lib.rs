@@ -62,11 +62,267 @@
 //! of tradeoffs, and while I believe the ones made by this library are good for
 //! most cases, they do not and cannot satisfy every possible case.
 #![no_std]
+#![cfg_attr(almost_portable_simd, feature(portable_simd))]
 #![allow(clippy::float_cmp)]
 // TODO: fix in next release by requiring Float: Copy
 #![allow(clippy::declare_interior_mutable_const)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+mod aabb;
+#[cfg(feature = "approx-compat")]
+mod approx_compat;
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "bevy_math")]
+mod bevy;
+mod binary;
+#[cfg(feature = "alloc")]
+mod calibrate;
+mod classify;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod color;
+mod comparator;
+pub mod compat;
+mod complex;
+mod constant;
+#[cfg(feature = "alloc")]
+mod curve;
+mod deterministic;
+mod diagnose;
+#[cfg(feature = "num-dual")]
+mod dual;
+#[cfg(feature = "env-tolerance")]
+pub mod env;
+mod float;
+#[cfg(feature = "alloc")]
+pub mod gradcheck;
+mod grid;
+#[cfg(feature = "half")]
+mod half_impls;
+mod hint;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod histogram;
 pub(crate) mod imp;
+mod impls;
+#[cfg(feature = "alloc")]
+mod index;
+#[cfg(feature = "alloc")]
+mod intern;
+#[cfg(any(almost_portable_simd, feature = "simba"))]
+mod lanes;
+mod linalg;
+mod macros;
+#[cfg(feature = "alloc")]
+mod map;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod math;
+mod monotonic;
+#[cfg(feature = "ndarray")]
+mod nd;
+mod nullable;
+#[cfg(feature = "oracle")]
+pub mod oracle;
+mod order;
+mod periodic;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod pose;
+#[cfg(feature = "pyo3")]
+mod python;
+mod ratio;
+mod report;
+#[cfg(feature = "alloc")]
+mod roots;
+#[cfg(feature = "alloc")]
+mod shrink;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod signal;
+#[cfg(almost_portable_simd)]
+mod simd;
+mod slice;
+#[cfg(feature = "insta")]
+mod snapshot;
+mod sparse;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod stats;
+#[cfg(feature = "std")]
+mod table;
+#[cfg(feature = "candle")]
+mod tensor;
+pub mod testutil;
+mod tolerance;
+#[cfg(feature = "ultraviolet")]
+mod ultraviolet_impls;
+mod uncertain;
+#[cfg(feature = "vek")]
+mod vek_impls;
+mod verdict;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "simba")]
+mod wide;
+
+pub use aabb::{
+    aabb_contains, aabb_contains_point, aabb_contains_point_with, aabb_contains_with, aabb_equal,
+    aabb_equal_with, aabb_intersects, aabb_intersects_with, aabb_overlap, aabb_overlap_with, Aabb,
+};
+/// Derives [`AlmostEqual`] for a struct, comparing it field by field. This
+/// requires the `derive` feature.
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// #[derive(Debug, Clone, Copy, almost::AlmostEqual)]
+/// struct State {
+///     x: f64,
+///     v: f64,
+///     // Compared with this tolerance, whatever tolerance the struct is
+///     // compared with.
+///     #[almost(tolerance = 1e-3)]
+///     energy: f64,
+///     // Not compared at all.
+///     #[almost(skip)]
+///     step: u32,
+/// }
+/// let a = State { x: 0.1 + 0.2, v: -1.0, energy: 10.0, step: 1 };
+/// let b = State { x: 0.3, v: -1.0, energy: 10.001, step: 2 };
+/// assert!(almost::equal(a, b));
+/// assert!(!almost::equal(a, State { v: -1.01, ..b }));
+/// assert!(almost::zero(State { x: 1e-12, v: 0.0, energy: 0.0, step: 7 }));
+/// # }
+/// ```
+///
+/// Structs are almost equal if every field is almost equal, with the
+/// tolerance the struct is compared with, or with the field's own
+/// `#[almost(tolerance = ...)]`. They're almost zero if every field is almost
+/// zero with the zero tolerance; a field's own tolerance is relative, so it
+/// isn't used for this. Fields marked `#[almost(skip)]` are ignored.
+///
+/// Every field which is compared has to implement [`AlmostEqual`] with the
+/// same `Float` type, so a struct of `f64`s, arrays of them, and other derived
+/// structs of them can be mixed freely. The default tolerance and machine
+/// epsilon are those of the first field. Tuple structs are supported, but
+/// enums and structs without any compared fields aren't.
+#[cfg(feature = "derive")]
+pub use almost_derive::AlmostEqual;
+#[cfg(feature = "approx-compat")]
+pub use approx_compat::{Almost, Approx};
+#[cfg(feature = "bevy_math")]
+pub use bevy::{transform_equal, TransformTolerance};
+#[cfg(feature = "std")]
+pub use binary::io_impl::{
+    compare_files, compare_files_with, compare_streams, compare_streams_with, StreamComparison,
+};
+#[cfg(feature = "bytemuck")]
+pub use binary::pod_impl::{cast_floats, compare_native_bytes, compare_native_bytes_with};
+pub use binary::{compare_bytes, compare_bytes_with, BinaryError, ByteOrder};
+#[cfg(feature = "alloc")]
+pub use calibrate::{Calibration, CalibrationResult};
+pub use classify::{classify_diff, closeness, closeness_with, matching_digits, DiffClass};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use color::{color_equal, color_equal_with, ColorEncoding, ColorMetric};
+pub use comparator::{Comparator, ComparatorBuilder, ComparatorError, NanPolicy};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use complex::complex_polar_equal;
+pub use complex::{complex_equal, complex_equal_with, ComplexMetric, ComplexParts};
+#[doc(hidden)]
+pub use constant::__ConstPair;
+pub use constant::{const_equal_f32, const_equal_f64, const_equal_with_f32, const_equal_with_f64};
+#[cfg(feature = "alloc")]
+pub use curve::{curves_equal, curves_equal_with, CurvePoint, CurveReport, DEFAULT_CURVE_SAMPLES};
+pub use deterministic::{deterministic_equal, deterministic_equal_with};
+pub use diagnose::{diagnose, diagnose_with, Comparison};
+#[cfg(feature = "num-dual")]
+pub use dual::{dual_equal_with, DualParts};
+pub use float::Float;
+pub use grid::{compare_grid, compare_grid_with, GridRegion, GridReport};
+#[cfg(feature = "half")]
+pub use half_impls::{BF16_TOLERANCE, F16_TOLERANCE};
+pub use hint::Hint;
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use histogram::{
+    histogram_equal, histogram_equal_with, BinTolerance, Histogram, HistogramMismatch,
+};
+#[cfg(feature = "alloc")]
+pub use index::SortedIndex;
+#[cfg(feature = "alloc")]
+pub use intern::InternPool;
+pub use linalg::{
+    almost_identity, almost_identity_with, almost_normalized, almost_normalized_with,
+    almost_orthogonal, almost_orthogonal_with, almost_row_stochastic, almost_row_stochastic_with,
+    almost_symmetric, almost_symmetric_with, frobenius_equal, frobenius_equal_with, metric_equal,
+    orthogonal, orthogonal_with, unit_length, unit_length_with, weighted_equal, DirectionSign,
+};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use linalg::{direction_equal, direction_equal_with};
+#[doc(hidden)]
+pub use macros::{__assert_almost, __assert_approx_eq, __matches_one};
+#[cfg(feature = "alloc")]
+pub use map::{TolerantMap, TolerantMapIter};
+pub use monotonic::{
+    almost_decreasing, almost_decreasing_with, almost_increasing, almost_increasing_with,
+    strictly_decreasing, strictly_decreasing_with, strictly_increasing, strictly_increasing_with,
+};
+#[cfg(feature = "ndarray")]
+pub use nd::{ndarray_equal, ndarray_equal_along, ndarray_equal_with, NdMismatch};
+#[cfg(feature = "alloc")]
+pub use nullable::{compare_nullable, compare_nullable_with, NullableReport};
+pub use nullable::{
+    nullable_equal, nullable_equal_with, nullable_iter_equal, nullable_iter_equal_with,
+};
+pub use order::{
+    compare, compare_with, definitely_greater, definitely_greater_with, definitely_less,
+    definitely_less_with, ge, ge_with, le, le_with, total_cmp, total_cmp_with, ApproxOrdering,
+    SortKey,
+};
+pub use periodic::{minimum_image, periodic_equal, periodic_equal_with};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use pose::{pose_equal, Pose, PoseTolerance};
+pub use ratio::{equal_ratio, equal_ratio_with};
+#[cfg(feature = "std")]
+pub use report::ReportTable;
+pub use report::{ElementDiff, ElementwiseReport};
+#[cfg(feature = "alloc")]
+pub use roots::{complex_roots_equal, complex_roots_equal_with, roots_equal, roots_equal_with};
+#[cfg(feature = "alloc")]
+pub use shrink::{shrink_failure, shrink_failure_with, Reproducer};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use signal::{equal_snr, snr_db};
+#[cfg(almost_portable_simd)]
+pub use simd::{
+    simd_equal_lanes_f32, simd_equal_lanes_f64, simd_zero_lanes_f32, simd_zero_lanes_f64,
+};
+pub use slice::{
+    all_almost_equal, all_almost_equal_with, all_almost_zero, all_almost_zero_with,
+    almost_constant, almost_constant_with, any_almost_zero, any_almost_zero_with, closest_match,
+    closest_match_with, equal_slices, equal_slices_with, max_of, max_of_with, min_of, min_of_with,
+    Agreement, ClosestMatch, Extremum, Spread, Ties,
+};
+#[cfg(feature = "insta")]
+pub use snapshot::{snapshot_redaction, snapshot_redaction_with, SnapshotComparator};
+#[cfg(feature = "nalgebra-sparse")]
+pub use sparse::{csc_equal, csc_equal_with, csr_equal, csr_equal_with};
+pub use sparse::{sparse_equal, sparse_equal_with};
+#[cfg(feature = "sprs")]
+pub use sparse::{sprs_mat_equal, sprs_mat_equal_with, sprs_vec_equal, sprs_vec_equal_with};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use stats::{means_equal, means_equal_with, SampleStats, DEFAULT_STANDARD_ERRORS};
+#[cfg(feature = "std")]
+pub use table::{
+    compare_table_files, compare_table_files_with, compare_tables, compare_tables_with, TableError,
+    TableReport, TableSide,
+};
+#[cfg(feature = "candle")]
+pub use tensor::{tensor_equal, tensor_equal_with, tensor_tolerance, TensorMismatch};
+pub use tolerance::{ParseToleranceError, Tolerance, ToleranceError};
+#[cfg(feature = "ultraviolet")]
+pub use ultraviolet_impls::{ultraviolet_equal_lanes, ultraviolet_zero_lanes, UltravioletWide};
+pub use uncertain::{ParseUncertainError, Uncertain};
+pub use verdict::{stable_verdict, Verdict};
+#[cfg(feature = "simba")]
+pub use wide::{simba_equal_lanes, simba_zero_lanes};
 
 /// Returns `true` if `lhs` and `rhs` are almost equal.
 ///
@@ -85,6 +341,7 @@ pub(crate) mod imp;
 ///
 /// Note that this returns false in the case that both values are NaN.
 #[inline]
+#[cfg_attr(feature = "audit", track_caller)]
 pub fn equal<T: AlmostEqual>(lhs: T, rhs: T) -> bool {
     lhs.almost_equals(rhs)
 }
@@ -92,8 +349,7 @@ pub fn equal<T: AlmostEqual>(lhs: T, rhs: T) -> bool {
 /// Returns `true` if `a` is almost zero.
 ///
 /// ```
-/// # use core as std;
-/// assert!(almost::zero(std::f32::EPSILON));
+/// assert!(almost::zero(f32::EPSILON));
 /// ```
 ///
 /// This is the correct function to use when comparing to see if a value is
@@ -109,7 +365,22 @@ pub fn equal<T: AlmostEqual>(lhs: T, rhs: T) -> bool {
 /// enough for it to be unlikely to cause false positives in practice. However,
 /// if you need a tighter bound, the function
 /// [`almost::zero_with`](zero_with) can be used.
+///
+/// Slices, arrays and vectors are almost zero if every element is, so a whole
+/// set of residuals can be checked at once:
+///
+/// ```
+/// let residuals = vec![1e-12, -3e-13, 0.0];
+/// assert!(almost::zero(&residuals[..]));
+/// assert!(almost::zero([[1e-12f32, 0.0], [0.0, -1e-9]]));
+/// assert!(!almost::zero(&[1e-12, 0.5][..]));
+/// ```
+///
+/// An empty slice is almost zero. For slices of floats,
+/// [`almost::all_almost_zero`](all_almost_zero) is faster, and returns the
+/// index of the first element which isn't almost zero.
 #[inline]
+#[cfg_attr(feature = "audit", track_caller)]
 pub fn zero<T: AlmostEqual>(a: T) -> bool {
     a.almost_zero()
 }
@@ -118,8 +389,7 @@ pub fn zero<T: AlmostEqual>(a: T) -> bool {
 /// tolerance.
 ///
 /// ```
-/// # use core as std;
-/// assert!(!almost::zero_with(std::f32::EPSILON, std::f32::EPSILON));
+/// assert!(!almost::zero_with(f32::EPSILON, f32::EPSILON));
 /// ```
 ///
 /// This is a version of [`almost::zero`](zero) which does not define a
@@ -140,8 +410,11 @@ pub fn zero<T: AlmostEqual>(a: T) -> bool {
 /// This function panics in debug mode if `tolerance` is not greater than zero,
 /// as the results are unlikely to be sensible.
 ///
-/// In release builds it should never panic.
+/// In release builds it should never panic. For `f32` and `f64`, with the
+/// `no-panic` feature, it never panics, and nothing is almost zero with such
+/// a tolerance.
 #[inline]
+#[cfg_attr(feature = "audit", track_caller)]
 pub fn zero_with<T: AlmostEqual>(v: T, tolerance: T::Float) -> bool {
     v.almost_zero_with(tolerance)
 }
@@ -159,14 +432,198 @@ pub fn zero_with<T: AlmostEqual>(v: T, tolerance: T::Float) -> bool {
 ///
 /// # Panics
 /// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
-/// or greater than 1.0, as the results are unlikely to be sensible.
+/// or greater than 1.0, as the results are unlikely to be sensible. For
+/// tolerances which aren't known in advance, [`Tolerance`] can be used to
+/// validate them up front instead.
 ///
-/// In release builds it should never panic.
+/// In release builds it should never panic. For `f32` and `f64`, with the
+/// `no-panic` feature, it never panics, and the tolerance is clamped into that
+/// range instead.
 #[inline]
+#[cfg_attr(feature = "audit", track_caller)]
 pub fn equal_with<T: AlmostEqual>(lhs: T, rhs: T, tolerance: T::Float) -> bool {
     lhs.almost_equals_with(rhs, tolerance)
 }
 
+/// Returns `true` if `lhs` and `rhs` are almost equal, or if both are almost
+/// zero using the absolute tolerance `zero_tol`.
+///
+/// ```
+/// // Components of a vector, where some should be exactly zero.
+/// let expected = [1.0, 0.0, -2.0];
+/// let actual = [1.0 + 1e-12, 1e-17, -2.0];
+/// assert!(expected.iter().zip(&actual).all(|(&e, &a)| almost::equal_or_both_tiny(e, a, 1e-10)));
+/// ```
+///
+/// This is the correct way to compare values which may legitimately be near
+/// zero, where neither [`almost::equal`](equal) (which never succeeds for
+/// values compared with zero) nor [`almost::zero`](zero) alone is enough. The
+/// choice of `zero_tol` depends on the scale of the values, see
+/// [`almost::zero_with`](zero_with).
+///
+/// # Panics
+/// This function panics in debug mode if `zero_tol` is not greater than zero,
+/// as the results are unlikely to be sensible.
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn equal_or_both_tiny<T>(lhs: T, rhs: T, zero_tol: T::Float) -> bool
+where
+    T: AlmostEqual + Copy,
+    T::Float: Copy,
+{
+    (lhs.almost_zero_with(zero_tol) && rhs.almost_zero_with(zero_tol)) || lhs.almost_equals(rhs)
+}
+
+/// Equivalent to [`almost::equal_or_both_tiny`](equal_or_both_tiny), but uses
+/// the provided relative tolerance.
+///
+/// ```
+/// let (rel_tol, zero_tol) = (1e-3, 1e-6);
+/// assert!(almost::equal_with_zero_tol(1.0f32, 1.0005, rel_tol, zero_tol));
+/// assert!(almost::equal_with_zero_tol(1e-7f32, -1e-7, rel_tol, zero_tol));
+/// assert!(!almost::equal_with_zero_tol(1e-7f32, 1e-5, rel_tol, zero_tol));
+/// ```
+///
+/// The absolute tolerance is only used when *both* values are within it, and
+/// otherwise the comparison is purely relative. This is the recommended pair of
+/// [`almost::zero_with`](zero_with) and [`almost::equal_with`](equal_with)
+/// checks, as a single call that's easy to use element-wise.
+///
+/// # Panics
+/// This function panics in debug mode if `zero_tol` is not greater than zero,
+/// or if `rel_tol` is less than `T::EPSILON` or greater than 1.0, as the
+/// results are unlikely to be sensible.
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn equal_with_zero_tol<T>(lhs: T, rhs: T, rel_tol: T::Float, zero_tol: T::Float) -> bool
+where
+    T: AlmostEqual + Copy,
+    T::Float: Copy,
+{
+    (lhs.almost_zero_with(zero_tol) && rhs.almost_zero_with(zero_tol))
+        || lhs.almost_equals_with(rhs, rel_tol)
+}
+
+/// Returns `true` if `a` and `b` are at most `max_ulps` units in the last place
+/// apart, that is, if stepping from one to the next representable value takes
+/// at most `max_ulps` steps to get from `a` to `b`.
+///
+/// ```
+/// assert!(almost::ulp_equal(0.1 + 0.2, 0.3, 1));
+/// assert!(!almost::ulp_equal(0.1 + 0.2, 0.3, 0));
+/// // Zeros are equal, and values on either side of zero are counted through it.
+/// assert!(almost::ulp_equal(-0.0f32, 0.0, 0));
+/// assert!(almost::ulp_equal(-f32::from_bits(1), f32::from_bits(1), 2));
+/// // The largest finite value is one ulp from infinity.
+/// assert!(almost::ulp_equal(f64::MAX, f64::INFINITY, 1));
+/// ```
+///
+/// This is for code which reasons about error in ulps, such as checking that
+/// an implementation of a math function is correctly rounded (0 ulps) or
+/// faithfully rounded (1 ulp). For values which come out of arbitrary
+/// computations, prefer [`almost::equal`](equal): a fixed number of ulps is a
+/// relative tolerance that jumps by a factor of two at every power of two,
+/// and near zero, where subnormals and the other sign are only a few ulps
+/// away, it's an absolute tolerance which is far stricter than any
+/// computation can meet.
+///
+/// The distance is the number of representable values crossed going from `a`
+/// to `b`, as measured by [`almost::ulp_distance`](ulp_distance). Both zeros
+/// are the same value, so the smallest positive and negative subnormals are 2
+/// ulps apart. Infinities are treated as just past the largest finite values,
+/// as in [`almost::equal`](equal). NaN is never equal to anything.
+#[inline]
+pub fn ulp_equal<T: Float>(a: T, b: T, max_ulps: u64) -> bool {
+    a.ulp_equal(b, max_ulps)
+}
+
+/// Returns the number of units in the last place between `a` and `b`, or
+/// `None` if either is NaN.
+///
+/// ```
+/// assert_eq!(almost::ulp_distance(1.0, 1.0 + 2.0 * f64::EPSILON), Some(2));
+/// // Ulps are half the size below a power of two.
+/// assert_eq!(almost::ulp_distance(1.0, 1.0 - f64::EPSILON), Some(2));
+/// assert_eq!(almost::ulp_distance(f32::MIN_POSITIVE, 0.0), Some(1 << 23));
+/// assert_eq!(almost::ulp_distance(1.0, f64::NAN), None);
+/// ```
+///
+/// This treats zeros and infinities as [`almost::ulp_equal`](ulp_equal)
+/// does.
+#[inline]
+pub fn ulp_distance<T: Float>(a: T, b: T) -> Option<u64> {
+    a.ulp_distance(b)
+}
+
+/// Returns `true` if the iterators `lhs` and `rhs` produce the same number of
+/// items, and each pair of items is almost equal.
+///
+/// ```
+/// let squares = (1..4).map(|i| (i as f64).sqrt().powi(2));
+/// assert!(almost::equal_iter(squares, [1.0, 2.0, 3.0]));
+/// assert!(!almost::equal_iter([1.0, 2.0], [1.0, 2.0, 3.0]));
+/// ```
+///
+/// This allows comparing the output of iterator adapters (or anything else
+/// which is `IntoIterator`) without collecting into a `Vec` first. Items are
+/// compared with [`almost::equal`](equal), and comparison stops at the first
+/// mismatch.
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn equal_iter<T, A, B>(lhs: A, rhs: B) -> bool
+where
+    T: AlmostEqual,
+    T::Float: Copy,
+    A: IntoIterator<Item = T>,
+    B: IntoIterator<Item = T>,
+{
+    let (mut lhs, mut rhs) = (lhs.into_iter(), rhs.into_iter());
+    loop {
+        match (lhs.next(), rhs.next()) {
+            (None, None) => return true,
+            (Some(a), Some(b)) => {
+                if !a.almost_equals(b) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Equivalent to [`almost::equal_iter`](equal_iter), but uses the provided
+/// relative tolerance.
+///
+/// ```
+/// let v = [1.0f32, 2.0];
+/// assert!(almost::equal_iter_with(v.iter(), &[1.01, 2.0], 0.1));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn equal_iter_with<T, A, B>(lhs: A, rhs: B, tolerance: T::Float) -> bool
+where
+    T: AlmostEqual,
+    T::Float: Copy,
+    A: IntoIterator<Item = T>,
+    B: IntoIterator<Item = T>,
+{
+    let (mut lhs, mut rhs) = (lhs.into_iter(), rhs.into_iter());
+    loop {
+        match (lhs.next(), rhs.next()) {
+            (None, None) => return true,
+            (Some(a), Some(b)) => {
+                if !a.almost_equals_with(b, tolerance) {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
 /// A trait for comparing floating point numbers. Not broadly intended to be
 /// used by most code (instead, use the functions at the crate root), however it
 /// could be useful for generic code too.
@@ -185,6 +642,32 @@ pub trait AlmostEqual {
     /// when computing tolerances.
     const MACHINE_EPSILON: Self::Float;
 
+    /// Returns [`DEFAULT_TOLERANCE`](AlmostEqual::DEFAULT_TOLERANCE) for the
+    /// type of `self`.
+    /// ```
+    /// # use almost::AlmostEqual;
+    /// let x = 1.5f32;
+    /// assert_eq!(x.default_tolerance(), almost::F32_TOLERANCE);
+    /// ```
+    ///
+    /// This is the same as the associated constant, but can be more convenient
+    /// in generic code, or when deriving a tolerance from the type of a value.
+    #[inline]
+    fn default_tolerance(&self) -> Self::Float {
+        Self::DEFAULT_TOLERANCE
+    }
+
+    /// Returns [`MACHINE_EPSILON`](AlmostEqual::MACHINE_EPSILON) for the type
+    /// of `self`.
+    /// ```
+    /// # use almost::AlmostEqual;
+    /// assert_eq!(1.5f64.machine_epsilon(), f64::EPSILON);
+    /// ```
+    #[inline]
+    fn machine_epsilon(&self) -> Self::Float {
+        Self::MACHINE_EPSILON
+    }
+
     /// Equivalent to [`almost::zero`](zero).
     /// ```
     /// # let v = 0.000001f32;
@@ -192,6 +675,7 @@ pub trait AlmostEqual {
     /// assert!(v.almost_zero());
     /// ```
     #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
     fn almost_zero(self) -> bool
     where
         Self: Sized,
@@ -206,6 +690,7 @@ pub trait AlmostEqual {
     /// assert!(a.almost_equals(b));
     /// ```
     #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
     fn almost_equals(self, rhs: Self) -> bool
     where
         Self: Sized,
@@ -233,52 +718,222 @@ pub trait AlmostEqual {
 /// The default tolerance used for `f64`. Equivalent to `f64::EPSILON.sqrt()`
 /// (or `0.000000014901161193847656_f64`), as we assume that around half of the
 /// precision bits of any arbitrary value have been rounded away.
+///
+/// This can be changed crate-wide with the `strict-defaults` and
+/// `loose-defaults` features.
+#[cfg(not(any(feature = "strict-defaults", feature = "loose-defaults")))]
 pub const F64_TOLERANCE: f64 = 0.000000014901161193847656_f64;
 /// The default tolerance used for `f32`. Equivalent to `f32::EPSILON.sqrt()`
 /// (or `0.00034526698_f32`), as we assume that around half of the precision
 /// bits of any arbitrary value have been rounded away.
+///
+/// This can be changed crate-wide with the `strict-defaults` and
+/// `loose-defaults` features.
+#[cfg(not(any(feature = "strict-defaults", feature = "loose-defaults")))]
 pub const F32_TOLERANCE: f32 = 0.00034526698_f32;
 
+/// The default tolerance used for `f64`. With the `strict-defaults` feature,
+/// this is `f64::EPSILON.powf(2.0 / 3.0)` (or `3.666852862501036e-11_f64`), as
+/// we assume that around two thirds of the precision bits are still accurate.
+#[cfg(feature = "strict-defaults")]
+pub const F64_TOLERANCE: f64 = 3.666852862501036e-11_f64;
+/// The default tolerance used for `f32`. With the `strict-defaults` feature,
+/// this is `f32::EPSILON.powf(2.0 / 3.0)` (or `2.4221818e-5_f32`), as we
+/// assume that around two thirds of the precision bits are still accurate.
+#[cfg(feature = "strict-defaults")]
+pub const F32_TOLERANCE: f32 = 2.4221818e-5_f32;
+
+/// The default tolerance used for `f64`. With the `loose-defaults` feature,
+/// this is `f64::EPSILON.cbrt()` (or `6.0554544523933395e-6_f64`), as we
+/// assume that only around a third of the precision bits are still accurate.
+#[cfg(all(feature = "loose-defaults", not(feature = "strict-defaults")))]
+pub const F64_TOLERANCE: f64 = 6.0554544523933395e-6_f64;
+/// The default tolerance used for `f32`. With the `loose-defaults` feature,
+/// this is `f32::EPSILON.cbrt()` (or `0.0049215667_f32`), as we assume that
+/// only around a third of the precision bits are still accurate.
+#[cfg(all(feature = "loose-defaults", not(feature = "strict-defaults")))]
+pub const F32_TOLERANCE: f32 = 0.0049215667_f32;
+
 impl AlmostEqual for f64 {
     type Float = f64;
 
-    const MACHINE_EPSILON: Self::Float = core::f64::EPSILON;
+    const MACHINE_EPSILON: Self::Float = f64::EPSILON;
 
     const DEFAULT_TOLERANCE: Self::Float = F64_TOLERANCE;
 
+    // Overridden so that the default path is inlined with the tolerance as a
+    // constant, rather than going through `almost_equals_with` (which isn't
+    // `#[inline]`, and has to check the tolerance). With `env-tolerance`, this
+    // is where the override replaces the default.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_equals(self, rhs: Self) -> bool {
+        #[cfg(any(feature = "audit", feature = "env-tolerance"))]
+        {
+            self.almost_equals_with(rhs, float::default_rel_tol())
+        }
+        #[cfg(not(any(feature = "audit", feature = "env-tolerance")))]
+        {
+            crate::imp::f64::eq_with_tol_impl(self, rhs, F64_TOLERANCE)
+        }
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_zero(self) -> bool {
+        #[cfg(any(feature = "audit", feature = "env-tolerance"))]
+        {
+            self.almost_zero_with(float::default_zero_tol())
+        }
+        #[cfg(not(any(feature = "audit", feature = "env-tolerance")))]
+        {
+            crate::imp::f64::abs(self) < F64_TOLERANCE
+        }
+    }
+
+    #[cfg_attr(feature = "audit", track_caller)]
+    #[cfg_attr(
+        all(feature = "no-panic", not(feature = "audit"), not(debug_assertions)),
+        no_panic::no_panic
+    )]
     fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
-        debug_assert!(tol < 1.0, "Tolerance should not be greater than 1.0");
-        debug_assert!(
-            tol >= Self::MACHINE_EPSILON,
-            "Tolerance should not be smaller than the machine epsilon"
+        let tol = float::check_rel_tol(tol);
+        let eq = crate::imp::f64::eq_with_tol_impl(self, rhs, tol);
+        #[cfg(feature = "audit")]
+        audit::record(
+            core::panic::Location::caller(),
+            crate::imp::f64::rel_err(self, rhs) / tol,
+            eq,
         );
-        crate::imp::f64::eq_with_tol_impl(self, rhs, tol)
+        eq
     }
 
+    #[cfg_attr(feature = "audit", track_caller)]
+    #[cfg_attr(
+        all(feature = "no-panic", not(feature = "audit"), not(debug_assertions)),
+        no_panic::no_panic
+    )]
     fn almost_zero_with(self, tol: Self::Float) -> bool {
-        debug_assert!(tol > 0.0);
-        crate::imp::f64::abs(self) < tol
+        float::check_zero_tol(tol);
+        let eq = crate::imp::f64::abs(self) < tol;
+        #[cfg(feature = "audit")]
+        audit::record(
+            core::panic::Location::caller(),
+            crate::imp::f64::abs(self) / tol,
+            eq,
+        );
+        eq
     }
 }
 
 impl AlmostEqual for f32 {
     type Float = f32;
 
-    const MACHINE_EPSILON: Self::Float = core::f32::EPSILON;
+    const MACHINE_EPSILON: Self::Float = f32::EPSILON;
 
     const DEFAULT_TOLERANCE: Self::Float = F32_TOLERANCE;
 
+    // Overridden so that the default path is inlined with the tolerance as a
+    // constant, rather than going through `almost_equals_with` (which isn't
+    // `#[inline]`, and has to check the tolerance). With `env-tolerance`, this
+    // is where the override replaces the default.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_equals(self, rhs: Self) -> bool {
+        #[cfg(any(feature = "audit", feature = "env-tolerance"))]
+        {
+            self.almost_equals_with(rhs, float::default_rel_tol())
+        }
+        #[cfg(not(any(feature = "audit", feature = "env-tolerance")))]
+        {
+            crate::imp::f32::eq_with_tol_impl(self, rhs, F32_TOLERANCE)
+        }
+    }
+
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn almost_zero(self) -> bool {
+        #[cfg(any(feature = "audit", feature = "env-tolerance"))]
+        {
+            self.almost_zero_with(float::default_zero_tol())
+        }
+        #[cfg(not(any(feature = "audit", feature = "env-tolerance")))]
+        {
+            crate::imp::f32::abs(self) < F32_TOLERANCE
+        }
+    }
+
+    #[cfg_attr(feature = "audit", track_caller)]
+    #[cfg_attr(
+        all(feature = "no-panic", not(feature = "audit"), not(debug_assertions)),
+        no_panic::no_panic
+    )]
     fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
-        debug_assert!(tol < 1.0, "Tolerance should not be greater than 1.0");
-        debug_assert!(
-            tol >= Self::MACHINE_EPSILON,
-            "Tolerance should not be smaller than the machine epsilon"
+        let tol = float::check_rel_tol(tol);
+        let eq = crate::imp::f32::eq_with_tol_impl(self, rhs, tol);
+        #[cfg(feature = "audit")]
+        audit::record(
+            core::panic::Location::caller(),
+            (crate::imp::f32::rel_err(self, rhs) / tol) as f64,
+            eq,
         );
-        crate::imp::f32::eq_with_tol_impl(self, rhs, tol)
+        eq
     }
 
+    #[cfg_attr(feature = "audit", track_caller)]
+    #[cfg_attr(
+        all(feature = "no-panic", not(feature = "audit"), not(debug_assertions)),
+        no_panic::no_panic
+    )]
     fn almost_zero_with(self, tol: Self::Float) -> bool {
-        debug_assert!(tol > 0.0);
-        crate::imp::f32::abs(self) < tol
+        float::check_zero_tol(tol);
+        let eq = crate::imp::f32::abs(self) < tol;
+        #[cfg(feature = "audit")]
+        audit::record(
+            core::panic::Location::caller(),
+            (crate::imp::f32::abs(self) / tol) as f64,
+            eq,
+        );
+        eq
     }
 }
+
+macro_rules! impl_for_ref {
+    ($($r:tt)+) => {
+        impl<'a, T: AlmostEqual + Copy> AlmostEqual for $($r)+ T {
+            type Float = T::Float;
+
+            const MACHINE_EPSILON: Self::Float = T::MACHINE_EPSILON;
+
+            const DEFAULT_TOLERANCE: Self::Float = T::DEFAULT_TOLERANCE;
+
+            #[inline]
+            #[cfg_attr(feature = "audit", track_caller)]
+            fn almost_equals(self, rhs: Self) -> bool {
+                (*self).almost_equals(*rhs)
+            }
+
+            #[inline]
+            #[cfg_attr(feature = "audit", track_caller)]
+            fn almost_zero(self) -> bool {
+                (*self).almost_zero()
+            }
+
+            #[inline]
+            #[cfg_attr(feature = "audit", track_caller)]
+            fn almost_equals_with(self, rhs: Self, tol: Self::Float) -> bool {
+                (*self).almost_equals_with(*rhs, tol)
+            }
+
+            #[inline]
+            #[cfg_attr(feature = "audit", track_caller)]
+            fn almost_zero_with(self, tol: Self::Float) -> bool {
+                (*self).almost_zero_with(tol)
+            }
+        }
+    };
+}
+
+// So that iterators over references can be compared without dereferencing.
+impl_for_ref!(&'a);
+impl_for_ref!(&'a mut);
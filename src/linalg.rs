@@ -0,0 +1,701 @@
+//! Comparisons for vectors and matrices, which look at them as a whole rather
+//! than element by element.
+
+use crate::Float;
+
+/// The largest magnitude in `values`, clamped to at least `MIN_POSITIVE` so it
+/// can be used as a divisor.
+#[inline]
+pub(crate) fn max_magnitude<'a, T, I>(values: I) -> T
+where
+    T: Float + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let mut scale = T::MIN_POSITIVE;
+    for v in values {
+        let m = v.abs();
+        if m > scale {
+            scale = m;
+        }
+    }
+    scale
+}
+
+/// Returns true if the matrices `lhs` and `rhs` (given as their elements, in
+/// any consistent order) are almost equal in the Frobenius norm.
+///
+/// ```
+/// // A tiny entry with a large relative error doesn't matter, as it's tiny
+/// // relative to the matrix as a whole.
+/// let a = [1.0, 2.0, 3.0, 1e-12];
+/// let b = [1.0, 2.0, 3.0, 2e-12];
+/// assert!(almost::frobenius_equal(&a, &b));
+/// assert!(!almost::equal(a[3], b[3]));
+/// ```
+///
+/// Specifically, this checks that `‖A − B‖_F < tol · max(‖A‖_F, ‖B‖_F)`. This is
+/// the standard acceptance criterion for results in numerical linear algebra,
+/// and avoids false failures from entries which are individually tiny (and so
+/// have large relative error), but insignificant for the matrix as a whole.
+///
+/// Any matrix type with contiguous storage can be compared by passing its
+/// elements as a slice, as the Frobenius norm doesn't depend on the shape.
+///
+/// Returns false if the lengths differ or if any element is NaN. If any element
+/// is infinite, the values are compared element-wise instead, as the norm is
+/// meaningless.
+#[inline]
+pub fn frobenius_equal<T: Float>(lhs: &[T], rhs: &[T]) -> bool {
+    frobenius_equal_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::frobenius_equal`](frobenius_equal), but uses the
+/// provided relative tolerance.
+///
+/// ```
+/// let a = [[1.0f32, 0.0], [0.0, 1.0]];
+/// let b = [[1.0f32, 0.01], [0.0, 1.0]];
+/// assert!(almost::frobenius_equal_with(a.as_flattened(), b.as_flattened(), 0.01));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+pub fn frobenius_equal_with<T: Float>(lhs: &[T], rhs: &[T], tolerance: T) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    if !lhs.iter().chain(rhs).all(|v| v.is_finite()) {
+        return lhs
+            .iter()
+            .zip(rhs)
+            .all(|(&a, &b)| a.almost_equals_with(b, tolerance));
+    }
+    // Rescale so the sums of squares can't overflow or underflow.
+    let scale = max_magnitude(lhs.iter().chain(rhs));
+    let zero = T::default();
+    let (mut diff_sq, mut lhs_sq, mut rhs_sq) = (zero, zero, zero);
+    let mut identical = true;
+    for (&a, &b) in lhs.iter().zip(rhs) {
+        identical &= a == b;
+        let (a, b) = (a / scale, b / scale);
+        diff_sq = diff_sq + (a - b) * (a - b);
+        lhs_sq = lhs_sq + a * a;
+        rhs_sq = rhs_sq + b * b;
+    }
+    let norm_sq = if lhs_sq > rhs_sq { lhs_sq } else { rhs_sq };
+    identical || diff_sq < tolerance * tolerance * norm_sq
+}
+
+/// Returns true if the distance between the vectors `lhs` and `rhs`, in the
+/// norm weighted by `weights`, is less than `max_distance`.
+///
+/// ```
+/// // A position in meters and a velocity in meters per second, where the
+/// // position is known to about 1mm, and the velocity to about 1%.
+/// let weights = [1.0 / (1e-3 * 1e-3), 1.0 / (0.01 * 0.01)];
+/// let expected = [100.0, 2.0];
+/// // Within three standard deviations.
+/// assert!(almost::weighted_equal(&[100.002, 2.01], &expected, &weights, 3.0));
+/// assert!(!almost::weighted_equal(&[100.004, 2.0], &expected, &weights, 3.0));
+/// // Relative to the vector, the position error is tiny.
+/// assert!(almost::frobenius_equal_with(&[100.004, 2.0], &expected, 1e-4));
+/// ```
+///
+/// The distance is `sqrt(Σ wᵢ(aᵢ − bᵢ)²)`, so that components in different
+/// units, or with different variances, are combined into one meaningful
+/// distance before it's compared. The weights are typically the inverse
+/// variance (`1 / σᵢ²`) of each component, in which case the distance is in
+/// standard deviations. A zero weight ignores a component. For correlated
+/// components, use [`almost::metric_equal`](metric_equal).
+///
+/// Unlike most comparisons in this crate, this isn't relative to the
+/// magnitudes of the vectors, as the weights already give the scale of each
+/// component.
+///
+/// Returns false if the lengths differ, if any weight is negative, or if any
+/// value (or weight) is NaN or infinite, unless the vectors are identical. It
+/// also returns false if `max_distance` isn't positive, as no distance is less
+/// than that.
+///
+/// # Panics
+/// This function panics in debug mode if `max_distance` isn't positive.
+pub fn weighted_equal<T: Float>(lhs: &[T], rhs: &[T], weights: &[T], max_distance: T) -> bool {
+    crate::float::check_zero_tol(max_distance);
+    if max_distance.is_nan() || max_distance <= T::default() {
+        return false;
+    }
+    if lhs.len() != rhs.len() || lhs.len() != weights.len() {
+        return false;
+    }
+    let zero = T::default();
+    if weights.iter().any(|&w| w.is_nan() || w < zero) {
+        return false;
+    }
+    if lhs.iter().zip(rhs).all(|(a, b)| a == b) {
+        return true;
+    }
+    if !lhs.iter().chain(rhs).chain(weights).all(|v| v.is_finite()) {
+        return false;
+    }
+    // Rescale the differences so the sum can't overflow or underflow.
+    let scale = max_difference(lhs, rhs);
+    let mut sum = zero;
+    for ((&a, &b), &w) in lhs.iter().zip(rhs).zip(weights) {
+        let d = (a - b) / scale;
+        sum = sum + w * d * d;
+    }
+    within_distance(sum, scale, max_distance)
+}
+
+/// Returns true if the distance between the vectors `lhs` and `rhs`, in the
+/// norm given by the `n` by `n` positive definite matrix `metric` (where `n`
+/// is the length of the vectors), is less than `max_distance`.
+///
+/// ```
+/// // Two measurements with standard deviations of 0.01, which are strongly
+/// // correlated, so they usually err in the same direction.
+/// let m = 1.0 / (1e-4 * (1.0 - 0.98 * 0.98));
+/// let inverse_covariance = [m, -0.98 * m, -0.98 * m, m];
+/// let expected = [1.0, 1.0];
+/// // An error along the correlation is expected, and so is allowed...
+/// assert!(almost::metric_equal(&[1.01, 1.01], &expected, &inverse_covariance, 3.0));
+/// // ...but the same size of error against it isn't.
+/// assert!(!almost::metric_equal(&[1.01, 0.99], &expected, &inverse_covariance, 3.0));
+/// ```
+///
+/// This is the Mahalanobis distance `sqrt((a − b)ᵀ M (a − b))`. With the
+/// inverse of a covariance matrix as the metric, it's in standard deviations,
+/// and accounts for correlations between the components. With a diagonal
+/// metric, this is [`almost::weighted_equal`](weighted_equal). The elements of
+/// the metric may be in row-major or column-major order, as it should be
+/// symmetric.
+///
+/// Returns false if the lengths of the vectors differ, if any value is NaN or
+/// infinite (unless the vectors are identical), or if the metric turns out not
+/// to be positive semidefinite. It also returns false if `max_distance` isn't
+/// positive.
+///
+/// # Panics
+/// Panics if `metric.len()` isn't `lhs.len() * lhs.len()`, and in debug mode if
+/// `max_distance` isn't positive.
+#[track_caller]
+pub fn metric_equal<T: Float>(lhs: &[T], rhs: &[T], metric: &[T], max_distance: T) -> bool {
+    let n = lhs.len();
+    assert_square(metric, n);
+    crate::float::check_zero_tol(max_distance);
+    if max_distance.is_nan() || max_distance <= T::default() {
+        return false;
+    }
+    if rhs.len() != n {
+        return false;
+    }
+    if lhs.iter().zip(rhs).all(|(a, b)| a == b) {
+        return true;
+    }
+    if !lhs.iter().chain(rhs).chain(metric).all(|v| v.is_finite()) {
+        return false;
+    }
+    let scale = max_difference(lhs, rhs);
+    let mut sum = T::default();
+    for i in 0..n {
+        let di = (lhs[i] - rhs[i]) / scale;
+        for j in 0..n {
+            sum = sum + di * metric[i * n + j] * ((lhs[j] - rhs[j]) / scale);
+        }
+    }
+    sum >= T::default() && within_distance(sum, scale, max_distance)
+}
+
+/// The largest magnitude of any difference between `lhs` and `rhs`, or
+/// `T::MIN_POSITIVE` if that's larger.
+#[inline]
+fn max_difference<T: Float>(lhs: &[T], rhs: &[T]) -> T {
+    let diffs = lhs.iter().zip(rhs).map(|(&a, &b)| (a - b).abs());
+    diffs.fold(T::MIN_POSITIVE, |m, d| if d > m { d } else { m })
+}
+
+/// Returns true if `sqrt(sum) * scale < max_distance`, without computing the
+/// square root.
+#[inline]
+fn within_distance<T: Float>(sum: T, scale: T, max_distance: T) -> bool {
+    let r = scale / max_distance;
+    // If this overflows, the distance was far too large anyway.
+    sum * r * r < T::from_f64(1.0)
+}
+
+/// The default tolerance, or `n * T::EPSILON` if that's larger, for results
+/// which are sums of `n` values.
+fn sum_tolerance<T: Float>(n: usize) -> T {
+    let per_element = T::from_f64(n as f64) * T::MACHINE_EPSILON;
+    // Capped well below 1.0, for absurdly long vectors.
+    if per_element > T::from_f64(0.5) {
+        T::from_f64(0.5)
+    } else if per_element > crate::float::default_rel_tol() {
+        per_element
+    } else {
+        crate::float::default_rel_tol()
+    }
+}
+
+/// Returns true if the vector `v` has a Euclidean norm of almost exactly 1.
+///
+/// ```
+/// let q = [0.5f32, 0.5, 0.5, 0.5];
+/// assert!(almost::unit_length(&q));
+/// let sixth = (1.0f64 / 6.0).sqrt();
+/// assert!(almost::unit_length(&[sixth; 6]));
+/// assert!(!almost::unit_length(&[0.6, 0.8 * (1.0 + 1e-4)]));
+/// ```
+///
+/// This is for validating normalized quaternions, direction vectors, and
+/// probability amplitudes. The norm is computed from a sum of squares, which
+/// can pick up roughly one `T::EPSILON` of rounding error per element, so the
+/// tolerance is the larger of the default tolerance and
+/// `v.len() as T * T::EPSILON`, which only matters for very long vectors (or
+/// with the `strict-defaults` feature).
+///
+/// Returns false if any element is NaN or infinite, or if `v` is empty.
+#[inline]
+pub fn unit_length<T: Float>(v: &[T]) -> bool {
+    unit_length_with(v, sum_tolerance(v.len()))
+}
+
+/// Equivalent to [`almost::unit_length`](unit_length), but the norm must be
+/// within the provided tolerance of 1.
+///
+/// ```
+/// assert!(almost::unit_length_with(&[0.6, 0.8 * 1.001], 0.002));
+/// assert!(!almost::unit_length_with(&[0.6, 0.8 * 1.001], 0.0005));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+pub fn unit_length_with<T: Float>(v: &[T], tolerance: T) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let mut sq = T::default();
+    for &x in v {
+        sq = sq + x * x;
+    }
+    // `1 - tol < |v| < 1 + tol`, squared, to avoid the square root.
+    let one = T::from_f64(1.0);
+    let (lo, hi) = (one - tolerance, one + tolerance);
+    sq > lo * lo && sq < hi * hi
+}
+
+/// Returns true if the vectors `u` and `v` are almost orthogonal: their dot
+/// product is negligible relative to the product of their norms.
+///
+/// ```
+/// let u = [1e10, 1e10, 0.0];
+/// let v = [1e10, -1e10 * (1.0 + 1e-12), 1e10];
+/// // The dot product is large, but tiny for vectors of this size.
+/// assert!(!almost::zero(1e20 - 1e20 * (1.0 + 1e-12)));
+/// assert!(almost::orthogonal(&u, &v));
+/// assert!(!almost::orthogonal(&[1e-10, 1e-10], &[1e-10, 0.0]));
+/// ```
+///
+/// This checks whether `|u · v| < tol · ‖u‖ · ‖v‖`, that is, whether the angle
+/// between the vectors has a cosine of almost zero. Checking the dot product
+/// with [`almost::zero`](crate::zero) instead is wrong for vectors which
+/// aren't around unit length: it fails for large vectors whose dot product has
+/// only rounding error, and passes for small vectors regardless of their
+/// direction.
+///
+/// A zero vector is orthogonal to everything. Returns false if the lengths
+/// differ, or if any element is NaN or infinite.
+#[inline]
+pub fn orthogonal<T: Float>(u: &[T], v: &[T]) -> bool {
+    orthogonal_with(u, v, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::orthogonal`](orthogonal), but uses the provided
+/// tolerance for the cosine of the angle between the vectors.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+pub fn orthogonal_with<T: Float>(u: &[T], v: &[T], tolerance: T) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    if u.len() != v.len() {
+        return false;
+    }
+    if !u.iter().chain(v).all(|x| x.is_finite()) {
+        return false;
+    }
+    // Rescale each vector separately so nothing can overflow or underflow,
+    // which doesn't change the result.
+    let (us, vs) = (max_magnitude(u), max_magnitude(v));
+    let zero = T::default();
+    let (mut dot, mut u_sq, mut v_sq) = (zero, zero, zero);
+    for (&a, &b) in u.iter().zip(v) {
+        let (a, b) = (a / us, b / vs);
+        dot = dot + a * b;
+        u_sq = u_sq + a * a;
+        v_sq = v_sq + b * b;
+    }
+    dot * dot < tolerance * tolerance * u_sq * v_sq || u_sq == zero || v_sq == zero
+}
+
+/// Checks that `m` has `n * n` elements, for the square matrix functions.
+#[inline]
+#[track_caller]
+fn assert_square<T>(m: &[T], n: usize) {
+    assert_eq!(
+        n.checked_mul(n),
+        Some(m.len()),
+        "an n by n matrix must have n * n elements"
+    );
+}
+
+/// Returns true if the `n` by `n` matrix `m` is almost symmetric.
+///
+/// ```
+/// let m = [
+///     4.0, 1.0, 1e-12,
+///     1.0, 3.0, 2.0,
+///     0.0, 2.0 * (1.0 + 1e-12), 5.0,
+/// ];
+/// assert!(almost::almost_symmetric(&m, 3));
+/// assert!(!almost::almost_symmetric(&[1.0, 2.0, 2.1, 1.0], 2));
+/// ```
+///
+/// This checks that `‖M − Mᵀ‖_F < tol · ‖M‖_F`, so like
+/// [`almost::frobenius_equal`](frobenius_equal), entries which are tiny relative
+/// to the matrix as a whole don't need to match. The elements may be in
+/// row-major or column-major order.
+///
+/// Returns false if any element is NaN or infinite. An empty matrix is
+/// symmetric.
+///
+/// # Panics
+/// Panics if `m.len()` isn't `n * n`.
+#[inline]
+#[track_caller]
+pub fn almost_symmetric<T: Float>(m: &[T], n: usize) -> bool {
+    almost_symmetric_with(m, n, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::almost_symmetric`](almost_symmetric), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// Panics if `m.len()` isn't `n * n`, and in debug mode if `tolerance` is less
+/// than `T::EPSILON` or greater than 1.0.
+#[track_caller]
+pub fn almost_symmetric_with<T: Float>(m: &[T], n: usize, tolerance: T) -> bool {
+    assert_square(m, n);
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    if !m.iter().all(|v| v.is_finite()) {
+        return false;
+    }
+    let scale = max_magnitude(m);
+    let zero = T::default();
+    let (mut diff_sq, mut norm_sq) = (zero, zero);
+    for i in 0..n {
+        for j in 0..n {
+            let a = m[i * n + j] / scale;
+            norm_sq = norm_sq + a * a;
+            if j > i {
+                let d = a - m[j * n + i] / scale;
+                // Each difference appears twice in `M − Mᵀ`.
+                diff_sq = diff_sq + T::from_f64(2.0) * d * d;
+            }
+        }
+    }
+    diff_sq == zero || diff_sq < tolerance * tolerance * norm_sq
+}
+
+/// Returns true if the `n` by `n` matrix `m` is almost the identity matrix.
+///
+/// ```
+/// let m = [1.0, 1e-12, 0.0, 1.0 - 1e-12];
+/// assert!(almost::almost_identity(&m, 2));
+/// assert!(!almost::almost_identity(&[1.0, 0.0, 0.0, 1.001], 2));
+/// ```
+///
+/// This checks that `‖M − I‖_F < tol · max(‖M‖_F, ‖I‖_F)`, as with
+/// [`almost::frobenius_equal`](frobenius_equal), so the off-diagonal elements
+/// only need to be small relative to the whole matrix, rather than exactly
+/// zero.
+///
+/// Returns false if any element is NaN or infinite. An empty matrix is the
+/// identity.
+///
+/// # Panics
+/// Panics if `m.len()` isn't `n * n`.
+#[inline]
+#[track_caller]
+pub fn almost_identity<T: Float>(m: &[T], n: usize) -> bool {
+    almost_identity_with(m, n, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::almost_identity`](almost_identity), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// Panics if `m.len()` isn't `n * n`, and in debug mode if `tolerance` is less
+/// than `T::EPSILON` or greater than 1.0.
+#[track_caller]
+pub fn almost_identity_with<T: Float>(m: &[T], n: usize, tolerance: T) -> bool {
+    assert_square(m, n);
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    if !m.iter().all(|v| v.is_finite()) {
+        return false;
+    }
+    let identity = |i: usize| T::from_f64(if i % (n + 1) == 0 { 1.0 } else { 0.0 });
+    let zero = T::default();
+    let (mut diff_sq, mut norm_sq) = (zero, zero);
+    for (i, &a) in m.iter().enumerate() {
+        let d = a - identity(i);
+        diff_sq = diff_sq + d * d;
+        norm_sq = norm_sq + a * a;
+    }
+    // An identity matrix is small enough that none of this can overflow, and
+    // if the sums do, the matrix certainly isn't the identity.
+    let identity_sq = T::from_f64(n as f64);
+    let norm_sq = if norm_sq > identity_sq {
+        norm_sq
+    } else {
+        identity_sq
+    };
+    diff_sq == zero || diff_sq < tolerance * tolerance * norm_sq
+}
+
+/// Returns true if the `n` by `n` matrix `m` is almost orthogonal, that is,
+/// if `MᵀM` is almost the identity matrix.
+///
+/// ```
+/// let (s, c) = (0.6, 0.8);
+/// let rotation = [c, -s, 0.0, s, c, 0.0, 0.0, 0.0, 1.0];
+/// assert!(almost::almost_orthogonal(&rotation, 3));
+/// let scaled = rotation.map(|x| x * 1.001);
+/// assert!(!almost::almost_orthogonal(&scaled, 3));
+/// ```
+///
+/// This is useful for validating rotations, and `Q` factors and eigenvector
+/// matrices from decompositions. It checks that `‖MᵀM − I‖_F < tol · ‖I‖_F`,
+/// which doesn't depend on whether the elements are in row-major or
+/// column-major order. `MᵀM` is computed directly, which takes time
+/// proportional to `n³`.
+///
+/// Returns false if any element is NaN or infinite. An empty matrix is
+/// orthogonal.
+///
+/// # Panics
+/// Panics if `m.len()` isn't `n * n`.
+#[inline]
+#[track_caller]
+pub fn almost_orthogonal<T: Float>(m: &[T], n: usize) -> bool {
+    almost_orthogonal_with(m, n, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::almost_orthogonal`](almost_orthogonal), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// Panics if `m.len()` isn't `n * n`, and in debug mode if `tolerance` is less
+/// than `T::EPSILON` or greater than 1.0.
+#[track_caller]
+pub fn almost_orthogonal_with<T: Float>(m: &[T], n: usize, tolerance: T) -> bool {
+    assert_square(m, n);
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    if !m.iter().all(|v| v.is_finite()) {
+        return false;
+    }
+    let zero = T::default();
+    let mut diff_sq = zero;
+    for i in 0..n {
+        for j in 0..n {
+            let mut dot = zero;
+            for k in 0..n {
+                dot = dot + m[k * n + i] * m[k * n + j];
+            }
+            let d = dot - T::from_f64(if i == j { 1.0 } else { 0.0 });
+            diff_sq = diff_sq + d * d;
+        }
+    }
+    diff_sq < tolerance * tolerance * T::from_f64(n as f64) || n == 0
+}
+
+/// Returns true if `p` is almost a probability vector: its elements are
+/// non-negative and sum to almost exactly 1.
+///
+/// ```
+/// let p = [0.1, 0.2, 0.3, 0.4];
+/// assert!(almost::almost_normalized(&p));
+/// assert!(!almost::almost_normalized(&[0.5, 0.6]));
+/// assert!(!almost::almost_normalized(&[1.5, -0.5]));
+/// ```
+///
+/// Like [`almost::unit_length`](unit_length), the tolerance grows with the
+/// length of `p` for long vectors, as each element adds rounding error to the
+/// sum. Elements may also be negative by up to the tolerance, as values which
+/// should be zero often end up slightly below it, e.g. after subtracting one
+/// probability from another.
+///
+/// Returns false if any element is NaN or infinite, or if `p` is empty.
+#[inline]
+pub fn almost_normalized<T: Float>(p: &[T]) -> bool {
+    almost_normalized_with(p, sum_tolerance(p.len()))
+}
+
+/// Equivalent to [`almost::almost_normalized`](almost_normalized), but the sum
+/// must be within the provided tolerance of 1.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+pub fn almost_normalized_with<T: Float>(p: &[T], tolerance: T) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let mut sum = T::default();
+    for &x in p {
+        if x.is_nan() || x < -tolerance {
+            return false;
+        }
+        sum = sum + x;
+    }
+    (sum - T::from_f64(1.0)).abs() < tolerance
+}
+
+/// Returns true if `m` is almost a row-stochastic matrix, with rows of `width`
+/// elements: each row is almost a probability vector, as checked by
+/// [`almost::almost_normalized`](almost_normalized).
+///
+/// ```
+/// let transitions = [
+///     0.9, 0.1, 0.0,
+///     0.2, 0.7, 0.1,
+///     0.0, 0.3, 0.7,
+/// ];
+/// assert!(almost::almost_row_stochastic(&transitions, 3));
+/// // The transpose isn't.
+/// let transposed = [0.9, 0.2, 0.0, 0.1, 0.7, 0.3, 0.0, 0.1, 0.7];
+/// assert!(!almost::almost_row_stochastic(&transposed, 3));
+/// ```
+///
+/// This is the invariant of a Markov chain's transition matrix, which tends
+/// to degrade as products of them accumulate rounding error. For a
+/// column-stochastic matrix, pass the elements in column-major order. An empty
+/// matrix is row-stochastic.
+///
+/// # Panics
+/// Panics if `m` isn't made of whole rows, as with
+/// [`almost::compare_grid`](crate::compare_grid), or if `width` is zero and
+/// `m` isn't empty.
+#[inline]
+#[track_caller]
+pub fn almost_row_stochastic<T: Float>(m: &[T], width: usize) -> bool {
+    almost_row_stochastic_with(m, width, sum_tolerance(width))
+}
+
+/// Equivalent to [`almost::almost_row_stochastic`](almost_row_stochastic), but
+/// each row must sum to within the provided tolerance of 1.
+///
+/// # Panics
+/// Panics if `m` isn't made of whole rows, and in debug mode if `tolerance` is
+/// less than `T::EPSILON` or greater than 1.0.
+#[track_caller]
+pub fn almost_row_stochastic_with<T: Float>(m: &[T], width: usize, tolerance: T) -> bool {
+    assert!(
+        m.is_empty() || (width != 0 && m.len() % width == 0),
+        "matrix length must be a multiple of the width"
+    );
+    m.chunks(width.max(1))
+        .all(|row| almost_normalized_with(row, tolerance))
+}
+
+/// Whether [`almost::direction_equal`](direction_equal) considers opposite
+/// vectors to have the same direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DirectionSign {
+    /// The vectors must point the same way.
+    Same,
+    /// The vectors may point the same way or opposite ways, i.e. they must lie
+    /// along the same line. This is for eigenvectors, normals of surfaces
+    /// without an orientation, and other vectors whose sign is arbitrary.
+    Either,
+}
+
+/// Returns true if the vectors `lhs` and `rhs` point in almost the same
+/// direction, regardless of their lengths.
+///
+/// ```
+/// use almost::DirectionSign;
+/// let a = [1.0, 2.0, 3.0];
+/// let b = [-2e6, -4e6, -6e6 * (1.0 + 1e-12)];
+/// assert!(!almost::direction_equal(&a, &b, DirectionSign::Same));
+/// assert!(almost::direction_equal(&a, &b, DirectionSign::Either));
+/// ```
+///
+/// This compares the cosine similarity of the vectors to 1. It's computed as
+/// the distance between the normalized vectors (the chord between them on
+/// the unit sphere), which must be less than the tolerance. For small angles
+/// the chord is almost exactly the angle in radians, and unlike the cosine it
+/// doesn't lose precision as the angle gets smaller. This requires the `std`
+/// or `libm` feature.
+///
+/// Returns false if the lengths differ, if either vector is zero (and so has
+/// no direction), or if any element is NaN or infinite.
+#[cfg(any(feature = "std", feature = "libm"))]
+#[inline]
+pub fn direction_equal<T: Float>(lhs: &[T], rhs: &[T], sign: DirectionSign) -> bool {
+    direction_equal_with(lhs, rhs, sign, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::direction_equal`](direction_equal), but uses the
+/// provided tolerance, which is about the largest angle allowed between the
+/// vectors, in radians.
+///
+/// ```
+/// use almost::DirectionSign;
+/// let a = [1.0f32, 0.0];
+/// let b = [1.0f32, 0.01];
+/// assert!(almost::direction_equal_with(&a, &b, DirectionSign::Same, 0.011));
+/// assert!(!almost::direction_equal_with(&a, &b, DirectionSign::Same, 0.009));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+#[cfg(any(feature = "std", feature = "libm"))]
+pub fn direction_equal_with<T: Float>(
+    lhs: &[T],
+    rhs: &[T],
+    sign: DirectionSign,
+    tolerance: T,
+) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    // The norm of each vector after rescaling it, so the sums of squares can't
+    // overflow or underflow.
+    let zero = T::default();
+    let norm = |v: &[T]| {
+        let scale = max_magnitude(v);
+        let mut sq = zero;
+        for &x in v {
+            sq = sq + (x / scale) * (x / scale);
+        }
+        (scale, T::from_f64(crate::math::sqrt(sq.to_f64())))
+    };
+    let ((ls, ln), (rs, rn)) = (norm(lhs), norm(rhs));
+    if !(ln.is_finite() && rn.is_finite() && ln > zero && rn > zero) {
+        return false;
+    }
+    let (mut diff_sq, mut sum_sq) = (zero, zero);
+    for (&a, &b) in lhs.iter().zip(rhs) {
+        let (a, b) = (a / ls / ln, b / rs / rn);
+        diff_sq = diff_sq + (a - b) * (a - b);
+        sum_sq = sum_sq + (a + b) * (a + b);
+    }
+    let chord_sq = match sign {
+        DirectionSign::Same => diff_sq,
+        DirectionSign::Either if sum_sq < diff_sq => sum_sq,
+        DirectionSign::Either => diff_sq,
+    };
+    chord_sq < tolerance * tolerance
+}
@@ -0,0 +1,521 @@
+//! Macros. These are exported at the crate root, as usual.
+
+/// Returns true if a value is almost equal to any of a set of candidates,
+/// written like an or-pattern.
+///
+/// ```
+/// fn describe(x: f64) -> &'static str {
+///     match x {
+///         x if almost::almost_matches!(x, 0.0) => "nothing",
+///         x if almost::almost_matches!(x, 0.5 | 0.25 | 0.125) => "a small power of two",
+///         x if almost::almost_matches!(x, core::f64::consts::PI | -core::f64::consts::PI) => "±π",
+///         _ => "something else",
+///     }
+/// }
+/// assert_eq!(describe(0.1 + 0.2 - 0.3), "nothing");
+/// assert_eq!(describe(1.0 / 8.0), "a small power of two");
+/// assert_eq!(describe(-4.0 * 1.0f64.atan()), "±π");
+/// assert_eq!(describe(0.3), "something else");
+/// ```
+///
+/// This is intended for dispatch on float values, where a `match` on float
+/// literals would compare exactly (and is deprecated with good reason). The
+/// value is evaluated once, and candidates are evaluated in order until one
+/// matches, so any expression (not just a literal) can be used, as long as it
+/// doesn't contain a top-level `|`.
+///
+/// Each candidate is compared with [`almost::equal`](crate::equal), except for
+/// candidates which are zero, which use [`almost::zero`](crate::zero) instead,
+/// as a relative comparison with zero is almost never what was intended.
+#[macro_export]
+macro_rules! almost_matches {
+    ($value:expr, $($candidates:tt)+) => {{
+        let value = $value;
+        $crate::__almost_matches!(value; []; $($candidates)+)
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __almost_matches {
+    ($value:ident; [$($cur:tt)+]; | $($rest:tt)+) => {
+        $crate::__matches_one($value, $($cur)+) || $crate::__almost_matches!($value; []; $($rest)+)
+    };
+    ($value:ident; [$($cur:tt)+]; $(,)?) => {
+        $crate::__matches_one($value, $($cur)+)
+    };
+    ($value:ident; [$($cur:tt)*]; $next:tt $($rest:tt)*) => {
+        $crate::__almost_matches!($value; [$($cur)* $next]; $($rest)*)
+    };
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn __matches_one<T: crate::Float>(value: T, candidate: T) -> bool {
+    if candidate == T::default() {
+        crate::zero(value)
+    } else {
+        crate::equal(value, candidate)
+    }
+}
+
+/// Fails compilation if two constant float expressions aren't almost equal.
+///
+/// ```
+/// const STEP: f64 = 1.0 / 3.0;
+/// const TABLE: [f64; 4] = [0.0, STEP, 2.0 * STEP, 3.0 * STEP];
+/// almost::const_assert_almost_eq!(TABLE[3], 1.0);
+/// // With a relative tolerance.
+/// almost::const_assert_almost_eq!(TABLE[2], 0.6667, 1e-4);
+/// ```
+///
+/// ```compile_fail
+/// almost::const_assert_almost_eq!(1.0f32 / 3.0, 0.33);
+/// ```
+///
+/// This is for validating precomputed tables and derived constants at build
+/// time, using [`almost::const_equal_f64`](crate::const_equal_f64) and the
+/// other `const fn` comparisons. It expands to an item, so it can be used at
+/// module level as well as inside functions.
+///
+/// The type of at least one of the expressions must be known: two untyped
+/// float literals (as in `const_assert_almost_eq!(0.1 + 0.2, 0.3)`) are
+/// ambiguous, so one needs a suffix, like `0.3f64`.
+#[macro_export]
+macro_rules! const_assert_almost_eq {
+    ($lhs:expr, $rhs:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::__ConstPair($lhs, $rhs).equal(),
+            ::core::concat!(
+                "assertion failed: `",
+                ::core::stringify!($lhs),
+                "` is almost equal to `",
+                ::core::stringify!($rhs),
+                "`"
+            )
+        );
+    };
+    ($lhs:expr, $rhs:expr, $tolerance:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::__ConstPair($lhs, $rhs).equal_with($tolerance),
+            ::core::concat!(
+                "assertion failed: `",
+                ::core::stringify!($lhs),
+                "` is almost equal to `",
+                ::core::stringify!($rhs),
+                "` with tolerance `",
+                ::core::stringify!($tolerance),
+                "`"
+            )
+        );
+    };
+}
+
+/// Asserts that two floats are almost equal, as a drop-in replacement for the
+/// macro of the same name from the `assert_approx_eq` crate.
+///
+/// ```
+/// use almost::assert_approx_eq;
+/// assert_approx_eq!(0.1 + 0.2, 0.3);
+/// // Large values are fine too, as the default is relative.
+/// assert_approx_eq!(1e9 * (0.1 + 0.2), 3e8);
+/// // With an explicit (absolute) epsilon, as before.
+/// assert_approx_eq!(1.0f32, 1.0004, 1e-3);
+/// ```
+///
+/// ```should_panic
+/// # use almost::assert_approx_eq;
+/// assert_approx_eq!(1e-9, 0.0);
+/// ```
+///
+/// Existing code can switch by replacing `use assert_approx_eq::assert_approx_eq`
+/// with `use almost::assert_approx_eq`. The differences are:
+///
+/// - Without an epsilon, the values are compared with
+///   [`almost::equal`](crate::equal) rather than with an absolute difference
+///   of `1e-6`, which is too loose for values much smaller than 1, and too
+///   strict for those much larger. Assertions which compare a value with zero
+///   need [`almost::zero`](crate::zero) (or an explicit epsilon) instead, and
+///   will fail with a message saying so.
+/// - With an epsilon, the behavior is the same: the absolute difference must
+///   be less than it.
+/// - On failure, the message also includes the relative error, and points out
+///   likely mistakes, as with [`Hint`](crate::Hint).
+///
+/// Each value is evaluated once. This works for `f32` and `f64`.
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::__assert_approx_eq($a, $b, None)
+    };
+    ($a:expr, $b:expr, $eps:expr $(,)?) => {
+        $crate::__assert_approx_eq($a, $b, Some($eps))
+    };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_approx_eq<T: crate::Float>(a: T, b: T, eps: Option<T>) {
+    let diff = (a - b).abs();
+    let passed = match eps {
+        Some(eps) => diff < eps,
+        None => crate::equal(a, b),
+    };
+    if passed {
+        return;
+    }
+    let tolerance = crate::float::default_rel_tol();
+    let hint = crate::Hint::detect(a, b, tolerance);
+    let rel = a.rel_err(b);
+    match (eps, hint) {
+        (Some(eps), _) => panic!(
+            "assertion failed: `(left !== right)` (left: `{:?}`, right: `{:?}`, \
+             expect diff: `{:?}`, real diff: `{:?}`, relative error: `{:?}`)",
+            a, b, eps, diff, rel
+        ),
+        (None, Some(hint)) => panic!(
+            "assertion failed: `(left !== right)` (left: `{:?}`, right: `{:?}`, \
+             relative error: `{:?}`, tolerance: `{:?}`): {}",
+            a, b, rel, tolerance, hint
+        ),
+        (None, None) => panic!(
+            "assertion failed: `(left !== right)` (left: `{:?}`, right: `{:?}`, \
+             relative error: `{:?}`, tolerance: `{:?}`)",
+            a, b, rel, tolerance
+        ),
+    }
+}
+
+/// Asserts that two floats are almost equal, printing both values, their
+/// absolute and relative differences, and the tolerance if they aren't.
+///
+/// ```
+/// use almost::assert_almost_eq;
+/// assert_almost_eq!(0.1 + 0.2, 0.3);
+/// // With a relative tolerance, as in `almost::equal_with`.
+/// assert_almost_eq!(1.0f32, 1.0004, 1e-3);
+/// // And a message, as with `assert_eq!`.
+/// let step = 0.5;
+/// assert_almost_eq!(step * 3.0, 1.5, 1e-9, "after {} steps", 3);
+/// ```
+///
+/// ```should_panic
+/// almost::assert_almost_eq!(1.0, 1.1);
+/// ```
+///
+/// The message on failure is like that of `assert_eq!`:
+///
+/// ```text
+/// assertion `left ~= right` failed
+///      left: 1.0
+///     right: 1.1
+///  abs diff: 0.10000000000000009
+///  rel diff: 0.09090909090909094
+/// tolerance: 1.4901161193847656e-8
+/// ```
+///
+/// The values are compared with [`almost::equal`](crate::equal), or with
+/// [`almost::equal_with`](crate::equal_with) if a tolerance is given. A custom
+/// message can only follow a tolerance, as it can't otherwise be told apart
+/// from one; use `T::DEFAULT_TOLERANCE` (from [`AlmostEqual`](crate::AlmostEqual))
+/// to keep the default. When the failure looks like a common mistake, such as
+/// a relative comparison with zero, the message also includes a
+/// [`Hint`](crate::Hint).
+///
+/// Each value is evaluated once, and the panic is reported at the location of
+/// the assertion. This works for `f32` and `f64`.
+#[macro_export]
+macro_rules! assert_almost_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__assert_almost($left, $right, None, true, None)
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        $crate::__assert_almost($left, $right, Some($tolerance), true, None)
+    };
+    ($left:expr, $right:expr, $tolerance:expr, $($arg:tt)+) => {
+        $crate::__assert_almost(
+            $left,
+            $right,
+            Some($tolerance),
+            true,
+            Some(::core::format_args!($($arg)+)),
+        )
+    };
+}
+
+/// Asserts that two floats are not almost equal, printing both values, their
+/// absolute and relative differences, and the tolerance if they are.
+///
+/// ```
+/// use almost::assert_almost_ne;
+/// assert_almost_ne!(0.1 + 0.2, 0.31);
+/// assert_almost_ne!(1.0f32, 1.01, 1e-3, "the perturbation should be visible");
+/// ```
+///
+/// ```should_panic
+/// almost::assert_almost_ne!(0.1 + 0.2, 0.3);
+/// ```
+///
+/// This is the opposite of [`assert_almost_eq!`](crate::assert_almost_eq), and
+/// takes the same arguments.
+#[macro_export]
+macro_rules! assert_almost_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::__assert_almost($left, $right, None, false, None)
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        $crate::__assert_almost($left, $right, Some($tolerance), false, None)
+    };
+    ($left:expr, $right:expr, $tolerance:expr, $($arg:tt)+) => {
+        $crate::__assert_almost(
+            $left,
+            $right,
+            Some($tolerance),
+            false,
+            Some(::core::format_args!($($arg)+)),
+        )
+    };
+}
+
+/// Equivalent to [`assert_almost_eq!`](crate::assert_almost_eq), but only
+/// checked when debug assertions are enabled, as with `debug_assert_eq!`.
+///
+/// ```
+/// let x = 2.0f64.sqrt();
+/// almost::debug_assert_almost_eq!(x * x, 2.0);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_almost_eq {
+    ($($arg:tt)*) => {
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_almost_eq!($($arg)*);
+        }
+    };
+}
+
+/// Equivalent to [`assert_almost_ne!`](crate::assert_almost_ne), but only
+/// checked when debug assertions are enabled, as with `debug_assert_ne!`.
+///
+/// ```
+/// almost::debug_assert_almost_ne!(1.0, 1.5);
+/// ```
+#[macro_export]
+macro_rules! debug_assert_almost_ne {
+    ($($arg:tt)*) => {
+        if ::core::cfg!(debug_assertions) {
+            $crate::assert_almost_ne!($($arg)*);
+        }
+    };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_almost<T: crate::Float>(
+    left: T,
+    right: T,
+    tolerance: Option<T>,
+    expect_equal: bool,
+    message: Option<core::fmt::Arguments<'_>>,
+) {
+    let tolerance = tolerance.unwrap_or(crate::float::default_rel_tol());
+    if left.almost_equals_with(right, tolerance) == expect_equal {
+        return;
+    }
+    let op = if expect_equal { "~=" } else { "!~=" };
+    let diff = if left == right {
+        T::default()
+    } else {
+        (left - right).abs()
+    };
+    let rel = left.rel_err(right);
+    let hint = if expect_equal {
+        crate::Hint::detect(left, right, tolerance)
+    } else {
+        None
+    };
+    struct Message<'a>(Option<core::fmt::Arguments<'a>>);
+    impl core::fmt::Display for Message<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match self.0 {
+                Some(args) => write!(f, ": {}", args),
+                None => Ok(()),
+            }
+        }
+    }
+    struct HintLine(Option<crate::Hint>);
+    impl core::fmt::Display for HintLine {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            match &self.0 {
+                Some(hint) => write!(f, "\n     hint: {}", hint),
+                None => Ok(()),
+            }
+        }
+    }
+    panic!(
+        "assertion `left {} right` failed{}\n     left: {:?}\n    right: {:?}\n \
+         abs diff: {:?}\n rel diff: {:?}\ntolerance: {:?}{}",
+        op,
+        Message(message),
+        left,
+        right,
+        diff,
+        rel,
+        tolerance,
+        HintLine(hint),
+    );
+}
+
+/// Compares two values, written as `lhs ~= rhs`, with the tolerances given by
+/// name, choosing between [`almost::equal`](crate::equal) and
+/// [`almost::zero`](crate::zero) from the form of the comparison.
+///
+/// ```
+/// use almost::almost;
+/// let (a, b) = (0.1 + 0.2, 0.3);
+/// assert!(almost!(a ~= b));
+/// assert!(almost!(a ~= b, rel = 1e-9));
+/// // Comparing with zero uses an absolute tolerance.
+/// let x = a - b;
+/// assert!(almost!(x ~= 0));
+/// assert!(almost!(x ~= 0.0, abs = 1e-12));
+/// // For values which may be tiny, both can be given.
+/// assert!(almost!(1e-17 ~= 1e-30, rel = 1e-9, abs = 1e-12));
+/// ```
+///
+/// The forms are:
+///
+/// - `lhs ~= 0`: [`almost::zero`](crate::zero), and with `abs = t`,
+///   [`almost::zero_with`](crate::zero_with).
+/// - `lhs ~= rhs`: [`almost::equal`](crate::equal), and with `rel = t`,
+///   [`almost::equal_with`](crate::equal_with).
+/// - `lhs ~= rhs, rel = t1, abs = t2`:
+///   [`almost::equal_with_zero_tol`](crate::equal_with_zero_tol).
+///
+/// The combinations which are almost always mistakes don't compile: a relative
+/// tolerance for a comparison with zero, which can only succeed if the value is
+/// exactly zero, and an absolute tolerance for a comparison with anything else.
+///
+/// ```compile_fail
+/// # let x = 1e-17;
+/// almost::almost!(x ~= 0, rel = 1e-9);
+/// ```
+///
+/// ```compile_fail
+/// # let (a, b) = (0.1 + 0.2, 0.3);
+/// almost::almost!(a ~= b, abs = 1e-12);
+/// ```
+///
+/// Zero is recognized as the literals `0`, `0.0`, and those with `f32` or
+/// `f64` suffixes. Anything else on the right hand side is compared with
+/// `equal`, so a constant which happens to be zero should be written as a
+/// literal here. Rust doesn't allow `≈` outside of strings, hence `~=`. As with
+/// [`almost_matches!`](crate::almost_matches), the operands can be any
+/// expressions which don't contain a top-level `,`, or (on the left hand side)
+/// a top-level `~=`, and each is evaluated once.
+#[macro_export]
+macro_rules! almost {
+    ($($tokens:tt)+) => {
+        $crate::__almost_lhs!([]; $($tokens)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __almost_lhs {
+    ([$($lhs:tt)+]; ~ = $($rest:tt)+) => {
+        $crate::__almost_rhs!(($($lhs)+); []; $($rest)+)
+    };
+    ([$($lhs:tt)*]; $next:tt $($rest:tt)*) => {
+        $crate::__almost_lhs!([$($lhs)* $next]; $($rest)*)
+    };
+    ([$($lhs:tt)*];) => {
+        ::core::compile_error!("expected a comparison, like `almost!(a ~= b)`")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __almost_rhs {
+    ($lhs:tt; [$($rhs:tt)+]; , $($opts:tt)*) => {
+        $crate::__almost_opts!($lhs; ($($rhs)+); $($opts)*)
+    };
+    ($lhs:tt; [$($rhs:tt)+];) => {
+        $crate::__almost_opts!($lhs; ($($rhs)+);)
+    };
+    ($lhs:tt; [$($rhs:tt)*]; $next:tt $($rest:tt)*) => {
+        $crate::__almost_rhs!($lhs; [$($rhs)* $next]; $($rest)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __almost_opts {
+    ($lhs:tt; $rhs:tt;) => {
+        $crate::__almost_rhs_kind!($lhs; $rhs; []; [])
+    };
+    ($lhs:tt; $rhs:tt; rel = $rel:expr $(,)?) => {
+        $crate::__almost_rhs_kind!($lhs; $rhs; [$rel]; [])
+    };
+    ($lhs:tt; $rhs:tt; abs = $abs:expr $(,)?) => {
+        $crate::__almost_rhs_kind!($lhs; $rhs; []; [$abs])
+    };
+    ($lhs:tt; $rhs:tt; rel = $rel:expr, abs = $abs:expr $(,)?) => {
+        $crate::__almost_rhs_kind!($lhs; $rhs; [$rel]; [$abs])
+    };
+    ($lhs:tt; $rhs:tt; abs = $abs:expr, rel = $rel:expr $(,)?) => {
+        $crate::__almost_rhs_kind!($lhs; $rhs; [$rel]; [$abs])
+    };
+    ($lhs:tt; $rhs:tt; $($opts:tt)*) => {
+        ::core::compile_error!("expected tolerances like `rel = 1e-9` and/or `abs = 1e-12`")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __almost_rhs_kind {
+    ($lhs:tt; (0); $rel:tt; $abs:tt) => { $crate::__almost_zero!($lhs; $rel; $abs) };
+    ($lhs:tt; (0.0); $rel:tt; $abs:tt) => { $crate::__almost_zero!($lhs; $rel; $abs) };
+    ($lhs:tt; (0f32); $rel:tt; $abs:tt) => { $crate::__almost_zero!($lhs; $rel; $abs) };
+    ($lhs:tt; (0f64); $rel:tt; $abs:tt) => { $crate::__almost_zero!($lhs; $rel; $abs) };
+    ($lhs:tt; (0.0f32); $rel:tt; $abs:tt) => { $crate::__almost_zero!($lhs; $rel; $abs) };
+    ($lhs:tt; (0.0f64); $rel:tt; $abs:tt) => { $crate::__almost_zero!($lhs; $rel; $abs) };
+    (($($lhs:tt)+); ($($rhs:tt)+); [$($rel:expr)?]; [$($abs:expr)?]) => {
+        $crate::__almost_equal!(($($lhs)+); ($($rhs)+); [$($rel)?]; [$($abs)?])
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __almost_zero {
+    (($($lhs:tt)+); []; []) => {
+        $crate::zero($($lhs)+)
+    };
+    (($($lhs:tt)+); []; [$abs:expr]) => {
+        $crate::zero_with($($lhs)+, $abs)
+    };
+    ($lhs:tt; [$rel:expr]; $abs:tt) => {
+        ::core::compile_error!(
+            "a relative tolerance can't be used to compare with zero; use `abs = ...` instead"
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __almost_equal {
+    (($($lhs:tt)+); ($($rhs:tt)+); []; []) => {
+        $crate::equal($($lhs)+, $($rhs)+)
+    };
+    (($($lhs:tt)+); ($($rhs:tt)+); [$rel:expr]; []) => {
+        $crate::equal_with($($lhs)+, $($rhs)+, $rel)
+    };
+    (($($lhs:tt)+); ($($rhs:tt)+); [$rel:expr]; [$abs:expr]) => {
+        $crate::equal_with_zero_tol($($lhs)+, $($rhs)+, $rel, $abs)
+    };
+    ($lhs:tt; $rhs:tt; []; [$abs:expr]) => {
+        ::core::compile_error!(
+            "`abs = ...` only applies when comparing with `0`; use `rel = ...`, \
+             or both for values which may be tiny"
+        )
+    };
+}
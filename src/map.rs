@@ -0,0 +1,303 @@
+//! A map keyed by floats, where lookups find keys which are almost equal.
+
+use crate::Float;
+use alloc::collections::{btree_map, BTreeMap};
+use alloc::vec::Vec;
+
+/// A map from floating point keys to values, where lookups will find an entry
+/// whose key is almost equal to the one requested.
+///
+/// ```
+/// let mut cache = almost::TolerantMap::new();
+/// cache.insert(0.3, "cached");
+/// assert_eq!(cache.get(0.1 + 0.2), Some(&"cached"));
+/// assert_eq!(cache.get(0.31), None);
+/// ```
+///
+/// This is intended for things like caches keyed by computed parameters
+/// (angles, lengths, and so on), where the key used for the lookup is
+/// unlikely to be bit-for-bit identical to the one used for insertion.
+///
+/// Internally, keys are grouped into buckets based on their representation,
+/// where the bucket size is chosen from the tolerance so that almost equal keys
+/// are always in the same or adjacent buckets. Lookups are `O(log n)` in the
+/// number of buckets, plus a linear scan of (at most) three buckets.
+///
+/// # Resolution limits
+///
+/// - Inserting a key which is almost equal to one already present replaces
+///   that entry's value, and keeps the existing key. Because approximate
+///   equality is not transitive, which entry absorbs a key may depend on the
+///   order of insertion — keys that are much closer together than the
+///   tolerance will behave predictably, keys that are about a tolerance apart
+///   may not.
+/// - If a lookup is almost equal to several keys, the one closest to it is
+///   used.
+/// - NaN keys may be inserted, but (as with every other map) can never be
+///   found again, except via iteration.
+/// - The tolerance must be at least the machine epsilon, and less than 0.5.
+#[derive(Debug, Clone)]
+pub struct TolerantMap<K, V> {
+    buckets: BTreeMap<i64, Vec<(K, V)>>,
+    len: usize,
+    tol: K,
+    shift: u32,
+}
+
+impl<K: Float, V> Default for TolerantMap<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Float, V> TolerantMap<K, V> {
+    /// Creates an empty map which compares keys using the default tolerance
+    /// (as in [`almost::equal`](crate::equal)).
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_tolerance(crate::float::default_rel_tol())
+    }
+
+    /// Creates an empty map which compares keys using the provided relative
+    /// tolerance (as in [`almost::equal_with`](crate::equal_with)).
+    ///
+    /// ```
+    /// let mut m = almost::TolerantMap::with_tolerance(0.01f32);
+    /// m.insert(1.0, 1);
+    /// assert_eq!(m.get(1.005), Some(&1));
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `tolerance` is less than the machine epsilon, or not less than
+    /// 0.5. Unlike the free functions, this is checked in release builds too,
+    /// as the map will not work properly otherwise.
+    pub fn with_tolerance(tolerance: K) -> Self {
+        assert!(
+            tolerance >= K::MACHINE_EPSILON && tolerance.exponent() < -1,
+            "TolerantMap tolerance must be in [MACHINE_EPSILON, 0.5): {:?}",
+            tolerance,
+        );
+        // Two positive values within `tol` of each other (relative to the
+        // larger of the two) are at most `tol * 2^(SIGNIFICAND_BITS + 2)` ulps
+        // apart, even across a binade boundary (and this holds for subnormals
+        // and for infinity too). `tol < 2^(exponent + 1)`, so making the bucket
+        // that size guarantees almost equal keys are in adjacent buckets.
+        let shift = (tolerance.exponent() + 1 + K::SIGNIFICAND_BITS as i32 + 2) as u32;
+        Self {
+            buckets: BTreeMap::new(),
+            len: 0,
+            tol: tolerance,
+            shift,
+        }
+    }
+
+    /// The tolerance used to compare keys.
+    #[inline]
+    pub fn tolerance(&self) -> K {
+        self.tol
+    }
+
+    /// Returns the number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the map contains no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes every entry from the map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.len = 0;
+    }
+
+    #[inline]
+    fn bucket(&self, key: K) -> i64 {
+        key.to_ordered() >> self.shift
+    }
+
+    /// Finds the bucket and position of the closest key almost equal to `key`.
+    fn find(&self, key: K) -> Option<(i64, usize)> {
+        let b = self.bucket(key);
+        let mut best: Option<(i64, usize, K)> = None;
+        for (&bucket, entries) in self.buckets.range(b - 1..=b + 1) {
+            for (i, &(k, _)) in entries.iter().enumerate() {
+                if !k.almost_equals_with(key, self.tol) {
+                    continue;
+                }
+                // Avoid `inf - inf`.
+                let dist = if k == key {
+                    K::default()
+                } else {
+                    (k - key).abs()
+                };
+                match best {
+                    Some((_, _, d)) if d <= dist => {}
+                    _ => best = Some((bucket, i, dist)),
+                }
+            }
+        }
+        best.map(|(bucket, i, _)| (bucket, i))
+    }
+
+    /// Returns a reference to the value of the entry whose key is almost equal
+    /// to `key`, if any.
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.get_key_value(key).map(|(_, v)| v)
+    }
+
+    /// Returns the key (as originally inserted) and value of the entry whose key
+    /// is almost equal to `key`, if any.
+    ///
+    /// ```
+    /// let mut m = almost::TolerantMap::new();
+    /// m.insert(0.3, ());
+    /// assert_eq!(m.get_key_value(0.1 + 0.2), Some((0.3, &())));
+    /// ```
+    #[inline]
+    pub fn get_key_value(&self, key: K) -> Option<(K, &V)> {
+        let (b, i) = self.find(key)?;
+        let (k, v) = &self.buckets[&b][i];
+        Some((*k, v))
+    }
+
+    /// Returns a mutable reference to the value of the entry whose key is
+    /// almost equal to `key`, if any.
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        let (b, i) = self.find(key)?;
+        self.buckets.get_mut(&b).map(|entries| &mut entries[i].1)
+    }
+
+    /// Returns true if the map has an entry whose key is almost equal to `key`.
+    #[inline]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Inserts a value into the map.
+    ///
+    /// If an entry with a key almost equal to `key` already exists, its value is
+    /// replaced, and the old value is returned. In this case, the key the entry
+    /// was originally inserted with is retained.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(v) = self.get_mut(key) {
+            return Some(core::mem::replace(v, value));
+        }
+        let b = self.bucket(key);
+        self.buckets.entry(b).or_default().push((key, value));
+        self.len += 1;
+        None
+    }
+
+    /// Returns a mutable reference to the value for the entry whose key is
+    /// almost equal to `key`, inserting the result of `f()` if no such entry
+    /// exists.
+    ///
+    /// ```
+    /// let mut m = almost::TolerantMap::new();
+    /// *m.get_or_insert_with(0.3, || 0) += 1;
+    /// *m.get_or_insert_with(0.1 + 0.2, || 0) += 1;
+    /// assert_eq!(m.get(0.3), Some(&2));
+    /// assert_eq!(m.len(), 1);
+    /// ```
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        let (b, i) = match self.find(key) {
+            Some(found) => found,
+            None => {
+                let b = self.bucket(key);
+                let entries = self.buckets.entry(b).or_default();
+                entries.push((key, f()));
+                self.len += 1;
+                (b, entries.len() - 1)
+            }
+        };
+        &mut self.buckets.get_mut(&b).expect("bucket exists")[i].1
+    }
+
+    /// Removes the entry whose key is almost equal to `key`, returning its
+    /// value.
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let (b, i) = self.find(key)?;
+        let entries = self.buckets.get_mut(&b)?;
+        let (_, v) = entries.swap_remove(i);
+        if entries.is_empty() {
+            self.buckets.remove(&b);
+        }
+        self.len -= 1;
+        Some(v)
+    }
+
+    /// Returns an iterator over the keys and values in the map. Entries are
+    /// roughly, but not exactly, in ascending order of their keys.
+    #[inline]
+    pub fn iter(&self) -> TolerantMapIter<'_, K, V> {
+        TolerantMapIter {
+            buckets: self.buckets.values(),
+            entries: [].iter(),
+            remaining: self.len,
+        }
+    }
+}
+
+impl<K: Float, V> Extend<(K, V)> for TolerantMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<K: Float, V> core::iter::FromIterator<(K, V)> for TolerantMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K: Float, V> IntoIterator for &'a TolerantMap<K, V> {
+    type Item = (K, &'a V);
+    type IntoIter = TolerantMapIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> TolerantMapIter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`TolerantMap`].
+#[derive(Debug, Clone)]
+pub struct TolerantMapIter<'a, K, V> {
+    buckets: btree_map::Values<'a, i64, Vec<(K, V)>>,
+    entries: core::slice::Iter<'a, (K, V)>,
+    remaining: usize,
+}
+
+impl<'a, K: Copy, V> Iterator for TolerantMapIter<'a, K, V> {
+    type Item = (K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((k, v)) = self.entries.next() {
+                self.remaining -= 1;
+                return Some((*k, v));
+            }
+            self.entries = self.buckets.next()?.iter();
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K: Copy, V> ExactSizeIterator for TolerantMapIter<'_, K, V> {}
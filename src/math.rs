@@ -0,0 +1,50 @@
+//! Math functions which aren't available in `core`. These come from `std` if
+//! it's enabled, and otherwise from `libm`.
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn pow(x: f64, y: f64) -> f64 {
+    x.powf(y)
+}
+
+#[cfg(all(feature = "libm", not(feature = "std")))]
+#[inline]
+pub(crate) fn pow(x: f64, y: f64) -> f64 {
+    libm::pow(x, y)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(all(feature = "libm", not(feature = "std")))]
+#[inline]
+pub(crate) fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn log10(x: f64) -> f64 {
+    x.log10()
+}
+
+#[cfg(all(feature = "libm", not(feature = "std")))]
+#[inline]
+pub(crate) fn log10(x: f64) -> f64 {
+    libm::log10(x)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn sin(x: f64) -> f64 {
+    x.sin()
+}
+
+#[cfg(all(feature = "libm", not(feature = "std")))]
+#[inline]
+pub(crate) fn sin(x: f64) -> f64 {
+    libm::sin(x)
+}
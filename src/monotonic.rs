@@ -0,0 +1,153 @@
+//! Checks that sequences are increasing or decreasing, up to tolerance.
+
+use crate::Float;
+
+/// Returns the index of the first element which breaks the ordering, where
+/// `ahead(a, b)` is true if `a` is strictly past `b` in the desired direction.
+fn check<T: Float, F>(values: &[T], tolerance: T, strict: bool, ahead: F) -> Result<(), usize>
+where
+    F: Fn(T, T) -> bool,
+{
+    let mut iter = values.iter().copied().enumerate();
+    let mut prev = match iter.next() {
+        Some((_, v)) if v.is_nan() => return Err(0),
+        Some((_, v)) => v,
+        None => return Ok(()),
+    };
+    for (i, v) in iter {
+        if v.is_nan() {
+            return Err(i);
+        }
+        let close = v.almost_equals_with(prev, tolerance);
+        if strict {
+            if close || !ahead(v, prev) {
+                return Err(i);
+            }
+            prev = v;
+        } else {
+            // Compare against the extreme so far, rather than the previous
+            // value, so that a long run of small steps in the wrong direction
+            // can't add up to a large one.
+            if !close && !ahead(v, prev) {
+                return Err(i);
+            }
+            if ahead(v, prev) {
+                prev = v;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `values` never decreases, other than by amounts which are
+/// almost equal. On failure, returns the index of the first element which is
+/// too small.
+///
+/// ```
+/// // Cumulative probabilities, with some jitter from rounding.
+/// let cdf = [0.1, 0.3, 0.6, 0.6 - 1e-12, 1.0];
+/// assert_eq!(almost::almost_increasing(&cdf), Ok(()));
+/// assert_eq!(almost::almost_increasing(&[1.0, 2.0, 1.5]), Err(2));
+/// ```
+///
+/// Each element is compared with the largest element before it (not just the
+/// previous one), so a sequence which slowly drifts downwards will fail, even
+/// if each step is within tolerance. NaN values always fail. The comparison is
+/// relative, so values which should be exactly zero may need to be handled
+/// separately.
+#[inline]
+pub fn almost_increasing<T: Float>(values: &[T]) -> Result<(), usize> {
+    almost_increasing_with(values, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::almost_increasing`](almost_increasing), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn almost_increasing_with<T: Float>(values: &[T], tolerance: T) -> Result<(), usize> {
+    check(values, tolerance, false, |a, b| a > b)
+}
+
+/// Checks that `values` never increases, other than by amounts which are
+/// almost equal. On failure, returns the index of the first element which is
+/// too large.
+///
+/// ```
+/// let v = [3.0, 2.0, 2.0 + 1e-12, 1.0];
+/// assert_eq!(almost::almost_decreasing(&v), Ok(()));
+/// ```
+///
+/// This is the same as [`almost::almost_increasing`](almost_increasing), but
+/// in the other direction.
+#[inline]
+pub fn almost_decreasing<T: Float>(values: &[T]) -> Result<(), usize> {
+    almost_decreasing_with(values, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::almost_decreasing`](almost_decreasing), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn almost_decreasing_with<T: Float>(values: &[T], tolerance: T) -> Result<(), usize> {
+    check(values, tolerance, false, |a, b| a < b)
+}
+
+/// Checks that each element of `values` is larger than the previous one, by
+/// more than the tolerance. On failure, returns the index of the first element
+/// which isn't.
+///
+/// ```
+/// let timestamps = [1.0, 2.0, 3.0];
+/// assert_eq!(almost::strictly_increasing(&timestamps), Ok(()));
+/// // Not a real step, just rounding error.
+/// assert_eq!(almost::strictly_increasing(&[1.0, 2.0, 2.0 + 1e-12]), Err(2));
+/// ```
+///
+/// NaN values always fail.
+#[inline]
+pub fn strictly_increasing<T: Float>(values: &[T]) -> Result<(), usize> {
+    strictly_increasing_with(values, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::strictly_increasing`](strictly_increasing), but uses
+/// the provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn strictly_increasing_with<T: Float>(values: &[T], tolerance: T) -> Result<(), usize> {
+    check(values, tolerance, true, |a, b| a > b)
+}
+
+/// Checks that each element of `values` is smaller than the previous one, by
+/// more than the tolerance. On failure, returns the index of the first element
+/// which isn't.
+///
+/// ```
+/// assert_eq!(almost::strictly_decreasing(&[3.0, 2.0, 1.0]), Ok(()));
+/// assert_eq!(almost::strictly_decreasing(&[3.0, 3.0, 1.0]), Err(1));
+/// ```
+///
+/// NaN values always fail.
+#[inline]
+pub fn strictly_decreasing<T: Float>(values: &[T]) -> Result<(), usize> {
+    strictly_decreasing_with(values, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::strictly_decreasing`](strictly_decreasing), but uses
+/// the provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn strictly_decreasing_with<T: Float>(values: &[T], tolerance: T) -> Result<(), usize> {
+    check(values, tolerance, true, |a, b| a < b)
+}
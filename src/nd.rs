@@ -0,0 +1,223 @@
+//! Comparison of `ndarray` arrays, with NumPy-style broadcasting.
+
+use crate::report::{ElementwiseReport, ReportBuilder};
+use crate::Float;
+use alloc::vec::Vec;
+use core::fmt;
+use ndarray::{ArrayBase, Axis, Data, Dimension, IxDyn};
+
+/// The reason an `ndarray` comparison failed, as returned by
+/// [`almost::ndarray_equal`](crate::ndarray_equal).
+#[derive(Debug, Clone, PartialEq)]
+pub enum NdMismatch<T> {
+    /// The shapes of the arrays can't be broadcast together.
+    Shape {
+        /// The shape of the left hand side.
+        lhs: Vec<usize>,
+        /// The shape of the right hand side.
+        rhs: Vec<usize>,
+    },
+    /// The arrays could be broadcast together, but some elements weren't almost
+    /// equal. Indices in the report are in the broadcast coordinates, given by
+    /// `shape`.
+    Values {
+        /// The shape both arrays were broadcast to.
+        shape: Vec<usize>,
+        /// Statistics about the comparison.
+        report: ElementwiseReport<T, Vec<usize>>,
+    },
+}
+
+impl<T: fmt::Display> fmt::Display for NdMismatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NdMismatch::Shape { lhs, rhs } => {
+                write!(
+                    f,
+                    "shapes {:?} and {:?} can't be broadcast together",
+                    lhs, rhs
+                )
+            }
+            NdMismatch::Values { shape, report } => {
+                write!(f, "arrays differ (broadcast to {:?}): {}", shape, report)
+            }
+        }
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> core::error::Error for NdMismatch<T> {}
+
+/// Computes the shape that arrays of shape `a` and `b` broadcast to, using the
+/// NumPy rules: shapes are aligned at the trailing axis, and each pair of axes
+/// must either be equal, or one of them must be 1.
+pub(crate) fn broadcast_shape(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+    let n = a.len().max(b.len());
+    let axis = |s: &[usize], i: usize| {
+        if i + s.len() >= n {
+            s[i + s.len() - n]
+        } else {
+            1
+        }
+    };
+    (0..n)
+        .map(|i| match (axis(a, i), axis(b, i)) {
+            (x, y) if x == y || y == 1 => Some(x),
+            (1, y) => Some(y),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns `Ok(())` if the arrays `lhs` and `rhs` are almost equal, after
+/// broadcasting them to a common shape.
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// let points: Array2<f64> = array![[1.0, 2.0, 3.0], [1.0, 2.0, 3.0 + 1e-12]];
+/// // Compare every row against the same expected row.
+/// assert!(almost::ndarray_equal(&points, &array![1.0, 2.0, 3.0]).is_ok());
+///
+/// let err = almost::ndarray_equal(&points, &array![1.0, 2.0, 3.1]).unwrap_err();
+/// assert!(matches!(
+///     err,
+///     almost::NdMismatch::Values { ref report, .. } if report.mismatches == 2
+/// ));
+/// ```
+///
+/// Broadcasting follows the same rules as NumPy, so test assertions ported
+/// from Python (e.g. `np.testing.assert_allclose`) translate directly. Note
+/// that this uses this crate's relative comparison, rather than NumPy's
+/// mixed absolute/relative one.
+///
+/// On failure, the returned [`NdMismatch`] describes the shapes involved, or
+/// the mismatched elements (with indices in the broadcast coordinates).
+///
+/// This requires the `ndarray` feature.
+#[inline]
+pub fn ndarray_equal<T, S1, S2, D1, D2>(
+    lhs: &ArrayBase<S1, D1>,
+    rhs: &ArrayBase<S2, D2>,
+) -> Result<(), NdMismatch<T>>
+where
+    T: Float,
+    S1: Data<Elem = T>,
+    S2: Data<Elem = T>,
+    D1: Dimension,
+    D2: Dimension,
+{
+    ndarray_equal_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::ndarray_equal`](ndarray_equal), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn ndarray_equal_with<T, S1, S2, D1, D2>(
+    lhs: &ArrayBase<S1, D1>,
+    rhs: &ArrayBase<S2, D2>,
+    tolerance: T,
+) -> Result<(), NdMismatch<T>>
+where
+    T: Float,
+    S1: Data<Elem = T>,
+    S2: Data<Elem = T>,
+    D1: Dimension,
+    D2: Dimension,
+{
+    compare(lhs, rhs, |_| tolerance)
+}
+
+/// Equivalent to [`almost::ndarray_equal`](ndarray_equal), but uses a separate
+/// relative tolerance for each position along `axis`.
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// // Two output channels with very different noise levels.
+/// let out = array![[1.0, 101.0], [2.0, 200.0]];
+/// let expected = array![[1.0, 100.0], [2.0, 200.0]];
+/// let tols = [1e-9, 1e-2];
+/// assert!(almost::ndarray_equal_along(&out, &expected, Axis(1), &tols).is_ok());
+/// assert!(almost::ndarray_equal_along(&out, &expected, Axis(0), &tols).is_err());
+/// ```
+///
+/// This is intended for things like multi-output models or multi-physics
+/// fields, where each column has a wildly different scale or accuracy. The
+/// axis refers to the shape after broadcasting, and `tolerances` may have a
+/// single element, in which case it's used for the whole axis.
+///
+/// # Panics
+/// Panics if `axis` is out of bounds for the broadcast shape, or if the length
+/// of `tolerances` is neither 1 nor the length of that axis. Also panics in
+/// debug mode if any tolerance is less than `T::EPSILON` or greater than 1.0.
+pub fn ndarray_equal_along<T, S1, S2, D1, D2>(
+    lhs: &ArrayBase<S1, D1>,
+    rhs: &ArrayBase<S2, D2>,
+    axis: Axis,
+    tolerances: &[T],
+) -> Result<(), NdMismatch<T>>
+where
+    T: Float,
+    S1: Data<Elem = T>,
+    S2: Data<Elem = T>,
+    D1: Dimension,
+    D2: Dimension,
+{
+    let axis = axis.index();
+    if let Some(shape) = broadcast_shape(lhs.shape(), rhs.shape()) {
+        assert!(
+            axis < shape.len(),
+            "axis {} is out of bounds for shape {:?}",
+            axis,
+            shape
+        );
+        assert!(
+            tolerances.len() == 1 || tolerances.len() == shape[axis],
+            "expected 1 or {} tolerances for axis {}, got {}",
+            shape[axis],
+            axis,
+            tolerances.len()
+        );
+    }
+    let single = tolerances.len() == 1;
+    compare(lhs, rhs, |index| {
+        tolerances[if single { 0 } else { index[axis] }]
+    })
+}
+
+fn compare<T, S1, S2, D1, D2, F>(
+    lhs: &ArrayBase<S1, D1>,
+    rhs: &ArrayBase<S2, D2>,
+    tolerance: F,
+) -> Result<(), NdMismatch<T>>
+where
+    T: Float,
+    S1: Data<Elem = T>,
+    S2: Data<Elem = T>,
+    D1: Dimension,
+    D2: Dimension,
+    F: Fn(&[usize]) -> T,
+{
+    let shape = broadcast_shape(lhs.shape(), rhs.shape()).ok_or_else(|| NdMismatch::Shape {
+        lhs: lhs.shape().to_vec(),
+        rhs: rhs.shape().to_vec(),
+    })?;
+    let dim = IxDyn(&shape);
+    // Can't fail, since we computed a compatible shape.
+    let (l, r) = match (lhs.broadcast(dim.clone()), rhs.broadcast(dim)) {
+        (Some(l), Some(r)) => (l, r),
+        _ => unreachable!("shapes should be compatible"),
+    };
+    let mut builder = ReportBuilder::new();
+    for ((index, &a), &b) in l.indexed_iter().zip(r.iter()) {
+        let tol = tolerance(index.slice());
+        builder.push(|| index.slice().to_vec(), a, b, tol);
+    }
+    let report = builder.finish();
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(NdMismatch::Values { shape, report })
+    }
+}
@@ -0,0 +1,184 @@
+//! Comparison of nullable values, such as numeric columns from a database.
+
+use crate::Float;
+#[cfg(feature = "alloc")]
+use {
+    crate::report::{ElementwiseReport, ReportBuilder},
+    alloc::vec::Vec,
+    core::fmt,
+};
+
+#[inline]
+fn nullable_pair<T: Float>(lhs: Option<T>, rhs: Option<T>, tolerance: T) -> bool {
+    match (lhs, rhs) {
+        (None, None) => true,
+        (Some(a), Some(b)) => a.almost_equals_with(b, tolerance),
+        _ => false,
+    }
+}
+
+/// Returns true if `lhs` and `rhs` have the same length, `None` in the same
+/// positions, and almost equal values everywhere else.
+///
+/// ```
+/// assert!(almost::nullable_equal(&[Some(0.1 + 0.2), None], &[Some(0.3), None]));
+/// assert!(!almost::nullable_equal(&[Some(0.0), None], &[None, Some(0.0)]));
+/// ```
+///
+/// This follows the usual semantics for nulls in data stores: `None` is only
+/// equal to `None`, and never to any value. Values are compared as with
+/// [`almost::equal`](crate::equal).
+#[inline]
+pub fn nullable_equal<T: Float>(lhs: &[Option<T>], rhs: &[Option<T>]) -> bool {
+    nullable_equal_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::nullable_equal`](nullable_equal), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn nullable_equal_with<T: Float>(lhs: &[Option<T>], rhs: &[Option<T>], tolerance: T) -> bool {
+    lhs.len() == rhs.len()
+        && nullable_iter_equal_with(lhs.iter().copied(), rhs.iter().copied(), tolerance)
+}
+
+/// Equivalent to [`almost::nullable_equal`](nullable_equal), but for
+/// iterators, such as the rows of a query.
+///
+/// ```
+/// let column = vec![Some(1.0f32), None, Some(3.0)];
+/// let expected = [1.0, f32::NAN, 3.0].map(|v| Some(v).filter(|v| !v.is_nan()));
+/// assert!(almost::nullable_iter_equal(column, expected));
+/// ```
+///
+/// Returns false if the iterators produce different numbers of items.
+#[inline]
+pub fn nullable_iter_equal<T, A, B>(lhs: A, rhs: B) -> bool
+where
+    T: Float,
+    A: IntoIterator<Item = Option<T>>,
+    B: IntoIterator<Item = Option<T>>,
+{
+    nullable_iter_equal_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::nullable_iter_equal`](nullable_iter_equal), but
+/// uses the provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn nullable_iter_equal_with<T, A, B>(lhs: A, rhs: B, tolerance: T) -> bool
+where
+    T: Float,
+    A: IntoIterator<Item = Option<T>>,
+    B: IntoIterator<Item = Option<T>>,
+{
+    let (mut lhs, mut rhs) = (lhs.into_iter(), rhs.into_iter());
+    loop {
+        match (lhs.next(), rhs.next()) {
+            (None, None) => return true,
+            (Some(a), Some(b)) if nullable_pair(a, b, tolerance) => {}
+            _ => return false,
+        }
+    }
+}
+
+/// The result of [`almost::compare_nullable`](compare_nullable). This requires
+/// the `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NullableReport<T> {
+    /// Statistics for the positions where both sides had a value.
+    pub values: ElementwiseReport<T, usize>,
+    /// The positions where one side was `None` and the other wasn't, in
+    /// increasing order.
+    pub null_mismatches: Vec<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> NullableReport<T> {
+    /// Returns true if nulls were in the same positions, and every pair of
+    /// values was almost equal.
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.null_mismatches.is_empty() && self.values.passed()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Display> fmt::Display for NullableReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.values)?;
+        if let Some(first) = self.null_mismatches.first() {
+            write!(
+                f,
+                "; nullness differs at {} positions, first at {}",
+                self.null_mismatches.len(),
+                first
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares two slices of nullable values element-wise, reporting statistics
+/// for the values, and the positions where nullness differs. This requires the
+/// `alloc` feature.
+///
+/// ```
+/// let actual = [Some(1.0), None, Some(3.0), Some(4.0)];
+/// let expected = [Some(1.0), Some(2.0), Some(3.5), Some(4.0)];
+/// let report = almost::compare_nullable(&actual, &expected);
+/// assert_eq!(report.null_mismatches, [1]);
+/// assert_eq!(report.values.total, 3);
+/// assert_eq!(report.values.first_mismatch.as_ref().unwrap().index, 2);
+/// assert!(!report.passed());
+/// ```
+///
+/// # Panics
+/// This function panics if the slices have different lengths, as positions
+/// would be meaningless.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn compare_nullable<T: Float>(lhs: &[Option<T>], rhs: &[Option<T>]) -> NullableReport<T> {
+    compare_nullable_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::compare_nullable`](compare_nullable), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics if the slices have different lengths. It also panics
+/// in debug mode if `tolerance` is less than `T::EPSILON` or greater than 1.0,
+/// as with [`almost::equal_with`](crate::equal_with).
+#[cfg(feature = "alloc")]
+pub fn compare_nullable_with<T: Float>(
+    lhs: &[Option<T>],
+    rhs: &[Option<T>],
+    tolerance: T,
+) -> NullableReport<T> {
+    assert_eq!(
+        lhs.len(),
+        rhs.len(),
+        "compare_nullable requires slices of the same length"
+    );
+    let mut values = ReportBuilder::new();
+    let mut null_mismatches = Vec::new();
+    for (i, (&a, &b)) in lhs.iter().zip(rhs).enumerate() {
+        match (a, b) {
+            (None, None) => {}
+            (Some(a), Some(b)) => {
+                values.push(|| i, a, b, tolerance);
+            }
+            _ => null_mismatches.push(i),
+        }
+    }
+    NullableReport {
+        values: values.finish(),
+        null_mismatches,
+    }
+}
@@ -0,0 +1,169 @@
+//! Checking comparisons against an exact computation of the relative error.
+//! This requires the `oracle` feature, which uses `astro-float`.
+//!
+//! [`almost::equal_with`](crate::equal_with) is defined as
+//!
+//! ```text
+//! |a - b| < tol * max(|a|, |b|, MIN_POSITIVE)
+//! ```
+//!
+//! but it's computed in the type being compared, with rescaling to avoid
+//! overflow, and with special cases for subnormals and infinities. This module
+//! evaluates the definition exactly instead, with enough bits that nothing is
+//! ever rounded, so that the bit-level implementation can be checked against
+//! it, whether by this crate's tests or by anyone who needs independent
+//! confirmation of a verdict.
+//!
+//! ```
+//! let v = almost::oracle::verify(0.1 + 0.2, 0.3, almost::F64_TOLERANCE);
+//! assert!(v.verdict && v.exact && v.agrees());
+//! assert!((v.rel_err - 1.85e-16).abs() < 1e-18);
+//! ```
+//!
+//! Infinities are treated as the power of two just past the largest finite
+//! value (`2^1024` for `f64`, and `2^128` for `f32`), as in
+//! [`almost::equal`](crate::equal), so that the largest values are almost equal
+//! to infinity, and equal infinities are equal. NaN is never equal to
+//! anything.
+//!
+//! This is far slower than the comparisons themselves, and only meant for
+//! testing.
+
+use crate::Float;
+use astro_float::{BigFloat, RoundingMode};
+use std::format;
+
+/// The result of [`verify`], comparing the verdict of
+/// [`almost::equal_with`](crate::equal_with) against the exact definition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Verification {
+    /// Whether [`almost::equal_with`](crate::equal_with) considered the values
+    /// almost equal.
+    pub verdict: bool,
+    /// Whether the values are almost equal, computed exactly.
+    pub exact: bool,
+    /// The exact relative error, `|a - b| / max(|a|, |b|, MIN_POSITIVE)`,
+    /// rounded to the nearest `f64`. This is 0 for equal values (including
+    /// equal infinities), and NaN if either value is NaN.
+    pub rel_err: f64,
+    /// Whether the exact relative error is within a few ulps of the tolerance,
+    /// where rounding in the type being compared can legitimately change the
+    /// verdict.
+    pub borderline: bool,
+}
+
+impl Verification {
+    /// Returns true if the verdict matches the exact one, or if they differ
+    /// only because the relative error is within rounding error of the
+    /// tolerance.
+    #[inline]
+    pub fn agrees(&self) -> bool {
+        self.verdict == self.exact || self.borderline
+    }
+}
+
+/// Enough bits to hold the difference of any two `f64` values exactly, or
+/// their product with a tolerance, with some to spare.
+const PRECISION: usize = 2304;
+
+/// How many ulps (of the type being compared) from the tolerance a relative
+/// error has to be for the verdict to be unaffected by rounding.
+const BORDERLINE_ULPS: f64 = 4.0;
+
+/// Compares `a` and `b` as [`almost::equal_with`](crate::equal_with) does with
+/// the relative tolerance `tolerance`, and also by computing the definition
+/// exactly, as described in the [module documentation](self).
+///
+/// ```
+/// // Subnormals are relative to `MIN_POSITIVE`, so they're all almost equal.
+/// let v = almost::oracle::verify(5e-324, 1e-310, 1e-2);
+/// assert!(v.verdict && v.exact && v.agrees());
+/// // The largest finite value is almost equal to infinity.
+/// let v = almost::oracle::verify(f32::MAX, f32::INFINITY, almost::F32_TOLERANCE);
+/// assert!(v.verdict && v.exact);
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than
+/// `T::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+pub fn verify<T: Float>(a: T, b: T, tolerance: T) -> Verification {
+    let verdict = a.almost_equals_with(b, tolerance);
+    if a.is_nan() || b.is_nan() {
+        return Verification {
+            verdict,
+            exact: false,
+            rel_err: f64::NAN,
+            borderline: false,
+        };
+    }
+    if a == b {
+        return Verification {
+            verdict,
+            exact: true,
+            rel_err: 0.0,
+            borderline: false,
+        };
+    }
+    let (a, b) = (exact(a), exact(b));
+    let diff = abs(a.sub(&b, PRECISION, RoundingMode::None));
+    let mut scale = exact(T::MIN_POSITIVE);
+    for v in [abs(a), abs(b)] {
+        if v.cmp(&scale).is_some_and(|c| c > 0) {
+            scale = v;
+        }
+    }
+    let tol = exact(tolerance);
+    let limit = tol.mul(&scale, PRECISION, RoundingMode::None);
+    let within = diff.cmp(&limit).is_some_and(|c| c < 0);
+    let rel = diff.div(&scale, 128, RoundingMode::ToEven);
+    let rel_err = format!("{}", rel).parse::<f64>().unwrap_or(f64::NAN);
+    let slack = BORDERLINE_ULPS * T::MACHINE_EPSILON.to_f64() * tolerance.to_f64();
+    Verification {
+        verdict,
+        exact: within,
+        rel_err,
+        borderline: (rel_err - tolerance.to_f64()).abs() <= slack,
+    }
+}
+
+/// Converts `v` exactly, with infinities replaced by the power of two just
+/// past the largest finite value.
+fn exact<T: Float>(v: T) -> BigFloat {
+    let mut exact = if v.is_finite() {
+        // Built from the integer significand and exponent, as `from_f64`
+        // doesn't convert every subnormal exactly.
+        let bits = v.to_f64().to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = bits & ((1 << 52) - 1);
+        let (significand, exponent) = if exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | 1 << 52, exponent - 1075)
+        };
+        // Not `from_word`, as `Word` is only 32 bits on some targets.
+        let significand = BigFloat::from_u64(significand, PRECISION);
+        significand.mul(&pow2(exponent), PRECISION, RoundingMode::None)
+    } else {
+        pow2(if T::BYTES == 4 { 128 } else { 1024 })
+    };
+    if v < T::default() {
+        exact.inv_sign();
+    }
+    exact
+}
+
+/// Returns `2^e`.
+fn pow2(e: i32) -> BigFloat {
+    let mut p = BigFloat::from_word(1, PRECISION);
+    // The significand is in `[0.5, 1)`.
+    p.set_exponent(e + 1);
+    p
+}
+
+fn abs(mut v: BigFloat) -> BigFloat {
+    if v.is_negative() {
+        v.inv_sign();
+    }
+    v
+}
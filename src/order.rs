@@ -0,0 +1,403 @@
+//! Orderings which take approximate equality into account.
+
+use crate::{AlmostEqual, Float};
+use core::cmp::Ordering;
+
+/// The bucket `v` falls in, such that buckets are at most `tol` wide relative
+/// to the values in them. NaN gets a bucket of its own, after all the others.
+#[inline]
+fn bucket<T: Float>(v: T, tol: T) -> i64 {
+    if v.is_nan() {
+        return i64::MAX;
+    }
+    let tol = crate::float::check_rel_tol(tol);
+    // A bucket is `2^shift` ulps wide. That's between `2^exponent` and half
+    // that relative to the values in it, and `tol` is between `2^exponent` and
+    // twice that, so the bucket is more than `tol / 4` and at most `tol` wide.
+    // A NaN tolerance (which gets past `check_rel_tol` without debug
+    // assertions) would overflow the shift, so it gets buckets one ulp wide
+    // instead, and only identical values are equal, much as `equal_with`
+    // rejects everything with it.
+    let shift = if tol.is_nan() {
+        0
+    } else {
+        (T::SIGNIFICAND_BITS as i32 + tol.exponent()).max(0) as u32
+    };
+    // Both zeros have the ordered value 0, so they're in the same bucket as
+    // the smallest positive values.
+    v.to_ordered() >> shift
+}
+
+/// Compares `lhs` and `rhs`, treating values which are in the same bucket of
+/// width at most the default tolerance as equal.
+///
+/// This is intended for use with `sort_by` and similar, typically along with a
+/// secondary criterion which determines the order of the values this considers
+/// equal.
+///
+/// ```
+/// let mut v = [(0.3, 'b'), (0.1 + 0.2, 'a'), (0.1, 'c')];
+/// v.sort_by(|x, y| almost::total_cmp(x.0, y.0).then(x.1.cmp(&y.1)));
+/// assert_eq!(v.iter().map(|x| x.1).collect::<String>(), "cab");
+/// ```
+///
+/// Values which differ only due to rounding error will then almost always be
+/// ordered by the secondary criterion (or, for a stable sort without one,
+/// their order in the input), rather than by the rounding error itself. This
+/// gives orderings of noisy data which are much more likely to be consistent
+/// across platforms and compiler versions. The buckets are computed from the
+/// bits of the values, so they don't depend on the platform either.
+///
+/// NaN values compare as equal to each other, and greater than all other
+/// values, so that they sort to the end. Zeros of either sign are equal.
+///
+/// # Buckets
+///
+/// Approximate equality is not transitive: it's possible for `a` to be almost
+/// equal to `b`, and `b` to `c`, without `a` being almost equal to `c`. So that
+/// this is a true total order, it doesn't compare with
+/// [`almost::equal`](crate::equal). Instead, the values are split into buckets of a
+/// power of two number of ulps, chosen so the buckets are at most the
+/// tolerance wide and more than a quarter of it, relative to the values in
+/// them, and values are equal if they're in the same bucket.
+///
+/// Values in the same bucket are always almost equal. The cost is that values
+/// which are almost equal but on either side of a bucket boundary compare as
+/// unequal, in the same order as `<` would put them.
+#[inline]
+pub fn total_cmp<T: Float>(lhs: T, rhs: T) -> Ordering {
+    total_cmp_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::total_cmp`](total_cmp), but uses buckets whose
+/// width is between a quarter of the provided relative tolerance and all of it.
+///
+/// ```
+/// use core::cmp::Ordering;
+/// assert_eq!(almost::total_cmp_with(1.0f32, 1.01, 0.05), Ordering::Equal);
+/// assert_eq!(almost::total_cmp_with(1.0f32, 1.01, 0.001), Ordering::Less);
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn total_cmp_with<T: Float>(lhs: T, rhs: T, tolerance: T) -> Ordering {
+    bucket(lhs, tolerance).cmp(&bucket(rhs, tolerance))
+}
+
+/// The result of [`almost::compare`](compare): whether one value is definitely
+/// less than, almost equal to, or definitely greater than another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApproxOrdering {
+    /// The left hand side is less than the right, and not almost equal to it.
+    Less,
+    /// The values are almost equal.
+    AlmostEqual,
+    /// The left hand side is greater than the right, and not almost equal to
+    /// it.
+    Greater,
+}
+
+impl ApproxOrdering {
+    /// Returns the ordering with the sides swapped, so `Less` becomes
+    /// `Greater` and vice versa.
+    #[inline]
+    pub fn reverse(self) -> Self {
+        match self {
+            ApproxOrdering::Less => ApproxOrdering::Greater,
+            ApproxOrdering::AlmostEqual => ApproxOrdering::AlmostEqual,
+            ApproxOrdering::Greater => ApproxOrdering::Less,
+        }
+    }
+}
+
+impl From<ApproxOrdering> for Ordering {
+    #[inline]
+    fn from(o: ApproxOrdering) -> Self {
+        match o {
+            ApproxOrdering::Less => Ordering::Less,
+            ApproxOrdering::AlmostEqual => Ordering::Equal,
+            ApproxOrdering::Greater => Ordering::Greater,
+        }
+    }
+}
+
+/// Compares with `tol`, or as with `almost::equal` if it's `None`.
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+fn compare_impl<T>(lhs: T, rhs: T, tol: Option<T::Float>) -> Option<ApproxOrdering>
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    // Checked first, so the tolerance is checked (in debug mode) even for
+    // values which are exactly equal.
+    let equal = match tol {
+        Some(tol) => lhs.almost_equals_with(rhs, tol),
+        None => lhs.almost_equals(rhs),
+    };
+    if equal {
+        return Some(ApproxOrdering::AlmostEqual);
+    }
+    match lhs.partial_cmp(&rhs)? {
+        Ordering::Less => Some(ApproxOrdering::Less),
+        Ordering::Greater => Some(ApproxOrdering::Greater),
+        // Only reachable for types whose `AlmostEqual` is stricter than `==`.
+        Ordering::Equal => Some(ApproxOrdering::AlmostEqual),
+    }
+}
+
+/// Compares `lhs` and `rhs`, returning whether `lhs` is definitely less than,
+/// almost equal to, or definitely greater than `rhs`, or `None` if either is
+/// NaN.
+///
+/// ```
+/// use almost::ApproxOrdering;
+/// assert_eq!(almost::compare(0.1 + 0.2, 0.3), Some(ApproxOrdering::AlmostEqual));
+/// assert_eq!(almost::compare(0.3, 0.4), Some(ApproxOrdering::Less));
+/// assert_eq!(almost::compare(f64::MAX, f64::INFINITY), Some(ApproxOrdering::AlmostEqual));
+/// assert_eq!(almost::compare(f32::NAN, 1.0), None);
+/// ```
+///
+/// This is for algorithms which branch three ways, such as a bisection which
+/// stops once the midpoint is almost equal to the target. Values are almost
+/// equal exactly when [`almost::equal`](crate::equal) says they are, and are
+/// otherwise ordered as with `<`, so infinities, subnormals and zeros of
+/// either sign are handled consistently with the rest of the crate.
+///
+/// Unlike [`almost::total_cmp`](total_cmp), which puts NaN last so it can be
+/// used for sorting, this makes NaN impossible to overlook.
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn compare<T>(lhs: T, rhs: T) -> Option<ApproxOrdering>
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    compare_impl(lhs, rhs, None)
+}
+
+/// Equivalent to [`almost::compare`](compare), but uses the provided relative
+/// tolerance.
+///
+/// ```
+/// use almost::ApproxOrdering;
+/// assert_eq!(almost::compare_with(1.0f32, 1.01, 0.05), Some(ApproxOrdering::AlmostEqual));
+/// assert_eq!(almost::compare_with(1.01f32, 1.0, 0.001), Some(ApproxOrdering::Greater));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn compare_with<T>(lhs: T, rhs: T, tolerance: T::Float) -> Option<ApproxOrdering>
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    compare_impl(lhs, rhs, Some(tolerance))
+}
+
+/// Returns true if `lhs` is less than or almost equal to `rhs`.
+///
+/// ```
+/// let limit = 0.3;
+/// assert!(almost::le(0.1 + 0.2, limit));
+/// assert!(0.1 + 0.2 > limit);
+/// assert!(!almost::le(0.31, limit));
+/// assert!(!almost::le(f64::NAN, limit));
+/// ```
+///
+/// This is the check to use instead of `x <= limit` when `x` is computed, and
+/// may have rounded to just past the limit. Values compare as with
+/// [`almost::compare`](compare), so this is false if either value is NaN. Its
+/// dual is [`almost::definitely_greater`](definitely_greater).
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn le<T>(lhs: T, rhs: T) -> bool
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    matches!(
+        compare(lhs, rhs),
+        Some(ApproxOrdering::Less | ApproxOrdering::AlmostEqual)
+    )
+}
+
+/// Equivalent to [`almost::le`](le), but uses the provided relative tolerance.
+///
+/// ```
+/// assert!(almost::le_with(1.01f32, 1.0, 0.05));
+/// assert!(!almost::le_with(1.01f32, 1.0, 0.001));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn le_with<T>(lhs: T, rhs: T, tolerance: T::Float) -> bool
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    matches!(
+        compare_with(lhs, rhs, tolerance),
+        Some(ApproxOrdering::Less | ApproxOrdering::AlmostEqual)
+    )
+}
+
+/// Returns true if `lhs` is greater than or almost equal to `rhs`.
+///
+/// ```
+/// assert!(almost::ge(0.7 - 0.4, 0.3));
+/// assert!(!almost::ge(0.29, 0.3));
+/// ```
+///
+/// This is [`almost::le`](le) with the sides swapped, and its dual is
+/// [`almost::definitely_less`](definitely_less). It's false if either value
+/// is NaN.
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn ge<T>(lhs: T, rhs: T) -> bool
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    le(rhs, lhs)
+}
+
+/// Equivalent to [`almost::ge`](ge), but uses the provided relative tolerance.
+///
+/// ```
+/// assert!(almost::ge_with(0.99f32, 1.0, 0.05));
+/// assert!(!almost::ge_with(0.99f32, 1.0, 0.001));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn ge_with<T>(lhs: T, rhs: T, tolerance: T::Float) -> bool
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    le_with(rhs, lhs, tolerance)
+}
+
+/// Returns true if `lhs` is less than `rhs`, and not almost equal to it.
+///
+/// ```
+/// assert!(almost::definitely_less(0.2, 0.3));
+/// assert!(!almost::definitely_less(0.3, 0.1 + 0.2));
+/// assert!(0.3 < 0.1 + 0.2);
+/// ```
+///
+/// This is the negation of [`almost::ge`](ge) for values which aren't NaN, and
+/// like it, it's false if either value is NaN.
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn definitely_less<T>(lhs: T, rhs: T) -> bool
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    compare(lhs, rhs) == Some(ApproxOrdering::Less)
+}
+
+/// Equivalent to [`almost::definitely_less`](definitely_less), but uses the
+/// provided relative tolerance.
+///
+/// ```
+/// assert!(almost::definitely_less_with(1.0f32, 1.01, 0.001));
+/// assert!(!almost::definitely_less_with(1.0f32, 1.01, 0.05));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn definitely_less_with<T>(lhs: T, rhs: T, tolerance: T::Float) -> bool
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    compare_with(lhs, rhs, tolerance) == Some(ApproxOrdering::Less)
+}
+
+/// Returns true if `lhs` is greater than `rhs`, and not almost equal to it.
+///
+/// ```
+/// assert!(almost::definitely_greater(0.4, 0.3));
+/// assert!(!almost::definitely_greater(0.1 + 0.2, 0.3));
+/// ```
+///
+/// This is the negation of [`almost::le`](le) for values which aren't NaN, and
+/// like it, it's false if either value is NaN.
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn definitely_greater<T>(lhs: T, rhs: T) -> bool
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    definitely_less(rhs, lhs)
+}
+
+/// Equivalent to [`almost::definitely_greater`](definitely_greater), but uses
+/// the provided relative tolerance.
+///
+/// ```
+/// assert!(almost::definitely_greater_with(1.01f32, 1.0, 0.001));
+/// assert!(!almost::definitely_greater_with(1.01f32, 1.0, 0.05));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+#[cfg_attr(feature = "audit", track_caller)]
+pub fn definitely_greater_with<T>(lhs: T, rhs: T, tolerance: T::Float) -> bool
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    definitely_less_with(rhs, lhs, tolerance)
+}
+
+/// A wrapper which implements `Ord` using [`almost::total_cmp`](total_cmp), for
+/// use as a sort key.
+///
+/// ```
+/// use almost::SortKey;
+/// let mut v = [(0.3, 2), (0.1 + 0.2, 1), (0.1, 3)];
+/// v.sort_by_key(|&(x, id)| (SortKey(x), id));
+/// assert_eq!(v.iter().map(|x| x.1).collect::<Vec<_>>(), [3, 1, 2]);
+/// ```
+///
+/// Putting this in a tuple along with a secondary key is the easiest way to get
+/// a deterministic order for values which are almost equal.
+///
+/// Values are only equal if they're in the same bucket, as described on
+/// [`almost::total_cmp`](total_cmp), so this is a true total order, and can
+/// also be used as the key of a `BTreeMap` or similar.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SortKey<T>(pub T);
+
+impl<T: Float> PartialEq for SortKey<T> {
+    #[inline]
+    fn eq(&self, o: &Self) -> bool {
+        total_cmp(self.0, o.0) == Ordering::Equal
+    }
+}
+
+impl<T: Float> Eq for SortKey<T> {}
+
+impl<T: Float> PartialOrd for SortKey<T> {
+    #[inline]
+    fn partial_cmp(&self, o: &Self) -> Option<Ordering> {
+        Some(self.cmp(o))
+    }
+}
+
+impl<T: Float> Ord for SortKey<T> {
+    #[inline]
+    fn cmp(&self, o: &Self) -> Ordering {
+        total_cmp(self.0, o.0)
+    }
+}
@@ -0,0 +1,97 @@
+//! Comparison of positions in a periodic domain, such as a simulation box with
+//! periodic boundary conditions.
+
+use crate::Float;
+
+/// Returns the displacement from `rhs` to `lhs` along an axis which wraps with
+/// the given period, choosing the shortest of the equivalent displacements
+/// (the "minimum image").
+///
+/// ```
+/// assert_eq!(almost::minimum_image(9.5, 0.5, 10.0), -1.0);
+/// assert_eq!(almost::minimum_image(0.5, 9.5, 10.0), 1.0);
+/// assert_eq!(almost::minimum_image(3.0, 1.0, 10.0), 2.0);
+/// ```
+///
+/// The result is in `[-period / 2, period / 2]`. If `period` is infinite, the
+/// axis doesn't wrap, and this is just `lhs - rhs`. The result is NaN if any
+/// input is NaN, or if `period` isn't positive.
+#[inline]
+pub fn minimum_image<T: Float>(lhs: T, rhs: T, period: T) -> T {
+    let zero = T::default();
+    if period.is_nan() || period <= zero {
+        return T::from_f64(f64::NAN);
+    }
+    if !period.is_finite() {
+        return lhs - rhs;
+    }
+    let half = period / T::from_f64(2.0);
+    // `%` is exact, so reduce each coordinate first, which avoids the rounding
+    // error of subtracting first when they're both far outside the box. Then
+    // reduce again, as the remainders can differ by up to two periods.
+    let d = (lhs % period - rhs % period) % period;
+    if d > half {
+        d - period
+    } else if d < -half {
+        d + period
+    } else {
+        d
+    }
+}
+
+/// Returns true if the points `lhs` and `rhs` are almost equal in a domain
+/// where each coordinate wraps with its own period.
+///
+/// ```
+/// let periods = [10.0, 10.0, 5.0];
+/// // The particle crossed the boundary on the first axis.
+/// let a = [9.999999999999, 2.0, 3.0];
+/// let b = [0.0, 2.0, 3.0];
+/// assert!(almost::periodic_equal(&a, &b, &periods));
+/// assert!(!almost::periodic_equal(&a, &[0.0, 2.0, 3.1], &periods));
+/// ```
+///
+/// Each axis is compared by taking the [`minimum_image`] displacement between
+/// the coordinates, and checking that it's within the tolerance relative to the
+/// period, which is the scale of the positions. A period of infinity means
+/// that the axis doesn't wrap, and the coordinates on it are compared with
+/// [`almost::equal`](crate::equal) instead.
+///
+/// Returns false if `lhs` and `rhs` have different lengths, or if any value is
+/// NaN.
+///
+/// # Panics
+/// Panics if `periods` doesn't have one period per coordinate.
+#[inline]
+pub fn periodic_equal<T: Float>(lhs: &[T], rhs: &[T], periods: &[T]) -> bool {
+    periodic_equal_with(lhs, rhs, periods, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::periodic_equal`](periodic_equal), but uses the
+/// provided relative tolerance.
+///
+/// ```
+/// let periods = [1.0f32, 1.0];
+/// assert!(almost::periodic_equal_with(&[0.001, 0.5], &[0.999, 0.5], &periods, 0.01));
+/// ```
+///
+/// # Panics
+/// Panics if `periods` doesn't have one period per coordinate. Also panics in
+/// debug mode if `tolerance` is less than `T::EPSILON` or greater than 1.0, as
+/// with [`almost::equal_with`](crate::equal_with).
+pub fn periodic_equal_with<T: Float>(lhs: &[T], rhs: &[T], periods: &[T], tolerance: T) -> bool {
+    assert_eq!(
+        lhs.len(),
+        periods.len(),
+        "periods must have one period per coordinate"
+    );
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    lhs.len() == rhs.len()
+        && lhs.iter().zip(rhs).zip(periods).all(|((&l, &r), &period)| {
+            if period.is_finite() {
+                minimum_image(l, r, period).abs() < tolerance * period
+            } else {
+                l.almost_equals_with(r, tolerance)
+            }
+        })
+}
@@ -0,0 +1,117 @@
+//! Comparison of rigid-body poses, with separate tolerances for translation
+//! and rotation. This requires the `std` or `libm` feature.
+
+use crate::linalg::{direction_equal_with, max_magnitude, DirectionSign};
+use crate::Float;
+
+/// A rigid-body pose: a rotation, followed by a translation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pose<T> {
+    /// The translation, or position.
+    pub translation: [T; 3],
+    /// The rotation, as a quaternion. The order of the components doesn't
+    /// matter, as long as both poses use the same one, and it doesn't need to
+    /// be exactly normalized.
+    pub rotation: [T; 4],
+}
+
+impl<T> Pose<T> {
+    /// Creates a pose from a translation and a rotation quaternion.
+    #[inline]
+    pub fn new(translation: [T; 3], rotation: [T; 4]) -> Self {
+        Pose {
+            translation,
+            rotation,
+        }
+    }
+}
+
+/// Tolerances for [`almost::pose_equal`](pose_equal).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoseTolerance<T> {
+    /// The characteristic length of the problem, such as the size of the
+    /// robot or the room. Translations are compared relative to this, rather
+    /// than to their own magnitudes, so that poses near the origin aren't held
+    /// to a much stricter standard than those far from it.
+    pub length: T,
+    /// The largest distance allowed between the translations, as a fraction of
+    /// `length`.
+    pub translation: T,
+    /// The largest angle allowed between the rotations, in radians.
+    pub angle: T,
+}
+
+impl<T> PoseTolerance<T> {
+    /// Creates tolerances allowing the translations to differ by at most
+    /// `translation * length`, and the rotations by at most `angle` radians.
+    #[inline]
+    pub fn new(length: T, translation: T, angle: T) -> Self {
+        PoseTolerance {
+            length,
+            translation,
+            angle,
+        }
+    }
+}
+
+/// Returns true if the poses `lhs` and `rhs` are almost equal: the distance
+/// between their translations is within the translation tolerance, and the
+/// rotation from one to the other is by at most the angular tolerance.
+///
+/// ```
+/// use almost::{Pose, PoseTolerance};
+/// // A quarter turn about z, at about 1m from the origin.
+/// let h = core::f64::consts::FRAC_1_SQRT_2;
+/// let expected = Pose::new([1.0, 0.0, 0.5], [0.0, 0.0, h, h]);
+/// // 0.3mm and about 0.06° off.
+/// let actual = Pose::new([1.0003, 0.0, 0.5], [0.0, 0.0, 0.7075, 0.7067]);
+/// // Within 1mm of each other in a 1m workspace, and 0.1°.
+/// let tol = PoseTolerance::new(1.0, 1e-3, 0.1f64.to_radians());
+/// assert!(almost::pose_equal(&actual, &expected, tol));
+/// // The sign of a quaternion doesn't affect the rotation.
+/// let flipped = Pose::new([1.0003, 0.0, 0.5], [0.0, 0.0, -0.7075, -0.7067]);
+/// assert!(almost::pose_equal(&flipped, &expected, tol));
+/// ```
+///
+/// Comparing the components of the transformation matrices answers a
+/// different question: a small rotation moves distant points a long way, and
+/// a tolerance relative to each component is far too strict for those which
+/// should be zero. This instead compares each part in its own units.
+///
+/// The angle between the rotations is computed from the distance between the
+/// normalized quaternions (whichever of `q` and `-q` is closer), which is
+/// accurate for the small angles tolerances are usually given as. Returns
+/// false if either quaternion is zero, or if anything in either pose is NaN or
+/// infinite.
+///
+/// # Panics
+/// This function panics in debug mode if `length` or `translation` aren't
+/// positive, or if `angle` is very small or larger than `2π/3` radians
+/// (120°).
+pub fn pose_equal<T: Float>(lhs: &Pose<T>, rhs: &Pose<T>, tolerance: PoseTolerance<T>) -> bool {
+    crate::float::check_zero_tol(tolerance.length);
+    crate::float::check_zero_tol(tolerance.translation);
+    let zero = T::default();
+    let mut diff = [zero; 3];
+    for (d, (&a, &b)) in diff
+        .iter_mut()
+        .zip(lhs.translation.iter().zip(&rhs.translation))
+    {
+        *d = a - b;
+    }
+    // The distance, rescaled so that squaring can't overflow or underflow.
+    let scale = max_magnitude(&diff);
+    let mut sq = zero;
+    for &d in &diff {
+        sq = sq + (d / scale) * (d / scale);
+    }
+    let distance = T::from_f64(crate::math::sqrt(sq.to_f64())) * scale;
+    if distance.is_nan() || distance > tolerance.translation * tolerance.length {
+        return false;
+    }
+    // For unit quaternions `q` and `r`, `|q - r| = 2 sin(θ / 4)`, where `θ` is
+    // the angle of the rotation between them.
+    let quarter = tolerance.angle.to_f64() / 4.0;
+    let chord = T::from_f64(2.0 * crate::math::sin(quarter));
+    direction_equal_with(&lhs.rotation, &rhs.rotation, DirectionSign::Either, chord)
+}
@@ -0,0 +1,146 @@
+//! A Python extension module, via `pyo3`. This requires the `pyo3` feature.
+//!
+//! This is for projects which test with both Rust and Python, so that both
+//! suites use the same definition of almost equal. Python floats are `f64`, so
+//! only that type is exposed, and the defaults are
+//! [`F64_TOLERANCE`](crate::F64_TOLERANCE). Invalid tolerances raise
+//! `ValueError`, rather than hitting the `debug_assert!`s the Rust functions
+//! use.
+//!
+//! The module is named `almost`. It can be built from this crate directly, by
+//! renaming the resulting `libalmost.so` (or `.dylib`, or `.dll`) to
+//! `almost.so` (or `.pyd` on Windows) after building it with:
+//!
+//! ```text
+//! cargo rustc --lib --release --features pyo3,pyo3/extension-module --crate-type cdylib
+//! ```
+//!
+//! From Python, it looks like:
+//!
+//! ```text
+//! >>> import almost
+//! >>> almost.equal(0.1 + 0.2, 0.3)
+//! True
+//! >>> almost.allclose([1.0, 1e-17], [1.0, 0.0], zero_tol=1e-12)
+//! True
+//! ```
+
+use crate::Tolerance;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::string::ToString;
+use std::vec::Vec;
+
+/// Returns true if `lhs` and `rhs` are almost equal, as with
+/// `almost::equal_with`, using `tolerance` or the default.
+#[pyfunction]
+#[pyo3(signature = (lhs, rhs, tolerance = None))]
+fn equal(lhs: f64, rhs: f64, tolerance: Option<f64>) -> PyResult<bool> {
+    Ok(rel_tolerance(tolerance)?.equal(lhs, rhs))
+}
+
+/// Returns true if `v` is almost zero, as with `almost::zero_with`, using
+/// `tolerance` or the default.
+#[pyfunction]
+#[pyo3(signature = (v, tolerance = None))]
+fn zero(v: f64, tolerance: Option<f64>) -> PyResult<bool> {
+    match tolerance {
+        Some(t) => Ok(crate::zero_with(v, zero_tolerance(t)?)),
+        None => Ok(crate::zero(v)),
+    }
+}
+
+/// Returns true if the sequences `lhs` and `rhs` have the same length, and
+/// every pair of elements is almost equal, using `tolerance` or the default.
+/// If `zero_tol` is given, pairs which are both within it of zero are also
+/// accepted, as with `almost::equal_with_zero_tol`.
+///
+/// Unlike `numpy.allclose`, the tolerance is symmetric and purely relative by
+/// default, and nothing is broadcast.
+#[pyfunction]
+#[pyo3(signature = (lhs, rhs, tolerance = None, zero_tol = None))]
+fn allclose(
+    lhs: Vec<f64>,
+    rhs: Vec<f64>,
+    tolerance: Option<f64>,
+    zero_tol: Option<f64>,
+) -> PyResult<bool> {
+    let tolerance = rel_tolerance(tolerance)?;
+    let zero_tol = zero_tol.map(zero_tolerance).transpose()?;
+    let tiny = |v: f64| zero_tol.is_some_and(|t| crate::zero_with(v, t));
+    let mut pairs = lhs.iter().zip(&rhs);
+    let close = |(&a, &b): (&f64, &f64)| (tiny(a) && tiny(b)) || tolerance.equal(a, b);
+    Ok(lhs.len() == rhs.len() && pairs.all(close))
+}
+
+/// Validates a relative tolerance, or returns the default.
+fn rel_tolerance(tolerance: Option<f64>) -> PyResult<Tolerance<f64>> {
+    match tolerance {
+        Some(t) => Tolerance::new(t).map_err(|e| PyValueError::new_err(e.to_string())),
+        None => Ok(Tolerance::default()),
+    }
+}
+
+/// Checks an absolute tolerance, as `zero_with` only does in debug builds.
+fn zero_tolerance(tolerance: f64) -> PyResult<f64> {
+    if tolerance > 0.0 {
+        Ok(tolerance)
+    } else {
+        Err(PyValueError::new_err("tolerance must be greater than zero"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_rel_tolerance() {
+        assert_eq!(rel_tolerance(None).ok(), Some(Tolerance::default()));
+        assert_eq!(
+            rel_tolerance(Some(1e-3)).ok().map(Tolerance::get),
+            Some(1e-3)
+        );
+        for &t in &[0.0, -1.0, 1.0, f64::NAN] {
+            assert!(rel_tolerance(Some(t)).is_err(), "{}", t);
+        }
+    }
+
+    #[test]
+    fn test_zero_tolerance() {
+        assert_eq!(zero_tolerance(1e-9).ok(), Some(1e-9));
+        for &t in &[0.0, -1.0, f64::NAN] {
+            assert!(zero_tolerance(t).is_err(), "{}", t);
+        }
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(equal(0.1 + 0.2, 0.3, None).ok(), Some(true));
+        assert!(equal(1.0, 1.0, Some(2.0)).is_err());
+        assert_eq!(zero(1e-10, Some(1e-9)).ok(), Some(true));
+        assert!(zero(0.0, Some(0.0)).is_err());
+        assert_eq!(
+            allclose(vec![1.0], vec![1.0, 2.0], None, None).ok(),
+            Some(false)
+        );
+        assert_eq!(allclose(vec![], vec![1.0], None, None).ok(), Some(false));
+        assert_eq!(allclose(vec![], vec![], None, None).ok(), Some(true));
+        let (a, b) = (vec![1.0, 1e-17], vec![1.0, 0.0]);
+        assert_eq!(allclose(a.clone(), b.clone(), None, None).ok(), Some(false));
+        assert_eq!(allclose(a, b, None, Some(1e-12)).ok(), Some(true));
+        assert!(allclose(vec![], vec![], None, Some(-1.0)).is_err());
+    }
+}
+
+/// The `almost` Python module.
+#[pymodule]
+#[pyo3(name = "almost")]
+fn almost_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(equal, m)?)?;
+    m.add_function(wrap_pyfunction!(zero, m)?)?;
+    m.add_function(wrap_pyfunction!(allclose, m)?)?;
+    m.add("F64_TOLERANCE", crate::F64_TOLERANCE)?;
+    Ok(())
+}
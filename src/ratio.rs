@@ -0,0 +1,89 @@
+//! Comparison by the ratio of two values, rather than their difference.
+
+use crate::Float;
+
+/// Returns true if the ratio of `lhs` and `rhs` is almost 1, using the default
+/// tolerance.
+///
+/// ```
+/// assert!(almost::equal_ratio(0.1 + 0.2, 0.3));
+/// assert!(!almost::equal_ratio(0.3, -0.3));
+/// ```
+///
+/// For the small tolerances used by default, this is almost the same as
+/// [`almost::equal`](crate::equal), so it's mostly useful through
+/// [`almost::equal_ratio_with`](equal_ratio_with), for criteria which are
+/// naturally given as a factor.
+#[inline]
+pub fn equal_ratio<T: Float>(lhs: T, rhs: T) -> bool {
+    equal_ratio_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Returns true if the ratio of `lhs` and `rhs` (in either order) is less than
+/// `1 + tolerance`.
+///
+/// ```
+/// // Growth factors within 2% of each other.
+/// assert!(almost::equal_ratio_with(1.93f64, 1.9, 0.02));
+/// assert!(almost::equal_ratio_with(1.9f64, 1.93, 0.02));
+/// // Within a factor of two.
+/// assert!(almost::equal_ratio_with(1.0f64, 1.9, 1.0));
+/// assert!(!almost::equal_ratio_with(0.4f64, 1.0, 1.0));
+/// // Subnormals are accepted by `equal`, but they're a factor of two apart.
+/// assert!(almost::equal(5e-324, 1e-323));
+/// assert!(!almost::equal_ratio_with(5e-324, 1e-323, 0.5));
+/// ```
+///
+/// The ratio is taken as the larger magnitude over the smaller, so it doesn't
+/// matter which argument is the reference, and a tolerance of `t` accepts
+/// values within a factor of `1 + t` of each other. This is how gains, growth
+/// factors and the like are usually specified, and porting such criteria to
+/// this directly avoids converting them into the form used by
+/// [`almost::equal_with`](crate::equal_with).
+///
+/// The difference is small for small tolerances. `equal_with` requires that
+/// `|lhs - rhs| < tolerance * max(|lhs|, |rhs|)`, which is the same as the
+/// ratio being less than `1 / (1 - tolerance)` (a little more than
+/// `1 + tolerance`). As the tolerance approaches 1, that allows any ratio at
+/// all, which is why it's limited to less than 1. Here, any tolerance of at
+/// least `T::EPSILON` is fine, including those much larger than 1.
+///
+/// The special cases differ too:
+///
+/// - Values of opposite signs are never almost equal, as their ratio is
+///   negative. For `equal_with` they also aren't, but only because the
+///   tolerance is less than 1.
+/// - Zero is only almost equal to zero (of either sign). Any other ratio with
+///   zero is zero or infinite.
+/// - `equal_with` compares subnormal values as though they were
+///   `T::MIN_POSITIVE`, as they have little precision of their own, so all of
+///   them are almost equal to each other. Here the ratio is computed exactly as
+///   given, so tiny subnormals are almost equal only if they're within the
+///   factor, which their coarse spacing often prevents. Use
+///   [`almost::equal_or_both_tiny`](crate::equal_or_both_tiny) if values this
+///   small should be treated as the same.
+/// - Equal infinities are almost equal, and an infinity isn't almost equal to
+///   any finite value. Ratios which overflow are too large to be within any
+///   finite tolerance, so this never needs to rescale.
+///
+/// NaN is never almost equal to anything.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`.
+pub fn equal_ratio_with<T: Float>(lhs: T, rhs: T, tolerance: T) -> bool {
+    let tolerance = crate::float::check_ratio_tol(tolerance);
+    if lhs == rhs {
+        return true;
+    }
+    let zero = T::default();
+    if (lhs < zero) != (rhs < zero) {
+        return false;
+    }
+    let (small, large) = if lhs.abs() < rhs.abs() {
+        (lhs.abs(), rhs.abs())
+    } else {
+        (rhs.abs(), lhs.abs())
+    };
+    // Dividing by zero gives infinity, and ratios of NaN compare as false.
+    large / small < T::from_f64(1.0) + tolerance
+}
@@ -0,0 +1,266 @@
+//! Reports describing the result of an element-wise comparison.
+
+use crate::{Float, Hint};
+use core::fmt;
+
+/// A single pair of elements from an element-wise comparison.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementDiff<T, I> {
+    /// The position of the elements. What this is depends on the comparison:
+    /// for slices it's a `usize`, for multi-dimensional arrays it's the full
+    /// set of coordinates.
+    pub index: I,
+    /// The left hand side element.
+    pub lhs: T,
+    /// The right hand side element.
+    pub rhs: T,
+    /// The absolute difference between the elements.
+    pub abs_error: T,
+    /// The relative difference between the elements, `abs_error` divided by
+    /// the larger of `|lhs|` and `|rhs|`.
+    pub rel_error: T,
+}
+
+/// Statistics from an element-wise comparison of two collections.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementwiseReport<T, I> {
+    /// The total number of element pairs compared.
+    pub total: usize,
+    /// The number of element pairs which were not almost equal.
+    pub mismatches: usize,
+    /// The first pair which was not almost equal, if any.
+    pub first_mismatch: Option<ElementDiff<T, I>>,
+    /// The pair with the largest relative error, whether or not it passed. NaN
+    /// is considered larger than everything else. `None` only if `total` is 0.
+    pub worst: Option<ElementDiff<T, I>>,
+    /// The largest absolute error of any pair.
+    pub max_abs_error: T,
+    /// A likely mistake detected in one of the mismatched pairs, if any. This
+    /// is for the first mismatch where a problem was found, which isn't
+    /// necessarily `first_mismatch`.
+    pub hint: Option<Hint>,
+}
+
+impl<T, I> ElementwiseReport<T, I> {
+    /// Returns true if every pair of elements was almost equal.
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.mismatches == 0
+    }
+}
+
+impl<T: fmt::Display, I: fmt::Debug> fmt::Display for ElementwiseReport<T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} of {} elements differ", self.mismatches, self.total)?;
+        if let Some(d) = &self.first_mismatch {
+            write!(f, "; first at {:?}: {} vs {}", d.index, d.lhs, d.rhs)?;
+        }
+        if let Some(d) = &self.worst {
+            write!(
+                f,
+                "; largest relative error {} at {:?}: {} vs {}",
+                d.rel_error, d.index, d.lhs, d.rhs
+            )?;
+        }
+        if let Some(hint) = &self.hint {
+            write!(f, " (hint: {})", hint)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + fmt::Display, I: fmt::Debug> ElementwiseReport<T, I> {
+    /// Returns a wrapper which displays the report as an aligned text table,
+    /// for bug reports and CI logs. This requires the `std` feature.
+    ///
+    /// ```
+    /// let report = almost::compare_grid(&[1.0, 2.0, 3.0, 4.0], &[1.0, 2.5, 3.0, 4.001], 2);
+    /// let text = format!("{:.4}", report.values.table());
+    /// assert_eq!(text, "\
+    /// 2 of 4 elements differ
+    /// metric                  index   lhs  rhs  abs err  rel err              ULPs  verdict
+    /// first mismatch          (0, 1)    2  2.5   0.5000   0.2000  1125899906842624  differ
+    /// largest relative error  (0, 1)    2  2.5   0.5000   0.2000  1125899906842624  differ");
+    /// ```
+    ///
+    /// There's a row for the first mismatch and for the pair with the largest
+    /// relative error, when there are any. A precision given in the format
+    /// string is used for the errors. As the report doesn't record the
+    /// tolerance, the largest relative error is shown as differing whenever
+    /// any pair did.
+    #[inline]
+    pub fn table(&self) -> ReportTable<'_, T, I> {
+        ReportTable { report: self }
+    }
+}
+
+/// Displays an [`ElementwiseReport`] as a table, see
+/// [`ElementwiseReport::table`]. This requires the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct ReportTable<'a, T, I> {
+    report: &'a ElementwiseReport<T, I>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Float + fmt::Display, I: fmt::Debug> fmt::Display for ReportTable<'_, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use std::string::{String, ToString};
+        use std::vec::Vec;
+        const HEADER: [&str; 8] = [
+            "metric", "index", "lhs", "rhs", "abs err", "rel err", "ULPs", "verdict",
+        ];
+        // The numeric columns, which are aligned to the right.
+        const NUMERIC: [bool; 8] = [false, false, true, true, true, true, true, false];
+        let r = self.report;
+        let err = |v: T| match f.precision() {
+            Some(p) => std::format!("{:.*}", p, v),
+            None => v.to_string(),
+        };
+        let row = |metric: &str, d: &ElementDiff<T, I>, verdict: &str| -> [String; 8] {
+            let ulps = if d.lhs.is_nan() || d.rhs.is_nan() {
+                "-".to_string()
+            } else if d.lhs == d.rhs {
+                "0".to_string()
+            } else {
+                d.lhs.to_ordered().abs_diff(d.rhs.to_ordered()).to_string()
+            };
+            [
+                metric.to_string(),
+                std::format!("{:?}", d.index),
+                d.lhs.to_string(),
+                d.rhs.to_string(),
+                err(d.abs_error),
+                err(d.rel_error),
+                ulps,
+                verdict.to_string(),
+            ]
+        };
+        let mut rows: Vec<[String; 8]> = Vec::new();
+        rows.push(HEADER.map(|h| h.to_string()));
+        if let Some(d) = &r.first_mismatch {
+            rows.push(row("first mismatch", d, "differ"));
+        }
+        if let Some(d) = &r.worst {
+            let verdict = if r.passed() { "equal" } else { "differ" };
+            rows.push(row("largest relative error", d, verdict));
+        }
+        let mut widths = [0; 8];
+        for row in &rows {
+            for (w, cell) in widths.iter_mut().zip(row) {
+                *w = (*w).max(cell.chars().count());
+            }
+        }
+        write!(f, "{} of {} elements differ", r.mismatches, r.total)?;
+        for row in &rows {
+            f.write_str("\n")?;
+            let mut line = String::new();
+            for (i, cell) in row.iter().enumerate() {
+                if i > 0 {
+                    line.push_str("  ");
+                }
+                let pad = widths[i] - cell.chars().count();
+                if NUMERIC[i] {
+                    line.extend(core::iter::repeat_n(' ', pad));
+                    line.push_str(cell);
+                } else {
+                    line.push_str(cell);
+                    line.extend(core::iter::repeat_n(' ', pad));
+                }
+            }
+            f.write_str(line.trim_end())?;
+        }
+        if let Some(hint) = &r.hint {
+            write!(f, "\nhint: {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds an `ElementwiseReport` one pair at a time.
+pub(crate) struct ReportBuilder<T, I> {
+    report: ElementwiseReport<T, I>,
+}
+
+impl<T: Float, I: Clone> ReportBuilder<T, I> {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            report: ElementwiseReport {
+                total: 0,
+                mismatches: 0,
+                first_mismatch: None,
+                worst: None,
+                max_abs_error: T::default(),
+                hint: None,
+            },
+        }
+    }
+
+    /// Compares and records a pair, returning whether it was almost equal.
+    /// `index` is only called if the pair needs to be stored.
+    pub(crate) fn push<F: FnOnce() -> I>(
+        &mut self,
+        index: F,
+        lhs: T,
+        rhs: T,
+        tolerance: T,
+    ) -> bool {
+        let equal = lhs.almost_equals_with(rhs, tolerance);
+        if !equal && self.report.hint.is_none() {
+            self.report.hint = Hint::detect(lhs, rhs, tolerance);
+        }
+        self.record(index, lhs, rhs, equal)
+    }
+
+    /// Records a pair which has already been compared by some other means.
+    pub(crate) fn record<F: FnOnce() -> I>(
+        &mut self,
+        index: F,
+        lhs: T,
+        rhs: T,
+        equal: bool,
+    ) -> bool {
+        let r = &mut self.report;
+        r.total += 1;
+        if !equal {
+            r.mismatches += 1;
+        }
+        let abs_error = if lhs == rhs {
+            T::default()
+        } else {
+            (lhs - rhs).abs()
+        };
+        let rel_error = lhs.rel_err(rhs);
+        if abs_error > r.max_abs_error || (abs_error.is_nan() && !r.max_abs_error.is_nan()) {
+            r.max_abs_error = abs_error;
+        }
+        let worse = match &r.worst {
+            None => true,
+            Some(w) => rel_error > w.rel_error || (rel_error.is_nan() && !w.rel_error.is_nan()),
+        };
+        if !worse && (equal || r.first_mismatch.is_some()) {
+            return equal;
+        }
+        let diff = ElementDiff {
+            index: index(),
+            lhs,
+            rhs,
+            abs_error,
+            rel_error,
+        };
+        if !equal && r.first_mismatch.is_none() {
+            r.first_mismatch = Some(diff.clone());
+        }
+        if worse {
+            r.worst = Some(diff);
+        }
+        equal
+    }
+
+    #[inline]
+    pub(crate) fn finish(self) -> ElementwiseReport<T, I> {
+        self.report
+    }
+}
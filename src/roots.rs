@@ -0,0 +1,306 @@
+//! Comparison of unordered sets of roots or eigenvalues.
+
+use crate::Float;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Returns true if the real values in `actual` and `expected` are almost equal
+/// as multisets, in whatever order they're given.
+///
+/// ```
+/// // Roots of (x - 1)^2 (x - 3), as a polynomial solver might return them.
+/// let found = [3.0, 1.0 + 1e-7, 1.0 - 1e-7];
+/// assert!(almost::roots_equal_with(&found, &[1.0, 1.0, 3.0], 1e-12));
+/// assert!(!almost::roots_equal_with(&found, &[1.0, 3.0, 3.0], 1e-12));
+/// ```
+///
+/// This is intended for comparing polynomial roots or the eigenvalues of a
+/// matrix, where sorting both and comparing element-wise falls apart as soon
+/// as nearly-equal values swap order.
+///
+/// Values are paired up by proximity, such that each value in `actual` is used
+/// exactly once, and each value in `expected` is used once for every time it
+/// appears. The comparison succeeds if such a pairing exists where every pair
+/// is close enough.
+///
+/// # Tolerance
+///
+/// Two things differ here from [`almost::equal`](crate::equal):
+///
+/// 1. Distances are relative to the largest magnitude of any value in either
+///    set, rather than the magnitudes of the two values being compared. This
+///    matches how error in computed roots and eigenvalues behaves: small ones
+///    are rarely accurate to more than the precision of the large ones.
+///
+/// 2. Clusters are given a looser tolerance. A root of multiplicity `m` is
+///    perturbed by roughly `tol^(1/m)` when the coefficients are perturbed by
+///    `tol` — a double root of a polynomial accurate to 12 digits is typically
+///    only accurate to 6. The multiplicity of a value in `expected` is
+///    determined by clustering: values closer together than `tol^(1/m)` form a
+///    cluster of size `m`, and every value in that cluster is compared using
+///    `tol^(1/m)`.
+///
+/// Returns false if the lengths differ, or if any value is NaN.
+#[inline]
+pub fn roots_equal<T: Float>(actual: &[T], expected: &[T]) -> bool {
+    roots_equal_with(actual, expected, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::roots_equal`](roots_equal), but uses the provided
+/// tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+#[inline]
+pub fn roots_equal_with<T: Float>(actual: &[T], expected: &[T], tolerance: T) -> bool {
+    let zero = T::default();
+    let actual: Vec<(T, T)> = actual.iter().map(|&v| (v, zero)).collect();
+    let expected: Vec<(T, T)> = expected.iter().map(|&v| (v, zero)).collect();
+    complex_roots_equal_with(&actual, &expected, tolerance)
+}
+
+/// Returns true if the complex values in `actual` and `expected` (given as
+/// `(re, im)` pairs) are almost equal as multisets, as with
+/// [`almost::roots_equal`](roots_equal).
+///
+/// ```
+/// // Eigenvalues of a rotation, in whatever order the solver produced.
+/// let found = [(0.0, -1.0), (1e-17, 1.0), (1.0, 0.0)];
+/// let expected = [(1.0, 0.0), (0.0, 1.0), (0.0, -1.0)];
+/// assert!(almost::complex_roots_equal(&found, &expected));
+/// ```
+///
+/// This is the same as [`almost::roots_equal`](roots_equal), but for complex
+/// values, where distances are measured in the complex plane. See its
+/// documentation for details.
+#[inline]
+pub fn complex_roots_equal<T: Float>(actual: &[(T, T)], expected: &[(T, T)]) -> bool {
+    complex_roots_equal_with(actual, expected, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::complex_roots_equal`](complex_roots_equal), but
+/// uses the provided tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as the results are unlikely to be sensible.
+pub fn complex_roots_equal_with<T: Float>(
+    actual: &[(T, T)],
+    expected: &[(T, T)],
+    tolerance: T,
+) -> bool {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    if actual.len() != expected.len() {
+        return false;
+    }
+    let mut scale = T::from_f64(0.0);
+    for &(re, im) in actual.iter().chain(expected) {
+        if re.is_nan() || im.is_nan() {
+            return false;
+        }
+        for m in [re.abs(), im.abs()] {
+            if m > scale {
+                scale = m;
+            }
+        }
+    }
+    if scale == T::from_f64(0.0) {
+        return true;
+    }
+    let scale = if scale > T::MIN_POSITIVE {
+        scale
+    } else {
+        T::MIN_POSITIVE
+    };
+    let scaled: Vec<(T, T)> = actual
+        .iter()
+        .map(|&(r, i)| (r / scale, i / scale))
+        .collect();
+    let reference: Vec<(T, T)> = expected
+        .iter()
+        .map(|&(r, i)| (r / scale, i / scale))
+        .collect();
+    if scaled
+        .iter()
+        .chain(&reference)
+        .any(|&(r, i)| r.is_nan() || i.is_nan())
+    {
+        // Only possible if there was an infinity.
+        return infinite_roots_equal(actual, expected, tolerance);
+    }
+
+    let mut thresholds = Thresholds::new(tolerance);
+    let clusters = cluster_sizes(&reference, &mut thresholds);
+    let limits: Vec<T> = clusters.iter().map(|&m| thresholds.get(m)).collect();
+    let close = |a: usize, e: usize| dist_sq(scaled[a], reference[e]) < limits[e];
+    has_perfect_matching(actual.len(), close)
+}
+
+/// Handles the (rare) case where some value is infinite. Infinite values must
+/// match exactly, and the rest are compared as normal.
+#[cold]
+fn infinite_roots_equal<T: Float>(actual: &[(T, T)], expected: &[(T, T)], tol: T) -> bool {
+    let is_inf = |&(r, i): &(T, T)| !(r.is_finite() && i.is_finite());
+    let mut inf_a: Vec<(T, T)> = actual.iter().copied().filter(is_inf).collect();
+    let mut inf_e: Vec<(T, T)> = expected.iter().copied().filter(is_inf).collect();
+    if inf_a.len() != inf_e.len() {
+        return false;
+    }
+    // Exact matching, so any pairing will do.
+    while let Some(v) = inf_a.pop() {
+        match inf_e.iter().position(|&e| e == v) {
+            Some(p) => {
+                inf_e.swap_remove(p);
+            }
+            None => return false,
+        }
+    }
+    let fin_a: Vec<(T, T)> = actual.iter().copied().filter(|v| !is_inf(v)).collect();
+    let fin_e: Vec<(T, T)> = expected.iter().copied().filter(|v| !is_inf(v)).collect();
+    complex_roots_equal_with(&fin_a, &fin_e, tol)
+}
+
+#[inline]
+fn dist_sq<T: Float>(a: (T, T), b: (T, T)) -> T {
+    let (dr, di) = (a.0 - b.0, a.1 - b.1);
+    dr * dr + di * di
+}
+
+/// Lazily computes `(tol^(1/m))^2` for each multiplicity `m`.
+struct Thresholds<T> {
+    tol: f64,
+    cache: Vec<T>,
+}
+
+impl<T: Float> Thresholds<T> {
+    fn new(tol: T) -> Self {
+        Self {
+            tol: tol.to_f64(),
+            cache: Vec::new(),
+        }
+    }
+
+    fn get(&mut self, m: usize) -> T {
+        while self.cache.len() < m {
+            let r = nth_root(self.tol, self.cache.len() + 1);
+            self.cache.push(T::from_f64(r * r));
+        }
+        self.cache[m - 1]
+    }
+}
+
+/// Computes `x^(1/n)` for `x` in `(0, 1)` with Newton's method. We don't have
+/// access to `powf` in `no_std`, but this only needs to be accurate enough to
+/// use as a tolerance.
+fn nth_root(x: f64, n: usize) -> f64 {
+    if n == 1 {
+        return x;
+    }
+    let nf = n as f64;
+    // The root is in `(x, 1)`, and Newton's method converges monotonically from
+    // above for this function.
+    let mut y = 1.0;
+    for _ in 0..1000 {
+        let mut p = 1.0;
+        for _ in 0..n - 1 {
+            p *= y;
+        }
+        let next = ((nf - 1.0) * y + x / p) / nf;
+        if next >= y {
+            break;
+        }
+        y = next;
+    }
+    y
+}
+
+/// Clusters `values` (as described in the docs for `roots_equal`), returning
+/// the size of the cluster each value belongs to.
+fn cluster_sizes<T: Float>(values: &[(T, T)], thresholds: &mut Thresholds<T>) -> Vec<usize> {
+    let n = values.len();
+    let mut parent: Vec<usize> = (0..n).collect();
+    let mut size = vec![1usize; n];
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    // Merging can loosen the threshold for the merged cluster, which may allow
+    // further merges, so repeat until nothing changes.
+    loop {
+        let mut changed = false;
+        for i in 0..n {
+            for j in i + 1..n {
+                let (ri, rj) = (find(&mut parent, i), find(&mut parent, j));
+                if ri == rj {
+                    continue;
+                }
+                let merged = size[ri] + size[rj];
+                if dist_sq(values[i], values[j]) < thresholds.get(merged) {
+                    parent[rj] = ri;
+                    size[ri] = merged;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    (0..n).map(|i| size[find(&mut parent, i)]).collect()
+}
+
+/// Returns true if the bipartite graph with `n` nodes on each side and edges
+/// given by `edge(left, right)` has a perfect matching. Uses augmenting paths
+/// found by breadth first search, which is `O(n^3)`, but avoids recursion.
+fn has_perfect_matching<F: Fn(usize, usize) -> bool>(n: usize, edge: F) -> bool {
+    let mut match_left: Vec<Option<usize>> = vec![None; n];
+    let mut match_right: Vec<Option<usize>> = vec![None; n];
+    let mut parent: Vec<usize> = vec![0; n];
+    let mut seen: Vec<bool> = vec![false; n];
+    let mut queue: Vec<usize> = Vec::with_capacity(n);
+    for start in 0..n {
+        seen.iter_mut().for_each(|s| *s = false);
+        queue.clear();
+        queue.push(start);
+        let mut head = 0;
+        let mut found = None;
+        'search: while head < queue.len() {
+            let l = queue[head];
+            head += 1;
+            for r in 0..n {
+                if seen[r] || !edge(l, r) {
+                    continue;
+                }
+                seen[r] = true;
+                parent[r] = l;
+                match match_right[r] {
+                    None => {
+                        found = Some(r);
+                        break 'search;
+                    }
+                    Some(next) => queue.push(next),
+                }
+            }
+        }
+        let mut r = match found {
+            Some(r) => r,
+            None => return false,
+        };
+        // Flip the edges along the augmenting path.
+        loop {
+            let l = parent[r];
+            let prev = match_left[l];
+            match_left[l] = Some(r);
+            match_right[r] = Some(l);
+            match prev {
+                Some(p) => r = p,
+                None => break,
+            }
+        }
+    }
+    true
+}
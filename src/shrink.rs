@@ -0,0 +1,188 @@
+//! Reducing a failed comparison of large arrays to a minimal reproducer.
+
+use crate::report::ElementDiff;
+use crate::Float;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+/// A minimal reproducer for a failed element-wise comparison, as returned by
+/// [`almost::shrink_failure`](shrink_failure).
+///
+/// Its `Display` output is a unit test containing only the pairs which
+/// weren't almost equal, so a failure in a comparison of millions of elements
+/// can be pasted into a test and debugged in isolation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reproducer<T> {
+    /// The number of elements in each of the original slices.
+    pub len: usize,
+    /// The smallest range of indices containing every pair which wasn't almost
+    /// equal.
+    pub window: Range<usize>,
+    /// The pairs which weren't almost equal, in order, with their indices into
+    /// the original slices.
+    pub violations: Vec<ElementDiff<T, usize>>,
+    /// The relative tolerance which was used.
+    pub tolerance: T,
+}
+
+impl<T: Float> Reproducer<T> {
+    /// The left hand side of each violating pair, in order.
+    #[inline]
+    pub fn lhs(&self) -> Vec<T> {
+        self.violations.iter().map(|d| d.lhs).collect()
+    }
+
+    /// The right hand side of each violating pair, in order.
+    #[inline]
+    pub fn rhs(&self) -> Vec<T> {
+        self.violations.iter().map(|d| d.rhs).collect()
+    }
+}
+
+/// Formats `v` as a Rust expression which evaluates to exactly `v`.
+struct Literal<T>(T);
+
+impl<T: Float> fmt::Display for Literal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = if T::BYTES == 4 { "f32" } else { "f64" };
+        let v = self.0;
+        if v.is_nan() {
+            write!(f, "{}::NAN", ty)
+        } else if v.is_finite() {
+            // `Debug` prints the shortest string which parses back to `v`, and
+            // always includes a `.` or an exponent.
+            write!(f, "{:?}", v)
+        } else if v < T::default() {
+            write!(f, "{}::NEG_INFINITY", ty)
+        } else {
+            write!(f, "{}::INFINITY", ty)
+        }
+    }
+}
+
+impl<T: Float> fmt::Display for Reproducer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ty = if T::BYTES == 4 { "f32" } else { "f64" };
+        let n = self.violations.len();
+        let list =
+            |f: &mut fmt::Formatter<'_>, name: &str, side: fn(&ElementDiff<T, usize>) -> T| {
+                write!(f, "    let {}: [{}; {}] = [", name, ty, n)?;
+                for (i, d) in self.violations.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", Literal(side(d)))?;
+                }
+                f.write_str("];\n")
+            };
+        f.write_str("#[test]\nfn reproduce_mismatch() {\n")?;
+        write!(
+            f,
+            "    // {} of {} elements differ, in {:?}, at indices [",
+            n, self.len, self.window
+        )?;
+        for (i, d) in self.violations.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", d.index)?;
+        }
+        f.write_str("].\n")?;
+        list(f, "lhs", |d| d.lhs)?;
+        list(f, "rhs", |d| d.rhs)?;
+        write!(
+            f,
+            "    for (a, b) in lhs.iter().zip(&rhs) {{\n        \
+             assert!(almost::equal_with(*a, *b, {}), \"{{}} vs {{}}\", a, b);\n    \
+             }}\n}}",
+            Literal(self.tolerance)
+        )
+    }
+}
+
+/// Compares `lhs` and `rhs` element-wise, returning `None` if every pair is
+/// almost equal, or a minimal [`Reproducer`] of the failure otherwise.
+///
+/// ```
+/// let expected: Vec<f64> = (0..100_000).map(|i| i as f64 * 0.5).collect();
+/// let mut actual: Vec<f64> = expected.iter().map(|v| v * (1.0 + 1e-15)).collect();
+/// actual[51_234] = 1.0;
+/// actual[51_240] = f64::NAN;
+/// let repro = almost::shrink_failure(&actual, &expected).unwrap();
+/// assert_eq!(repro.window, 51_234..51_241);
+/// assert_eq!(repro.lhs().len(), 2);
+/// assert_eq!(repro.violations[1].index, 51_240);
+/// ```
+///
+/// The reproducer holds the smallest window of indices which contains every
+/// pair that failed, and those pairs themselves. Elements which were almost
+/// equal can't affect an element-wise comparison, so they're left out, and
+/// the pairs in the reproducer fail in exactly the same way as they did in
+/// the full comparison. Printing it gives a unit test which can be pasted
+/// into a test file:
+///
+/// ```text
+/// #[test]
+/// fn reproduce_mismatch() {
+///     // 2 of 100000 elements differ, in 51234..51241, at indices [51234, 51240].
+///     let lhs: [f64; 2] = [1.0, f64::NAN];
+///     let rhs: [f64; 2] = [25617.0, 25620.0];
+///     for (a, b) in lhs.iter().zip(&rhs) {
+///         assert!(almost::equal_with(*a, *b, 1.4901161193847656e-8), "{} vs {}", a, b);
+///     }
+/// }
+/// ```
+///
+/// Values are printed so that they parse back to exactly the same bits, so
+/// the test reproduces differences of a single ulp. Pairs are compared as
+/// with [`almost::equal`](crate::equal). This requires the `alloc` feature.
+///
+/// # Panics
+/// This function panics if the slices have different lengths.
+#[inline]
+pub fn shrink_failure<T: Float>(lhs: &[T], rhs: &[T]) -> Option<Reproducer<T>> {
+    shrink_failure_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::shrink_failure`](shrink_failure), but uses the
+/// provided relative tolerance, which is also used in the reproducer.
+///
+/// ```
+/// let repro = almost::shrink_failure_with(&[1.0f32, 2.0, 3.0], &[1.0, 2.1, 3.0], 0.01).unwrap();
+/// assert_eq!(repro.window, 1..2);
+/// assert!(format!("{}", repro).contains("almost::equal_with(*a, *b, 0.01)"));
+/// ```
+///
+/// # Panics
+/// This function panics if the slices have different lengths. It also panics
+/// in debug mode if `tolerance` is less than `T::EPSILON` or greater than 1.0,
+/// as with [`almost::equal_with`](crate::equal_with).
+pub fn shrink_failure_with<T: Float>(lhs: &[T], rhs: &[T], tolerance: T) -> Option<Reproducer<T>> {
+    assert_eq!(
+        lhs.len(),
+        rhs.len(),
+        "shrink_failure requires slices of the same length"
+    );
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    let violations: Vec<_> = lhs
+        .iter()
+        .zip(rhs)
+        .enumerate()
+        .filter(|&(_, (&a, &b))| !a.almost_equals_with(b, tolerance))
+        .map(|(index, (&a, &b))| ElementDiff {
+            index,
+            lhs: a,
+            rhs: b,
+            abs_error: if a == b { T::default() } else { (a - b).abs() },
+            rel_error: a.rel_err(b),
+        })
+        .collect();
+    let window = violations.first()?.index..violations.last()?.index + 1;
+    Some(Reproducer {
+        len: lhs.len(),
+        window,
+        violations,
+        tolerance,
+    })
+}
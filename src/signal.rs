@@ -0,0 +1,61 @@
+//! Comparison of sampled signals by their signal-to-error ratio. This requires
+//! the `std` or `libm` feature.
+
+use crate::Float;
+
+/// Returns the signal-to-error ratio of `actual` relative to `reference`, in
+/// decibels: `10 · log10(Σ reference² / Σ (reference − actual)²)`.
+///
+/// ```
+/// let reference = [0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -0.5];
+/// let actual = reference.map(|x| x + 0.001);
+/// let snr = almost::snr_db(&reference, &actual);
+/// assert!(snr > 55.0 && snr < 56.0);
+/// ```
+///
+/// This is infinite if the signals are identical (including if both are
+/// entirely zero), and negative infinity if only `reference` is. It's NaN if
+/// the lengths differ, or if any sample is NaN or infinite.
+pub fn snr_db<T: Float>(reference: &[T], actual: &[T]) -> T {
+    if reference.len() != actual.len() {
+        return T::from_f64(f64::NAN);
+    }
+    // Rescale so the sums of squares can't overflow or underflow.
+    let scale = crate::linalg::max_magnitude(reference.iter().chain(actual));
+    let (mut signal, mut error) = (0.0, 0.0);
+    for (&a, &b) in reference.iter().zip(actual) {
+        if !a.is_finite() || !b.is_finite() {
+            return T::from_f64(f64::NAN);
+        }
+        let (a, b) = ((a / scale).to_f64(), (b / scale).to_f64());
+        signal += a * a;
+        error += (a - b) * (a - b);
+    }
+    if error == 0.0 {
+        return T::from_f64(f64::INFINITY);
+    }
+    T::from_f64(10.0 * crate::math::log10(signal / error))
+}
+
+/// Returns true if the signal-to-error ratio of `actual` relative to
+/// `reference` is at least `min_db` decibels, as computed by
+/// [`almost::snr_db`](snr_db).
+///
+/// ```
+/// let reference: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).sin()).collect();
+/// // Quantized to 8 bits, which gives about 48 dB.
+/// let quantized: Vec<f32> = reference.iter().map(|x| (x * 127.0).round() / 127.0).collect();
+/// assert!(almost::equal_snr(&reference, &quantized, 40.0));
+/// assert!(!almost::equal_snr(&reference, &quantized, 60.0));
+/// ```
+///
+/// This is the usual acceptance test for audio and other DSP code, where the
+/// error is spread across the whole signal, so that samples near zero have
+/// large relative errors even when the result is fine, and an element-wise
+/// comparison would fail.
+///
+/// Returns false if the lengths differ, or if any sample is NaN or infinite.
+#[inline]
+pub fn equal_snr<T: Float>(reference: &[T], actual: &[T], min_db: T) -> bool {
+    snr_db(reference, actual) >= min_db
+}
@@ -0,0 +1,132 @@
+//! Implementations for `core::simd` vectors. This requires the `portable-simd`
+//! feature, and a nightly compiler.
+
+use crate::lanes::equal_lanes;
+use crate::AlmostEqual;
+use core::simd::cmp::SimdPartialOrd;
+use core::simd::num::SimdFloat;
+use core::simd::{Mask, Simd};
+
+macro_rules! impl_simd {
+    ($fp:ident, $mask:ident, $equal_lanes:ident, $zero_lanes:ident) => {
+        /// Compares each lane of `lhs` and `rhs`, with the relative tolerance
+        /// `tol`, as in [`almost::equal_with`](crate::equal_with).
+        ///
+        /// The [`AlmostEqual`] impl for `Simd` checks that every lane is almost
+        /// equal. Use this to get the result for each lane instead.
+        ///
+        /// # Panics
+        /// This function panics in debug mode if `tol` is less than
+        /// `EPSILON` or greater than 1.0, as with
+        /// [`almost::equal_with`](crate::equal_with).
+        #[inline]
+        pub fn $equal_lanes<const N: usize>(
+            lhs: Simd<$fp, N>,
+            rhs: Simd<$fp, N>,
+            tol: $fp,
+        ) -> Mask<$mask, N> {
+            equal_lanes(lhs, rhs, tol)
+        }
+
+        /// Checks whether each lane of `v` is almost zero, with the tolerance
+        /// `tol`, as in [`almost::zero_with`](crate::zero_with).
+        ///
+        /// # Panics
+        /// This function panics in debug mode if `tol` is not greater than
+        /// zero, as with [`almost::zero_with`](crate::zero_with).
+        #[inline]
+        pub fn $zero_lanes<const N: usize>(v: Simd<$fp, N>, tol: $fp) -> Mask<$mask, N> {
+            crate::float::check_zero_tol(tol);
+            v.abs().simd_lt(Simd::splat(tol))
+        }
+
+        impl<const N: usize> crate::lanes::Lanes for Simd<$fp, N> {
+            type Float = $fp;
+
+            type Mask = Mask<$mask, N>;
+
+            const LANES: usize = N;
+
+            #[inline]
+            fn splat(v: $fp) -> Self {
+                Simd::splat(v)
+            }
+
+            #[inline]
+            fn abs(self) -> Self {
+                SimdFloat::abs(self)
+            }
+
+            #[inline]
+            fn max(self, rhs: Self) -> Self {
+                self.simd_max(rhs)
+            }
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                self - rhs
+            }
+
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                self * rhs
+            }
+
+            #[inline]
+            fn lt(self, rhs: Self) -> Self::Mask {
+                self.simd_lt(rhs)
+            }
+
+            #[inline]
+            fn extract(self, i: usize) -> $fp {
+                self[i]
+            }
+
+            #[inline]
+            fn all(mask: Self::Mask) -> bool {
+                mask.all()
+            }
+
+            #[inline]
+            fn test(mask: Self::Mask, i: usize) -> bool {
+                mask.test(i)
+            }
+
+            #[inline]
+            fn set(mask: &mut Self::Mask, i: usize, value: bool) {
+                mask.set(i, value);
+            }
+        }
+
+        impl<const N: usize> AlmostEqual for Simd<$fp, N> {
+            type Float = $fp;
+
+            const DEFAULT_TOLERANCE: $fp = <$fp as AlmostEqual>::DEFAULT_TOLERANCE;
+
+            const MACHINE_EPSILON: $fp = $fp::EPSILON;
+
+            #[inline]
+            fn almost_equals(self, rhs: Self) -> bool {
+                self.almost_equals_with(rhs, crate::float::default_rel_tol())
+            }
+
+            #[inline]
+            fn almost_zero(self) -> bool {
+                self.almost_zero_with(crate::float::default_zero_tol())
+            }
+
+            #[inline]
+            fn almost_equals_with(self, rhs: Self, tol: $fp) -> bool {
+                $equal_lanes(self, rhs, tol).all()
+            }
+
+            #[inline]
+            fn almost_zero_with(self, tol: $fp) -> bool {
+                $zero_lanes(self, tol).all()
+            }
+        }
+    };
+}
+
+impl_simd!(f32, i32, simd_equal_lanes_f32, simd_zero_lanes_f32);
+impl_simd!(f64, i64, simd_equal_lanes_f64, simd_zero_lanes_f64);
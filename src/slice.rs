@@ -0,0 +1,628 @@
+//! Helpers which operate on whole slices of values.
+
+use crate::{AlmostEqual, Float};
+
+/// The extreme value of a slice, as returned by [`almost::max_of`](crate::max_of)
+/// or [`almost::min_of`](crate::min_of).
+///
+/// In addition to the extreme value itself, this remembers the slice it came
+/// from, so that every element which is almost equal to the extreme (e.g. ties
+/// which only differ due to roundoff) can be recovered.
+#[derive(Debug, Clone, Copy)]
+pub struct Extremum<'a, T: AlmostEqual> {
+    values: &'a [T],
+    index: usize,
+    // `None` to compare with each value's default.
+    tol: Option<T::Float>,
+}
+
+impl<'a, T> Extremum<'a, T>
+where
+    T: AlmostEqual + Copy,
+    T::Float: Copy,
+{
+    /// The extreme value itself.
+    #[inline]
+    pub fn value(&self) -> T {
+        self.values[self.index]
+    }
+
+    /// The index of the first element exactly equal to the extreme value.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns an iterator over the indices of every element which is almost
+    /// equal to the extreme value, in ascending order. This always includes
+    /// [`index`](Extremum::index).
+    ///
+    /// Note that this only includes the values almost equal to the extreme
+    /// itself, and not values which are almost equal to one of those.
+    #[inline]
+    pub fn ties(&self) -> Ties<'a, T> {
+        Ties {
+            iter: self.values.iter().enumerate(),
+            value: self.value(),
+            tol: self.tol,
+        }
+    }
+
+    /// The number of elements which are almost equal to the extreme value.
+    /// This is always at least 1.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.ties().count()
+    }
+}
+
+/// An iterator over the indices of the ties for an [`Extremum`].
+#[derive(Debug, Clone)]
+pub struct Ties<'a, T: AlmostEqual> {
+    iter: core::iter::Enumerate<core::slice::Iter<'a, T>>,
+    value: T,
+    tol: Option<T::Float>,
+}
+
+impl<'a, T> Iterator for Ties<'a, T>
+where
+    T: AlmostEqual + Copy,
+    T::Float: Copy,
+{
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let (value, tol) = (self.value, self.tol);
+        self.iter
+            .find(|&(_, &v)| match tol {
+                Some(tol) => v.almost_equals_with(value, tol),
+                None => v.almost_equals(value),
+            })
+            .map(|(i, _)| i)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+fn extremum_by<T, F>(values: &[T], tol: Option<T::Float>, better: F) -> Option<Extremum<'_, T>>
+where
+    T: AlmostEqual + PartialOrd + Copy,
+    F: Fn(&T, &T) -> bool,
+{
+    // NaN is never the extreme, and is never compared against, since it isn't
+    // almost equal to anything.
+    let mut iter = values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.partial_cmp(v).is_some());
+    let (mut index, mut best) = iter.next()?;
+    for (i, v) in iter {
+        if better(v, best) {
+            index = i;
+            best = v;
+        }
+    }
+    Some(Extremum { values, index, tol })
+}
+
+/// Returns the maximum value in `values`, along with every index which holds a
+/// value almost equal to it.
+///
+/// ```
+/// let v = [0.3, 0.1 + 0.2, 0.25, f64::NAN];
+/// let max = almost::max_of(&v).unwrap();
+/// assert_eq!(max.value(), 0.1 + 0.2);
+/// assert_eq!(max.ties().collect::<Vec<_>>(), [0, 1]);
+/// ```
+///
+/// This is useful when choosing the "best" candidates, where tied values may
+/// differ from each other only due to rounding error, and arbitrarily picking
+/// one would discard the others.
+///
+/// NaN values are ignored. Returns `None` if the slice is empty or contains only
+/// NaN.
+#[inline]
+pub fn max_of<T>(values: &[T]) -> Option<Extremum<'_, T>>
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    extremum_by(values, None, |a, b| a > b)
+}
+
+/// Returns the minimum value in `values`, along with every index which holds a
+/// value almost equal to it.
+///
+/// ```
+/// let v = [0.5, 0.25, 0.75, 0.25 + 1e-12];
+/// let min = almost::min_of(&v).unwrap();
+/// assert_eq!(min.index(), 1);
+/// assert_eq!(min.count(), 2);
+/// ```
+///
+/// This is the same as [`almost::max_of`](max_of), except that it finds the
+/// minimum. NaN values are ignored. Returns `None` if the slice is empty or
+/// contains only NaN.
+#[inline]
+pub fn min_of<T>(values: &[T]) -> Option<Extremum<'_, T>>
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    extremum_by(values, None, |a, b| a < b)
+}
+
+/// Equivalent to [`almost::max_of`](max_of), but ties are determined using the
+/// provided relative tolerance.
+///
+/// ```
+/// let v = [1.0f32, 0.99, 0.5];
+/// let max = almost::max_of_with(&v, 0.05).unwrap();
+/// assert_eq!(max.count(), 2);
+/// ```
+///
+/// # Panics
+/// Enumerating the ties panics in debug mode if `tolerance` is less than
+/// `T::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn max_of_with<T>(values: &[T], tolerance: T::Float) -> Option<Extremum<'_, T>>
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    extremum_by(values, Some(tolerance), |a, b| a > b)
+}
+
+/// Equivalent to [`almost::min_of`](min_of), but ties are determined using the
+/// provided relative tolerance.
+///
+/// ```
+/// let v = [1.0f32, 1.01, 2.0];
+/// let min = almost::min_of_with(&v, 0.05).unwrap();
+/// assert_eq!(min.ties().collect::<Vec<_>>(), [0, 1]);
+/// ```
+///
+/// # Panics
+/// Enumerating the ties panics in debug mode if `tolerance` is less than
+/// `T::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn min_of_with<T>(values: &[T], tolerance: T::Float) -> Option<Extremum<'_, T>>
+where
+    T: AlmostEqual + PartialOrd + Copy,
+{
+    extremum_by(values, Some(tolerance), |a, b| a < b)
+}
+
+/// The element of a slice closest to some target, as returned by
+/// [`almost::closest_match`](crate::closest_match).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestMatch<T> {
+    /// The index of the closest element.
+    pub index: usize,
+    /// The value of the closest element.
+    pub value: T,
+    /// The relative error between `value` and the target, i.e. the difference
+    /// between them divided by the larger of their magnitudes.
+    pub relative_error: T,
+    /// The tolerance which was used to decide `is_almost_equal`.
+    pub tolerance: T,
+    /// Whether `value` is almost equal to the target.
+    pub is_almost_equal: bool,
+}
+
+impl<T: Float> ClosestMatch<T> {
+    /// The relative error as a multiple of the tolerance. Values less than 1
+    /// indicate that the match was almost equal.
+    ///
+    /// For a failed lookup, this gives a sense of how far off the nearest
+    /// candidate was — `1.01` and `1e6` usually indicate very different
+    /// problems.
+    #[inline]
+    pub fn tolerance_ratio(&self) -> T {
+        self.relative_error / self.tolerance
+    }
+}
+
+/// Returns the element of `values` which is closest to `target` by relative
+/// error, along with whether or not it's almost equal to it.
+///
+/// ```
+/// let v = [1.0, 2.0, 3.0];
+/// let m = almost::closest_match(&v, 2.1).unwrap();
+/// assert_eq!((m.index, m.value), (1, 2.0));
+/// assert!(!m.is_almost_equal);
+/// assert!(m.tolerance_ratio() > 1000.0);
+/// ```
+///
+/// This is mostly useful for diagnosing failed lookups. If several values are
+/// equally close, the first is returned. NaN values in `values` are ignored,
+/// and `None` is returned if there are no other values, or if `target` is NaN.
+#[inline]
+pub fn closest_match<T: Float>(values: &[T], target: T) -> Option<ClosestMatch<T>> {
+    closest_match_with(values, target, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::closest_match`](closest_match), but uses the provided
+/// relative tolerance.
+///
+/// ```
+/// let v = [1.0f32, 2.0, 3.0];
+/// let m = almost::closest_match_with(&v, 2.1, 0.1).unwrap();
+/// assert!(m.is_almost_equal);
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn closest_match_with<T: Float>(
+    values: &[T],
+    target: T,
+    tolerance: T,
+) -> Option<ClosestMatch<T>> {
+    if target.is_nan() {
+        return None;
+    }
+    let mut best: Option<(usize, T, T)> = None;
+    for (i, &v) in values.iter().enumerate() {
+        let err = v.rel_err(target);
+        // NaN values have a NaN error, and are skipped.
+        let closer = match best {
+            Some((_, _, e)) => err < e,
+            None => !err.is_nan(),
+        };
+        if closer {
+            best = Some((i, v, err));
+        }
+    }
+    best.map(|(index, value, relative_error)| ClosestMatch {
+        index,
+        value,
+        relative_error,
+        tolerance,
+        is_almost_equal: value.almost_equals_with(target, tolerance),
+    })
+}
+
+/// The smallest and largest of `first` and `rest`, ignoring NaN (unless it's
+/// `first`).
+#[inline]
+fn min_max<T: Float>(first: T, rest: &[T]) -> (T, T) {
+    let (mut lo, mut hi) = (first, first);
+    for &v in rest {
+        if v < lo || lo.is_nan() {
+            lo = v;
+        }
+        if v > hi || hi.is_nan() {
+            hi = v;
+        }
+    }
+    (lo, hi)
+}
+
+/// What [`almost::all_almost_equal`](all_almost_equal) compares each element
+/// against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Agreement {
+    /// Every element must be almost equal to every other element.
+    Pairwise,
+    /// Every element must be almost equal to the mean of the elements. This is
+    /// looser than `Pairwise`, as two elements on opposite sides of the mean
+    /// can be up to twice the tolerance apart.
+    WithMean,
+}
+
+/// Returns true if the elements of `values` all agree with each other, for
+/// example because they're repeated measurements or redundant computations of
+/// the same quantity.
+///
+/// ```
+/// use almost::Agreement;
+/// let runs = [0.30000000000000004, 0.3, 0.29999999999999993];
+/// assert!(almost::all_almost_equal(&runs, Agreement::Pairwise));
+/// assert!(!almost::all_almost_equal(&[1.0, 1.1], Agreement::WithMean));
+/// ```
+///
+/// Returns false if any element is NaN, and otherwise true if there are fewer
+/// than two elements. The `Pairwise` check doesn't compare every pair of elements, as it's
+/// equivalent to comparing the smallest and largest, so it only takes linear
+/// time.
+#[inline]
+pub fn all_almost_equal<T: Float>(values: &[T], agreement: Agreement) -> bool {
+    all_almost_equal_with(values, agreement, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::all_almost_equal`](all_almost_equal), but uses the
+/// provided relative tolerance.
+///
+/// ```
+/// use almost::Agreement;
+/// let v = [1.0f32, 1.01, 0.99];
+/// assert!(almost::all_almost_equal_with(&v, Agreement::WithMean, 0.02));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn all_almost_equal_with<T: Float>(values: &[T], agreement: Agreement, tolerance: T) -> bool {
+    let (first, rest) = match values.split_first() {
+        Some(split) => split,
+        None => return true,
+    };
+    if values.iter().any(|v| v.is_nan()) {
+        return false;
+    }
+    match agreement {
+        Agreement::Pairwise => {
+            // The pair with the largest relative difference is always the
+            // smallest and largest: values with opposite signs are never
+            // almost equal, and otherwise moving either further from the other
+            // increases the difference by more than the scale.
+            let (lo, hi) = min_max(*first, rest);
+            lo.almost_equals_with(hi, tolerance)
+        }
+        Agreement::WithMean => {
+            // A running mean, so that large values don't overflow.
+            let mut mean = *first;
+            for (i, &v) in rest.iter().enumerate() {
+                if v != mean {
+                    mean = mean + (v - mean) / T::from_f64((i + 2) as f64);
+                }
+            }
+            values
+                .iter()
+                .all(|&v| v.almost_equals_with(mean, tolerance))
+        }
+    }
+}
+
+/// The range of values in a slice, as returned by
+/// [`almost::almost_constant`](almost_constant).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spread<T> {
+    /// The smallest value.
+    pub min: T,
+    /// The largest value.
+    pub max: T,
+    /// The difference between `max` and `min`, relative to the larger of their
+    /// magnitudes. This is NaN if any value was NaN.
+    pub relative: T,
+    /// Whether `min` and `max` (and so every value) are almost equal.
+    pub is_constant: bool,
+}
+
+/// Checks whether `values` is constant up to tolerance, returning the spread
+/// of the values either way. Returns `None` if the slice is empty.
+///
+/// ```
+/// // Energy should be conserved by the simulation.
+/// let energy = [10.0, 10.0 + 1e-12, 10.0 - 3e-12, 10.0];
+/// let spread = almost::almost_constant(&energy).unwrap();
+/// assert!(spread.is_constant);
+/// assert!(spread.relative < 1e-12);
+///
+/// let spread = almost::almost_constant(&[1.0, 2.0, 1.5]).unwrap();
+/// assert!(!spread.is_constant);
+/// assert_eq!((spread.min, spread.max, spread.relative), (1.0, 2.0, 0.5));
+/// ```
+///
+/// This is the same check as [`almost::all_almost_equal`](all_almost_equal)
+/// using `Agreement::Pairwise`, but the spread found is useful for reporting
+/// failures, or for checking that a signal is converging. NaN values are
+/// ignored when finding `min` and `max`, but mean the slice is never constant.
+#[inline]
+pub fn almost_constant<T: Float>(values: &[T]) -> Option<Spread<T>> {
+    almost_constant_with(values, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::almost_constant`](almost_constant), but uses the
+/// provided relative tolerance.
+///
+/// ```
+/// let tail = [0.999f32, 1.0, 1.001];
+/// assert!(almost::almost_constant_with(&tail, 0.01).unwrap().is_constant);
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn almost_constant_with<T: Float>(values: &[T], tolerance: T) -> Option<Spread<T>> {
+    let (first, rest) = values.split_first()?;
+    let (min, max) = min_max(*first, rest);
+    let has_nan = values.iter().any(|v| v.is_nan());
+    Some(Spread {
+        min,
+        max,
+        relative: if has_nan {
+            T::from_f64(f64::NAN)
+        } else {
+            min.rel_err(max)
+        },
+        is_constant: !has_nan && min.almost_equals_with(max, tolerance),
+    })
+}
+
+/// The number of elements checked at a time by the zero scans. Large enough to
+/// fill a few vector registers for `f32`.
+const ZERO_SCAN_LANES: usize = 16;
+
+/// Returns the index of the first element of `values` for which
+/// `almost_zero_with(tol) == want`.
+fn zero_scan<T: Float>(values: &[T], tol: T, want: bool) -> Option<usize> {
+    crate::float::check_zero_tol(tol);
+    if cfg!(feature = "audit") {
+        return values.iter().position(|&v| v.almost_zero_with(tol) == want);
+    }
+    if tol.is_nan() || tol <= T::default() {
+        // Nothing is almost zero.
+        return if want || values.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+    }
+    // For non-NaN values, comparing the magnitude bits is the same as comparing
+    // the magnitudes, and NaN's bits are larger than any positive `tol`'s, so
+    // it's never almost zero. This keeps the loop free of floating point
+    // comparisons and branches, so it vectorizes.
+    let limit = tol.to_ordered();
+    let is_zero = |v: &T| v.abs().to_ordered() < limit;
+    let mut chunks = values.chunks_exact(ZERO_SCAN_LANES);
+    let mut start = 0;
+    for chunk in &mut chunks {
+        let hits = chunk
+            .iter()
+            .fold(0usize, |n, v| n + (is_zero(v) == want) as usize);
+        if hits != 0 {
+            return chunk
+                .iter()
+                .position(|v| is_zero(v) == want)
+                .map(|i| start + i);
+        }
+        start += ZERO_SCAN_LANES;
+    }
+    chunks
+        .remainder()
+        .iter()
+        .position(|v| is_zero(v) == want)
+        .map(|i| start + i)
+}
+
+/// Checks that every element of `values` is almost zero, returning the index
+/// of the first one which isn't.
+///
+/// ```
+/// let residual = [1e-20, -3e-19, 0.0, 2e-5, 1e-18];
+/// assert_eq!(almost::all_almost_zero(&residual[..3]), Ok(()));
+/// assert_eq!(almost::all_almost_zero(&residual), Err(3));
+/// ```
+///
+/// This is equivalent to checking each element with
+/// [`almost::zero`](crate::zero) (so NaN isn't almost zero), but is written so
+/// that it's vectorized, for checks which are in hot loops, such as checking a
+/// residual after every iteration of a solver.
+///
+/// With the `audit` feature, every element is checked with
+/// [`almost::zero_with`](crate::zero_with) instead, so that it's recorded.
+#[inline]
+pub fn all_almost_zero<T: Float>(values: &[T]) -> Result<(), usize> {
+    all_almost_zero_with(values, crate::float::default_zero_tol())
+}
+
+/// Equivalent to [`almost::all_almost_zero`](all_almost_zero), but uses the
+/// provided tolerance, as in [`almost::zero_with`](crate::zero_with).
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is not greater than zero,
+/// as with [`almost::zero_with`](crate::zero_with).
+#[inline]
+pub fn all_almost_zero_with<T: Float>(values: &[T], tolerance: T) -> Result<(), usize> {
+    match zero_scan(values, tolerance, false) {
+        Some(i) => Err(i),
+        None => Ok(()),
+    }
+}
+
+/// Returns the index of the first element of `values` which is almost zero, if
+/// any.
+///
+/// ```
+/// let pivots = [3.0, -2.5, 1e-17, 4.0];
+/// assert_eq!(almost::any_almost_zero(&pivots), Some(2));
+/// assert_eq!(almost::any_almost_zero(&pivots[..2]), None);
+/// ```
+///
+/// Like [`almost::all_almost_zero`](all_almost_zero), this is vectorized, except
+/// with the `audit` feature.
+#[inline]
+pub fn any_almost_zero<T: Float>(values: &[T]) -> Option<usize> {
+    any_almost_zero_with(values, crate::float::default_zero_tol())
+}
+
+/// Equivalent to [`almost::any_almost_zero`](any_almost_zero), but uses the
+/// provided tolerance, as in [`almost::zero_with`](crate::zero_with).
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is not greater than zero,
+/// as with [`almost::zero_with`](crate::zero_with).
+#[inline]
+pub fn any_almost_zero_with<T: Float>(values: &[T], tolerance: T) -> Option<usize> {
+    zero_scan(values, tolerance, true)
+}
+
+/// The number of pairs checked at a time by [`equal_slices_with`].
+const EQUAL_SCAN_LANES: usize = 16;
+
+/// Returns true if `lhs` and `rhs` have the same length, and each pair of
+/// elements is almost equal.
+///
+/// ```
+/// let expected: Vec<f32> = (0..10_000).map(|i| i as f32 * 0.25).collect();
+/// let mut actual: Vec<f32> = expected.iter().map(|v| v * (1.0 + 1e-6)).collect();
+/// assert!(almost::equal_slices(&actual, &expected));
+/// actual[9_000] += 100.0;
+/// assert!(!almost::equal_slices(&actual, &expected));
+/// ```
+///
+/// This gives the same result as [`almost::equal`](crate::equal) on the
+/// slices, but is written so that it's vectorized, for comparing large arrays,
+/// such as the output of a simulation. Pairs are checked a chunk at a time
+/// with a simplified comparison, which is exact for finite values which
+/// aren't tiny; chunks containing anything else (or a pair which isn't almost
+/// equal) are checked again with the full comparison.
+///
+/// With the `audit` feature, every pair is checked with the full comparison,
+/// so that it's recorded.
+#[inline]
+pub fn equal_slices<T: Float>(lhs: &[T], rhs: &[T]) -> bool {
+    equal_slices_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::equal_slices`](equal_slices), but uses the provided
+/// relative tolerance, as in [`almost::equal_with`](crate::equal_with).
+///
+/// ```
+/// assert!(almost::equal_slices_with(&[1.0, 2.0], &[1.01, 2.0], 0.05));
+/// assert!(!almost::equal_slices_with(&[1.0, 2.0], &[1.01, 2.0], 0.001));
+/// assert!(!almost::equal_slices_with(&[1.0], &[1.0, 2.0], 0.05));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn equal_slices_with<T: Float>(lhs: &[T], rhs: &[T], tolerance: T) -> bool {
+    let tol = crate::float::check_rel_tol(tolerance);
+    let full = |a: &[T], b: &[T]| a.iter().zip(b).all(|(&a, &b)| a.almost_equals_with(b, tol));
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    if cfg!(feature = "audit") {
+        return full(lhs, rhs);
+    }
+    // Below this, `tol * scale` can be subnormal, and the full comparison
+    // rescales first. It's `MIN_POSITIVE * 2^(SIGNIFICAND_BITS + 1)`.
+    let min_scale = T::MIN_POSITIVE * T::from_f64((1u64 << (T::SIGNIFICAND_BITS + 1)) as f64);
+    let infinity = T::from_f64(f64::INFINITY);
+    // The full comparison, for finite values with a scale of at least
+    // `min_scale`, and false for everything else (including NaN). It has no
+    // branches, so the loop below vectorizes.
+    let simple = |a: T, b: T| {
+        let (x, y) = (a.abs(), b.abs());
+        let scale = if x > y { x } else { y };
+        (scale >= min_scale) & (scale < infinity) & ((a - b).abs() < tol * scale)
+    };
+    let mut lhs_chunks = lhs.chunks_exact(EQUAL_SCAN_LANES);
+    let mut rhs_chunks = rhs.chunks_exact(EQUAL_SCAN_LANES);
+    for (a, b) in (&mut lhs_chunks).zip(&mut rhs_chunks) {
+        let passed = a
+            .iter()
+            .zip(b)
+            .fold(0usize, |n, (&a, &b)| n + simple(a, b) as usize);
+        if passed != EQUAL_SCAN_LANES && !full(a, b) {
+            return false;
+        }
+    }
+    full(lhs_chunks.remainder(), rhs_chunks.remainder())
+}
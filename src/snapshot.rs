@@ -0,0 +1,317 @@
+//! Integration with `insta`, for snapshots which contain floats. This requires
+//! the `insta` feature.
+
+use insta::internals::{Content, Redaction};
+use insta::{Comparator, Snapshot};
+use std::boxed::Box;
+use std::format;
+use std::string::ToString;
+use std::vec::Vec;
+
+/// Returns an `insta` redaction which rounds every float in the value to the
+/// precision the default tolerances allow, and flushes values which are
+/// almost zero (as with [`almost::zero`](crate::zero)) to zero.
+///
+/// ```
+/// let mut settings = insta::Settings::new();
+/// settings.add_redaction(".**", almost::snapshot_redaction());
+/// settings.bind(|| {
+///     let energies = [0.1 + 0.2, 1.0 - 1e-12, 2e-17];
+///     insta::assert_yaml_snapshot!(energies, @r"
+///     - 0.3
+///     - 1
+///     - 0
+///     ");
+/// });
+/// ```
+///
+/// Floats are rounded to as many significant decimal digits as the relative
+/// tolerance permits, which is 7 for `f64` and 3 for `f32` by default, so
+/// results which differ only by rounding error on another platform or
+/// compiler usually produce the same snapshot. Values which are very close to
+/// a rounding boundary can still round differently; use a
+/// [`SnapshotComparator`] as well if that's a problem.
+///
+/// This works with any selector, and rounds the floats anywhere inside the
+/// values it selects, so `".**"` rounds every float in the snapshot.
+#[inline]
+pub fn snapshot_redaction() -> Redaction {
+    let f32_tolerances = Tolerances::new(
+        crate::float::default_rel_tol::<f32>() as f64,
+        crate::float::default_zero_tol::<f32>() as f64,
+    );
+    let f64_tolerances = Tolerances::new(
+        crate::float::default_rel_tol(),
+        crate::float::default_zero_tol(),
+    );
+    insta::dynamic_redaction(move |value, _| round(value, f32_tolerances, f64_tolerances))
+}
+
+/// Equivalent to [`almost::snapshot_redaction`](snapshot_redaction), but
+/// rounds using the provided relative tolerance, and flushes values smaller
+/// than the absolute tolerance `zero_tol` to zero. These are used for both
+/// `f32` and `f64` values.
+///
+/// ```
+/// let mut settings = insta::Settings::new();
+/// settings.add_redaction(".**", almost::snapshot_redaction_with(1e-3, 1e-9));
+/// settings.bind(|| {
+///     insta::assert_yaml_snapshot!([9.87654321, 1e-10], @r"
+///     - 9.88
+///     - 0
+///     ");
+/// });
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than
+/// `f64::EPSILON` or greater than 1.0, or if `zero_tol` is not greater than
+/// zero.
+pub fn snapshot_redaction_with(tolerance: f64, zero_tol: f64) -> Redaction {
+    crate::float::check_zero_tol(zero_tol);
+    let tolerances = Tolerances::new(crate::float::check_rel_tol(tolerance), zero_tol);
+    insta::dynamic_redaction(move |value, _| round(value, tolerances, tolerances))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Tolerances {
+    /// The number of significant decimal digits to keep.
+    digits: usize,
+    zero: f64,
+}
+
+impl Tolerances {
+    #[inline]
+    fn new(tolerance: f64, zero: f64) -> Self {
+        Tolerances {
+            digits: digits(tolerance),
+            zero,
+        }
+    }
+}
+
+/// Returns how many significant decimal digits a relative tolerance leaves
+/// meaningful.
+fn digits(tolerance: f64) -> usize {
+    let digits = -crate::math::log10(tolerance);
+    if digits.is_nan() || digits < 1.0 {
+        1
+    } else {
+        (digits as usize).min(17)
+    }
+}
+
+/// Rounds `v` to `digits` significant digits, going through decimal so that
+/// the rounded value prints as briefly as possible.
+fn round_to(v: f64, t: Tolerances) -> f64 {
+    if !v.is_finite() {
+        v
+    } else if v.abs() < t.zero {
+        0.0
+    } else {
+        let s = format!("{:.*e}", t.digits - 1, v);
+        s.parse().unwrap_or(v)
+    }
+}
+
+/// Rounds every float inside `value`.
+fn round(value: Content, f32_tol: Tolerances, f64_tol: Tolerances) -> Content {
+    let round = |v| round(v, f32_tol, f64_tol);
+    let seq = |s: Vec<Content>| s.into_iter().map(round).collect();
+    let fields =
+        |s: Vec<(&'static str, Content)>| s.into_iter().map(|(k, v)| (k, round(v))).collect();
+    match value {
+        Content::F32(v) => Content::F32(round_to(v as f64, f32_tol) as f32),
+        Content::F64(v) => Content::F64(round_to(v, f64_tol)),
+        Content::Some(v) => Content::Some(Box::new(round(*v))),
+        Content::NewtypeStruct(name, v) => Content::NewtypeStruct(name, Box::new(round(*v))),
+        Content::NewtypeVariant(name, index, variant, v) => {
+            Content::NewtypeVariant(name, index, variant, Box::new(round(*v)))
+        }
+        Content::Seq(s) => Content::Seq(seq(s)),
+        Content::Tuple(s) => Content::Tuple(seq(s)),
+        Content::TupleStruct(name, s) => Content::TupleStruct(name, seq(s)),
+        Content::TupleVariant(name, index, variant, s) => {
+            Content::TupleVariant(name, index, variant, seq(s))
+        }
+        Content::Map(m) => Content::Map(m.into_iter().map(|(k, v)| (k, round(v))).collect()),
+        Content::Struct(name, s) => Content::Struct(name, fields(s)),
+        Content::StructVariant(name, index, variant, s) => {
+            Content::StructVariant(name, index, variant, fields(s))
+        }
+        other => other,
+    }
+}
+
+/// An `insta` comparator which considers text snapshots to match if they're
+/// the same apart from numbers which are almost equal.
+///
+/// ```
+/// let mut settings = insta::Settings::new();
+/// settings.set_comparator(Box::new(almost::SnapshotComparator::new()));
+/// settings.bind(|| {
+///     // The stored snapshot was recorded on a platform with a different libm.
+///     let area = std::f64::consts::PI * 2.5f64.powi(2);
+///     insta::assert_snapshot!(format!("area: {} m²", area), @"area: 19.634954085 m²");
+/// });
+/// ```
+///
+/// Snapshots are compared token by token. Tokens which are decimal numbers
+/// (such as `-12`, `0.5` or `1.25e-7`, but not the digits in `v2`) in the
+/// same places in both snapshots match if they're almost equal with the
+/// relative tolerance, or if both are within the absolute zero tolerance, as
+/// with [`almost::equal_with_zero_tol`](crate::equal_with_zero_tol). All other
+/// text has to match exactly. Binary snapshots are compared exactly.
+///
+/// Unlike [`almost::snapshot_redaction`](snapshot_redaction), this doesn't
+/// change what's stored, so updated snapshots still contain the exact values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotComparator {
+    tolerance: f64,
+    zero_tol: f64,
+}
+
+impl Default for SnapshotComparator {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapshotComparator {
+    /// Creates a comparator which compares numbers with the default tolerances
+    /// for `f64` (as in [`almost::equal`](crate::equal) and
+    /// [`almost::zero`](crate::zero)).
+    #[inline]
+    pub fn new() -> Self {
+        SnapshotComparator {
+            tolerance: crate::float::default_rel_tol(),
+            zero_tol: crate::float::default_zero_tol(),
+        }
+    }
+
+    /// Creates a comparator which compares numbers with the provided relative
+    /// tolerance, and considers numbers smaller than `zero_tol` to be equal.
+    ///
+    /// # Panics
+    /// Panics in debug mode if `tolerance` is less than `f64::EPSILON` or
+    /// greater than 1.0, or if `zero_tol` is not greater than zero.
+    #[inline]
+    pub fn with_tolerance(tolerance: f64, zero_tol: f64) -> Self {
+        crate::float::check_zero_tol(zero_tol);
+        SnapshotComparator {
+            tolerance: crate::float::check_rel_tol(tolerance),
+            zero_tol,
+        }
+    }
+
+    /// Returns true if the texts `reference` and `test` are the same, apart
+    /// from numbers which are almost equal.
+    pub fn text_matches(&self, reference: &str, test: &str) -> bool {
+        let (mut a, mut b) = (Tokens::new(reference), Tokens::new(test));
+        loop {
+            match (a.next(), b.next()) {
+                (None, None) => return true,
+                (Some(x), Some(y)) if x == y => {}
+                (Some(Token::Number(x)), Some(Token::Number(y))) => {
+                    match (x.parse::<f64>(), y.parse::<f64>()) {
+                        (Ok(x), Ok(y))
+                            if crate::equal_with_zero_tol(x, y, self.tolerance, self.zero_tol) => {}
+                        _ => return false,
+                    }
+                }
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl Comparator for SnapshotComparator {
+    fn matches(&self, reference: &Snapshot, test: &Snapshot) -> bool {
+        match (reference.contents().as_text(), test.contents().as_text()) {
+            (Some(a), Some(b)) => self.text_matches(&a.to_string(), &b.to_string()),
+            _ => insta::DefaultComparator.matches(reference, test),
+        }
+    }
+
+    fn dyn_clone(&self) -> Box<dyn Comparator> {
+        Box::new(*self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    Number(&'a str),
+    Char(char),
+}
+
+/// Splits text into numbers and the characters between them.
+struct Tokens<'a> {
+    rest: &'a str,
+    /// Whether the previous character could be part of an identifier, in which
+    /// case digits are part of it, not a number.
+    in_word: bool,
+}
+
+impl<'a> Tokens<'a> {
+    #[inline]
+    fn new(s: &'a str) -> Self {
+        Tokens {
+            rest: s,
+            in_word: false,
+        }
+    }
+}
+
+/// Returns the length of the number at the start of `s`, or 0 if it doesn't
+/// start with one.
+fn number_len(s: &str) -> usize {
+    let b = s.as_bytes();
+    let digits = |mut i: usize| {
+        while b.get(i).is_some_and(u8::is_ascii_digit) {
+            i += 1;
+        }
+        i
+    };
+    let start = usize::from(b.first() == Some(&b'-'));
+    let mut i = digits(start);
+    let mut mantissa = i > start;
+    if b.get(i) == Some(&b'.') && (mantissa || b.get(i + 1).is_some_and(u8::is_ascii_digit)) {
+        let j = digits(i + 1);
+        mantissa |= j > i + 1;
+        i = j;
+    }
+    if !mantissa {
+        return 0;
+    }
+    if matches!(b.get(i), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(b.get(i + 1), Some(b'-' | b'+')));
+        let j = digits(i + 1 + sign);
+        if j > i + 1 + sign {
+            i = j;
+        }
+    }
+    i
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let c = self.rest.chars().next()?;
+        let len = if self.in_word {
+            0
+        } else {
+            number_len(self.rest)
+        };
+        if len > 0 {
+            let (number, rest) = self.rest.split_at(len);
+            self.rest = rest;
+            self.in_word = rest.starts_with(|c: char| c.is_alphanumeric() || c == '_');
+            return Some(Token::Number(number));
+        }
+        self.rest = &self.rest[c.len_utf8()..];
+        self.in_word = c.is_alphanumeric() || c == '_';
+        Some(Token::Char(c))
+    }
+}
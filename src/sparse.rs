@@ -0,0 +1,278 @@
+//! Comparison of sparse vectors and matrices.
+
+use crate::float::{default_rel_tol, default_zero_tol};
+use crate::Float;
+
+/// Walks two sorted sparse sequences in lockstep.
+fn sparse_walk<K, T, A, B>(mut a: A, mut b: B, rel_tol: T, zero_tol: T) -> bool
+where
+    K: Ord + Copy,
+    T: Float,
+    A: Iterator<Item = (K, T)>,
+    B: Iterator<Item = (K, T)>,
+{
+    let zero = T::default();
+    let (mut next_a, mut next_b) = (a.next(), b.next());
+    let (mut last_a, mut last_b): (Option<K>, Option<K>) = (None, None);
+    loop {
+        let (ka, kb) = (next_a.map(|e| e.0), next_b.map(|e| e.0));
+        // Unsorted or duplicated indices can't be compared this way.
+        if ka.is_some() && ka <= last_a || kb.is_some() && kb <= last_b {
+            return false;
+        }
+        let (va, vb) = match (next_a, next_b) {
+            (None, None) => return true,
+            (Some((ka, va)), Some((kb, vb))) if ka == kb => (va, vb),
+            (Some((ka, va)), Some((kb, _))) if ka < kb => (va, zero),
+            (Some(_), Some((_, vb))) => (zero, vb),
+            (Some((_, va)), None) => (va, zero),
+            (None, Some((_, vb))) => (zero, vb),
+        };
+        if !crate::equal_with_zero_tol(va, vb, rel_tol, zero_tol) {
+            return false;
+        }
+        // Advance whichever side(s) we just consumed.
+        let advance_a = ka.is_some() && (kb.is_none() || ka <= kb);
+        let advance_b = kb.is_some() && (ka.is_none() || kb <= ka);
+        if advance_a {
+            last_a = ka;
+            next_a = a.next();
+        }
+        if advance_b {
+            last_b = kb;
+            next_b = b.next();
+        }
+    }
+}
+
+/// Returns true if the sparse data `lhs` and `rhs`, given as `(index, value)`
+/// pairs, are almost equal.
+///
+/// ```
+/// let a = [(0, 1.0), (3, 0.1 + 0.2), (7, 1e-30)];
+/// let b = [(0, 1.0), (3, 0.3)];
+/// assert!(almost::sparse_equal(&a, &b));
+/// ```
+///
+/// Entries present in only one of the inputs are compared against zero using
+/// the zero tolerance (as with [`almost::zero`](crate::zero)), so an explicitly
+/// stored near-zero entry doesn't cause a structural mismatch. Entries present
+/// in both are equal if they're almost equal, or if both are almost zero.
+///
+/// The index can be any ordered type. For matrices, use `(row, col)` (or
+/// `(col, row)`, for column-major storage) as the index.
+///
+/// Both inputs must be sorted by index, with no duplicates, and false is
+/// returned if they aren't. False is also returned if any value is NaN.
+#[inline]
+pub fn sparse_equal<K, T>(lhs: &[(K, T)], rhs: &[(K, T)]) -> bool
+where
+    K: Ord + Copy,
+    T: Float,
+{
+    sparse_equal_with(lhs, rhs, default_rel_tol(), default_zero_tol())
+}
+
+/// Equivalent to [`almost::sparse_equal`](sparse_equal), but uses the provided
+/// relative tolerance, and the provided absolute tolerance for comparing with
+/// absent entries.
+///
+/// ```
+/// let a = [((0, 0), 1.0f32), ((1, 1), 2.0), ((1, 2), 0.001)];
+/// let b = [((0, 0), 1.0f32), ((1, 1), 2.01)];
+/// assert!(almost::sparse_equal_with(&a, &b, 0.01, 0.01));
+/// assert!(!almost::sparse_equal_with(&a, &b, 0.01, 0.0001));
+/// ```
+///
+/// # Panics
+/// This function panics in debug mode if either tolerance is invalid, as in
+/// [`almost::equal_with`](crate::equal_with) and
+/// [`almost::zero_with`](crate::zero_with).
+#[inline]
+pub fn sparse_equal_with<K, T>(lhs: &[(K, T)], rhs: &[(K, T)], rel_tol: T, zero_tol: T) -> bool
+where
+    K: Ord + Copy,
+    T: Float,
+{
+    sparse_walk(lhs.iter().copied(), rhs.iter().copied(), rel_tol, zero_tol)
+}
+
+#[cfg(feature = "sprs")]
+mod sprs_impl {
+    use super::sparse_walk;
+    use crate::float::{default_rel_tol, default_zero_tol};
+    use crate::Float;
+    use sprs::{CsMatViewI, CsVecViewI, SpIndex};
+
+    /// Returns true if the `sprs` sparse vectors `lhs` and `rhs` are almost
+    /// equal. This requires the `sprs` feature.
+    ///
+    /// ```
+    /// let a = sprs::CsVec::new(4, vec![0, 2], vec![1.0, 0.1 + 0.2]);
+    /// let b = sprs::CsVec::new(4, vec![0, 2, 3], vec![1.0, 0.3, 0.0]);
+    /// assert!(almost::sprs_vec_equal(a.view(), b.view()));
+    /// ```
+    ///
+    /// This has the same semantics as [`almost::sparse_equal`](crate::sparse_equal),
+    /// and additionally returns false if the dimensions differ.
+    #[inline]
+    pub fn sprs_vec_equal<T: Float, I: SpIndex>(
+        lhs: CsVecViewI<'_, T, I>,
+        rhs: CsVecViewI<'_, T, I>,
+    ) -> bool {
+        sprs_vec_equal_with(lhs, rhs, default_rel_tol(), default_zero_tol())
+    }
+
+    /// Equivalent to [`almost::sprs_vec_equal`](sprs_vec_equal), but uses the
+    /// provided tolerances, as in
+    /// [`almost::sparse_equal_with`](crate::sparse_equal_with).
+    pub fn sprs_vec_equal_with<T: Float, I: SpIndex>(
+        lhs: CsVecViewI<'_, T, I>,
+        rhs: CsVecViewI<'_, T, I>,
+        rel_tol: T,
+        zero_tol: T,
+    ) -> bool {
+        lhs.dim() == rhs.dim()
+            && sparse_walk(
+                lhs.iter().map(|(i, &v)| (i, v)),
+                rhs.iter().map(|(i, &v)| (i, v)),
+                rel_tol,
+                zero_tol,
+            )
+    }
+
+    /// Returns true if the `sprs` sparse matrices `lhs` and `rhs` are almost
+    /// equal. This requires the `sprs` feature.
+    ///
+    /// ```
+    /// let a = sprs::CsMat::<f64>::eye(3);
+    /// let mut b = sprs::TriMat::new((3, 3));
+    /// for i in 0..3 {
+    ///     b.add_triplet(i, i, 1.0 + 1e-12);
+    /// }
+    /// b.add_triplet(0, 2, 1e-20);
+    /// assert!(almost::sprs_mat_equal(a.view(), b.to_csc::<usize>().view()));
+    /// ```
+    ///
+    /// This has the same semantics as [`almost::sparse_equal`](crate::sparse_equal),
+    /// and additionally returns false if the shapes differ. Matrices with
+    /// different storage orders can be compared, although this requires
+    /// converting one of them.
+    #[inline]
+    pub fn sprs_mat_equal<T: Float, I: SpIndex, Iptr: SpIndex>(
+        lhs: CsMatViewI<'_, T, I, Iptr>,
+        rhs: CsMatViewI<'_, T, I, Iptr>,
+    ) -> bool {
+        sprs_mat_equal_with(lhs, rhs, default_rel_tol(), default_zero_tol())
+    }
+
+    /// Equivalent to [`almost::sprs_mat_equal`](sprs_mat_equal), but uses the
+    /// provided tolerances, as in
+    /// [`almost::sparse_equal_with`](crate::sparse_equal_with).
+    pub fn sprs_mat_equal_with<T: Float, I: SpIndex, Iptr: SpIndex>(
+        lhs: CsMatViewI<'_, T, I, Iptr>,
+        rhs: CsMatViewI<'_, T, I, Iptr>,
+        rel_tol: T,
+        zero_tol: T,
+    ) -> bool {
+        if lhs.shape() != rhs.shape() {
+            return false;
+        }
+        if lhs.storage() != rhs.storage() {
+            let rhs = rhs.to_other_storage();
+            return sprs_mat_equal_with(lhs, rhs.view(), rel_tol, zero_tol);
+        }
+        lhs.outer_iterator()
+            .zip(rhs.outer_iterator())
+            .all(|(l, r)| sprs_vec_equal_with(l, r, rel_tol, zero_tol))
+    }
+}
+
+#[cfg(feature = "sprs")]
+pub use sprs_impl::*;
+
+#[cfg(feature = "nalgebra-sparse")]
+mod nalgebra_sparse_impl {
+    use super::sparse_walk;
+    use crate::float::{default_rel_tol, default_zero_tol};
+    use crate::Float;
+    use nalgebra_sparse::{CscMatrix, CsrMatrix};
+
+    /// Returns true if the `nalgebra-sparse` CSR matrices `lhs` and `rhs` are
+    /// almost equal. This requires the `nalgebra-sparse` feature.
+    ///
+    /// ```
+    /// use nalgebra_sparse::{CooMatrix, CsrMatrix};
+    /// let a = CsrMatrix::<f64>::identity(3);
+    /// let mut b = CooMatrix::new(3, 3);
+    /// for i in 0..3 {
+    ///     b.push(i, i, 1.0 + 1e-12);
+    /// }
+    /// b.push(0, 2, 1e-20);
+    /// assert!(almost::csr_equal(&a, &CsrMatrix::from(&b)));
+    /// ```
+    ///
+    /// This has the same semantics as [`almost::sparse_equal`](crate::sparse_equal),
+    /// and additionally returns false if the shapes differ.
+    #[inline]
+    pub fn csr_equal<T: Float>(lhs: &CsrMatrix<T>, rhs: &CsrMatrix<T>) -> bool {
+        csr_equal_with(lhs, rhs, default_rel_tol(), default_zero_tol())
+    }
+
+    /// Equivalent to [`almost::csr_equal`](csr_equal), but uses the provided
+    /// tolerances, as in [`almost::sparse_equal_with`](crate::sparse_equal_with).
+    pub fn csr_equal_with<T: Float>(
+        lhs: &CsrMatrix<T>,
+        rhs: &CsrMatrix<T>,
+        rel_tol: T,
+        zero_tol: T,
+    ) -> bool {
+        (lhs.nrows(), lhs.ncols()) == (rhs.nrows(), rhs.ncols())
+            && sparse_walk(
+                lhs.triplet_iter().map(|(r, c, &v)| ((r, c), v)),
+                rhs.triplet_iter().map(|(r, c, &v)| ((r, c), v)),
+                rel_tol,
+                zero_tol,
+            )
+    }
+
+    /// Returns true if the `nalgebra-sparse` CSC matrices `lhs` and `rhs` are
+    /// almost equal. This requires the `nalgebra-sparse` feature.
+    ///
+    /// ```
+    /// use nalgebra_sparse::CscMatrix;
+    /// let a = CscMatrix::try_from_csc_data(2, 2, vec![0, 1, 2], vec![0, 1], vec![1.0f32, 0.3])
+    ///     .unwrap();
+    /// let b = CscMatrix::try_from_csc_data(2, 2, vec![0, 1, 1], vec![0], vec![1.0f32]).unwrap();
+    /// assert!(!almost::csc_equal(&a, &b));
+    /// assert!(almost::csc_equal_with(&a, &b, 1e-3, 0.5));
+    /// ```
+    ///
+    /// This has the same semantics as [`almost::sparse_equal`](crate::sparse_equal),
+    /// and additionally returns false if the shapes differ.
+    #[inline]
+    pub fn csc_equal<T: Float>(lhs: &CscMatrix<T>, rhs: &CscMatrix<T>) -> bool {
+        csc_equal_with(lhs, rhs, default_rel_tol(), default_zero_tol())
+    }
+
+    /// Equivalent to [`almost::csc_equal`](csc_equal), but uses the provided
+    /// tolerances, as in [`almost::sparse_equal_with`](crate::sparse_equal_with).
+    pub fn csc_equal_with<T: Float>(
+        lhs: &CscMatrix<T>,
+        rhs: &CscMatrix<T>,
+        rel_tol: T,
+        zero_tol: T,
+    ) -> bool {
+        // The triplets are in column-major order.
+        (lhs.nrows(), lhs.ncols()) == (rhs.nrows(), rhs.ncols())
+            && sparse_walk(
+                lhs.triplet_iter().map(|(r, c, &v)| ((c, r), v)),
+                rhs.triplet_iter().map(|(r, c, &v)| ((c, r), v)),
+                rel_tol,
+                zero_tol,
+            )
+    }
+}
+
+#[cfg(feature = "nalgebra-sparse")]
+pub use nalgebra_sparse_impl::*;
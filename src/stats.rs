@@ -0,0 +1,111 @@
+//! Comparison of sample means, with a tolerance derived from the samples'
+//! variance. This requires the `std` or `libm` feature.
+
+use crate::Float;
+
+/// Summary statistics of a sample, such as the results of a Monte Carlo run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleStats<T> {
+    /// The sample mean.
+    pub mean: T,
+    /// The unbiased sample variance, with `count - 1` in the denominator.
+    pub variance: T,
+    /// The number of values in the sample.
+    pub count: usize,
+}
+
+impl<T: Float> SampleStats<T> {
+    /// Computes the mean and variance of `values`, using Welford's algorithm.
+    ///
+    /// ```
+    /// let s = almost::SampleStats::from_samples(&[1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!((s.mean, s.variance, s.count), (2.5, 5.0 / 3.0, 4));
+    /// ```
+    ///
+    /// The variance of a sample with fewer than two values is NaN.
+    pub fn from_samples(values: &[T]) -> Self {
+        let mut mean = T::default();
+        let mut m2 = T::default();
+        for (i, &x) in values.iter().enumerate() {
+            let delta = x - mean;
+            mean = mean + delta / T::from_f64((i + 1) as f64);
+            m2 = m2 + delta * (x - mean);
+        }
+        let variance = if values.len() < 2 {
+            T::from_f64(f64::NAN)
+        } else {
+            m2 / T::from_f64((values.len() - 1) as f64)
+        };
+        SampleStats {
+            mean,
+            variance,
+            count: values.len(),
+        }
+    }
+}
+
+/// The default number of standard errors allowed by
+/// [`almost::means_equal`](means_equal).
+pub const DEFAULT_STANDARD_ERRORS: f64 = 3.0;
+
+/// Returns true if the means of two samples differ by no more than
+/// [`DEFAULT_STANDARD_ERRORS`] times the standard error of their difference.
+///
+/// ```
+/// use almost::SampleStats;
+/// let expected = SampleStats { mean: 0.5, variance: 1.0 / 12.0, count: 10_000 };
+/// let run = SampleStats { mean: 0.503, variance: 0.0831, count: 10_000 };
+/// assert!(almost::means_equal(run, expected));
+/// let biased = SampleStats { mean: 0.52, variance: 0.0831, count: 10_000 };
+/// assert!(!almost::means_equal(biased, expected));
+/// ```
+///
+/// This is an equivalence check in the style of a two-sample t-test, for
+/// comparing estimates whose error is statistical rather than from rounding,
+/// where a fixed relative tolerance is either too tight to pass reliably or too
+/// loose to catch anything. The standard error comes from the pooled variance,
+/// so it assumes the samples have similar variances.
+///
+/// As with a t-test, a correct result occasionally fails: with the default of
+/// three standard errors, about 0.3% of the time for large samples. If both
+/// variances are zero, the means must be almost equal, as with
+/// [`almost::equal`](crate::equal). The variance of a sample with only one value
+/// isn't used. If either sample is empty, there are fewer than three values in
+/// total, or a variance which is used is NaN, this returns false.
+#[inline]
+pub fn means_equal<T: Float>(lhs: SampleStats<T>, rhs: SampleStats<T>) -> bool {
+    means_equal_with(lhs, rhs, T::from_f64(DEFAULT_STANDARD_ERRORS))
+}
+
+/// Equivalent to [`almost::means_equal`](means_equal), but allows the provided
+/// number of standard errors.
+///
+/// # Panics
+/// This function panics in debug mode if `standard_errors` isn't positive.
+pub fn means_equal_with<T: Float>(
+    lhs: SampleStats<T>,
+    rhs: SampleStats<T>,
+    standard_errors: T,
+) -> bool {
+    crate::float::check_zero_tol(standard_errors);
+    if lhs.count == 0 || rhs.count == 0 || lhs.count + rhs.count < 3 {
+        return false;
+    }
+    if lhs.variance == T::default() && rhs.variance == T::default() {
+        return lhs.mean.almost_equals(rhs.mean);
+    }
+    let (n1, n2) = (lhs.count as f64, rhs.count as f64);
+    // A single value says nothing about the variance, so its own (which is
+    // usually NaN) is skipped rather than poisoning the result.
+    let sum_sq = |s: SampleStats<T>, n: f64| {
+        if s.count == 1 {
+            0.0
+        } else {
+            (n - 1.0) * s.variance.to_f64()
+        }
+    };
+    let pooled = (sum_sq(lhs, n1) + sum_sq(rhs, n2)) / (n1 + n2 - 2.0);
+    let standard_error = crate::math::sqrt(pooled * (1.0 / n1 + 1.0 / n2));
+    let diff = crate::imp::f64::abs(lhs.mean.to_f64() - rhs.mean.to_f64());
+    diff <= standard_errors.to_f64() * standard_error
+}
@@ -0,0 +1,275 @@
+//! Comparison of numeric tables in text form, such as CSV files. This requires
+//! the `std` feature.
+
+use crate::report::{ElementwiseReport, ReportBuilder};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// One of the two tables in a comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableSide {
+    /// The left hand side.
+    Lhs,
+    /// The right hand side.
+    Rhs,
+}
+
+/// The reason two tables couldn't be compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableError {
+    /// A cell (other than in the header) wasn't a number.
+    InvalidNumber {
+        /// The table containing the cell.
+        side: TableSide,
+        /// The line of the cell, counting from 1.
+        line: usize,
+        /// The column of the cell, counting from 0.
+        column: usize,
+        /// The contents of the cell.
+        text: String,
+    },
+    /// Only one table had a header, or the headers were different.
+    HeaderMismatch {
+        /// The header of the left hand side, if it had one.
+        lhs: Option<Vec<String>>,
+        /// The header of the right hand side, if it had one.
+        rhs: Option<Vec<String>>,
+    },
+    /// The tables had different numbers of rows.
+    RowCount {
+        /// The number of rows on the left hand side.
+        lhs: usize,
+        /// The number of rows on the right hand side.
+        rhs: usize,
+    },
+    /// A row had a different number of columns in each table.
+    ColumnCount {
+        /// The row, counting from 0 (and not including the header).
+        row: usize,
+        /// The number of columns on the left hand side.
+        lhs: usize,
+        /// The number of columns on the right hand side.
+        rhs: usize,
+    },
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::InvalidNumber {
+                side,
+                line,
+                column,
+                text,
+            } => write!(
+                f,
+                "{:?} line {}, column {}: {:?} is not a number",
+                side, line, column, text
+            ),
+            TableError::HeaderMismatch { lhs, rhs } => {
+                write!(f, "headers differ ({:?} vs {:?})", lhs, rhs)
+            }
+            TableError::RowCount { lhs, rhs } => {
+                write!(
+                    f,
+                    "tables have different numbers of rows ({} vs {})",
+                    lhs, rhs
+                )
+            }
+            TableError::ColumnCount { row, lhs, rhs } => write!(
+                f,
+                "row {} has different numbers of columns ({} vs {})",
+                row, lhs, rhs
+            ),
+        }
+    }
+}
+
+impl core::error::Error for TableError {}
+
+/// The result of [`almost::compare_tables`](compare_tables).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableReport {
+    /// The column names, if the tables had a header.
+    pub headers: Option<Vec<String>>,
+    /// Statistics about the cells. Indices are `(row, column)`, counting from
+    /// 0, and not including the header, blank lines or comments.
+    pub values: ElementwiseReport<f64, (usize, usize)>,
+}
+
+impl TableReport {
+    /// Returns true if every cell was almost equal.
+    #[inline]
+    pub fn passed(&self) -> bool {
+        self.values.passed()
+    }
+
+    /// The name of a column, from the header.
+    #[inline]
+    pub fn column_name(&self, column: usize) -> Option<&str> {
+        self.headers.as_ref()?.get(column).map(|s| s.as_str())
+    }
+}
+
+impl fmt::Display for TableReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.values)?;
+        let first = self.values.first_mismatch.as_ref();
+        if let Some(name) = first.and_then(|d| self.column_name(d.index.1)) {
+            write!(f, "; first mismatch in column {:?}", name)?;
+        }
+        Ok(())
+    }
+}
+
+/// A parsed table.
+struct Table {
+    header: Option<Vec<String>>,
+    /// Each row, along with its line number.
+    rows: Vec<(usize, Vec<f64>)>,
+}
+
+fn parse_table(text: &str, side: TableSide) -> Result<Table, TableError> {
+    let mut table = Table {
+        header: None,
+        rows: Vec::new(),
+    };
+    let lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'));
+    for (line, l) in lines {
+        let cells: Vec<&str> = if l.contains(',') {
+            l.split(',').map(|c| c.trim()).collect()
+        } else {
+            l.split_whitespace().collect()
+        };
+        let parsed: Result<Vec<f64>, usize> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c.parse().map_err(|_| i))
+            .collect();
+        match parsed {
+            Ok(row) => table.rows.push((line, row)),
+            Err(_) if table.header.is_none() && table.rows.is_empty() => {
+                table.header = Some(cells.iter().map(|c| c.to_string()).collect());
+            }
+            Err(column) => {
+                return Err(TableError::InvalidNumber {
+                    side,
+                    line,
+                    column,
+                    text: cells[column].to_string(),
+                })
+            }
+        }
+    }
+    Ok(table)
+}
+
+/// Parses two numeric tables, and compares them cell by cell.
+///
+/// ```
+/// let golden = "t, pressure, temperature\n0.0, 101.325, 293.15\n0.5, 101.2, 293.4\n";
+/// let output = "t, pressure, temperature\n0.0, 101.325, 293.15\n0.5, 101.3, 293.4\n";
+/// let report = almost::compare_tables(output, golden).unwrap();
+/// assert_eq!(report.values.mismatches, 1);
+/// assert_eq!(report.values.first_mismatch.as_ref().unwrap().index, (1, 1));
+/// assert_eq!(report.column_name(1), Some("pressure"));
+/// ```
+///
+/// Each line is split on commas if it contains any, and on whitespace
+/// otherwise. Blank lines and lines starting with `#` are ignored. If the first
+/// remaining line isn't all numbers, it's treated as a header, and the headers
+/// of the tables must match. Cells are compared as with
+/// [`almost::equal`](crate::equal), so a table of results with exact zeros
+/// (rather than ones which are only almost zero) may need a looser tolerance.
+///
+/// An error is returned if any other cell isn't a number, or if the tables
+/// have different shapes.
+#[inline]
+pub fn compare_tables(lhs: &str, rhs: &str) -> Result<TableReport, TableError> {
+    compare_tables_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::compare_tables`](compare_tables), but uses the
+/// provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than
+/// `f64::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+pub fn compare_tables_with(
+    lhs: &str,
+    rhs: &str,
+    tolerance: f64,
+) -> Result<TableReport, TableError> {
+    let lhs = parse_table(lhs, TableSide::Lhs)?;
+    let rhs = parse_table(rhs, TableSide::Rhs)?;
+    if lhs.header != rhs.header {
+        return Err(TableError::HeaderMismatch {
+            lhs: lhs.header,
+            rhs: rhs.header,
+        });
+    }
+    if lhs.rows.len() != rhs.rows.len() {
+        return Err(TableError::RowCount {
+            lhs: lhs.rows.len(),
+            rhs: rhs.rows.len(),
+        });
+    }
+    let mut builder = ReportBuilder::new();
+    for (row, ((_, l), (_, r))) in lhs.rows.iter().zip(&rhs.rows).enumerate() {
+        if l.len() != r.len() {
+            return Err(TableError::ColumnCount {
+                row,
+                lhs: l.len(),
+                rhs: r.len(),
+            });
+        }
+        for (column, (&a, &b)) in l.iter().zip(r).enumerate() {
+            builder.push(|| (row, column), a, b, tolerance);
+        }
+    }
+    Ok(TableReport {
+        headers: lhs.header,
+        values: builder.finish(),
+    })
+}
+
+/// Reads two files containing numeric tables, and compares them as with
+/// [`almost::compare_tables`](compare_tables). This requires the `std`
+/// feature.
+///
+/// Errors reading either file are returned, as are the errors from
+/// `compare_tables`, with the kind `InvalidData` and a [`TableError`] as the
+/// inner error.
+#[inline]
+pub fn compare_table_files<P: AsRef<Path>, Q: AsRef<Path>>(
+    lhs: P,
+    rhs: Q,
+) -> io::Result<TableReport> {
+    compare_table_files_with(lhs, rhs, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`almost::compare_table_files`](compare_table_files), but uses
+/// the provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than
+/// `f64::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+pub fn compare_table_files_with<P: AsRef<Path>, Q: AsRef<Path>>(
+    lhs: P,
+    rhs: Q,
+    tolerance: f64,
+) -> io::Result<TableReport> {
+    let lhs = std::fs::read_to_string(lhs)?;
+    let rhs = std::fs::read_to_string(rhs)?;
+    compare_tables_with(&lhs, &rhs, tolerance)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
@@ -0,0 +1,179 @@
+//! Comparison of `candle` tensors. This requires the `candle` feature.
+
+use crate::report::{ElementwiseReport, ReportBuilder};
+use candle_core::{DType, Tensor};
+use std::boxed::Box;
+use std::fmt;
+use std::vec::Vec;
+
+/// The reason a tensor comparison failed, as returned by
+/// [`almost::tensor_equal`](tensor_equal).
+#[derive(Debug)]
+pub enum TensorMismatch {
+    /// The tensors have different shapes.
+    Shape {
+        /// The shape of the left hand side.
+        lhs: Vec<usize>,
+        /// The shape of the right hand side.
+        rhs: Vec<usize>,
+    },
+    /// One of the tensors isn't of a floating point dtype with a default
+    /// tolerance.
+    UnsupportedDType(DType),
+    /// Some elements weren't almost equal. Indices in the report are
+    /// multi-dimensional indices into the tensors.
+    Values {
+        /// The relative tolerance which was used.
+        tolerance: f64,
+        /// Statistics about the comparison, with the values converted to
+        /// `f64`. The `worst` element is the worst offender.
+        report: Box<ElementwiseReport<f64, Vec<usize>>>,
+    },
+    /// Reading the values of a tensor failed.
+    Candle(candle_core::Error),
+}
+
+impl fmt::Display for TensorMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TensorMismatch::Shape { lhs, rhs } => {
+                write!(f, "tensors have different shapes ({:?} vs {:?})", lhs, rhs)
+            }
+            TensorMismatch::UnsupportedDType(dtype) => {
+                write!(f, "can't compare tensors of dtype {:?}", dtype)
+            }
+            TensorMismatch::Values { tolerance, report } => {
+                write!(f, "tensors differ (tolerance {}): {}", tolerance, report)
+            }
+            TensorMismatch::Candle(e) => write!(f, "failed to read tensor: {}", e),
+        }
+    }
+}
+
+impl core::error::Error for TensorMismatch {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            TensorMismatch::Candle(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<candle_core::Error> for TensorMismatch {
+    #[inline]
+    fn from(e: candle_core::Error) -> Self {
+        TensorMismatch::Candle(e)
+    }
+}
+
+/// Returns the default relative tolerance for tensors of type `dtype`, or
+/// `None` if it isn't `F16`, `BF16`, `F32` or `F64`.
+///
+/// ```
+/// use candle_core::DType;
+/// assert_eq!(almost::tensor_tolerance(DType::F32), Some(almost::F32_TOLERANCE as f64));
+/// assert!(almost::tensor_tolerance(DType::BF16) > almost::tensor_tolerance(DType::F16));
+/// assert_eq!(almost::tensor_tolerance(DType::U8), None);
+/// ```
+///
+/// For `F32` and `F64`, this is [`F32_TOLERANCE`](crate::F32_TOLERANCE) or
+/// [`F64_TOLERANCE`](crate::F64_TOLERANCE). The half precision types assume
+/// the same fraction of their precision is accurate as those do, so by
+/// default this is `0.03125` for `F16` and about `0.088` for `BF16`, and the
+/// `strict-defaults` and `loose-defaults` features affect them too.
+pub fn tensor_tolerance(dtype: DType) -> Option<f64> {
+    let epsilon = match dtype {
+        DType::F64 => return Some(crate::F64_TOLERANCE),
+        DType::F32 => return Some(crate::F32_TOLERANCE as f64),
+        DType::F16 => 9.765625e-4,
+        DType::BF16 => 7.8125e-3,
+        _ => return None,
+    };
+    // The power of epsilon that the `f32` default is, e.g. 1/2.
+    let fraction =
+        crate::math::log10(crate::F32_TOLERANCE as f64) / crate::math::log10(f32::EPSILON as f64);
+    Some(crate::math::pow(epsilon, fraction))
+}
+
+/// Returns `Ok(())` if the tensors `lhs` and `rhs` have the same shape and
+/// almost equal elements, using the default tolerance for their dtypes.
+///
+/// ```
+/// use candle_core::{DType, Device, Tensor};
+/// let reference = Tensor::new(&[[0.5f32, 1.0], [1.5, 2.0]], &Device::Cpu)?;
+/// // Half precision output is checked with the looser `f16` tolerance.
+/// let output = reference.affine(1.005, 0.0)?.to_dtype(DType::F16)?;
+/// assert!(almost::tensor_equal(&output, &reference).is_ok());
+/// let wrong = reference.affine(1.1, 0.0)?;
+/// let err = almost::tensor_equal(&wrong, &reference).unwrap_err();
+/// assert!(matches!(
+///     err,
+///     almost::TensorMismatch::Values { ref report, .. } if report.mismatches == 4
+/// ));
+/// # Ok::<(), candle_core::Error>(())
+/// ```
+///
+/// If the dtypes differ, such as when checking a half precision model against
+/// an `f32` reference, the looser of their tolerances is used. The elements
+/// are converted to `f64` and compared as by
+/// [`almost::equal_with`](crate::equal_with), so this reads both tensors
+/// back from their devices.
+///
+/// On failure, the returned [`TensorMismatch`] describes the shapes or the
+/// mismatched elements, including the worst offender. This requires the
+/// `candle` feature.
+pub fn tensor_equal(lhs: &Tensor, rhs: &Tensor) -> Result<(), TensorMismatch> {
+    let tolerance =
+        |t: &Tensor| tensor_tolerance(t.dtype()).ok_or(TensorMismatch::UnsupportedDType(t.dtype()));
+    let tolerance = tolerance(lhs)?.max(tolerance(rhs)?);
+    tensor_equal_with(lhs, rhs, tolerance)
+}
+
+/// Equivalent to [`almost::tensor_equal`](tensor_equal), but uses the provided
+/// relative tolerance, regardless of the dtypes.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than
+/// `f64::EPSILON` or greater than 1.0, as with
+/// [`almost::equal_with`](crate::equal_with).
+pub fn tensor_equal_with(lhs: &Tensor, rhs: &Tensor, tolerance: f64) -> Result<(), TensorMismatch> {
+    let tolerance = crate::float::check_rel_tol(tolerance);
+    if lhs.dims() != rhs.dims() {
+        return Err(TensorMismatch::Shape {
+            lhs: lhs.dims().to_vec(),
+            rhs: rhs.dims().to_vec(),
+        });
+    }
+    for t in [lhs, rhs] {
+        if tensor_tolerance(t.dtype()).is_none() {
+            return Err(TensorMismatch::UnsupportedDType(t.dtype()));
+        }
+    }
+    let values = |t: &Tensor| t.flatten_all()?.to_dtype(DType::F64)?.to_vec1::<f64>();
+    let (a, b) = (values(lhs)?, values(rhs)?);
+    let dims = lhs.dims();
+    let mut report = ReportBuilder::new();
+    for (i, (&a, &b)) in a.iter().zip(&b).enumerate() {
+        report.push(|| unravel(i, dims), a, b, tolerance);
+    }
+    let report = report.finish();
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(TensorMismatch::Values {
+            tolerance,
+            report: Box::new(report),
+        })
+    }
+}
+
+/// Converts an index into the flattened (row-major) tensor into a
+/// multi-dimensional index.
+fn unravel(mut flat: usize, dims: &[usize]) -> Vec<usize> {
+    let mut index = std::vec![0; dims.len()];
+    for (i, &d) in dims.iter().enumerate().rev() {
+        index[i] = flat % d;
+        flat /= d;
+    }
+    index
+}
@@ -0,0 +1,326 @@
+//! Values on either side of the tolerance boundary, and at a controlled
+//! distance from each other, for testing code built on top of this crate.
+//!
+//! ```
+//! use almost::testutil::{anchors, just_equal, just_unequal};
+//!
+//! // A wrapper which should behave exactly like `almost::equal`.
+//! fn my_equal(a: f64, b: f64) -> bool {
+//!     almost::equal(a, b)
+//! }
+//!
+//! for &x in anchors::<f64>().iter() {
+//!     if let Some(y) = just_equal(x) {
+//!         assert!(my_equal(x, y), "{} {}", x, y);
+//!     }
+//!     if let Some(y) = just_unequal(x) {
+//!         assert!(!my_equal(x, y), "{} {}", x, y);
+//!     }
+//! }
+//! ```
+//!
+//! [`just_equal`] and [`just_unequal`] are adjacent values: the last value
+//! above `x` which is almost equal to it, and the one right after it. A wrapper
+//! which rescales its inputs, changes the tolerance, or uses a different
+//! formula will get at least one of these wrong for some anchor.
+
+use crate::Float;
+use core::marker::PhantomData;
+
+/// Values to use as the anchor for [`just_equal`] and [`just_unequal`]. These
+/// include both zeros, subnormals, several binades of normal values of both
+/// signs, the extremes, infinities, and NaN.
+pub fn anchors<T: Float>() -> [T; 19] {
+    let one = T::from_f64(1.0);
+    let max = T::from_ordered(T::from_f64(f64::INFINITY).to_ordered() - 1);
+    [
+        T::from_f64(0.0),
+        T::from_f64(-0.0),
+        T::from_ordered(1),
+        T::from_ordered(T::MIN_POSITIVE.to_ordered() - 1),
+        T::MIN_POSITIVE,
+        T::from_f64(1e-7),
+        T::from_f64(0.1),
+        T::from_ordered(one.to_ordered() - 1),
+        one,
+        -one,
+        T::from_f64(1.5),
+        T::from_f64(-0.75),
+        T::from_f64(12345.678),
+        T::from_f64(1e30),
+        max,
+        -max,
+        T::from_f64(f64::INFINITY),
+        T::from_f64(f64::NEG_INFINITY),
+        T::from_f64(f64::NAN),
+    ]
+}
+
+/// The ordered representation of the largest value above `x` which is almost
+/// equal to it, or `None` if `x` isn't finite.
+fn last_equal<T: Float>(x: T, tolerance: T) -> Option<i64> {
+    if !x.is_finite() {
+        return None;
+    }
+    // Equality only gets harder to satisfy moving away from `x`, so the values
+    // above it which are equal form a range, and we can bisect for the end.
+    let mut lo = x.to_ordered();
+    let mut hi = T::from_f64(f64::INFINITY).to_ordered() + 1;
+    while hi.abs_diff(lo) > 1 {
+        let mid = lo + (hi.abs_diff(lo) / 2) as i64;
+        if x.almost_equals_with(T::from_ordered(mid), tolerance) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Some(lo)
+}
+
+/// Returns the largest value above `x` which is almost equal to it, with the
+/// default tolerance.
+///
+/// ```
+/// let y = almost::testutil::just_equal(1.0f32).unwrap();
+/// assert!(almost::equal(1.0, y));
+/// assert!(y > 1.0);
+/// ```
+///
+/// Returns `None` if `x` is NaN or infinite, or if no value above `x` is
+/// almost equal to it.
+#[inline]
+pub fn just_equal<T: Float>(x: T) -> Option<T> {
+    just_equal_with(x, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`just_equal`], but uses the provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn just_equal_with<T: Float>(x: T, tolerance: T) -> Option<T> {
+    let last = last_equal(x, tolerance)?;
+    if last > x.to_ordered() {
+        Some(T::from_ordered(last))
+    } else {
+        None
+    }
+}
+
+/// Returns the smallest value above `x` which is not almost equal to it, with
+/// the default tolerance. This is the value right after
+/// [`just_equal(x)`](just_equal).
+///
+/// ```
+/// let y = almost::testutil::just_unequal(1.0f32).unwrap();
+/// assert!(!almost::equal(1.0, y));
+/// assert!(y < 1.01);
+/// ```
+///
+/// Returns `None` if `x` is NaN or infinite, or if every value above `x`
+/// (including infinity) is almost equal to it.
+#[inline]
+pub fn just_unequal<T: Float>(x: T) -> Option<T> {
+    just_unequal_with(x, crate::float::default_rel_tol())
+}
+
+/// Equivalent to [`just_unequal`], but uses the provided relative tolerance.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn just_unequal_with<T: Float>(x: T, tolerance: T) -> Option<T> {
+    let last = last_equal(x, tolerance)?;
+    if last < T::from_f64(f64::INFINITY).to_ordered() {
+        Some(T::from_ordered(last + 1))
+    } else {
+        None
+    }
+}
+
+/// Returns `(x, y)`, where `y` is `n` ULPs (representable values) above `x`,
+/// or below it if `n` is negative.
+///
+/// ```
+/// use almost::testutil::pair_at_ulps;
+/// assert_eq!(pair_at_ulps(1.0f32, -1), (1.0, 1.0 - f32::EPSILON / 2.0));
+/// // Steps cross binades, and zero, correctly.
+/// assert_eq!(pair_at_ulps(-f64::from_bits(1), 2), (-f64::from_bits(1), f64::from_bits(1)));
+/// ```
+///
+/// Both zeros are treated as the same value. `y` is NaN if `x` is NaN, or if
+/// it would be past infinity.
+pub fn pair_at_ulps<T: Float>(x: T, n: i64) -> (T, T) {
+    if x.is_nan() {
+        return (x, x);
+    }
+    let y = x
+        .to_ordered()
+        .checked_add(n)
+        .map_or(T::from_f64(f64::NAN), T::from_ordered);
+    (x, y)
+}
+
+/// Returns `(x, y)`, where `y` is as close as possible to having a relative
+/// error of exactly `rel` from `x`, as measured by
+/// [`almost::equal_with`](crate::equal_with).
+///
+/// ```
+/// use almost::testutil::pair_at_relative_error;
+/// let (x, y) = pair_at_relative_error(1.0, 1e-6);
+/// assert!(almost::equal_with(x, y, 1.000001e-6));
+/// assert!(!almost::equal_with(x, y, 0.999999e-6));
+/// ```
+///
+/// `y` has the same sign as `x` and a smaller magnitude, so it can't overflow,
+/// although if `x` is close to `MIN_POSITIVE` it may be subnormal. If `x` is
+/// zero, NaN or infinite it's returned as both values.
+///
+/// # Panics
+/// This function panics in debug mode if `rel` is negative or not less than
+/// 1.0.
+pub fn pair_at_relative_error<T: Float>(x: T, rel: T) -> (T, T) {
+    let zero = T::default();
+    debug_assert!(
+        rel >= zero && rel < T::from_f64(1.0),
+        "Relative error must be in [0.0, 1.0)"
+    );
+    if !x.is_finite() || x == zero {
+        return (x, x);
+    }
+    // This is exact, aside from rounding, which we then fix by walking to the
+    // best neighbor. An ULP step changes the error by at most one ULP of `x`
+    // (relative to it), so this takes only a few steps.
+    let miss = |y: T| (x.rel_err(y) - rel).abs();
+    let mut best = (x * (T::from_f64(1.0) - rel)).to_ordered();
+    for &step in [-1, 1].iter() {
+        while miss(T::from_ordered(best + step)) < miss(T::from_ordered(best)) {
+            best += step;
+        }
+    }
+    (x, T::from_ordered(best))
+}
+
+/// Returns an iterator over every representable value from `a` to `b`
+/// (inclusive), in increasing order.
+///
+/// ```
+/// use almost::testutil::{just_equal, ulp_range};
+/// let edge = just_equal(1.0f32).unwrap();
+/// // Every value in between is also equal, exhaustively.
+/// assert!(ulp_range(1.0, edge).all(|y| almost::equal(1.0, y)));
+/// assert_eq!(ulp_range(1.0, edge).last(), Some(edge));
+///
+/// let v: Vec<f64> = ulp_range(-f64::from_bits(1), f64::from_bits(1)).collect();
+/// assert_eq!(v, [-f64::from_bits(1), 0.0, f64::from_bits(1)]);
+/// ```
+///
+/// This is for exhaustively testing a function in a small neighborhood of an
+/// interesting point, such as a tolerance boundary, or where a function
+/// switches between approximations. Both zeros are treated as the same value,
+/// so only `0.0` is produced. The range is empty if `a` is greater than `b`,
+/// or if either is NaN.
+pub fn ulp_range<T: Float>(a: T, b: T) -> UlpRange<T> {
+    let (next, last) = if a.is_nan() || b.is_nan() {
+        (1, 0)
+    } else {
+        (a.to_ordered(), b.to_ordered())
+    };
+    UlpRange {
+        next,
+        last,
+        step: 1,
+        marker: PhantomData,
+    }
+}
+
+/// An iterator over consecutive representable values, returned by
+/// [`ulp_range`].
+#[derive(Debug, Clone)]
+pub struct UlpRange<T> {
+    /// The ordered representation of the next value from the front.
+    next: i64,
+    /// The ordered representation of the next value from the back. This is
+    /// always a whole number of steps from `next`, and if it's less than
+    /// `next`, the range is empty.
+    last: i64,
+    step: u64,
+    marker: PhantomData<T>,
+}
+
+impl<T: Float> UlpRange<T> {
+    /// Changes the iterator to only produce every `n`th value, starting from
+    /// the next one. The values are then `n` ULPs apart.
+    ///
+    /// ```
+    /// use almost::testutil::ulp_range;
+    /// let v: Vec<f32> = ulp_range(1.0, 2.0).step_ulps(1 << 22).collect();
+    /// assert_eq!(v, [1.0, 1.5, 2.0]);
+    /// ```
+    ///
+    /// Unlike `Iterator::step_by`, the last value from the back (and so the
+    /// end of the range) moves to the last value which is a whole number of
+    /// steps from the front.
+    ///
+    /// # Panics
+    /// Panics if `n` is zero.
+    pub fn step_ulps(mut self, n: u64) -> Self {
+        assert!(n != 0, "step_ulps requires a nonzero step");
+        if self.next <= self.last {
+            let span = self.last.abs_diff(self.next);
+            self.last = self.next + (span - span % n) as i64;
+        }
+        self.step = n;
+        self
+    }
+
+    #[inline]
+    fn remaining(&self) -> u64 {
+        if self.next > self.last {
+            0
+        } else {
+            self.last.abs_diff(self.next) / self.step + 1
+        }
+    }
+}
+
+impl<T: Float> Iterator for UlpRange<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.next > self.last {
+            return None;
+        }
+        let v = T::from_ordered(self.next);
+        match self.next.checked_add_unsigned(self.step) {
+            Some(n) => self.next = n,
+            None => self.last = self.next - 1,
+        }
+        Some(v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.remaining();
+        if n <= usize::MAX as u64 {
+            (n as usize, Some(n as usize))
+        } else {
+            (usize::MAX, None)
+        }
+    }
+}
+
+impl<T: Float> DoubleEndedIterator for UlpRange<T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.next > self.last {
+            return None;
+        }
+        let v = T::from_ordered(self.last);
+        match self.last.checked_sub_unsigned(self.step) {
+            Some(n) => self.last = n,
+            None => self.next = self.last + 1,
+        }
+        Some(v)
+    }
+}
+
+impl<T: Float> core::iter::FusedIterator for UlpRange<T> {}
@@ -0,0 +1,324 @@
+//! The [`Tolerance`] type, for relative tolerances which have been validated
+//! ahead of time.
+
+use crate::Float;
+use core::fmt;
+
+/// A relative tolerance which is known to be valid: finite, no smaller than
+/// the machine epsilon, and less than 1.0.
+///
+/// ```
+/// use almost::Tolerance;
+/// let tol = Tolerance::new(1e-6).unwrap();
+/// assert!(tol.equal(1.0, 1.0 + 1e-7));
+/// assert!(!tol.equal(1.0, 1.0 + 1e-5));
+///
+/// assert!(Tolerance::new(1.5).is_err());
+/// assert!(Tolerance::new(f64::EPSILON / 2.0).is_err());
+/// ```
+///
+/// [`almost::equal_with`](crate::equal_with) checks its tolerance with a
+/// `debug_assert!` on every call. When the tolerance comes from configuration
+/// or is computed at runtime, it's better to validate it once, with a proper
+/// error, and then compare with [`Tolerance::equal`], which never panics.
+///
+/// The `Default` is the default tolerance for the type, e.g.
+/// [`F64_TOLERANCE`](crate::F64_TOLERANCE), or the one set by `ALMOST_REL_TOL`
+/// with the `env-tolerance` feature.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Tolerance<T>(T);
+
+/// The reason a value isn't a valid [`Tolerance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToleranceError {
+    /// The value was NaN or infinite.
+    NotFinite,
+    /// The value was smaller than the machine epsilon (including zero and
+    /// negative values).
+    TooSmall,
+    /// The value was 1.0 or greater, which would make everything with the
+    /// same sign almost equal.
+    TooLarge,
+}
+
+impl fmt::Display for ToleranceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ToleranceError::NotFinite => "tolerance must be finite",
+            ToleranceError::TooSmall => "tolerance must not be smaller than the machine epsilon",
+            ToleranceError::TooLarge => "tolerance must be less than 1.0",
+        })
+    }
+}
+
+impl core::error::Error for ToleranceError {}
+
+/// The reason a string couldn't be parsed as a [`Tolerance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseToleranceError {
+    /// The string wasn't a number, or a number of digits, ulps or percent.
+    InvalidSyntax,
+    /// The string was understood, but isn't a valid tolerance.
+    Invalid(ToleranceError),
+}
+
+impl fmt::Display for ParseToleranceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseToleranceError::InvalidSyntax => f.write_str(
+                "invalid tolerance (expected e.g. `1e-9`, `6digits`, `4ulps` or `0.1%`)",
+            ),
+            ParseToleranceError::Invalid(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl core::error::Error for ParseToleranceError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ParseToleranceError::Invalid(e) => Some(e),
+            ParseToleranceError::InvalidSyntax => None,
+        }
+    }
+}
+
+impl From<ToleranceError> for ParseToleranceError {
+    #[inline]
+    fn from(e: ToleranceError) -> Self {
+        ParseToleranceError::Invalid(e)
+    }
+}
+
+impl<T: Float> Tolerance<T> {
+    /// Validates `tolerance`, returning an error if it's not finite, smaller
+    /// than `T::EPSILON`, or not less than 1.0.
+    pub fn new(tolerance: T) -> Result<Self, ToleranceError> {
+        if !tolerance.is_finite() {
+            Err(ToleranceError::NotFinite)
+        } else if tolerance < T::MACHINE_EPSILON {
+            Err(ToleranceError::TooSmall)
+        } else if tolerance >= T::from_f64(1.0) {
+            Err(ToleranceError::TooLarge)
+        } else {
+            Ok(Tolerance(tolerance))
+        }
+    }
+
+    /// Returns the tolerance which assumes that the given fraction of the
+    /// precision bits are accurate, that is, `T::EPSILON.powf(fraction)`. This
+    /// requires the `std` or `libm` feature.
+    ///
+    /// ```
+    /// use almost::Tolerance;
+    /// let half = Tolerance::<f64>::from_accurate_fraction(0.5).unwrap();
+    /// assert!(almost::equal(half.get(), (f64::EPSILON).sqrt()));
+    /// let third = Tolerance::<f32>::from_accurate_fraction(1.0 / 3.0).unwrap();
+    /// assert!(almost::equal(third.get(), f32::EPSILON.cbrt()));
+    /// ```
+    ///
+    /// This is how the default tolerances are chosen, see
+    /// [`F64_TOLERANCE`](crate::F64_TOLERANCE), and allows the same reasoning
+    /// for any type, or for a fraction only known at runtime.
+    ///
+    /// Returns an error if `fraction` isn't in `(0.0, 1.0]`, as the tolerance
+    /// would be too large or too small.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn from_accurate_fraction(fraction: T) -> Result<Self, ToleranceError> {
+        let eps = T::MACHINE_EPSILON.to_f64();
+        Tolerance::new(T::from_f64(crate::math::pow(eps, fraction.to_f64())))
+    }
+
+    /// Returns the tolerance of the sum of two values which are each within
+    /// their tolerances, where `scales` are (roughly) the values being added.
+    ///
+    /// ```
+    /// use almost::Tolerance;
+    /// let (t1, t2) = (Tolerance::new(1e-6).unwrap(), Tolerance::new(1e-4).unwrap());
+    /// // The larger value dominates.
+    /// let sum = Tolerance::for_sum(t1, t2, (100.0, 1.0)).unwrap();
+    /// assert!(sum.get() < 2e-6);
+    /// // Taking the difference of nearly equal values amplifies the error.
+    /// let difference = Tolerance::for_sum(t1, t1, (1.0, -0.999)).unwrap();
+    /// assert!(difference.get() > 1e-3);
+    /// assert!(Tolerance::for_sum(t1, t1, (1.0, -1.0)).is_err());
+    /// ```
+    ///
+    /// Each operand may be off by its tolerance relative to its own magnitude,
+    /// so the sum may be off by `t1 * |a| + t2 * |b|`, relative to `|a + b|`.
+    /// The scales are signed, so that a difference can be given as the sum
+    /// with `-b`, and so that cancellation is accounted for. They only need to
+    /// be accurate to a few digits, such as the expected values.
+    ///
+    /// As with [`Tolerance::for_product`], this includes the rounding of the
+    /// operation itself. Returns [`ToleranceError::TooLarge`] if the sum
+    /// cancels so much that no relative tolerance is meaningful, and
+    /// [`ToleranceError::NotFinite`] if either scale is NaN or infinite.
+    pub fn for_sum(t1: Self, t2: Self, scales: (T, T)) -> Result<Self, ToleranceError> {
+        let (a, b) = scales;
+        if !(a.is_finite() && b.is_finite()) {
+            return Err(ToleranceError::NotFinite);
+        }
+        // Rescaled, so that neither the sum nor the products can overflow.
+        let scale = crate::linalg::max_magnitude(&[a, b]);
+        let (a, b) = (a / scale, b / scale);
+        let error = t1.0 * a.abs() + t2.0 * b.abs();
+        Self::derived(error / (a + b).abs())
+    }
+
+    /// Returns the tolerance of the product of two values which are each
+    /// within their tolerances.
+    ///
+    /// ```
+    /// use almost::Tolerance;
+    /// let t = Tolerance::new(1e-6).unwrap();
+    /// // About 2e-6, and slightly more.
+    /// let product = Tolerance::for_product(t, t).unwrap();
+    /// assert!(product.get() > 2e-6 && almost::equal_with(product.get(), 2e-6, 1e-5));
+    /// ```
+    ///
+    /// The relative errors of the operands compound, so this is
+    /// `t1 + t2 + t1 * t2`, regardless of the values, plus the rounding of
+    /// the multiplication (half of `T::EPSILON`). Returns
+    /// [`ToleranceError::TooLarge`] if that's not less than 1.
+    pub fn for_product(t1: Self, t2: Self) -> Result<Self, ToleranceError> {
+        Self::derived(t1.0 + t2.0 + t1.0 * t2.0)
+    }
+
+    /// Returns the tolerance of the quotient of two values which are each
+    /// within their tolerances, where `t1` is the tolerance of the numerator,
+    /// and `t2` that of the denominator.
+    ///
+    /// ```
+    /// use almost::Tolerance;
+    /// let (num, den) = (Tolerance::new(1e-3).unwrap(), Tolerance::new(0.1).unwrap());
+    /// // A denominator which is only known to within 10%.
+    /// let quotient = Tolerance::for_quotient(num, den).unwrap();
+    /// assert!(quotient.get() > 0.11);
+    /// assert!(Tolerance::for_quotient(den, num).unwrap().get() < 0.102);
+    /// ```
+    ///
+    /// A denominator which is too small increases the quotient more than one
+    /// which is too large decreases it, so this is `(t1 + t2) / (1 - t2)`, plus
+    /// the rounding of the division. Returns [`ToleranceError::TooLarge`] if
+    /// that's not less than 1.
+    pub fn for_quotient(t1: Self, t2: Self) -> Result<Self, ToleranceError> {
+        Self::derived((t1.0 + t2.0) / (T::from_f64(1.0) - t2.0))
+    }
+
+    /// Validates the tolerance of a derived quantity, adding the rounding error
+    /// of the final operation.
+    fn derived(tolerance: T) -> Result<Self, ToleranceError> {
+        let tolerance = tolerance + T::MACHINE_EPSILON / T::from_f64(2.0);
+        if tolerance.is_nan() || tolerance >= T::from_f64(1.0) {
+            // NaN if a sum is exactly zero.
+            Err(ToleranceError::TooLarge)
+        } else {
+            Tolerance::new(tolerance)
+        }
+    }
+
+    /// The tolerance value.
+    #[inline]
+    pub fn get(self) -> T {
+        self.0
+    }
+
+    /// Returns true if `lhs` and `rhs` are almost equal with this tolerance,
+    /// as with [`almost::equal_with`](crate::equal_with), except that this
+    /// can't panic.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn equal(self, lhs: T, rhs: T) -> bool {
+        // Can't trip the debug assertions, since we've already checked.
+        lhs.almost_equals_with(rhs, self.0)
+    }
+}
+
+impl<T: Float> Default for Tolerance<T> {
+    #[inline]
+    fn default() -> Self {
+        Tolerance(crate::float::default_rel_tol())
+    }
+}
+
+/// Writes the tolerance in exponent notation, such as `1e-6`, which parses
+/// back to the same tolerance. The precision, if any, is passed through.
+impl<T: fmt::LowerExp> fmt::Display for Tolerance<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerExp::fmt(&self.0, f)
+    }
+}
+
+fn parse_tolerance<T: Float + core::str::FromStr>(s: &str) -> Result<T, ParseToleranceError> {
+    let s = s.trim();
+    let unit = |suffix: &str| s.strip_suffix(suffix).map(str::trim_end);
+    let value = if let Some(n) = unit("digits").or_else(|| unit("digit")) {
+        let n: i32 = n.parse().map_err(|_| ParseToleranceError::InvalidSyntax)?;
+        crate::uncertain::parse_scaled("1", n.saturating_neg())
+    } else if let Some(n) = unit("ulps").or_else(|| unit("ulp")) {
+        let n: u32 = n.parse().map_err(|_| ParseToleranceError::InvalidSyntax)?;
+        Some(T::from_f64(n as f64) * T::MACHINE_EPSILON)
+    } else if let Some(p) = unit("%") {
+        // Shifted by two places rather than divided, so it's correctly rounded.
+        let (mantissa, exp) = match p.find(['e', 'E']) {
+            Some(i) => (&p[..i], p[i + 1..].parse::<i32>().ok()),
+            None => (p, Some(0)),
+        };
+        exp.and_then(|e| crate::uncertain::parse_scaled(mantissa, e.saturating_sub(2)))
+    } else {
+        s.parse().ok()
+    };
+    let value = value.ok_or(ParseToleranceError::InvalidSyntax)?;
+    Ok(Tolerance::new(value)?.0)
+}
+
+macro_rules! impl_try_from {
+    ($fp:ident) => {
+        impl core::convert::TryFrom<$fp> for Tolerance<$fp> {
+            type Error = ToleranceError;
+            #[inline]
+            fn try_from(v: $fp) -> Result<Self, ToleranceError> {
+                Tolerance::new(v)
+            }
+        }
+
+        /// Parses a tolerance written as a number (`1e-9`), a number of
+        /// significant digits which should agree (`6digits`, which is
+        /// `1e-6`), a number of multiples of the machine epsilon (`4ulps`),
+        /// or a percentage (`0.1%`).
+        ///
+        /// ```
+        /// use almost::Tolerance;
+        /// let tol: Tolerance<f64> = "6digits".parse().unwrap();
+        /// assert_eq!(tol.get(), 1e-6);
+        /// assert_eq!("0.1%".parse::<Tolerance<f64>>().unwrap().get(), 1e-3);
+        /// assert_eq!("4 ulps".parse::<Tolerance<f32>>().unwrap().get(), 4.0 * f32::EPSILON);
+        /// assert_eq!(tol.to_string(), "1e-6");
+        /// assert!("150%".parse::<Tolerance<f64>>().is_err());
+        /// ```
+        ///
+        /// Whitespace is allowed around the number and before the unit. The
+        /// result is validated as with [`Tolerance::new`]. An ulp of a normal
+        /// value is at most `T::EPSILON` relative to it, so `4ulps` accepts
+        /// every pair of normal values which are within 4 ulps of each other,
+        /// and some which are a little further apart. Subnormal values have
+        /// larger relative ulps, so this doesn't hold for them.
+        impl core::str::FromStr for Tolerance<$fp> {
+            type Err = ParseToleranceError;
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, ParseToleranceError> {
+                parse_tolerance(s).map(Tolerance)
+            }
+        }
+
+        impl From<Tolerance<$fp>> for $fp {
+            #[inline]
+            fn from(t: Tolerance<$fp>) -> $fp {
+                t.0
+            }
+        }
+    };
+}
+
+impl_try_from!(f32);
+impl_try_from!(f64);
@@ -0,0 +1,262 @@
+//! Implementations of [`AlmostEqual`] for `ultraviolet` types, and per-lane
+//! comparisons of its wide types. This requires the `ultraviolet` feature.
+
+use crate::{AlmostEqual, Float};
+use ultraviolet::{
+    f32x4, f32x8, f64x2, f64x4, Bivec2, Bivec2x4, Bivec2x8, Bivec3, Bivec3x4, Bivec3x8, DBivec2,
+    DBivec2x2, DBivec2x4, DBivec3, DBivec3x2, DBivec3x4, DMat2, DMat2x2, DMat2x4, DMat3, DMat3x2,
+    DMat3x4, DMat4, DMat4x2, DMat4x4, DRotor2, DRotor2x2, DRotor2x4, DRotor3, DRotor3x2, DRotor3x4,
+    DVec2, DVec2x2, DVec2x4, DVec3, DVec3x2, DVec3x4, DVec4, DVec4x2, DVec4x4, Mat2, Mat2x4,
+    Mat2x8, Mat3, Mat3x4, Mat3x8, Mat4, Mat4x4, Mat4x8, Rotor2, Rotor2x4, Rotor2x8, Rotor3,
+    Rotor3x4, Rotor3x8, Vec2, Vec2x4, Vec2x8, Vec3, Vec3x4, Vec3x8, Vec4, Vec4x4, Vec4x8,
+};
+
+/// Wide `ultraviolet` types, which hold a value in each of several SIMD lanes,
+/// as compared by [`almost::ultraviolet_equal_lanes`](ultraviolet_equal_lanes).
+///
+/// This is implemented for the wide vector, bivector, matrix and rotor types,
+/// such as `Vec3x4`, `DMat4x2` and `Rotor3x8`.
+pub trait UltravioletWide: AlmostEqual {
+    /// The mask type of the lanes, such as `f32x4`, which has every bit set in
+    /// the lanes which are true, as used by `blend`.
+    type Mask;
+
+    /// Compares each lane of `self` and `rhs`, as in
+    /// [`almost::ultraviolet_equal_lanes`](ultraviolet_equal_lanes).
+    fn equal_lanes(self, rhs: Self, tol: Self::Float) -> Self::Mask;
+
+    /// Checks whether each lane of `self` is almost zero, as in
+    /// [`almost::ultraviolet_zero_lanes`](ultraviolet_zero_lanes).
+    fn zero_lanes(self, tol: Self::Float) -> Self::Mask;
+}
+
+/// Compares each lane of the wide `ultraviolet` values `lhs` and `rhs`, with
+/// the relative tolerance `tol`, as the [`AlmostEqual`] impls for the scalar
+/// types do. This requires the `ultraviolet` feature.
+///
+/// ```
+/// use ultraviolet::{Vec3, Vec3x4};
+/// let a = Vec3x4::from([Vec3::new(1.0, 2.0, 3.0); 4]);
+/// let b = Vec3x4::from([
+///     Vec3::new(1.0, 2.0, 3.0),
+///     Vec3::new(1.0, 2.000001, 3.0),
+///     Vec3::new(1.0, 2.1, 3.0),
+///     Vec3::new(1.0, 2.0, 3.0),
+/// ]);
+/// let lanes = almost::ultraviolet_equal_lanes(a, b, almost::F32_TOLERANCE);
+/// assert_eq!(lanes.move_mask(), 0b1011);
+/// // The mask can be used to pick between values in each lane.
+/// let fixed = Vec3x4::blend(lanes, b, a);
+/// assert!(almost::equal(fixed, a));
+/// ```
+///
+/// The [`AlmostEqual`] impls for the wide types check that every lane is
+/// almost equal. Use this to get the result for each lane instead, as a mask
+/// which can be used with `blend`. Each lane is compared as by
+/// [`almost::frobenius_equal_with`](crate::frobenius_equal_with), relative to
+/// the larger norm of the values in that lane.
+///
+/// # Panics
+/// This function panics in debug mode if `tol` is less than `EPSILON` or
+/// greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn ultraviolet_equal_lanes<W: UltravioletWide>(lhs: W, rhs: W, tol: W::Float) -> W::Mask {
+    lhs.equal_lanes(rhs, tol)
+}
+
+/// Checks whether each lane of the wide `ultraviolet` value `v` is almost
+/// zero, meaning every component in that lane is, with the tolerance `tol`, as
+/// in [`almost::zero_with`](crate::zero_with). This requires the `ultraviolet`
+/// feature.
+///
+/// # Panics
+/// This function panics in debug mode if `tol` is not greater than zero, as
+/// with [`almost::zero_with`](crate::zero_with).
+#[inline]
+pub fn ultraviolet_zero_lanes<W: UltravioletWide>(v: W, tol: W::Float) -> W::Mask {
+    v.zero_lanes(tol)
+}
+
+/// Compares each lane of two wide values, given as the lanes of each of their
+/// components.
+#[inline]
+fn equal_lanes<T: Float, const C: usize, const L: usize>(
+    lhs: [[T; L]; C],
+    rhs: [[T; L]; C],
+    tol: T,
+) -> [bool; L] {
+    let mut eq = [false; L];
+    for (i, e) in eq.iter_mut().enumerate() {
+        let (a, b) = (lhs.map(|c| c[i]), rhs.map(|c| c[i]));
+        *e = crate::frobenius_equal_with(&a, &b, tol);
+    }
+    eq
+}
+
+/// Checks whether each lane of a wide value, given as the lanes of each of its
+/// components, is almost zero.
+#[inline]
+fn zero_lanes<T: Float, const C: usize, const L: usize>(v: [[T; L]; C], tol: T) -> [bool; L] {
+    let mut zero = [false; L];
+    for (i, z) in zero.iter_mut().enumerate() {
+        *z = v.iter().all(|c| c[i].almost_zero_with(tol));
+    }
+    zero
+}
+
+macro_rules! impl_uv {
+    ($ty:ident, $fp:ident, $what:literal, |$v:ident| $to_array:expr) => {
+        #[doc = concat!(
+            $what,
+            " are almost equal if the norm of their difference is almost zero relative to the",
+            " larger of their norms, as with [`almost::frobenius_equal`](crate::frobenius_equal),",
+            " so components which should be zero don't need to be exactly zero. They're almost",
+            " zero if every component is."
+        )]
+        impl AlmostEqual for $ty {
+            type Float = $fp;
+
+            const MACHINE_EPSILON: $fp = $fp::EPSILON;
+
+            const DEFAULT_TOLERANCE: $fp = <$fp as AlmostEqual>::DEFAULT_TOLERANCE;
+
+            #[inline]
+            fn almost_equals(self, rhs: Self) -> bool {
+                self.almost_equals_with(rhs, crate::float::default_rel_tol())
+            }
+
+            #[inline]
+            fn almost_zero(self) -> bool {
+                self.almost_zero_with(crate::float::default_zero_tol())
+            }
+
+            #[inline]
+            fn almost_equals_with(self, rhs: Self, tol: $fp) -> bool {
+                let to_array = |$v: $ty| $to_array;
+                crate::frobenius_equal_with(&to_array(self), &to_array(rhs), tol)
+            }
+
+            #[inline]
+            fn almost_zero_with(self, tol: $fp) -> bool {
+                let to_array = |$v: $ty| $to_array;
+                to_array(self).iter().all(|&v| v.almost_zero_with(tol))
+            }
+        }
+    };
+}
+
+macro_rules! impl_uv_wide {
+    ($ty:ident, $fp:ident, $mask:ident, $lanes:literal, |$v:ident| $to_array:expr) => {
+        impl UltravioletWide for $ty {
+            type Mask = $mask;
+
+            #[inline]
+            fn equal_lanes(self, rhs: Self, tol: $fp) -> $mask {
+                let lanes = |$v: $ty| $to_array.map(<[$fp; $lanes]>::from);
+                let eq = equal_lanes(lanes(self), lanes(rhs), tol);
+                $mask::from(eq.map(|e| if e { <$fp>::from_bits(!0) } else { 0.0 }))
+            }
+
+            #[inline]
+            fn zero_lanes(self, tol: $fp) -> $mask {
+                let lanes = |$v: $ty| $to_array.map(<[$fp; $lanes]>::from);
+                let zero = zero_lanes(lanes(self), tol);
+                $mask::from(zero.map(|z| if z { <$fp>::from_bits(!0) } else { 0.0 }))
+            }
+        }
+
+        /// Wide values are almost equal if every lane is, as with the scalar
+        /// types, and almost zero if every lane is. Use
+        /// [`almost::ultraviolet_equal_lanes`](ultraviolet_equal_lanes) for the
+        /// result in each lane.
+        impl AlmostEqual for $ty {
+            type Float = $fp;
+
+            const MACHINE_EPSILON: $fp = $fp::EPSILON;
+
+            const DEFAULT_TOLERANCE: $fp = <$fp as AlmostEqual>::DEFAULT_TOLERANCE;
+
+            #[inline]
+            fn almost_equals(self, rhs: Self) -> bool {
+                self.almost_equals_with(rhs, crate::float::default_rel_tol())
+            }
+
+            #[inline]
+            fn almost_zero(self) -> bool {
+                self.almost_zero_with(crate::float::default_zero_tol())
+            }
+
+            #[inline]
+            fn almost_equals_with(self, rhs: Self, tol: $fp) -> bool {
+                self.equal_lanes(rhs, tol).all()
+            }
+
+            #[inline]
+            fn almost_zero_with(self, tol: $fp) -> bool {
+                self.zero_lanes(tol).all()
+            }
+        }
+    };
+}
+
+/// Implements everything for a scalar type and its wide versions, which
+/// convert to arrays of their components in the same way.
+macro_rules! impl_uv_family {
+    (
+        $what:literal, |$v:ident| $to_array:expr,
+        $($ty:ident: $fp:ident, [$($wide:ident: $mask:ident, $lanes:literal),*];)*
+    ) => {
+        $(
+            impl_uv!($ty, $fp, $what, |$v| $to_array);
+            $(impl_uv_wide!($wide, $fp, $mask, $lanes, |$v| $to_array);)*
+        )*
+    };
+}
+
+impl_uv_family!(
+    "Vectors", |v| *v.as_array(),
+    Vec2: f32, [Vec2x4: f32x4, 4, Vec2x8: f32x8, 8];
+    Vec3: f32, [Vec3x4: f32x4, 4, Vec3x8: f32x8, 8];
+    Vec4: f32, [Vec4x4: f32x4, 4, Vec4x8: f32x8, 8];
+    DVec2: f64, [DVec2x2: f64x2, 2, DVec2x4: f64x4, 4];
+    DVec3: f64, [DVec3x2: f64x2, 2, DVec3x4: f64x4, 4];
+    DVec4: f64, [DVec4x2: f64x2, 2, DVec4x4: f64x4, 4];
+);
+
+impl_uv_family!(
+    "Matrices", |m| *m.as_array(),
+    Mat2: f32, [Mat2x4: f32x4, 4, Mat2x8: f32x8, 8];
+    Mat3: f32, [Mat3x4: f32x4, 4, Mat3x8: f32x8, 8];
+    Mat4: f32, [Mat4x4: f32x4, 4, Mat4x8: f32x8, 8];
+    DMat2: f64, [DMat2x2: f64x2, 2, DMat2x4: f64x4, 4];
+    DMat3: f64, [DMat3x2: f64x2, 2, DMat3x4: f64x4, 4];
+    DMat4: f64, [DMat4x2: f64x2, 2, DMat4x4: f64x4, 4];
+);
+
+impl_uv_family!(
+    "Bivectors", |b| [b.xy],
+    Bivec2: f32, [Bivec2x4: f32x4, 4, Bivec2x8: f32x8, 8];
+    DBivec2: f64, [DBivec2x2: f64x2, 2, DBivec2x4: f64x4, 4];
+);
+
+impl_uv_family!(
+    "Bivectors", |b| [b.xy, b.xz, b.yz],
+    Bivec3: f32, [Bivec3x4: f32x4, 4, Bivec3x8: f32x8, 8];
+    DBivec3: f64, [DBivec3x2: f64x2, 2, DBivec3x4: f64x4, 4];
+);
+
+impl_uv_family!(
+    "Rotors (compared componentwise, so `r` and `-r` differ even though \
+     they're the same rotation)",
+    |r| [r.s, r.bv.xy],
+    Rotor2: f32, [Rotor2x4: f32x4, 4, Rotor2x8: f32x8, 8];
+    DRotor2: f64, [DRotor2x2: f64x2, 2, DRotor2x4: f64x4, 4];
+);
+
+impl_uv_family!(
+    "Rotors (compared componentwise, so `r` and `-r` differ even though \
+     they're the same rotation)",
+    |r| [r.s, r.bv.xy, r.bv.xz, r.bv.yz],
+    Rotor3: f32, [Rotor3x4: f32x4, 4, Rotor3x8: f32x8, 8];
+    DRotor3: f64, [DRotor3x2: f64x2, 2, DRotor3x4: f64x4, 4];
+);
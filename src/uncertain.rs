@@ -0,0 +1,301 @@
+//! Values with an absolute uncertainty, and their concise notation.
+
+use crate::Float;
+use core::fmt;
+
+/// A value along with an absolute uncertainty, or error bound, such as
+/// `1.2345 ± 0.0012`.
+///
+/// ```
+/// use almost::Uncertain;
+/// let x = Uncertain::new(1.23456, 0.00123);
+/// assert_eq!(x.to_string(), "1.2346(12)");
+/// assert_eq!(format!("{:.1}", x), "1.235(1)");
+/// assert_eq!(format!("{:#}", x), "1.2346 ± 0.0012");
+/// ```
+///
+/// `Display` uses the concise notation from metrology and physics papers,
+/// where the digits in parentheses are the uncertainty in the last digits of
+/// the value, so that logs and failure messages show both at a glance. The
+/// uncertainty is rounded to two significant digits, or as many as the
+/// precision in the format string, and the value is rounded to match. The
+/// alternate form (`{:#}`) writes the uncertainty out in full instead.
+///
+/// Scientific notation is used when the uncertainty is 10 or more, or the
+/// value is smaller than `1e-4`, as in `6.022140760(12)e23`. If either number
+/// isn't finite, or the uncertainty is zero (or extremely small), both are
+/// written with their own `Display` impls, as `value ± uncertainty`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Uncertain<T> {
+    /// The value.
+    pub value: T,
+    /// The absolute uncertainty in the value. This should not be negative.
+    pub uncertainty: T,
+}
+
+impl<T: Float> Uncertain<T> {
+    /// Creates a value with the provided absolute uncertainty. A negative
+    /// uncertainty is replaced by its magnitude.
+    #[inline]
+    pub fn new(value: T, uncertainty: T) -> Self {
+        Uncertain {
+            value,
+            uncertainty: uncertainty.abs(),
+        }
+    }
+
+    /// Creates a value with an uncertainty of `tolerance` relative to its
+    /// magnitude, such as one of the crate's relative tolerances.
+    ///
+    /// ```
+    /// let x = almost::Uncertain::relative(250.0, 1e-3);
+    /// assert_eq!(x.to_string(), "250.00(25)");
+    /// ```
+    #[inline]
+    pub fn relative(value: T, tolerance: T) -> Self {
+        Uncertain::new(value, value * tolerance)
+    }
+}
+
+impl<T: Float + fmt::Display> fmt::Display for Uncertain<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (value, unc) = (self.value.to_f64(), self.uncertainty.abs().to_f64());
+        let digits = f.precision().unwrap_or(2).clamp(1, 15) as i32;
+        if !value.is_finite() || !unc.is_finite() || unc == 0.0 || unc < 1e-300 {
+            return write!(f, "{} ± {}", self.value, self.uncertainty);
+        }
+        // The position of the last digit shown, as a power of ten.
+        let mut last = decade(unc) - (digits - 1);
+        let mut unc_digits = (unc / pow10(last) + 0.5) as u64;
+        // Rounding can carry into another digit, e.g. 0.0996 to 2 digits.
+        if unc_digits >= 10u64.pow(digits as u32) {
+            unc_digits /= 10;
+            last += 1;
+        }
+        let value_decade = if value == 0.0 {
+            last
+        } else {
+            decade(crate::imp::f64::abs(value))
+        };
+        if last <= 0 && value_decade >= -4 {
+            let decimals = -last as usize;
+            if f.alternate() {
+                let unc = unc_digits as f64 * pow10(last);
+                write!(f, "{:.*} ± {:.*}", decimals, value, decimals, unc)
+            } else {
+                write!(f, "{:.*}({})", decimals, value, unc_digits)
+            }
+        } else {
+            // Scientific notation, with the exponent from whichever is larger.
+            let exp = value_decade.max(last + digits - 1);
+            let decimals = (exp - last) as usize;
+            let mantissa = value / pow10(exp);
+            if f.alternate() {
+                let unc = unc_digits as f64 * pow10(last - exp);
+                write!(
+                    f,
+                    "({:.*} ± {:.*})e{}",
+                    decimals, mantissa, decimals, unc, exp
+                )
+            } else {
+                write!(f, "{:.*}({})e{}", decimals, mantissa, unc_digits, exp)
+            }
+        }
+    }
+}
+
+/// The power of ten of the leading digit of `x`, which must be positive and
+/// finite.
+fn decade(mut x: f64) -> i32 {
+    let mut e = 0;
+    while x >= 10.0 {
+        x /= 10.0;
+        e += 1;
+    }
+    while x < 1.0 {
+        x *= 10.0;
+        e -= 1;
+    }
+    e
+}
+
+/// `10^n`, for `n` in the range where this is exact (and beyond, if not).
+#[inline]
+fn pow10(n: i32) -> f64 {
+    let mut p = 1.0;
+    for _ in 0..n.unsigned_abs() {
+        p *= 10.0;
+    }
+    if n < 0 {
+        1.0 / p
+    } else {
+        p
+    }
+}
+
+/// The reason a string couldn't be parsed as an [`Uncertain`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseUncertainError {
+    /// The value wasn't a valid number.
+    InvalidValue,
+    /// The uncertainty wasn't a valid number, or was negative or NaN.
+    InvalidUncertainty,
+    /// There was no uncertainty, in either notation.
+    MissingUncertainty,
+}
+
+impl fmt::Display for ParseUncertainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseUncertainError::InvalidValue => "invalid value",
+            ParseUncertainError::InvalidUncertainty => "invalid uncertainty",
+            ParseUncertainError::MissingUncertainty => {
+                "missing uncertainty (expected e.g. `1.234 ± 0.005` or `1.234(5)`)"
+            }
+        })
+    }
+}
+
+impl core::error::Error for ParseUncertainError {}
+
+impl<T: Float> Uncertain<T> {
+    /// Returns true if `x` is consistent with this value, that is, if it's
+    /// within the uncertainty of it.
+    ///
+    /// ```
+    /// use almost::Uncertain;
+    /// // The fine-structure constant, as quoted in CODATA 2018.
+    /// let expected: Uncertain<f64> = "7.2973525693(11)e-3".parse().unwrap();
+    /// assert!(expected.contains(1.0 / 137.035999084));
+    /// assert!(!expected.contains(1.0 / 137.036));
+    /// ```
+    ///
+    /// This is for checking results against values copied from papers or lab
+    /// reports, with their stated uncertainty as the tolerance. The bound is
+    /// inclusive, and NaN is never contained.
+    #[inline]
+    pub fn contains(&self, x: T) -> bool {
+        (x - self.value).abs() <= self.uncertainty
+    }
+}
+
+/// A small buffer for rewriting numbers before parsing them, or formatting
+/// them in pieces.
+pub(crate) struct Buf {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Buf {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Buf {
+            bytes: [0; 64],
+            len: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn as_str(&self) -> &str {
+        // Only ever written to with whole `str`s.
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl fmt::Write for Buf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Parses `mantissa` scaled by `10^exp`, without losing precision.
+pub(crate) fn parse_scaled<T: core::str::FromStr>(mantissa: &str, exp: i32) -> Option<T> {
+    use core::fmt::Write;
+    let mut buf = Buf::new();
+    write!(buf, "{}e{}", mantissa, exp).ok()?;
+    buf.as_str().parse().ok()
+}
+
+fn parse_uncertain<T: Float + core::str::FromStr>(
+    s: &str,
+) -> Result<Uncertain<T>, ParseUncertainError> {
+    let s = s.trim();
+    let sep = ["±", "+/-", "+-"]
+        .iter()
+        .find_map(|sep| s.find(sep).map(|i| (i, sep.len())));
+    let (value, unc) = if let Some((i, len)) = sep {
+        let value = s[..i].trim().parse().ok();
+        (value, s[i + len..].trim().parse().ok())
+    } else if let Some(open) = s.find('(') {
+        let close = s.find(')').ok_or(ParseUncertainError::InvalidUncertainty)?;
+        let (mantissa, digits) = (&s[..open], s.get(open + 1..close).unwrap_or(""));
+        let exp = match &s[close + 1..] {
+            "" => Some(0),
+            e if e.starts_with(['e', 'E']) => e[1..].parse::<i32>().ok(),
+            _ => None,
+        };
+        let exp = exp.ok_or(ParseUncertainError::InvalidValue)?;
+        let value = parse_scaled(mantissa, exp);
+        // Digits without a point are in units of the value's last digit.
+        let unc = if digits.contains('.') || digits.is_empty() {
+            parse_scaled(digits, exp)
+        } else if digits.bytes().all(|b| b.is_ascii_digit()) {
+            let decimals = mantissa.find('.').map_or(0, |p| mantissa.len() - p - 1);
+            parse_scaled(digits, exp.saturating_sub(decimals as i32))
+        } else {
+            None
+        };
+        (value, unc)
+    } else {
+        return Err(ParseUncertainError::MissingUncertainty);
+    };
+    let value: T = value.ok_or(ParseUncertainError::InvalidValue)?;
+    let unc: T = unc.ok_or(ParseUncertainError::InvalidUncertainty)?;
+    if unc.is_nan() || unc < T::default() {
+        return Err(ParseUncertainError::InvalidUncertainty);
+    }
+    Ok(Uncertain {
+        value,
+        uncertainty: unc,
+    })
+}
+
+macro_rules! impl_from_str {
+    ($fp:ident) => {
+        /// Parses a value with an uncertainty, in either the concise notation
+        /// which `Display` produces, or with an explicit uncertainty.
+        ///
+        /// ```
+        /// use almost::Uncertain;
+        /// let a: Uncertain<f64> = "1.234(5)".parse().unwrap();
+        /// let b: Uncertain<f64> = "1.234 ± 0.005".parse().unwrap();
+        /// let c: Uncertain<f64> = "1.234 +/- 0.005".parse().unwrap();
+        /// assert_eq!((a.value, a.uncertainty), (1.234, 0.005));
+        /// assert_eq!(a, b);
+        /// assert_eq!(b, c);
+        /// ```
+        ///
+        /// In the concise notation, the digits in parentheses are in units of
+        /// the last digit of the value, unless they contain a decimal point
+        /// (as in `9.7(1.0)`). An exponent after the parentheses, as in
+        /// `6.022(12)e23`, applies to both numbers. Each number is parsed
+        /// directly (rather than computed from the others), so the results
+        /// are correctly rounded.
+        impl core::str::FromStr for Uncertain<$fp> {
+            type Err = ParseUncertainError;
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, ParseUncertainError> {
+                parse_uncertain(s)
+            }
+        }
+    };
+}
+
+impl_from_str!(f32);
+impl_from_str!(f64);
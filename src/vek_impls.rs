@@ -0,0 +1,57 @@
+//! Implementations of [`AlmostEqual`] for `vek` types. This requires the `vek`
+//! feature.
+
+use crate::{AlmostEqual, Float};
+use vek::{Mat4, Quaternion, Vec2, Vec3, Vec4};
+
+macro_rules! impl_vek {
+    ($ty:ident, $what:literal, $to_array:expr) => {
+        #[doc = concat!(
+            $what,
+            " are almost equal if the norm of their difference is almost zero relative to the",
+            " larger of their norms, as with [`almost::frobenius_equal`](crate::frobenius_equal),",
+            " so components which should be zero don't need to be exactly zero. They're almost",
+            " zero if every component is."
+        )]
+        impl<T: Float> AlmostEqual for $ty<T> {
+            type Float = T;
+
+            const MACHINE_EPSILON: T = T::MACHINE_EPSILON;
+
+            const DEFAULT_TOLERANCE: T = T::DEFAULT_TOLERANCE;
+
+            #[inline]
+            fn almost_equals(self, rhs: Self) -> bool {
+                self.almost_equals_with(rhs, crate::float::default_rel_tol())
+            }
+
+            #[inline]
+            fn almost_zero(self) -> bool {
+                self.almost_zero_with(crate::float::default_zero_tol())
+            }
+
+            #[inline]
+            fn almost_equals_with(self, rhs: Self, tol: T) -> bool {
+                let to_array = $to_array;
+                crate::frobenius_equal_with(&to_array(self), &to_array(rhs), tol)
+            }
+
+            #[inline]
+            fn almost_zero_with(self, tol: T) -> bool {
+                let to_array = $to_array;
+                to_array(self).iter().all(|&v| v.almost_zero_with(tol))
+            }
+        }
+    };
+}
+
+impl_vek!(Vec2, "Vectors", Vec2::into_array);
+impl_vek!(Vec3, "Vectors", Vec3::into_array);
+impl_vek!(Vec4, "Vectors", Vec4::into_array);
+impl_vek!(Mat4, "Matrices", Mat4::into_col_array);
+impl_vek!(
+    Quaternion,
+    "Quaternions (compared componentwise, so `q` and `-q` differ even though \
+     they're the same rotation)",
+    |q: Quaternion<T>| [q.x, q.y, q.z, q.w]
+);
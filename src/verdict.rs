@@ -0,0 +1,55 @@
+//! Checking whether the result of a comparison is robust.
+
+use crate::Float;
+
+/// The result of [`almost::stable_verdict`](stable_verdict).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Verdict {
+    /// Whether the values were almost equal.
+    pub equal: bool,
+    /// Whether the values would have compared the same way after moving
+    /// either (or both) of them by one ULP in either direction.
+    pub stable: bool,
+}
+
+/// Compares `a` and `b` with the relative tolerance `tolerance`, and checks if
+/// the result would change if either input were rounded differently.
+///
+/// ```
+/// let v = almost::stable_verdict(1.0, 1.1, 0.2);
+/// assert!(v.equal && v.stable);
+///
+/// // Sitting right on the boundary: a slightly different rounding of either
+/// // input (e.g. on a different platform) flips the result.
+/// let b = 1.0 - 0.25;
+/// assert!(!almost::equal_with(1.0f32, b, 0.25));
+/// let v = almost::stable_verdict(1.0f32, b, 0.25);
+/// assert!(!v.equal && !v.stable);
+/// ```
+///
+/// Each input is moved by -1, 0 and +1 ULP, and every combination is compared.
+/// An unstable verdict means the comparison is right on the edge of the
+/// tolerance, which is a common cause of tests which pass on one platform and
+/// fail on another. Either the tolerance or the test inputs should be changed.
+///
+/// Non-finite values are not perturbed.
+///
+/// # Panics
+/// This function panics in debug mode if `tolerance` is less than `T::EPSILON`
+/// or greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+pub fn stable_verdict<T: Float>(a: T, b: T, tolerance: T) -> Verdict {
+    let equal = a.almost_equals_with(b, tolerance);
+    let nudge = |x: T, k: i64| {
+        if x.is_finite() {
+            T::from_ordered(x.to_ordered() + k)
+        } else {
+            x
+        }
+    };
+    let stable = [-1, 0, 1].iter().all(|&i| {
+        [-1, 0, 1]
+            .iter()
+            .all(|&j| nudge(a, i).almost_equals_with(nudge(b, j), tolerance) == equal)
+    });
+    Verdict { equal, stable }
+}
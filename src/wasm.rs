@@ -0,0 +1,132 @@
+//! JavaScript bindings, via `wasm-bindgen`. This requires the `wasm` feature.
+//!
+//! These are for tools which run in the browser (or node), such as test
+//! viewers and visualizations, so that they agree exactly with the Rust code
+//! about which values are almost equal. JavaScript numbers are `f64`, so only
+//! that type is exposed, and the defaults are [`F64_TOLERANCE`](crate::F64_TOLERANCE).
+//!
+//! Invalid tolerances throw an `Error`, rather than hitting the
+//! `debug_assert!`s the Rust functions use.
+//!
+//! To build the module, something like this works, followed by running
+//! `wasm-bindgen` on the output as usual:
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! ```
+
+use crate::{Tolerance, ToleranceError};
+use wasm_bindgen::prelude::*;
+
+/// `almost::equal` for JavaScript numbers.
+#[wasm_bindgen(js_name = equal)]
+pub fn js_equal(lhs: f64, rhs: f64) -> bool {
+    crate::equal(lhs, rhs)
+}
+
+/// `almost::zero` for JavaScript numbers.
+#[wasm_bindgen(js_name = zero)]
+pub fn js_zero(v: f64) -> bool {
+    crate::zero(v)
+}
+
+/// `almost::equal_with` for JavaScript numbers. Throws if `tolerance` isn't a
+/// valid relative tolerance.
+#[wasm_bindgen(js_name = equalWith)]
+pub fn js_equal_with(lhs: f64, rhs: f64, tolerance: f64) -> Result<bool, JsError> {
+    Ok(Tolerance::new(tolerance)?.equal(lhs, rhs))
+}
+
+/// `almost::zero_with` for JavaScript numbers. Throws if `tolerance` isn't
+/// positive.
+#[wasm_bindgen(js_name = zeroWith)]
+pub fn js_zero_with(v: f64, tolerance: f64) -> Result<bool, JsError> {
+    let tolerance = zero_tolerance(tolerance).map_err(JsError::new)?;
+    Ok(crate::zero_with(v, tolerance))
+}
+
+/// Returns true if the `Float64Array`s `lhs` and `rhs` have the same length,
+/// and every pair of elements is almost equal, with `tolerance` or the default.
+/// Throws if `tolerance` isn't a valid relative tolerance.
+#[wasm_bindgen(js_name = equalArrays)]
+pub fn js_equal_arrays(lhs: &[f64], rhs: &[f64], tolerance: Option<f64>) -> Result<bool, JsError> {
+    Ok(lhs.len() == rhs.len() && js_first_mismatch(lhs, rhs, tolerance)?.is_none())
+}
+
+/// Returns the index of the first pair of elements of the `Float64Array`s
+/// `lhs` and `rhs` which aren't almost equal, with `tolerance` or the default,
+/// or `undefined` if there isn't one. If the lengths differ, only the common
+/// prefix is compared. Throws if `tolerance` isn't a valid relative tolerance.
+#[wasm_bindgen(js_name = firstMismatch)]
+pub fn js_first_mismatch(
+    lhs: &[f64],
+    rhs: &[f64],
+    tolerance: Option<f64>,
+) -> Result<Option<usize>, JsError> {
+    let tolerance = rel_tolerance(tolerance)?;
+    let mut pairs = lhs.iter().zip(rhs);
+    Ok(pairs.position(|(&a, &b)| !tolerance.equal(a, b)))
+}
+
+// The checks return Rust errors, which are only converted to `JsError`s at the
+// boundary, since creating one panics outside of wasm.
+
+/// Validates a relative tolerance, or returns the default.
+fn rel_tolerance(tolerance: Option<f64>) -> Result<Tolerance<f64>, ToleranceError> {
+    match tolerance {
+        Some(t) => Tolerance::new(t),
+        None => Ok(Tolerance::default()),
+    }
+}
+
+/// Checks an absolute tolerance, as `zero_with` only does in debug builds.
+fn zero_tolerance(tolerance: f64) -> Result<f64, &'static str> {
+    if tolerance > 0.0 {
+        Ok(tolerance)
+    } else {
+        Err("tolerance must be greater than zero")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rel_tolerance() {
+        assert_eq!(rel_tolerance(None), Ok(Tolerance::default()));
+        assert_eq!(rel_tolerance(Some(1e-3)).map(Tolerance::get), Ok(1e-3));
+        assert_eq!(rel_tolerance(Some(0.0)), Err(ToleranceError::TooSmall));
+        assert_eq!(rel_tolerance(Some(1.0)), Err(ToleranceError::TooLarge));
+        assert_eq!(
+            rel_tolerance(Some(f64::NAN)),
+            Err(ToleranceError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn test_zero_tolerance() {
+        assert_eq!(zero_tolerance(1e-9), Ok(1e-9));
+        assert!(zero_tolerance(0.0).is_err());
+        assert!(zero_tolerance(-1.0).is_err());
+        assert!(zero_tolerance(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_arrays() {
+        assert!(matches!(js_equal_arrays(&[1.0], &[1.0], None), Ok(true)));
+        assert!(matches!(
+            js_equal_arrays(&[1.0], &[1.0, 2.0], None),
+            Ok(false)
+        ));
+        assert!(matches!(js_equal_arrays(&[], &[1.0], Some(0.1)), Ok(false)));
+        let (a, b) = ([1.0, 2.0, 3.0], [1.0, 2.1, 3.5]);
+        assert!(matches!(js_first_mismatch(&a, &b, None), Ok(Some(1))));
+        assert!(matches!(js_first_mismatch(&a, &b, Some(0.1)), Ok(Some(2))));
+        assert!(matches!(
+            js_first_mismatch(&a, &b[..2], Some(0.1)),
+            Ok(None)
+        ));
+        assert!(matches!(js_zero_with(1e-10, 1e-9), Ok(true)));
+    }
+}
@@ -0,0 +1,159 @@
+//! Implementations for `simba`'s SIMD values, as used by `nalgebra`'s SIMD
+//! types. This requires the `simba` feature.
+
+use crate::lanes::{equal_lanes, Lanes};
+use crate::{AlmostEqual, Float};
+use core::ops::{Mul, Sub};
+use simba::simd::{
+    SimdBool, SimdPartialOrd, SimdSigned, SimdValue, WideF32x4, WideF32x8, WideF64x4,
+};
+
+/// A `simba` SIMD value, as [`Lanes`]. This is a wrapper, as a blanket impl
+/// would overlap with the one for `core::simd` vectors.
+#[derive(Clone, Copy)]
+struct Simba<V>(V);
+
+impl<V, T> Lanes for Simba<V>
+where
+    V: SimdPartialOrd<Element = T> + SimdSigned + Sub<Output = V> + Mul<Output = V> + Copy,
+    T: Float,
+    V::SimdBool: SimdValue<Element = bool>,
+{
+    type Float = T;
+
+    type Mask = V::SimdBool;
+
+    const LANES: usize = V::LANES;
+
+    #[inline]
+    fn splat(v: T) -> Self {
+        Simba(V::splat(v))
+    }
+
+    #[inline]
+    fn abs(self) -> Self {
+        Simba(self.0.simd_abs())
+    }
+
+    #[inline]
+    fn max(self, rhs: Self) -> Self {
+        Simba(self.0.simd_max(rhs.0))
+    }
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Simba(self.0 - rhs.0)
+    }
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Simba(self.0 * rhs.0)
+    }
+
+    #[inline]
+    fn lt(self, rhs: Self) -> V::SimdBool {
+        self.0.simd_lt(rhs.0)
+    }
+
+    #[inline]
+    fn extract(self, i: usize) -> T {
+        self.0.extract(i)
+    }
+
+    #[inline]
+    fn all(mask: V::SimdBool) -> bool {
+        mask.all()
+    }
+
+    #[inline]
+    fn test(mask: V::SimdBool, i: usize) -> bool {
+        mask.extract(i)
+    }
+
+    #[inline]
+    fn set(mask: &mut V::SimdBool, i: usize, value: bool) {
+        mask.replace(i, value);
+    }
+}
+
+/// Compares each lane of the `simba` SIMD values `lhs` and `rhs`, with the
+/// relative tolerance `tol`, as in [`almost::equal_with`](crate::equal_with).
+/// This requires the `simba` feature.
+///
+/// ```
+/// use simba::simd::{SimdBool, WideF32x4};
+/// let a = WideF32x4::from([1.0, 2.0, 3.0, 4.0]);
+/// let b = WideF32x4::from([1.0, 2.000001, 3.1, 4.0]);
+/// let lanes = almost::simba_equal_lanes(a, b, almost::F32_TOLERANCE);
+/// assert_eq!(lanes.bitmask(), 0b1011);
+/// ```
+///
+/// The [`AlmostEqual`] impls for `WideF32x4`, `WideF32x8` and `WideF64x4`
+/// check that every lane is almost equal. Use this to get the result for each
+/// lane instead, as a mask which can be used with `select` or `if_else`.
+///
+/// # Panics
+/// This function panics in debug mode if `tol` is less than `EPSILON` or
+/// greater than 1.0, as with [`almost::equal_with`](crate::equal_with).
+#[inline]
+pub fn simba_equal_lanes<V, T>(lhs: V, rhs: V, tol: T) -> V::SimdBool
+where
+    V: SimdPartialOrd<Element = T> + SimdSigned + Sub<Output = V> + Mul<Output = V> + Copy,
+    T: Float,
+    V::SimdBool: SimdValue<Element = bool>,
+{
+    equal_lanes(Simba(lhs), Simba(rhs), tol)
+}
+
+/// Checks whether each lane of the `simba` SIMD value `v` is almost zero,
+/// with the tolerance `tol`, as in [`almost::zero_with`](crate::zero_with).
+/// This requires the `simba` feature.
+///
+/// # Panics
+/// This function panics in debug mode if `tol` is not greater than zero, as
+/// with [`almost::zero_with`](crate::zero_with).
+#[inline]
+pub fn simba_zero_lanes<V, T>(v: V, tol: T) -> V::SimdBool
+where
+    V: SimdPartialOrd<Element = T> + SimdSigned + Copy,
+    T: Float,
+{
+    crate::float::check_zero_tol(tol);
+    v.simd_abs().simd_lt(V::splat(tol))
+}
+
+macro_rules! impl_simba {
+    ($ty:ident, $fp:ident) => {
+        impl AlmostEqual for $ty {
+            type Float = $fp;
+
+            const DEFAULT_TOLERANCE: $fp = <$fp as AlmostEqual>::DEFAULT_TOLERANCE;
+
+            const MACHINE_EPSILON: $fp = $fp::EPSILON;
+
+            #[inline]
+            fn almost_equals(self, rhs: Self) -> bool {
+                self.almost_equals_with(rhs, crate::float::default_rel_tol())
+            }
+
+            #[inline]
+            fn almost_zero(self) -> bool {
+                self.almost_zero_with(crate::float::default_zero_tol())
+            }
+
+            #[inline]
+            fn almost_equals_with(self, rhs: Self, tol: $fp) -> bool {
+                simba_equal_lanes(self, rhs, tol).all()
+            }
+
+            #[inline]
+            fn almost_zero_with(self, tol: $fp) -> bool {
+                simba_zero_lanes(self, tol).all()
+            }
+        }
+    };
+}
+
+impl_simba!(WideF32x4, f32);
+impl_simba!(WideF32x8, f32);
+impl_simba!(WideF64x4, f64);
@@ -0,0 +1,74 @@
+use almost::{
+    aabb_contains, aabb_contains_point, aabb_contains_with, aabb_equal, aabb_equal_with,
+    aabb_intersects, aabb_overlap, Aabb, AlmostEqual,
+};
+
+#[test]
+fn test_aabb_equal() {
+    let a = Aabb::new([0.0, 0.0], [1.0, 2.0]);
+    assert!(aabb_equal(&a, &a));
+    // Zero and tiny coordinates are compared relative to the box as a whole.
+    assert!(aabb_equal(&a, &Aabb::new([-1e-17, 1e-17], [1.0, 2.0])));
+    assert!(!aabb_equal(&a, &Aabb::new([0.0, 0.0], [1.0, 2.001])));
+    assert!(aabb_equal_with(
+        &a,
+        &Aabb::new([0.0, 0.0], [1.0, 2.001]),
+        1e-3
+    ));
+    assert!(almost::equal(a, a));
+    assert!(Aabb::new([0.0f32; 3], [1e-7; 3]).almost_zero());
+    // One ULP apart at a shared face, far from the origin.
+    let far = Aabb::new([1e6, 0.0], [1e6 + 1.0, 1.0]);
+    let next = f64::from_bits((1e6f64).to_bits() + 1);
+    assert!(aabb_equal(&far, &Aabb::new([next, 0.0], [1e6 + 1.0, 1.0])));
+
+    let inf = f64::INFINITY;
+    let b = Aabb::new([-inf, 0.0], [inf, 1.0]);
+    assert!(aabb_equal(&b, &b));
+    assert!(!aabb_equal(&b, &Aabb::new([-inf, 0.0], [1e300, 1.0])));
+    // Infinite coordinates don't make the tolerance infinite.
+    assert!(!aabb_equal(&b, &Aabb::new([-inf, 0.5], [inf, 1.0])));
+    let nan = Aabb::new([f64::NAN, 0.0], [1.0, 1.0]);
+    assert!(!aabb_equal(&nan, &nan));
+}
+
+#[test]
+fn test_aabb_contains() {
+    let outer = Aabb::new([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]);
+    let inner = Aabb::new([0.25, 0.25, 0.25], [0.5, 0.5, 0.5]);
+    assert!(aabb_contains(&outer, &inner));
+    assert!(!aabb_contains(&inner, &outer));
+    assert!(aabb_contains(&outer, &outer));
+    let barely = Aabb::new([-1e-12, 0.0, 0.0], [1.0, 1.0 + 1e-12, 1.0]);
+    assert!(aabb_contains(&outer, &barely));
+    let outside = Aabb::new([-1e-3, 0.0, 0.0], [1.0, 1.0, 1.0]);
+    assert!(!aabb_contains(&outer, &outside));
+    assert!(aabb_contains_with(&outer, &outside, 1e-2));
+
+    assert!(aabb_contains_point(&outer, &[1.0, 0.0, 0.5]));
+    assert!(aabb_contains_point(&outer, &[1.0 + 1e-12, -1e-12, 0.5]));
+    assert!(!aabb_contains_point(&outer, &[1.001, 0.0, 0.5]));
+    assert!(!aabb_contains_point(&outer, &[0.5, f64::NAN, 0.5]));
+    let everything = Aabb::new([f64::NEG_INFINITY; 3], [f64::INFINITY; 3]);
+    assert!(aabb_contains(&everything, &outer));
+    assert!(!aabb_contains(&outer, &everything));
+}
+
+#[test]
+fn test_aabb_intersects_and_overlap() {
+    let a = Aabb::new([0.0, 0.0], [1.0, 1.0]);
+    let touching = Aabb::new([1.0, 0.0], [2.0, 1.0]);
+    let almost_touching = Aabb::new([1.0 + 1e-12, 0.0], [2.0, 1.0]);
+    let overlapping = Aabb::new([1.0 - 1e-12, 0.5], [2.0, 1.0]);
+    let apart = Aabb::new([1.1, 0.0], [2.0, 1.0]);
+    let inside = Aabb::new([0.5, 0.5], [0.6, 0.6]);
+    for b in &[touching, almost_touching, overlapping] {
+        assert!(aabb_intersects(&a, b) && aabb_intersects(b, &a));
+        assert!(!aabb_overlap(&a, b) && !aabb_overlap(b, &a));
+    }
+    assert!(!aabb_intersects(&a, &apart) && !aabb_overlap(&a, &apart));
+    assert!(aabb_intersects(&a, &inside) && aabb_overlap(&a, &inside));
+    // Corners touching diagonally.
+    let corner = Aabb::new([1.0, 1.0], [2.0, 2.0]);
+    assert!(aabb_intersects(&a, &corner) && !aabb_overlap(&a, &corner));
+}
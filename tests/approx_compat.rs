@@ -0,0 +1,83 @@
+#![cfg(feature = "approx-compat")]
+
+use almost::{Almost, Approx};
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+#[test]
+fn test_almost_relative_eq() {
+    assert_eq!(Almost::<f64>::default_max_relative(), almost::F64_TOLERANCE);
+    assert_eq!(Almost::<f32>::default_epsilon(), almost::F32_TOLERANCE);
+    approx::assert_relative_eq!(Almost(0.1 + 0.2), Almost(0.3));
+    approx::assert_relative_eq!(Almost(0.0), Almost(-0.0));
+    approx::assert_relative_eq!(Almost(f32::INFINITY), Almost(f32::INFINITY));
+    approx::assert_relative_ne!(Almost(1.0), Almost(1.001));
+    approx::assert_relative_ne!(Almost(f64::NAN), Almost(f64::NAN));
+    approx::assert_relative_ne!(Almost(f64::INFINITY), Almost(f64::NEG_INFINITY));
+    // The same as `equal_with_zero_tol`.
+    let values = [0.0, 1e-9, -1e-9, 1e-3, 1.0, 1.01, f64::MAX, f64::INFINITY];
+    for &a in &values {
+        for &b in &values {
+            assert_eq!(
+                Almost(a).relative_eq(&Almost(b), 1e-6, 0.05),
+                almost::equal_with_zero_tol(a, b, 0.05, 1e-6)
+            );
+        }
+    }
+    approx::assert_relative_eq!(Almost(1.0), Almost(1.01), max_relative = 0.05);
+    approx::assert_relative_eq!(Almost(1e-7), Almost(-1e-7), epsilon = 1e-6);
+}
+
+#[test]
+fn test_almost_abs_diff_and_ulps() {
+    approx::assert_abs_diff_eq!(Almost(1.0), Almost(1.05), epsilon = 0.1);
+    approx::assert_abs_diff_ne!(Almost(1.0), Almost(1.2), epsilon = 0.1);
+    approx::assert_abs_diff_eq!(Almost(f64::INFINITY), Almost(f64::INFINITY));
+    approx::assert_abs_diff_ne!(Almost(f64::MAX), Almost(f64::INFINITY));
+    approx::assert_abs_diff_ne!(Almost(f32::NAN), Almost(f32::NAN));
+    let next = f64::from_bits(1.0f64.to_bits() + 4);
+    let far = f64::from_bits(1.0f64.to_bits() + 5);
+    assert!(Almost(1.0).ulps_eq(&Almost(next), f64::EPSILON, 4));
+    assert!(!Almost(1.0).ulps_eq(&Almost(far), f64::EPSILON, 4));
+    approx::assert_ulps_eq!(Almost(1.0), Almost(far), max_ulps = 5);
+    approx::assert_ulps_ne!(Almost(f64::NAN), Almost(f64::NAN));
+}
+
+#[test]
+fn test_almost_tolerance_range() {
+    // An `epsilon` of zero only accepts equal values.
+    assert!(Almost(1.0).abs_diff_eq(&Almost(1.0), 0.0));
+    assert!(!Almost(1.0).abs_diff_eq(&Almost(1.0 + f64::EPSILON), 0.0));
+    assert!(!Almost(1.0f32).abs_diff_eq(&Almost(1.5), -1.0));
+    assert!(Almost(0.0).relative_eq(&Almost(-0.0), 0.0, 1e-6));
+    assert!(!Almost(1e-9).relative_eq(&Almost(-1e-9), 0.0, 1e-6));
+    assert!(Almost(1.0).ulps_eq(&Almost(1.0 + f64::EPSILON), 0.0, 1));
+    assert!(!Almost(1.0).ulps_eq(&Almost(1.0 + 2.0 * f64::EPSILON), 0.0, 1));
+    approx::assert_abs_diff_eq!(Almost(2.0), Almost(2.0), epsilon = 0.0);
+
+    // `max_relative` is clamped to at least the machine epsilon.
+    assert!(Almost(1.0).relative_eq(&Almost(1.0), 0.0, 1e-20));
+    assert!(!Almost(1.0).relative_eq(&Almost(1.0 + 1e-15), 0.0, 1e-20));
+    assert!(!Almost(1.0f32).relative_eq(&Almost(1.0 + 4.0 * f32::EPSILON), 0.0, 0.0));
+    assert!(Almost(1.0f32).relative_eq(&Almost(1.0), 0.0, 0.0));
+    approx::assert_relative_ne!(Almost(1.0), Almost(1.0 + 1e-15), max_relative = 1e-20);
+    // And to less than 1.0.
+    assert!(Almost(1.0).relative_eq(&Almost(100.0), 0.0, 2.0));
+    assert!(!Almost(1.0).relative_eq(&Almost(-1.0), 0.0, 2.0));
+}
+
+#[test]
+fn test_approx_adapter() {
+    assert!(almost::equal(Approx(0.1 + 0.2), Approx(0.3)));
+    assert!(!almost::equal(Approx(1.0), Approx(1.01)));
+    assert!(almost::equal_with(Approx(1.0), Approx(1.01), 0.05));
+    assert!(!almost::equal(Approx(f64::NAN), Approx(f64::NAN)));
+    assert!(almost::zero(Approx(-1e-12)));
+    assert!(!almost::zero(Approx(1.0f32)));
+    assert!(almost::zero_with(Approx(0.5f32), 0.5));
+    assert_eq!(
+        almost::AlmostEqual::default_tolerance(&Approx(1.0f32)),
+        almost::F32_TOLERANCE
+    );
+    // Both directions compose.
+    assert!(almost::equal(Approx(Almost(1e-12)), Approx(Almost(0.0))));
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "audit")]
+
+// Everything is in one test, since the records are global.
+#[test]
+fn test_audit() {
+    almost::audit::reset();
+    let line = line!() + 2;
+    for i in 0..10 {
+        almost::equal_with(1.0 + i as f32 * 3e-4, 1.0, 1e-3);
+    }
+    assert!(almost::zero(1e-12f64));
+    assert!(!almost::equal(f64::NAN, 1.0));
+    let summary = almost::audit::summary();
+    assert_eq!(summary.len(), 3);
+    let e = &summary[0];
+    assert_eq!((e.location.file(), e.location.line()), (file!(), line));
+    assert_eq!((e.comparisons, e.failures), (10, 6));
+    let r = e.largest_passing_ratio.unwrap();
+    assert!(r > 0.85 && r < 0.95, "{}", r);
+    let r = e.smallest_failing_ratio.unwrap();
+    assert!(r > 1.15 && r < 1.25, "{}", r);
+    assert_eq!(
+        summary[1].largest_passing_ratio,
+        Some(1e-12 / almost::F64_TOLERANCE)
+    );
+    assert_eq!(summary[1].smallest_failing_ratio, None);
+    assert_eq!(
+        (summary[2].failures, summary[2].largest_passing_ratio),
+        (1, None)
+    );
+    assert!(summary[2].smallest_failing_ratio.unwrap().is_nan());
+    almost::audit::print_summary();
+    almost::audit::reset();
+    assert!(almost::audit::summary().is_empty());
+
+    // Comparisons made by the other functions are recorded where they're
+    // called from, too.
+    let line = line!() + 1;
+    assert!(almost::equal_or_both_tiny(1.0, 1.0, 1e-10));
+    assert!(almost::equal_with_zero_tol(0.0f32, 1e-9, 1e-3, 1e-6));
+    assert!(almost::equal_iter([1.0, 2.0], [1.0, 2.0]));
+    assert!(almost::equal_iter_with([1.0f32], [1.01], 0.1));
+    let lines = almost::audit::summary()
+        .iter()
+        .map(|e| (e.location.file(), e.location.line()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        (line..line + 4).map(|l| (file!(), l)).collect::<Vec<_>>()
+    );
+    almost::audit::reset();
+
+    // And by the impls for aggregates.
+    let line = line!() + 1;
+    assert!(almost::equal(1.0..2.0, 1.0..2.0));
+    assert!(almost::equal(&[1.0][..], &[1.0][..]));
+    assert!(almost::equal([1.0f32], [1.0]));
+    assert!(almost::equal(vec![1.0], vec![1.0]));
+    assert!(almost::zero(std::borrow::Cow::Borrowed(&[0.0f32][..])));
+    let lines = almost::audit::summary()
+        .iter()
+        .map(|e| (e.location.file(), e.location.line()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        (line..line + 5).map(|l| (file!(), l)).collect::<Vec<_>>()
+    );
+    almost::audit::reset();
+
+    // The slice scans check each element with the full comparison, so it's
+    // recorded (inside this crate).
+    assert!(almost::equal_slices(&[1.0; 40], &[1.0; 40]));
+    assert_eq!(almost::all_almost_zero(&[0.0f32; 40]), Ok(()));
+    assert_eq!(almost::any_almost_zero(&[1.0, 0.0, 1.0]), Some(1));
+    let counts = almost::audit::summary()
+        .iter()
+        .map(|e| (e.comparisons, e.failures))
+        .collect::<Vec<_>>();
+    assert_eq!(counts.iter().map(|c| c.0).sum::<u64>(), 82);
+    assert_eq!(counts.iter().map(|c| c.1).sum::<u64>(), 1);
+    almost::audit::reset();
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "bevy_math")]
+
+use almost::TransformTolerance;
+use bevy_math::{DVec3, Quat, Rect, Vec2, Vec3};
+use bevy_transform::components::Transform;
+
+#[test]
+fn test_bevy_vectors() {
+    let a = Vec3::new(1.0, 0.0, -2.0);
+    assert!(almost::equal(a, Vec3::new(1.0 + 1e-7, 1e-9, -2.0)));
+    assert!(!almost::equal(a, Vec3::new(1.0, 1e-2, -2.0)));
+    assert!(almost::equal(
+        Vec2::new(0.1 + 0.2, 3.0),
+        Vec2::new(0.3, 3.0)
+    ));
+    assert!(almost::equal(
+        DVec3::new(1.0, 0.0, 0.0),
+        DVec3::new(1.0, 1e-17, 0.0)
+    ));
+    assert!(!almost::equal(Vec3::NAN, Vec3::NAN));
+    assert!(almost::zero(Vec3::new(1e-9, 0.0, -1e-12)));
+
+    let q = Quat::from_rotation_z(0.1);
+    let r = Quat::from_rotation_z(0.03) * Quat::from_rotation_z(0.07);
+    assert!(almost::equal(q, r));
+    assert!(!almost::equal(q, -q));
+}
+
+#[test]
+fn test_bevy_rect() {
+    let a = Rect::new(0.0, 0.0, 0.1 + 0.2, 100.0);
+    assert!(almost::equal(a, Rect::new(1e-6, 0.0, 0.3, 100.0)));
+    assert!(!almost::equal(a, Rect::new(0.0, 0.0, 0.4, 100.0)));
+    assert!(almost::zero(Rect::new(0.0, 0.0, 1e-9, 1e-9)));
+}
+
+#[test]
+fn test_bevy_transform() {
+    let t = Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(Quat::from_rotation_x(1.0));
+    let u = Transform::from_xyz(1.0, 2.0, 3.0 + 1e-7).with_rotation(-t.rotation);
+    assert!(almost::equal(t, u));
+    assert!(!almost::equal(t, u.with_scale(Vec3::splat(1.1))));
+
+    let tol = TransformTolerance::new(10.0, 1e-3, 1e-3, 1e-4);
+    let near = Transform::from_xyz(1.005, 2.0, 3.0)
+        .with_rotation(Quat::from_rotation_x(1.0005))
+        .with_scale(Vec3::new(1.0, 1.00005, 1.0));
+    assert!(almost::transform_equal(&near, &t, tol));
+    assert!(almost::transform_equal(&u, &t, tol));
+    let moved = near.with_translation(Vec3::new(1.02, 2.0, 3.0));
+    assert!(!almost::transform_equal(&moved, &t, tol));
+    let turned = near.with_rotation(Quat::from_rotation_x(1.002));
+    assert!(!almost::transform_equal(&turned, &t, tol));
+    let scaled = near.with_scale(Vec3::new(1.0, 1.0, 1.001));
+    assert!(!almost::transform_equal(&scaled, &t, tol));
+    let infinite = near.with_scale(Vec3::INFINITY);
+    assert!(!almost::transform_equal(&infinite, &infinite, tol));
+}
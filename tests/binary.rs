@@ -0,0 +1,129 @@
+use almost::{BinaryError, ByteOrder};
+
+fn encode(values: &[f64], order: ByteOrder) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|v| match order {
+            ByteOrder::Little => v.to_le_bytes(),
+            ByteOrder::Big => v.to_be_bytes(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_compare_bytes() {
+    let a: Vec<f64> = (0..1000).map(|i| i as f64 * 0.1).collect();
+    let mut b = a.clone();
+    b[700] = -b[700];
+    for &order in &[ByteOrder::Little, ByteOrder::Big] {
+        let (ea, eb) = (encode(&a, order), encode(&b, order));
+        let r = almost::compare_bytes::<f64>(&ea, &ea, order).unwrap();
+        assert!(r.passed());
+        assert_eq!(r.total, 1000);
+        let r = almost::compare_bytes::<f64>(&ea, &eb, order).unwrap();
+        assert_eq!(r.mismatches, 1);
+        assert_eq!(r.first_mismatch.unwrap().index, 700);
+        assert_eq!(
+            almost::compare_bytes::<f64>(&ea[1..], &eb[1..], order),
+            Err(BinaryError::Misaligned {
+                len: 7999,
+                element_size: 8
+            })
+        );
+        assert_eq!(
+            almost::compare_bytes::<f64>(&ea[8..], &eb, order),
+            Err(BinaryError::LengthMismatch {
+                lhs: 999,
+                rhs: 1000
+            })
+        );
+        // As f32, still aligned, but it's garbage.
+        assert!(!almost::compare_bytes::<f32>(&ea, &eb, order)
+            .unwrap()
+            .passed());
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_compare_files() {
+    use std::io::Write;
+    let dir = std::env::temp_dir().join(format!("almost-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    // Larger than the chunk size, and not a multiple of it.
+    let a: Vec<f64> = (0..10_001).map(|i| (i as f64).sin()).collect();
+    let mut b = a.clone();
+    b[9000] = 2.0;
+    let write = |name: &str, v: &[f64]| {
+        let path = dir.join(name);
+        let mut f = std::fs::File::create(&path).unwrap();
+        f.write_all(&encode(v, ByteOrder::Big)).unwrap();
+        path
+    };
+    let (pa, pb, pc) = (write("a", &a), write("b", &b), write("c", &a[..5000]));
+    let r = almost::compare_files::<f64, _, _>(&pa, &pa, ByteOrder::Big).unwrap();
+    assert!(r.passed());
+    assert_eq!(r.total, 10_001);
+    let r = almost::compare_files::<f64, _, _>(&pa, &pb, ByteOrder::Big).unwrap();
+    assert_eq!(r.mismatches, 1);
+    assert_eq!(r.first_mismatch.unwrap().index, 9000);
+    let e = almost::compare_files::<f64, _, _>(&pa, &pc, ByteOrder::Big).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    assert!(e.to_string().contains("10001 vs 5000"), "{}", e);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_compare_streams() {
+    let a: Vec<f64> = (0..5000).map(|i| i as f64).collect();
+    let mut b = a.clone();
+    b[4000] = -1.0;
+    b[4500] = -1.0;
+    let (ea, eb) = (encode(&a, ByteOrder::Little), encode(&b, ByteOrder::Little));
+    let r = almost::compare_streams::<f64, _, _>(&ea[..], &ea[..], ByteOrder::Little).unwrap();
+    assert!(r.passed());
+    assert_eq!(r.elements, 5000);
+    let r = almost::compare_streams::<f64, _, _>(&ea[..], &eb[..], ByteOrder::Little).unwrap();
+    assert_eq!(r.elements, 4001);
+    assert_eq!(r.first_mismatch_offset(), Some(32000));
+    // A mismatch before the end of the shorter stream is still reported.
+    let r = almost::compare_streams::<f64, _, _>(&ea[..], &eb[..32008], ByteOrder::Little);
+    assert_eq!(r.unwrap().first_mismatch.unwrap().index, 4000);
+    let r = almost::compare_streams::<f64, _, _>(&ea[..], &ea[..8000], ByteOrder::Little);
+    assert_eq!(r.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_cast_floats() {
+    let floats = [1.0f64, 2.0, 3.0, 4.0];
+    let bytes: &[u8] = bytemuck::cast_slice(&floats);
+    assert_eq!(almost::cast_floats::<f64>(bytes), Ok(&floats[..]));
+    assert_eq!(
+        almost::cast_floats::<f64>(&bytes[1..9]),
+        Err(BinaryError::Unaligned { element_align: 8 })
+    );
+    assert_eq!(
+        almost::cast_floats::<f64>(&bytes[..9]),
+        Err(BinaryError::Misaligned {
+            len: 9,
+            element_size: 8
+        })
+    );
+    let r = almost::compare_native_bytes::<f64>(bytes, &bytes[8..]);
+    assert_eq!(r, Err(BinaryError::LengthMismatch { lhs: 4, rhs: 3 }));
+    let r = almost::compare_native_bytes::<f64>(&bytes[..16], &bytes[16..]).unwrap();
+    assert_eq!(r.mismatches, 2);
+}
+
+#[test]
+fn test_report_hint() {
+    let a = encode(&[1.0, 1e-9, 3.0], ByteOrder::Little);
+    let b = encode(&[2.0, 0.0, 3.0], ByteOrder::Little);
+    let report = almost::compare_bytes::<f64>(&a, &b, ByteOrder::Little).unwrap();
+    assert_eq!(report.hint, Some(almost::Hint::ZeroOperand));
+    assert!(report.to_string().contains("use `almost::zero`"));
+    let report = almost::compare_bytes::<f64>(&a, &a, ByteOrder::Little).unwrap();
+    assert_eq!(report.hint, None);
+}
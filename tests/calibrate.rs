@@ -0,0 +1,46 @@
+#![cfg(feature = "alloc")]
+
+use almost::Calibration;
+
+#[test]
+fn test_calibration() {
+    // Summing well-conditioned values loses very little.
+    let data: Vec<f64> = (1..100).map(|i| i as f64).collect();
+    let sum = |x: &[f64]| x.iter().sum::<f64>();
+    let r = Calibration::new().trials(1000).run(&data, sum);
+    assert_eq!((r.trials, r.non_finite), (1000, 0));
+    assert!(r.median_error <= r.percentile_error && r.percentile_error <= r.max_error);
+    assert!(r.recommended_tolerance > r.percentile_error);
+    assert!(r.recommended_tolerance <= 4.0 * r.percentile_error.max(f64::EPSILON));
+    assert!(r.recommended_tolerance < 1e-14);
+
+    // More jitter means more error.
+    let r2 = Calibration::new()
+        .trials(1000)
+        .jitter_ulps(1000)
+        .run(&data, sum);
+    assert!(r2.max_error > r.max_error);
+
+    // Deterministic for a given seed.
+    let c = Calibration::new().trials(100).jitter_ulps(8).seed(5);
+    assert_eq!(c.run(&data, sum), c.run(&data, sum));
+
+    // Zero jitter means no error at all.
+    let r = Calibration::new().jitter_ulps(0).trials(10).run(&data, sum);
+    assert_eq!((r.max_error, r.recommended_tolerance), (0.0, f64::EPSILON));
+}
+
+#[test]
+fn test_calibration_non_finite() {
+    let r = Calibration::new().trials(100).run(&[1.0f32], |x| {
+        if x[0] > 1.0 {
+            f32::INFINITY
+        } else {
+            x[0]
+        }
+    });
+    assert!(r.non_finite > 0 && r.non_finite < 100);
+    let r = Calibration::new().trials(10).run(&[1.0f32], |_| f32::NAN);
+    assert_eq!(r.non_finite, 10);
+    assert!(r.recommended_tolerance.is_nan());
+}
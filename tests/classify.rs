@@ -0,0 +1,79 @@
+use almost::{classify_diff, DiffClass};
+
+#[test]
+fn test_classify_diff() {
+    assert_eq!(classify_diff(0.0, -0.0), DiffClass::Identical);
+    assert_eq!(classify_diff(1.5f32, 1.5), DiffClass::Identical);
+    let tiny = f64::from_bits(1);
+    assert_eq!(classify_diff(tiny, -tiny), DiffClass::WithinUlps(2));
+    assert_eq!(classify_diff(-1e-300, 1e-300), DiffClass::OppositeSign);
+    assert_eq!(classify_diff(-1.0, 1.0), DiffClass::OppositeSign);
+    assert_eq!(classify_diff(1.0, 1.5), DiffClass::BeyondTolerance);
+    assert_eq!(classify_diff(-1.0, -10.0), DiffClass::DifferentMagnitude);
+    assert_eq!(classify_diff(0.0, 1.0), DiffClass::ZeroVsNonzero);
+    assert_eq!(
+        classify_diff(f32::INFINITY, 1.0),
+        DiffClass::InfiniteVsFinite
+    );
+    assert_eq!(
+        classify_diff(f32::MAX, f32::INFINITY),
+        DiffClass::InfiniteVsFinite
+    );
+    assert_eq!(
+        classify_diff(f32::NEG_INFINITY, f32::INFINITY),
+        DiffClass::OppositeInfinities
+    );
+    assert_eq!(classify_diff(f32::NAN, f32::NAN), DiffClass::BothNaN);
+    assert_eq!(classify_diff(1.0, f32::NAN), DiffClass::OneNaN);
+}
+
+#[test]
+fn test_matching_digits() {
+    use almost::matching_digits;
+    assert_eq!(matching_digits(1.0, 1.0), 17);
+    assert_eq!(matching_digits(0.0, -0.0), 17);
+    assert_eq!(matching_digits(1.5f32, 1.5), 9);
+    assert_eq!(matching_digits(f64::INFINITY, f64::INFINITY), 17);
+    assert_eq!(matching_digits(1.0, 1.0 + f64::EPSILON), 15);
+    assert_eq!(matching_digits(1.0f32, 1.0 + f32::EPSILON), 6);
+    assert_eq!(matching_digits(123456.0, 123457.0), 5);
+    assert_eq!(matching_digits(-2.0, -2.1), 1);
+    assert_eq!(matching_digits(0.9999, 1.0001), 3);
+    assert_eq!(matching_digits(1e300, 1.00002e300), 4);
+    assert_eq!(matching_digits(1e-300, 1.0002e-300), 3);
+    // Adjacent subnormals are relative to `MIN_POSITIVE`.
+    assert_eq!(matching_digits(5e-324, 1e-323), 15);
+
+    assert_eq!(matching_digits(1.0, -1.0), 0);
+    assert_eq!(matching_digits(1e-20, 0.0), 0);
+    assert_eq!(matching_digits(1.0, 100.0), 0);
+    assert_eq!(matching_digits(f64::NAN, f64::NAN), 0);
+    assert_eq!(matching_digits(f64::INFINITY, f64::MAX), 0);
+    assert_eq!(matching_digits(f64::INFINITY, -f64::INFINITY), 0);
+}
+
+#[test]
+fn test_closeness() {
+    use almost::{closeness, closeness_with};
+    assert_eq!(closeness(1.0, 1.0), 1.0);
+    assert_eq!(closeness(0.0, -0.0), 1.0);
+    assert_eq!(closeness(f32::INFINITY, f32::INFINITY), 1.0);
+    assert!(closeness(1.0, 1.0 + f64::EPSILON) > 0.99);
+    assert_eq!(closeness(1.0, -1.0), 0.0);
+    assert_eq!(closeness(1e-20, 0.0), 0.0);
+    assert_eq!(closeness(f64::NAN, f64::NAN), 0.0);
+    assert_eq!(closeness(f64::INFINITY, -f64::INFINITY), 0.0);
+
+    // Decreasing, and nonzero exactly when `equal_with` passes.
+    let mut last = 1.0;
+    for i in 1..40 {
+        let b = 1.0 + i as f64 * 0.003;
+        let score = closeness_with(1.0, b, 0.1);
+        assert!(score < last || score == 0.0);
+        assert_eq!(score > 0.0, almost::equal_with(1.0, b, 0.1));
+        last = score;
+    }
+    assert_eq!(last, 0.0);
+    let midway = closeness_with(2.0f32, 2.0 / 0.95, 0.1);
+    assert!((midway - 0.5625).abs() < 1e-3);
+}
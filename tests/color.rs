@@ -0,0 +1,48 @@
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use almost::{color_equal, color_equal_with, ColorEncoding, ColorMetric};
+
+#[test]
+fn test_color_equal() {
+    use ColorEncoding::{Linear, Srgb};
+    use ColorMetric::{DeltaE, LinearRgb};
+    let white = [1.0, 1.0, 1.0];
+    assert!(color_equal(white, white, Srgb, DeltaE));
+    assert!(color_equal(white, [1.0, 1.0, 0.999], Srgb, DeltaE));
+    assert!(!color_equal(white, [1.0, 1.0, 0.9], Srgb, DeltaE));
+    assert!(!color_equal(white, [1.0, 1.0, 0.9], Linear, LinearRgb));
+
+    // Near white, an 8-bit sRGB step is larger than the linear tolerance, but
+    // not noticeable.
+    let a = [254.0 / 255.0; 3];
+    assert!(!color_equal(white, a, Srgb, LinearRgb));
+    assert!(color_equal(white, a, Srgb, DeltaE));
+
+    // The same linear difference is much more visible in the shadows.
+    let (dark, darker) = ([0.01f32; 3], [0.005f32; 3]);
+    assert!(!color_equal(dark, darker, Linear, DeltaE));
+    assert!(color_equal_with(dark, darker, Linear, LinearRgb, 0.01));
+    assert!(color_equal_with(dark, darker, Linear, DeltaE, 5.0));
+
+    // Linear colors out of the [0, 1] range (HDR, or wide gamut).
+    assert!(color_equal(
+        [4.0, -0.01, 0.0],
+        [4.0, -0.01, 0.001],
+        Linear,
+        LinearRgb
+    ));
+    assert!(color_equal(
+        [-0.5, 0.0, 0.0],
+        [-0.5, 0.0, 0.0],
+        Srgb,
+        DeltaE
+    ));
+
+    assert!(!color_equal(
+        [f64::NAN, 0.0, 0.0],
+        [0.0; 3],
+        Linear,
+        LinearRgb
+    ));
+    assert!(!color_equal([f64::NAN, 0.0, 0.0], [0.0; 3], Srgb, DeltaE));
+}
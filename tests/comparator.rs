@@ -0,0 +1,77 @@
+use almost::{Comparator, ComparatorError, NanPolicy, ToleranceError};
+
+#[test]
+fn test_default_comparator() {
+    let cmp = Comparator::<f64>::default();
+    assert_eq!(cmp, Comparator::new());
+    assert_eq!(cmp.tolerance(), almost::F64_TOLERANCE);
+    assert_eq!(cmp.zero_tolerance(), None);
+    assert_eq!(cmp.nan_policy(), NanPolicy::Unequal);
+    for &(a, b) in &[
+        (0.1 + 0.2, 0.3),
+        (1.0, 1.1),
+        (1e-20, 0.0),
+        (f64::NAN, f64::NAN),
+    ] {
+        assert_eq!(cmp.equal(a, b), almost::equal(a, b), "{} {}", a, b);
+        assert_eq!(cmp.zero(a - b), almost::zero(a - b), "{} {}", a, b);
+    }
+    assert_eq!(Comparator::builder().build(), Ok(Comparator::<f32>::new()));
+}
+
+#[test]
+fn test_comparator_settings() {
+    let cmp = Comparator::builder()
+        .tolerance(1e-3f32)
+        .zero_tolerance(1e-6)
+        .build()
+        .unwrap();
+    assert_eq!(cmp.tolerance(), 1e-3);
+    assert_eq!(cmp.zero_tolerance(), Some(1e-6));
+    assert!(cmp.equal(1.0, 1.0005));
+    assert!(!cmp.equal(1.0, 1.002));
+    assert!(cmp.equal(1e-7, -1e-7));
+    assert!(!cmp.equal(1e-7, 1e-5));
+    assert!(cmp.zero(-5e-7));
+    assert!(!cmp.zero(5e-6));
+    assert!(!cmp.zero(f32::NAN));
+    assert!(!cmp.equal(f32::NAN, f32::NAN));
+}
+
+#[test]
+fn test_comparator_nan_policy() {
+    let cmp = Comparator::builder()
+        .nan_policy(NanPolicy::EqualToNan)
+        .build()
+        .unwrap();
+    assert!(cmp.equal(f64::NAN, -f64::NAN));
+    assert!(!cmp.equal(f64::NAN, 0.0));
+    assert!(!cmp.equal(1.0, f64::NAN));
+    assert!(cmp.equal(f64::INFINITY, f64::INFINITY));
+    assert!(!cmp.zero(f64::NAN));
+}
+
+#[test]
+fn test_comparator_errors() {
+    let build = |tol: f64| Comparator::builder().tolerance(tol).build();
+    assert_eq!(build(f64::NAN), Err(ToleranceError::NotFinite.into()));
+    assert_eq!(build(0.0), Err(ToleranceError::TooSmall.into()));
+    assert_eq!(build(1.0), Err(ToleranceError::TooLarge.into()));
+    assert!(build(f64::EPSILON).is_ok());
+    let zero = |tol: f64| Comparator::builder().zero_tolerance(tol).build();
+    for &z in &[0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert_eq!(zero(z), Err(ComparatorError::ZeroTolerance), "{}", z);
+    }
+    assert!(zero(f64::MIN_POSITIVE).is_ok());
+    // The relative tolerance is reported first.
+    let err = Comparator::builder()
+        .tolerance(2.0)
+        .zero_tolerance(-1.0)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "tolerance must be less than 1.0");
+    assert_eq!(
+        ComparatorError::ZeroTolerance.to_string(),
+        "zero tolerance must be positive and finite"
+    );
+}
@@ -0,0 +1,148 @@
+use almost::compat::{all_equal, all_equal_with, AllEqualMismatch, MeanDifference};
+
+#[test]
+fn test_all_equal() {
+    // Examples checked against R 4.3.
+    assert_eq!(all_equal(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]), Ok(()));
+    assert_eq!(
+        all_equal(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0000001]),
+        Err(AllEqualMismatch::MeanDifference {
+            kind: MeanDifference::Relative,
+            value: (3.0000001f64 - 3.0) / 3.0,
+        })
+    );
+    // pi vs 355/113: "Mean relative difference: 8.491368e-08".
+    let pi = core::f64::consts::PI;
+    match all_equal(&[pi], &[355.0 / 113.0]) {
+        Err(e @ AllEqualMismatch::MeanDifference { kind, value }) => {
+            assert_eq!(kind, MeanDifference::Relative);
+            assert!(almost::equal_with(value, 8.491368e-08, 1e-6));
+            assert_eq!(e.to_string(), "Mean relative difference: 8.491368e-08");
+        }
+        r => panic!("{:?}", r),
+    }
+    assert_eq!(all_equal_with(&[pi], &[355.0 / 113.0], 1e-7, None), Ok(()));
+    // The target's magnitude is the scale, so the order matters.
+    assert!(all_equal_with(&[1.0], &[1.1], 0.095, None).is_err());
+    assert!(all_equal_with(&[1.1], &[1.0], 0.095, None).is_ok());
+}
+
+#[test]
+fn test_all_equal_absolute_and_scaled() {
+    // The mean magnitude is below the tolerance, so the difference is absolute.
+    let e = all_equal(&[1e-10], &[2e-10]);
+    assert_eq!(e, Ok(()));
+    let e = all_equal(&[0.0], &[1e-7]).unwrap_err();
+    assert_eq!(
+        e,
+        AllEqualMismatch::MeanDifference {
+            kind: MeanDifference::Absolute,
+            value: 1e-7
+        }
+    );
+    assert_eq!(e.to_string(), "Mean absolute difference: 1e-07");
+    let e = all_equal_with(&[10.0], &[11.0], 0.5, Some(1.0)).unwrap_err();
+    assert_eq!(e.to_string(), "Mean absolute difference: 1");
+    let e = all_equal_with(&[10.0], &[11.0], 0.1, Some(2.0)).unwrap_err();
+    assert_eq!(e.to_string(), "Mean scaled difference: 0.5");
+    assert!(all_equal_with(&[10.0], &[11.0], 0.5, Some(4.0)).is_ok());
+}
+
+#[test]
+fn test_all_equal_message() {
+    // Formatted as R's `format`, checked against R 4.3.
+    let message = |target: f64, current: f64| match all_equal(&[target], &[current]) {
+        Err(e) => e.to_string(),
+        Ok(()) => String::new(),
+    };
+    assert_eq!(message(1.0, 1.5), "Mean relative difference: 0.5");
+    assert_eq!(message(3.0, 4.0), "Mean relative difference: 0.3333333");
+    assert_eq!(
+        message(1.0, 1.0001234),
+        "Mean relative difference: 0.0001234"
+    );
+    assert_eq!(
+        message(1.0, 1.00001234),
+        "Mean relative difference: 1.234e-05"
+    );
+    assert_eq!(
+        message(1.0, 1.2345678e6),
+        "Mean relative difference: 1234567"
+    );
+    assert_eq!(message(1.0, 1e12), "Mean relative difference: 1e+12");
+    assert_eq!(message(1.0, 1e200), "Mean relative difference: 1e+200");
+    assert_eq!(message(1.0, f64::INFINITY), "Mean relative difference: Inf");
+    // The difference is rounded before trailing zeros are dropped.
+    assert_eq!(message(1.0, 2.99999999), "Mean relative difference: 2");
+}
+
+#[test]
+fn test_all_equal_special() {
+    let nan = f64::NAN;
+    let inf = f64::INFINITY;
+    assert_eq!(all_equal(&[1.0, nan], &[1.0, nan]), Ok(()));
+    let e = all_equal(&[1.0, nan], &[nan, 1.0]).unwrap_err();
+    assert_eq!(
+        e,
+        AllEqualMismatch::NaMismatch {
+            target: 1,
+            current: 1
+        }
+    );
+    let e = all_equal(&[1.0, 2.0], &[1.0, nan]).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "'is.NA' value mismatch: 1 in current 0 in target"
+    );
+    assert_eq!(all_equal(&[inf, -inf], &[inf, -inf]), Ok(()));
+    assert!(all_equal(&[inf], &[1.0]).is_err());
+    assert!(all_equal(&[1.0], &[inf]).is_err());
+    let e = all_equal(&[1.0, 2.0], &[1.0]).unwrap_err();
+    assert_eq!(e.to_string(), "Lengths (2, 1) differ");
+    assert_eq!(all_equal::<f32>(&[], &[]), Ok(()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_uniquetol() {
+    use almost::compat::{uniquetol, uniquetol_with};
+    assert!(uniquetol::<f64>(&[]).is_empty());
+    assert_eq!(uniquetol(&[3.0, 1.0, 2.0, 1.0]), [1.0, 2.0, 3.0]);
+    // The scale is the largest magnitude, so small values are merged.
+    assert_eq!(uniquetol(&[1e-7, 2e-7, 1.0]), [1e-7, 1.0]);
+    assert_eq!(uniquetol(&[1e-7, 2e-7]), [1e-7, 2e-7]);
+    // Each representative is the start of a run.
+    assert_eq!(
+        uniquetol_with(&[0.0, 0.6, 1.2, 1.8, 10.0], 0.1),
+        [0.0, 1.2, 10.0]
+    );
+    // f32 has a looser default.
+    assert_eq!(uniquetol(&[1.0f32, 1.00005]), [1.0]);
+    assert_eq!(uniquetol(&[1.0f64, 1.00005]), [1.0, 1.00005]);
+    let inf = f64::INFINITY;
+    let r = uniquetol(&[f64::NAN, inf, 1.0, -inf, inf, f64::NAN, 0.0]);
+    assert_eq!(r[..4], [-inf, 0.0, 1.0, inf]);
+    assert_eq!(r.len(), 6);
+    assert!(r[4].is_nan() && r[5].is_nan());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_ismembertol() {
+    use almost::compat::{ismembertol, ismembertol_with};
+    assert!(ismembertol::<f64>(&[], &[1.0]).is_empty());
+    assert_eq!(ismembertol(&[1.0], &[]), [None]);
+    // The first match in `b` is returned.
+    assert_eq!(ismembertol_with(&[1.0], &[5.0, 1.4, 0.9], 0.1), [Some(1)]);
+    // Both sides contribute to the scale.
+    assert_eq!(
+        ismembertol_with(&[100.0, 1.0], &[1.5], 0.01),
+        [None, Some(0)]
+    );
+    assert_eq!(ismembertol_with(&[1.0], &[1.5], 0.01), [None]);
+    let inf = f64::INFINITY;
+    assert_eq!(
+        ismembertol(&[inf, -inf, f64::NAN, 0.0], &[f64::NAN, inf, 0.0]),
+        [Some(1), None, None, Some(2)]
+    );
+}
@@ -0,0 +1,88 @@
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use std::f64::consts::PI;
+
+fn polar(magnitude: f64, phase: f64) -> (f64, f64) {
+    (magnitude * phase.cos(), magnitude * phase.sin())
+}
+
+#[test]
+fn test_complex_polar_equal() {
+    let deg = 1f64.to_radians();
+    // The magnitude tolerance is relative, and the phase tolerance absolute.
+    for &m in &[1e-200, 1e-3, 1.0, 1e200] {
+        let a = polar(m, 0.3);
+        assert!(almost::complex_polar_equal(
+            a,
+            polar(m * 1.009, 0.3),
+            0.01,
+            deg
+        ));
+        assert!(!almost::complex_polar_equal(
+            a,
+            polar(m * 1.011, 0.3),
+            0.01,
+            deg
+        ));
+        assert!(almost::complex_polar_equal(
+            a,
+            polar(m, 0.3 + 0.99 * deg),
+            0.01,
+            deg
+        ));
+        assert!(!almost::complex_polar_equal(
+            a,
+            polar(m, 0.3 + 1.01 * deg),
+            0.01,
+            deg
+        ));
+    }
+    // Wrapping around ±π.
+    let a = polar(2.0, PI - 0.2 * deg);
+    assert!(almost::complex_polar_equal(
+        a,
+        polar(2.0, -PI + 0.7 * deg),
+        1e-6,
+        deg
+    ));
+    assert!(!almost::complex_polar_equal(
+        a,
+        polar(2.0, -PI + 0.9 * deg),
+        1e-6,
+        deg
+    ));
+    // Any phase is allowed with a tolerance of π.
+    assert!(almost::complex_polar_equal(
+        (1.0, 0.0),
+        (-1.0, 0.0),
+        1e-6,
+        PI
+    ));
+    assert!(almost::complex_polar_equal(
+        [1.0f32, 0.0],
+        [0.0, 1.0],
+        1e-3,
+        10.0
+    ));
+
+    assert!(almost::complex_polar_equal(
+        (0.0, 0.0),
+        (0.0, 0.0),
+        0.01,
+        deg
+    ));
+    assert!(!almost::complex_polar_equal(
+        (0.0, 0.0),
+        (1e-300, 0.0),
+        0.01,
+        deg
+    ));
+    assert!(!almost::complex_polar_equal(
+        (f64::NAN, 0.0),
+        (f64::NAN, 0.0),
+        0.01,
+        deg
+    ));
+    let inf = (f64::INFINITY, 0.0);
+    assert!(!almost::complex_polar_equal(inf, inf, 0.01, deg));
+}
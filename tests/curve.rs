@@ -0,0 +1,96 @@
+#![cfg(feature = "alloc")]
+
+use almost::{curves_equal, curves_equal_with};
+
+#[test]
+fn test_curves_equal() {
+    let f = |x: f64| x * x * x - x;
+    let r = curves_equal(f, |x| x * (x * x - 1.0), -2.0, 2.0);
+    assert!(r.passed(), "{}", r);
+    assert_eq!(r.scale, 6.0);
+    assert!(r.samples <= almost::DEFAULT_CURVE_SAMPLES);
+    // Relative to the scale of the curve, not at each point, so the roots
+    // don't matter.
+    let r = curves_equal(f, |x| f(x) + 1e-12, -2.0, 2.0);
+    assert!(r.passed(), "{}", r);
+    let r = curves_equal(f, |x| f(x) * 1.001, -2.0, 2.0);
+    assert!(!r.passed());
+    assert!(2.0 - r.parameter.abs() < 1e-3, "{}", r);
+    assert!(almost::equal_with(r.deviation, 1e-3 / 1.001, 1e-9), "{}", r);
+    let text = r.to_string();
+    assert!(text.starts_with("largest relative deviation"), "{}", text);
+}
+
+#[test]
+fn test_curves_equal_piecewise() {
+    // A narrow spike between the initial samples, from a piecewise-linear
+    // function with an extra knot.
+    let spike = |x: f64| {
+        let d = (x - 0.3027).abs();
+        if d < 1e-3 {
+            1e-4 * (1.0 - d / 1e-3)
+        } else {
+            0.0
+        }
+    };
+    let r = curves_equal(|x: f64| x.sin(), |x: f64| x.sin() + spike(x), 0.0, 1.0);
+    assert!(!r.passed());
+    assert!((r.parameter - 0.3027).abs() < 1e-3, "{}", r);
+    assert!(r.deviation > 5e-5, "{}", r);
+    // With few enough samples, it's missed.
+    let r = curves_equal_with(
+        |x: f64| x.sin(),
+        |x: f64| x.sin() + spike(x),
+        0.0,
+        1.0,
+        1e-6,
+        40,
+    );
+    assert!(r.passed());
+    assert_eq!(r.samples, 40);
+}
+
+#[test]
+fn test_curves_equal_parametric() {
+    let helix = |t: f32| [t.cos(), t.sin(), t / 10.0];
+    let r = curves_equal(helix, helix, 0.0, 10.0);
+    assert!(r.passed());
+    assert_eq!(r.deviation, 0.0);
+    let tilted = |t: f32| [t.cos(), t.sin(), t / 10.0 + 1e-3 * t];
+    let r = curves_equal(helix, tilted, 0.0, 10.0);
+    assert!(!r.passed());
+    assert!(10.0 - r.parameter < 1e-3, "{}", r);
+}
+
+#[test]
+fn test_curves_equal_special() {
+    let r = curves_equal(|x: f64| x, |x: f64| x, 1.0, 1.0);
+    assert!(r.passed());
+    assert_eq!(r.samples, 1);
+    let r = curves_equal_with(|x: f64| x, |x: f64| x, 0.0, 1.0, 1e-6, 0);
+    assert_eq!(r.samples, 2);
+    let mut calls = 0;
+    let r = curves_equal(
+        |x: f64| {
+            calls += 1;
+            if x > 0.5 {
+                f64::NAN
+            } else {
+                x
+            }
+        },
+        |x: f64| x,
+        0.0,
+        1.0,
+    );
+    assert!(!r.passed() && r.deviation.is_nan());
+    assert!(r.parameter > 0.5);
+    assert!(calls < 40, "{}", calls);
+    assert!(!curves_equal(|x: f64| x, |_| f64::INFINITY, 0.0, 1.0).passed());
+}
+
+#[test]
+#[should_panic]
+fn test_curves_equal_bad_range() {
+    curves_equal(|x: f64| x, |x: f64| x, 1.0, 0.0);
+}
@@ -0,0 +1,140 @@
+#![cfg(feature = "derive")]
+
+use almost::AlmostEqual;
+
+#[derive(Debug, Clone, Copy, AlmostEqual)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, AlmostEqual)]
+struct Body {
+    position: Point,
+    velocity: [f64; 2],
+    #[almost(tolerance = 0.01)]
+    mass: f64,
+    #[almost(skip)]
+    id: u64,
+}
+
+#[derive(Debug, Clone, Copy, AlmostEqual)]
+struct Pair(f32, #[almost(tolerance = 0.5)] f32);
+
+#[derive(Debug, Clone, AlmostEqual)]
+struct Wrapper<T> {
+    value: T,
+}
+
+#[test]
+fn test_named_fields() {
+    let a = Point {
+        x: 0.1 + 0.2,
+        y: 1.0,
+    };
+    let b = Point { x: 0.3, y: 1.0 };
+    assert!(almost::equal(a, b));
+    assert!(!almost::equal(a, Point { y: 1.001, ..b }));
+    assert!(almost::equal_with(a, Point { y: 1.001, ..b }, 0.01));
+    assert!(!almost::equal(a, Point { y: f64::NAN, ..b }));
+    assert!(almost::zero(Point {
+        x: 1e-12,
+        y: -1e-13
+    }));
+    assert!(!almost::zero(a));
+    assert_eq!(Point::DEFAULT_TOLERANCE, almost::F64_TOLERANCE);
+    assert_eq!(Point::MACHINE_EPSILON, f64::EPSILON);
+}
+
+#[test]
+fn test_nested_and_attributes() {
+    let a = Body {
+        position: Point { x: 1.0, y: 2.0 },
+        velocity: [0.5, -0.5],
+        mass: 10.0,
+        id: 1,
+    };
+    // The mass is compared with its own tolerance, and the id not at all.
+    let b = Body {
+        mass: 10.05,
+        id: 2,
+        ..a
+    };
+    assert!(almost::equal(a, b));
+    assert_ne!(a.id, b.id);
+    assert!(almost::equal_with(a, b, 1e-12));
+    assert!(!almost::equal(a, Body { mass: 11.0, ..a }));
+    assert!(!almost::equal(
+        a,
+        Body {
+            velocity: [0.5, -0.51],
+            ..a
+        }
+    ));
+    assert!(!almost::equal(
+        a,
+        Body {
+            position: Point { x: 1.01, y: 2.0 },
+            ..a
+        }
+    ));
+    // The zero tolerance is absolute, so the mass's tolerance isn't used.
+    let zero = Body {
+        position: Point { x: 0.0, y: 0.0 },
+        velocity: [0.0, 1e-12],
+        mass: 0.001,
+        id: 3,
+    };
+    assert!(!almost::zero(zero));
+    assert!(almost::zero_with(zero, 0.01));
+}
+
+#[test]
+fn test_tuple_and_generic() {
+    assert!(almost::equal(Pair(1.0, 1.0), Pair(1.0, 1.4)));
+    assert!(!almost::equal(Pair(1.0, 1.0), Pair(1.1, 1.0)));
+    assert_eq!(Pair::DEFAULT_TOLERANCE, almost::F32_TOLERANCE);
+    assert!(almost::equal(
+        Wrapper { value: 0.1 + 0.2 },
+        Wrapper { value: 0.3 }
+    ));
+    assert!(almost::equal(
+        &Point { x: 1.0, y: 2.0 },
+        &Point { x: 1.0, y: 2.0 }
+    ));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_generic_vec() {
+    assert!(almost::equal(
+        Wrapper {
+            value: vec![1.0f32]
+        },
+        Wrapper { value: vec![1.0] }
+    ));
+    assert!(!almost::equal(
+        Wrapper {
+            value: vec![1.0f32]
+        },
+        Wrapper { value: vec![] }
+    ));
+}
+
+#[derive(Debug, Clone, Copy, AlmostEqual)]
+struct Mixed<T, U> {
+    a: T,
+    b: U,
+    c: T,
+}
+
+#[test]
+fn test_mixed_generics() {
+    let a = Mixed {
+        a: 1.0,
+        b: [2.0, 3.0],
+        c: 4.0,
+    };
+    assert!(almost::equal(a, a));
+    assert!(!almost::equal(a, Mixed { b: [2.0, 3.1], ..a }));
+}
@@ -0,0 +1,88 @@
+use almost::{deterministic_equal, deterministic_equal_with};
+use rand::{prelude::*, rngs::SmallRng};
+
+#[test]
+fn test_deterministic_specials() {
+    assert!(deterministic_equal(0.0, -0.0));
+    assert!(deterministic_equal(1.0, 1.0));
+    assert!(!deterministic_equal(1.0, -1.0));
+    assert!(!deterministic_equal(f64::NAN, f64::NAN));
+    assert!(!deterministic_equal(1.0, f64::NAN));
+    assert!(deterministic_equal(f64::INFINITY, f64::INFINITY));
+    assert!(!deterministic_equal(f64::INFINITY, f64::NEG_INFINITY));
+    assert!(!deterministic_equal(f32::INFINITY, 1.0));
+    assert!(!deterministic_equal(f64::MAX, f64::MIN));
+    // Subnormals are compared relative to `MIN_POSITIVE`.
+    assert!(deterministic_equal(f64::from_bits(1), 0.0));
+    assert!(deterministic_equal(f32::from_bits(1), -f32::from_bits(3)));
+    assert!(!deterministic_equal(f64::MIN_POSITIVE, 0.0));
+    // Widely separated magnitudes.
+    assert!(!deterministic_equal(1e300, 1e-300));
+    assert!(!deterministic_equal(1e300, -1e-300));
+    assert!(!deterministic_equal(
+        f64::MIN_POSITIVE * 4.0,
+        f64::from_bits(1)
+    ));
+    assert_eq!(
+        deterministic_equal_with(f64::MAX, f64::INFINITY, 0.75),
+        almost::equal_with(f64::MAX, f64::INFINITY, 0.75)
+    );
+    // Exactly on the boundary.
+    let b = 1.0 - 2.0f64.powi(-20);
+    let tol = 2.0f64.powi(-20);
+    assert!(!deterministic_equal_with(1.0, b, tol));
+    assert!(deterministic_equal_with(1.0, b, tol * (1.0 + f64::EPSILON)));
+}
+
+fn random_value(rng: &mut SmallRng) -> f64 {
+    loop {
+        let v = f64::from_bits(rng.gen());
+        if !v.is_nan() {
+            return v;
+        }
+    }
+}
+
+#[test]
+fn test_deterministic_matches_float() {
+    let mut rng = SmallRng::seed_from_u64(0x1234_5678);
+    let tolerances = [almost::F64_TOLERANCE, 1e-3, f64::EPSILON * 2.0, 0.5];
+    for _ in 0..100_000 {
+        let a = random_value(&mut rng);
+        let b = match rng.gen_range(0, 4) {
+            0 => random_value(&mut rng),
+            1 => a * (1.0 + rng.gen_range(-1e-2, 1e-2)),
+            2 => a * (1.0 + rng.gen_range(-1e-7, 1e-7)),
+            _ => f64::from_bits(a.to_bits().wrapping_add(rng.gen_range(0, 16))),
+        };
+        if b.is_nan() {
+            continue;
+        }
+        for &tol in tolerances.iter() {
+            // Rounding can only matter when the values are on the edge.
+            if !almost::stable_verdict(a, b, tol).stable {
+                continue;
+            }
+            assert_eq!(
+                deterministic_equal_with(a, b, tol),
+                almost::equal_with(a, b, tol),
+                "{:e} {:e} {:e}",
+                a,
+                b,
+                tol
+            );
+            let (a, b) = (a as f32, b as f32);
+            let tol = (tol as f32).max(f32::EPSILON);
+            if !b.is_nan() && almost::stable_verdict(a, b, tol).stable {
+                assert_eq!(
+                    deterministic_equal_with(a, b, tol),
+                    almost::equal_with(a, b, tol),
+                    "{:e} {:e} {:e}",
+                    a,
+                    b,
+                    tol
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use almost::Hint;
+
+#[test]
+fn test_diagnose() {
+    let c = almost::diagnose(0.1 + 0.2, 0.3);
+    assert!(c.passed);
+    assert_eq!(c.ulps, Some(1));
+    assert_eq!(c.abs_diff, (0.1 + 0.2) - 0.3);
+    assert_eq!(c.scale, 0.1 + 0.2);
+    assert_eq!(c.tolerance, almost::F64_TOLERANCE);
+    assert_eq!(c.hint, None);
+    assert!(c.rel_diff < c.tolerance);
+
+    let c = almost::diagnose(-2.0f32, 2.0);
+    assert!(!c.passed);
+    assert_eq!(c.abs_diff, 4.0);
+    assert_eq!(c.rel_diff, 2.0);
+    assert_eq!(c.scale, 2.0);
+    assert_eq!(c.hint, None);
+
+    let c = almost::diagnose(0.0, 0.0);
+    assert!(c.passed);
+    assert_eq!(c.scale, f64::MIN_POSITIVE);
+    assert_eq!(c.rel_diff, 0.0);
+}
+
+#[test]
+fn test_diagnose_special() {
+    let c = almost::diagnose(f64::NAN, 1.0);
+    assert!(!c.passed);
+    assert!(c.abs_diff.is_nan() && c.rel_diff.is_nan());
+    assert_eq!(c.ulps, None);
+    assert_eq!(c.hint, Some(Hint::NanOperand));
+
+    let c = almost::diagnose(f32::MAX, f32::INFINITY);
+    assert!(c.passed);
+    assert_eq!(c.abs_diff, f32::INFINITY);
+    assert_eq!(c.ulps, Some(1));
+
+    let c = almost::diagnose_with(1.0, 1.0 + 4.0 * f64::EPSILON, f64::EPSILON);
+    assert!(!c.passed);
+    assert_eq!(c.ulps, Some(4));
+    assert_eq!(c.hint, Some(Hint::EpsilonTolerance));
+}
+
+#[test]
+fn test_agrees_with_equal() {
+    let values = [0.0f32, 1e-40, 1.0, 1.0001, 1.001, -1.0, 1e30, f32::INFINITY];
+    for &a in &values {
+        for &b in &values {
+            let c = almost::diagnose_with(a, b, 1e-3);
+            assert_eq!(c.passed, almost::equal_with(a, b, 1e-3));
+            assert_eq!(c.passed, c.rel_diff < 1e-3, "{}", c);
+        }
+    }
+}
+
+#[test]
+fn test_display() {
+    let c = almost::diagnose_with(1.0f32, f32::NAN, 0.01);
+    assert_eq!(
+        c.to_string(),
+        "     left: 1.0\n    right: NaN\n abs diff: NaN\n rel diff: NaN\n     ulps: none\n    \
+         scale: 1.0\ntolerance: 0.01\n   passed: false\n     hint: a value is NaN, which is never \
+         equal to anything (even NaN)"
+    );
+    let c = almost::diagnose_with(1.0, 2.0, 0.1);
+    assert_eq!(
+        c.to_string(),
+        "     left: 1.0\n    right: 2.0\n abs diff: 1.0\n rel diff: 0.5\n     ulps: 4503599627370496\n    \
+         scale: 2.0\ntolerance: 0.1\n   passed: false"
+    );
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "num-dual")]
+
+use almost::{dual_equal_with, AlmostEqual};
+use num_dual::{Dual2_64, Dual64, HyperDual64};
+
+#[test]
+fn test_dual_equal() {
+    let a = Dual64 { re: 1.0, eps: 2.0 };
+    assert!(almost::equal(a, a));
+    assert!(almost::equal(
+        a,
+        Dual64 {
+            re: 1.0 + 1e-12,
+            eps: 2.0 - 1e-12
+        }
+    ));
+    assert!(!almost::equal(
+        a,
+        Dual64 {
+            re: 1.0,
+            eps: 2.001
+        }
+    ));
+    assert!(dual_equal_with(
+        a,
+        Dual64 {
+            re: 1.0,
+            eps: 2.001
+        },
+        1e-10,
+        1e-3
+    ));
+    assert!(!dual_equal_with(
+        a,
+        Dual64 {
+            re: 1.001,
+            eps: 2.0
+        },
+        1e-10,
+        1e-3
+    ));
+    // A vanishing derivative must be exactly zero.
+    let flat = Dual64 { re: 1.0, eps: 0.0 };
+    assert!(!dual_equal_with(
+        flat,
+        Dual64 {
+            re: 1.0,
+            eps: 1e-17
+        },
+        1e-10,
+        1e-3
+    ));
+    assert!(Dual64 {
+        re: 1e-300,
+        eps: 0.0
+    }
+    .almost_zero());
+    assert!(!Dual64 { re: 0.0, eps: 1.0 }.almost_zero());
+    // NaN in any part is never equal.
+    let nan = Dual64 {
+        re: 1.0,
+        eps: f64::NAN,
+    };
+    assert!(!almost::equal(nan, nan));
+}
+
+#[test]
+fn test_higher_order_duals() {
+    let x = Dual2_64 {
+        re: 3.0,
+        v1: 1.0,
+        v2: 0.0,
+    };
+    // x^2 has v1 = 2x and v2 = 2.
+    let sq = x * x;
+    assert!(dual_equal_with(
+        sq,
+        Dual2_64 {
+            re: 9.0,
+            v1: 6.0,
+            v2: 2.0
+        },
+        1e-12,
+        1e-6
+    ));
+    assert!(!dual_equal_with(
+        sq,
+        Dual2_64 {
+            re: 9.0,
+            v1: 6.0,
+            v2: 2.1
+        },
+        1e-12,
+        1e-6
+    ));
+    let h = HyperDual64 {
+        re: 1.0,
+        eps1: 2.0,
+        eps2: 3.0,
+        eps1eps2: 4.0,
+    };
+    let mut g = h;
+    g.eps1eps2 = 4.0001;
+    assert!(!almost::equal(h, g));
+    assert!(dual_equal_with(h, g, 1e-12, 1e-3));
+    assert!(almost::equal_with(h, g, 1e-3));
+}
+
+#[test]
+fn test_nested_duals() {
+    // Second derivatives by nesting, with the tolerance applying to each level.
+    let inner = |re, eps| Dual64 { re, eps };
+    let a = num_dual::Dual {
+        re: inner(1.0, 2.0),
+        eps: inner(3.0, 4.0),
+    };
+    let b = num_dual::Dual {
+        re: inner(1.0, 2.0),
+        eps: inner(3.0, 4.0001),
+    };
+    assert!(!almost::equal(a, b));
+    assert!(dual_equal_with(a, b, 1e-12, 1e-3));
+}
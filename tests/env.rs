@@ -0,0 +1,87 @@
+#![cfg(feature = "env-tolerance")]
+
+// The variables are only read once, so everything is in one test, which sets
+// them before any comparison.
+#[test]
+fn test_env_tolerance() {
+    std::env::set_var("ALMOST_REL_TOL", "0.1%");
+    std::env::set_var("ALMOST_ZERO_TOL", "1e-2");
+    assert_eq!(almost::env::rel_tolerance::<f64>(), Some(1e-3));
+    assert_eq!(almost::env::rel_tolerance::<f32>(), Some(1e-3));
+    assert_eq!(almost::env::zero_tolerance::<f64>(), Some(1e-2));
+
+    assert!(almost::equal(1.0, 1.0005));
+    assert!(!almost::equal(1.0, 1.002));
+    assert!(almost::equal(1.0f32, 1.0005));
+    assert!(!almost::equal_with(1.0, 1.0005, 1e-6));
+    assert!(almost::zero(0.005));
+    assert!(almost::zero(0.005f32));
+    assert!(!almost::zero_with(0.005, 1e-3));
+    // Explicit tolerances are used as is, even if they're the default.
+    assert!(!almost::equal_with(1.0, 1.0005, almost::F64_TOLERANCE));
+    assert!(!almost::equal_with(1.0f32, 1.0005, almost::F32_TOLERANCE));
+    assert!(!almost::zero_with(0.005, almost::F64_TOLERANCE));
+    // Through the impls for other types.
+    assert!(almost::equal(1.0..2.0, 1.0005..2.0));
+    assert!(almost::equal(Ok::<_, ()>(1.0), Ok(1.0005)));
+    assert!(almost::equal(&[1.0, 2.0][..], &[1.0005, 2.0][..]));
+    assert!(almost::equal(vec![[1.0f32]], vec![[1.0005]]));
+    assert!(almost::zero(std::rc::Rc::new(0.005)));
+    assert!(almost::Tolerance::default().equal(1.0, 1.0005));
+    // And the other functions which default to it.
+    almost::assert_almost_eq!(1.0, 1.0005);
+    almost::assert_approx_eq!(1.0, 1.0005);
+    let err = std::panic::catch_unwind(|| almost::assert_approx_eq!(1.0, 1.002)).unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+    assert!(msg.contains("tolerance: `0.001`"), "{}", msg);
+    assert!(almost::equal_slices(&[1.0, 2.0], &[1.0005, 2.0]));
+    assert_eq!(almost::all_almost_zero(&[0.005f32, -0.005]), Ok(()));
+    assert!(almost::equal_iter([1.0, 2.0], [1.0005, 2.0]));
+    assert!(almost::equal_iter([1.0..2.0], [1.0005..2.0]));
+    assert!(almost::equal_or_both_tiny(1.0, 1.0005, 1e-30));
+    // `compare` agrees with `equal`.
+    assert_eq!(
+        almost::compare(1.0, 1.0005),
+        Some(almost::ApproxOrdering::AlmostEqual)
+    );
+    assert_eq!(
+        almost::compare(1.0f32, 1.002),
+        Some(almost::ApproxOrdering::Less)
+    );
+    assert!(almost::le(1.0005, 1.0));
+    assert!(!almost::definitely_less(1.0, 1.0005));
+    assert_eq!(almost::max_of(&[1.0005, 1.0, 0.5]).unwrap().count(), 2);
+    assert!(almost::compare_tables("1.0 2.0", "1.0005 2.0").is_ok());
+    #[cfg(feature = "insta")]
+    {
+        let mut settings = insta::Settings::new();
+        settings.add_redaction(".**", almost::snapshot_redaction());
+        settings.bind(|| {
+            insta::assert_yaml_snapshot!([1.23456, 0.005], @r"
+            - 1.23
+            - 0
+            ");
+        });
+    }
+    #[cfg(feature = "vek")]
+    assert!(almost::equal(
+        vek::Vec2::new(1.0, 2.0),
+        vek::Vec2::new(1.0005, 2.0)
+    ));
+    #[cfg(feature = "derive")]
+    {
+        #[derive(Clone, Copy, almost::AlmostEqual)]
+        struct Point {
+            x: f64,
+            #[almost(tolerance = 1e-6)]
+            y: f64,
+        }
+        let p = Point { x: 1.0, y: 2.0 };
+        assert!(almost::equal(p, Point { x: 1.0005, y: 2.0 }));
+        assert!(!almost::equal(p, Point { x: 1.0, y: 2.001 }));
+    }
+
+    // Changing them afterwards has no effect.
+    std::env::set_var("ALMOST_REL_TOL", "bogus");
+    assert!(almost::equal(1.0, 1.0005));
+}
@@ -190,6 +190,7 @@ macro_rules! assert_not_almost_equal {
 }
 
 #[test]
+#[allow(clippy::legacy_numeric_constants)]
 fn test_f32() {
     use ieee754::Ieee754;
     use rand::{distributions::Uniform, prelude::*, rngs::SmallRng};
@@ -277,3 +278,15 @@ fn test_f32() {
         assert!(almost::zero(-v), "{} {:x}", v, v.to_bits());
     }
 }
+
+#[test]
+fn test_small_scale() {
+    // Just above `MIN_POSITIVE`, `tol * scale` is subnormal, and would round
+    // off part of the tolerance.
+    let a = f32::MIN_POSITIVE;
+    let b = f32::from_bits(a.to_bits() + 8);
+    assert_almost_equal!(a, b, 1e-6f32);
+    assert_almost_equal!(b, a, 1e-6f32);
+    assert_not_almost_equal!(a, f32::from_bits(a.to_bits() + 9), 1e-6f32);
+    assert_not_almost_equal!(a, b, 2.0 * f32::EPSILON);
+}
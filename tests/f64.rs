@@ -6346,6 +6346,7 @@ macro_rules! assert_not_almost_equal {
 }
 
 #[test]
+#[allow(clippy::legacy_numeric_constants)]
 fn test_f64() {
     use ieee754::Ieee754;
     use rand::{distributions::Uniform, prelude::*, rngs::SmallRng};
@@ -6433,3 +6434,15 @@ fn test_f64() {
         assert!(almost::zero(-v), "{} {:x}", v, v.to_bits());
     }
 }
+
+#[test]
+fn test_small_scale() {
+    // Just above `MIN_POSITIVE`, `tol * scale` is subnormal, and would round
+    // off part of the tolerance.
+    let a = f64::MIN_POSITIVE;
+    let b = f64::from_bits(a.to_bits() + 45036446638);
+    assert_almost_equal!(a, b, 1e-5f64);
+    assert_almost_equal!(b, a, 1e-5f64);
+    assert_not_almost_equal!(a, f64::from_bits(a.to_bits() + 45036446639), 1e-5f64);
+    assert_not_almost_equal!(a, b, 2.0 * f64::EPSILON);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "alloc")]
+
+use almost::gradcheck;
+
+#[test]
+fn test_gradcheck() {
+    // Rosenbrock, at a point and at its minimum (where the gradient is zero).
+    let f = |v: &[f64]| (1.0 - v[0]).powi(2) + 100.0 * (v[1] - v[0] * v[0]).powi(2);
+    let grad = |v: &[f64]| {
+        [
+            -2.0 * (1.0 - v[0]) - 400.0 * v[0] * (v[1] - v[0] * v[0]),
+            200.0 * (v[1] - v[0] * v[0]),
+        ]
+    };
+    for x in &[[-1.2, 1.0], [1.0, 1.0], [1e3, -1e4], [1e-8, 0.0]] {
+        let r = gradcheck::check(f, x, &grad(x));
+        assert!(r.passed(), "{:?}: {:?}", x, r);
+        assert_eq!(r.worst(), None);
+    }
+    // Off by a small amount in one component.
+    let x = [-1.2, 1.0];
+    let mut g = grad(&x);
+    g[1] *= 1.0001;
+    let r = gradcheck::check(f, &x, &g);
+    assert!(!r.passed());
+    assert!(r.components[0].passed);
+    assert_eq!(r.worst(), Some(1));
+    assert!((r.components[1].relative_error - 1e-4).abs() < 1e-6);
+    // But that's fine with a looser tolerance.
+    assert!(gradcheck::check_with(f, &x, &g, 1e-3).passed());
+}
+
+#[test]
+fn test_gradcheck_nan() {
+    let r = gradcheck::check(|v| v[0].sqrt(), &[-1.0], &[0.0]);
+    assert!(!r.passed());
+    assert!(r.components[0].relative_error.is_nan());
+    assert!(gradcheck::check(|_| 0.0, &[], &[]).passed());
+}
+
+#[test]
+#[should_panic]
+fn test_gradcheck_length() {
+    gradcheck::check(|v| v[0], &[1.0], &[]);
+}
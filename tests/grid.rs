@@ -0,0 +1,70 @@
+use almost::{compare_grid, compare_grid_with, GridRegion};
+
+#[test]
+fn test_compare_grid() {
+    let expected: Vec<f64> = (0..30).map(|i| i as f64 * 0.1).collect();
+    let report = compare_grid(&expected, &expected, 5);
+    assert!(report.passed());
+    assert_eq!((report.width, report.height), (5, 6));
+    assert_eq!(report.bounding_box, None);
+    assert_eq!(report.fraction_differing(), 0.0);
+
+    let mut actual = expected.clone();
+    actual[5 + 3] += 1.0;
+    actual[4 * 5 + 1] = f64::NAN;
+    actual[2 * 5 + 4] *= 1.0 + 1e-12;
+    let report = compare_grid(&actual, &expected, 5);
+    assert_eq!(report.values.mismatches, 2);
+    assert_eq!(report.values.first_mismatch.as_ref().unwrap().index, (1, 3));
+    assert_eq!(
+        report.bounding_box,
+        Some(GridRegion {
+            rows: 1..5,
+            cols: 1..4
+        })
+    );
+    assert_eq!(report.fraction_differing(), 2.0 / 30.0);
+    assert!(!report.passed_with_fraction(0.05));
+    assert!(report.passed_with_fraction(0.1));
+    let text = report.to_string();
+    assert!(
+        text.starts_with("5x6 grid: 2 of 30 elements differ"),
+        "{}",
+        text
+    );
+    assert!(text.contains("rows 1..5, columns 1..4"), "{}", text);
+
+    let loose = compare_grid_with(&[1.0f32, 2.0], &[1.01, 2.0], 1, 0.1);
+    assert!(loose.passed());
+    assert_eq!(loose.height, 2);
+
+    let empty = compare_grid::<f64>(&[], &[], 0);
+    assert!(empty.passed() && empty.height == 0);
+}
+
+#[test]
+#[should_panic]
+fn test_compare_grid_ragged() {
+    compare_grid(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0], 2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_report_table() {
+    let report = compare_grid(&[1.0f64, 2.0], &[1.0, 2.0 + 1e-13], 2);
+    let text = report.values.table().to_string();
+    assert_eq!(text.lines().count(), 3, "{}", text);
+    assert!(text.lines().nth(2).unwrap().ends_with("equal"), "{}", text);
+
+    let report = compare_grid(&[f64::NAN, 0.0, 1.0, 2.0], &[1.0, 1.0, 1.0, 3.0], 2);
+    let text = report.values.table().to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines[0], "3 of 4 elements differ");
+    assert!(lines[1].starts_with("metric "), "{}", text);
+    // The columns are aligned.
+    let col = lines[1].find("verdict").unwrap();
+    assert_eq!(lines[2].find("differ"), Some(col), "{}", text);
+    assert_eq!(lines[3].find("differ"), Some(col), "{}", text);
+    assert!(lines[2].contains(" - "), "{}", text);
+    assert!(lines[4].starts_with("hint: "), "{}", text);
+}
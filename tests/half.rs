@@ -0,0 +1,106 @@
+#![cfg(feature = "half")]
+
+use almost::AlmostEqual;
+use half::{bf16, f16};
+
+fn h(v: f32) -> f16 {
+    f16::from_f32(v)
+}
+
+fn b(v: f32) -> bf16 {
+    bf16::from_f32(v)
+}
+
+#[test]
+fn test_tolerances() {
+    assert_eq!(f16::DEFAULT_TOLERANCE, almost::F16_TOLERANCE);
+    assert_eq!(bf16::DEFAULT_TOLERANCE, almost::BF16_TOLERANCE);
+    assert_eq!(f16::MACHINE_EPSILON, f16::EPSILON);
+    let eps = f16::EPSILON.to_f64();
+    let tol = almost::F16_TOLERANCE.to_f64();
+    assert!(tol > eps && tol < 0.25);
+    let eps = bf16::EPSILON.to_f64();
+    let tol = almost::BF16_TOLERANCE.to_f64();
+    assert!(tol > eps && tol < 0.25);
+}
+
+#[test]
+fn test_f16() {
+    assert!(almost::equal(h(1.0), h(1.0)));
+    assert!(almost::equal(h(0.0), h(-0.0)));
+    assert!(almost::equal(h(1000.0), h(1000.5)));
+    assert!(!almost::equal(h(1.0), h(2.0)));
+    assert!(!almost::equal(h(-1.0), h(1.0)));
+    assert!(almost::equal_with(h(1.0), h(1.25), h(0.25)));
+    assert!(!almost::equal_with(h(1.0), h(1.25), h(0.1)));
+    assert!(!almost::equal(f16::NAN, f16::NAN));
+    assert!(!almost::equal(f16::NAN, h(1.0)));
+    // Subnormals are compared relative to `MIN_POSITIVE`.
+    let tiny = f16::from_bits(1);
+    assert!(almost::equal(tiny, h(0.0)));
+    assert!(almost::equal(tiny, f16::from_bits(3)));
+    assert!(!almost::equal(f16::MIN_POSITIVE, h(0.0)));
+    // Infinities are just past `MAX`.
+    assert!(almost::equal(f16::INFINITY, f16::INFINITY));
+    assert!(almost::equal(f16::MAX, f16::INFINITY));
+    assert!(almost::equal(f16::MIN, f16::NEG_INFINITY));
+    assert!(!almost::equal(f16::MAX, f16::NEG_INFINITY));
+    assert!(!almost::equal(f16::INFINITY, f16::NEG_INFINITY));
+    assert!(!almost::equal(h(1000.0), f16::INFINITY));
+    assert!(!almost::equal(tiny, f16::INFINITY));
+}
+
+#[test]
+fn test_bf16() {
+    assert!(almost::equal(b(3.0), b(3.0)));
+    assert!(almost::equal(b(100.0), b(100.5)));
+    assert!(!almost::equal(b(1.0), b(2.0)));
+    assert!(!almost::equal(bf16::NAN, bf16::NAN));
+    // `bf16` has the range of `f32`, so this isn't subnormal.
+    assert!(!almost::equal(b(1e-30), b(0.0)));
+    assert!(almost::equal(bf16::from_bits(1), b(0.0)));
+    assert!(almost::equal(bf16::MAX, bf16::INFINITY));
+    assert!(!almost::equal(b(1e38), bf16::INFINITY));
+    assert!(almost::equal(bf16::NEG_INFINITY, bf16::NEG_INFINITY));
+    assert!(!almost::equal(bf16::NEG_INFINITY, bf16::INFINITY));
+}
+
+#[test]
+fn test_zero() {
+    assert!(almost::zero(h(0.0)));
+    assert!(almost::zero(h(-1e-3)));
+    assert!(!almost::zero(h(0.5)));
+    assert!(!almost::zero(f16::NAN));
+    assert!(!almost::zero(f16::INFINITY));
+    assert!(almost::zero_with(h(0.05), h(0.1)));
+    assert!(!almost::zero_with(h(0.05), h(0.01)));
+    assert!(almost::zero(b(1e-3)));
+    assert!(!almost::zero(bf16::NAN));
+    assert!(almost::zero_with(b(1e-30), b(1e-20)));
+}
+
+#[test]
+fn test_aggregates() {
+    assert!(almost::equal([h(1.0), h(2.0)], [h(1.0), h(2.001)]));
+    assert!(almost::equal(&[b(1.0)][..], &[b(1.0)][..]));
+}
+
+#[cfg(all(debug_assertions, not(feature = "no-panic")))]
+#[test]
+#[should_panic]
+fn test_tolerance_too_small() {
+    almost::equal_with(h(1.0), h(1.0), f16::from_bits(1));
+}
+
+#[cfg(feature = "no-panic")]
+#[test]
+fn test_clamped_tolerance() {
+    // Clamped to `f16::EPSILON`, rather than `f64::EPSILON`.
+    assert!(almost::equal_with(
+        h(1.0),
+        h(1.0) + f16::EPSILON,
+        f16::from_bits(1)
+    ));
+    assert!(almost::equal_with(b(1.0), b(1.5), bf16::from_f32(2.0)));
+    assert!(!almost::equal_with(b(1.0), b(-1.0), bf16::from_f32(2.0)));
+}
@@ -0,0 +1,59 @@
+#![cfg(any(feature = "std", feature = "libm"))]
+use almost::{histogram_equal, histogram_equal_with, BinTolerance, Histogram, HistogramMismatch};
+
+const EDGES: [f64; 5] = [0.0, 1.0, 2.0, 3.0, 4.0];
+
+#[test]
+fn test_histogram_equal() {
+    let a = Histogram::new(&EDGES, &[1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(histogram_equal(a, a), Ok(()));
+    let b = Histogram::new(&EDGES, &[1.0, 2.0, 3.5, 4.0]);
+    match histogram_equal(a, b) {
+        Err(HistogramMismatch::Contents(r)) => {
+            assert_eq!(r.mismatches, 1);
+            assert_eq!(r.first_mismatch.unwrap().index, 2);
+        }
+        r => panic!("{:?}", r),
+    }
+    let rel = BinTolerance::Relative(0.2);
+    assert_eq!(histogram_equal_with(a, b, 1e-6, rel), Ok(()));
+
+    let edges = [0.0, 1.0, 2.0 + 1e-3, 3.0, 4.0];
+    let c = Histogram::new(&edges, a.contents);
+    match histogram_equal_with(a, c, 1e-6, rel) {
+        Err(HistogramMismatch::Edges(r)) => assert_eq!(r.first_mismatch.unwrap().index, 2),
+        r => panic!("{:?}", r),
+    }
+    let d = Histogram::new(&[0.0, 1.0], &[1.0]);
+    let e = histogram_equal(a, d).unwrap_err();
+    assert_eq!(e, HistogramMismatch::BinCount { lhs: 4, rhs: 1 });
+    assert!(e.to_string().contains("4 vs 1"), "{}", e);
+}
+
+#[test]
+fn test_histogram_poisson() {
+    let a = Histogram::new(&EDGES, &[0.0, 100.0, 10000.0, 1.0]);
+    // Differences of 10, 100 and 1 are each within one standard deviation.
+    let b = Histogram::new(&EDGES, &[0.0, 110.0, 10100.0, 2.0]);
+    assert_eq!(
+        histogram_equal_with(a, b, 1e-9, BinTolerance::Poisson(1.0)),
+        Ok(())
+    );
+    // But a difference of 30 in a bin of 100 isn't.
+    let c = Histogram::new(&EDGES, &[0.0, 130.0, 10100.0, 2.0]);
+    let r = match histogram_equal_with(a, c, 1e-9, BinTolerance::Poisson(1.0)) {
+        Err(HistogramMismatch::Contents(r)) => r,
+        r => panic!("{:?}", r),
+    };
+    assert_eq!((r.mismatches, r.total), (1, 4));
+    assert_eq!(r.first_mismatch.unwrap().index, 1);
+    assert!(histogram_equal_with(a, c, 1e-9, BinTolerance::Poisson(3.0)).is_ok());
+    let nan = Histogram::new(&EDGES, &[f64::NAN, 100.0, 10000.0, 1.0]);
+    assert!(histogram_equal_with(a, nan, 1e-9, BinTolerance::Poisson(3.0)).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_histogram_malformed() {
+    Histogram::new(&EDGES, &[1.0f64; 4][..3]);
+}
@@ -0,0 +1,118 @@
+#[cfg(feature = "alloc")]
+use std::{borrow::Cow, rc::Rc, sync::Arc};
+
+#[test]
+fn test_result() {
+    let ok = |v: f64| -> Result<f64, &str> { Ok(v) };
+    assert!(almost::equal(ok(0.1 + 0.2), ok(0.3)));
+    assert!(!almost::equal(ok(1.0), ok(1.1)));
+    assert!(almost::equal(Err("bad"), Err::<f64, _>("bad")));
+    assert!(!almost::equal(Err("bad"), Err::<f64, _>("worse")));
+    assert!(!almost::equal(ok(1.0), Err("bad")));
+    assert!(almost::zero(ok(1e-12)));
+    assert!(!almost::zero(Err::<f64, _>(())));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_pointers() {
+    assert!(almost::equal(Rc::new(0.1 + 0.2), Rc::new(0.3)));
+    assert!(almost::equal(Arc::new(0.1f32 + 0.2), Arc::new(0.3)));
+    assert!(!almost::equal(Arc::new(1.0), Arc::new(1.1)));
+    assert!(almost::zero(Rc::new(1e-9f32)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cow_slice() {
+    let a: Cow<'_, [f64]> = Cow::Owned(vec![1.0, 0.1 + 0.2]);
+    let b: Cow<'_, [f64]> = Cow::Borrowed(&[1.0, 0.3]);
+    assert!(almost::equal(a.clone(), b.clone()));
+    assert!(!almost::equal(a, Cow::Borrowed(&[1.0])));
+    assert!(!almost::equal_with(b, Cow::Borrowed(&[1.0, 0.31]), 1e-3));
+    assert!(almost::zero(Cow::Borrowed(&[0.0, 1e-12][..])));
+    assert!(!almost::zero(Cow::Borrowed(&[0.0, 1.0][..])));
+}
+
+#[test]
+fn test_ranges() {
+    assert!(almost::equal(0.0..0.1 + 0.2, 0.0..0.3));
+    assert!(!almost::equal(0.0..1.0, 0.0..1.1));
+    assert!(!almost::equal(0.0..1.0, 1e-9..1.0));
+    assert!(almost::equal(1.0f32..=2.0, 1.0..=2.0 + 1e-6));
+    assert!(!almost::equal(1.0f32..=2.0, 1.5..=2.0));
+    assert!(almost::zero(-1e-12..1e-12));
+    assert!(!almost::zero(0.0..=1.0));
+}
+
+#[test]
+fn test_slices_and_arrays() {
+    let a = [1.0, 0.1 + 0.2, -2.0];
+    let b = [1.0, 0.3, -2.0];
+    assert!(almost::equal(a, b));
+    assert!(almost::equal(&a, &b));
+    assert!(almost::equal(&a[..], &b[..]));
+    assert!(!almost::equal(&a[..], &b[..2]));
+    assert!(!almost::equal([1.0f32, 2.0], [1.0, 2.1]));
+    assert!(almost::equal_with([1.0f32, 2.0], [1.0, 2.1], 0.1));
+    assert!(!almost::equal([f64::NAN], [f64::NAN]));
+    assert!(almost::equal::<[f64; 0]>([], []));
+    assert!(almost::equal::<&[f32]>(&[], &[]));
+    assert!(almost::zero([0.0, 1e-12]));
+    assert!(!almost::zero(&[0.0, 1.0][..]));
+    // Element-wise, so small components aren't swamped by large ones.
+    assert!(!almost::equal([1e6, 1e-3], [1e6, 2e-3]));
+    // Nested arrays and arrays of other types.
+    assert!(almost::equal(
+        [[1.0, 2.0], [3.0, 4.0]],
+        [[1.0, 2.0], [3.0, 4.0 + 1e-12]]
+    ));
+    assert!(almost::equal([0.0..1.0], [0.0..1.0 + 1e-12]));
+}
+
+#[test]
+fn test_zero_aggregates() {
+    let residuals = [1e-13, -2e-12, 0.0, -0.0];
+    assert!(almost::zero(&residuals[..]));
+    assert!(almost::zero(residuals));
+    assert!(almost::zero::<&[f64]>(&[]));
+    assert!(!almost::zero(&[0.0, f64::NAN][..]));
+    assert!(!almost::zero(&[0.0, f64::INFINITY][..]));
+    assert!(almost::zero_with(&[0.5f32, -0.5][..], 1.0));
+    assert!(!almost::zero_with(&residuals[..], 1e-12));
+    assert!(almost::zero(&[[0.0, 1e-13], [-1e-13, 0.0]][..]));
+    // Agrees with the vectorized path for slices of floats.
+    let mut values = [0.0f32; 37];
+    for i in 0..values.len() {
+        values[i] = 1.0;
+        assert_eq!(
+            almost::zero(&values[..]),
+            almost::all_almost_zero(&values).is_ok()
+        );
+        values[i] = 0.0;
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_vec() {
+    assert!(almost::equal(vec![0.1 + 0.2, 1.0], vec![0.3, 1.0]));
+    assert!(!almost::equal(vec![1.0], vec![1.0, 1.0]));
+    assert!(!almost::equal(vec![1.0f32], vec![]));
+    assert!(almost::equal(Vec::<f64>::new(), vec![]));
+    assert!(almost::equal(vec![[1.0, 2.0]], vec![[1.0, 2.0]]));
+    assert!(almost::zero(vec![1e-9f32, -1e-9]));
+    assert!(!almost::zero(vec![1.0]));
+}
+
+#[test]
+fn test_references() {
+    use almost::AlmostEqual;
+    let (a, b) = ([0.1 + 0.2, 1.0], [0.3, 1.0]);
+    assert!(a.iter().zip(b.iter()).all(|(x, y)| x.almost_equals(y)));
+    assert!(a.iter().zip(&b).all(|(x, y)| almost::equal(x, y)));
+    assert!(a.iter().map(|x| x - x).all(almost::zero));
+    assert!(b.iter().all(|x| !almost::zero(x)));
+    let (mut x, mut y) = (1.0, 1.0 + 1e-12);
+    assert!(almost::equal(&mut x, &mut y));
+}
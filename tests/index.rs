@@ -0,0 +1,49 @@
+#![cfg(feature = "alloc")]
+
+use almost::SortedIndex;
+use rand::{distributions::Uniform, prelude::*, rngs::SmallRng};
+
+#[test]
+fn test_index_matches_brute_force() {
+    let mut rng = SmallRng::seed_from_u64(0x5eed);
+    let dist = Uniform::new(-4.0f64, 4.0);
+    // Lots of clustered values, so that there's something to find.
+    let values: Vec<f64> = (0..500)
+        .map(|_| {
+            let v: f64 = dist.sample(&mut rng);
+            (v * 8.0).round() / 8.0 * (1.0 + 1e-10 * dist.sample(&mut rng))
+        })
+        .chain(vec![0.0, -0.0, f64::NAN, f64::INFINITY, 1e-310])
+        .collect();
+    let index = SortedIndex::new(values.clone());
+    assert_eq!(index.len(), values.len() - 1);
+
+    for &tol in &[almost::F64_TOLERANCE, 1e-3, 0.1] {
+        let index = SortedIndex::with_tolerance(values.clone(), tol);
+        let queries = values.iter().copied().chain(vec![0.0, 5.0, -5.0, 1e-320]);
+        for x in queries.chain((0..200).map(|_| dist.sample(&mut rng))) {
+            let mut expected: Vec<usize> = values
+                .iter()
+                .enumerate()
+                .filter(|&(_, &v)| almost::equal_with(v, x, tol))
+                .map(|(i, _)| i)
+                .collect();
+            let mut found: Vec<usize> = index.within(x).iter().map(|e| e.1).collect();
+            expected.sort_unstable();
+            found.sort_unstable();
+            assert_eq!(found, expected, "query {:e}, tol {:e}", x, tol);
+        }
+    }
+}
+
+#[test]
+fn test_index_nearest() {
+    let index: SortedIndex<f32> = vec![1.0, 2.0, 4.0, f32::NAN].into_iter().collect();
+    assert_eq!(index.nearest(0.0), Some((1.0, 0)));
+    assert_eq!(index.nearest(2.9), Some((4.0, 2)));
+    assert_eq!(index.nearest(100.0), Some((4.0, 2)));
+    assert_eq!(index.nearest(f32::NAN), None);
+    assert!(index.contains(2.0));
+    assert!(!index.contains(2.1));
+    assert_eq!(SortedIndex::<f32>::new(vec![]).nearest(1.0), None);
+}
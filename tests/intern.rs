@@ -0,0 +1,47 @@
+#![cfg(feature = "alloc")]
+
+use almost::InternPool;
+
+#[test]
+fn test_intern_pool() {
+    let mut pool = InternPool::new();
+    assert!(pool.is_empty());
+    assert_eq!(pool.get(1.0), None);
+    assert_eq!(pool.intern(1.0), 1.0);
+    assert_eq!(pool.intern(1.0 + f64::EPSILON), 1.0);
+    assert_eq!(pool.intern(-1.0), -1.0);
+    assert_eq!(pool.intern(0.0), 0.0);
+    assert_eq!(pool.intern(-0.0), 0.0);
+    assert_eq!(pool.intern(1e-300), 1e-300);
+    assert_eq!(pool.intern(f64::INFINITY), f64::INFINITY);
+    assert_eq!(pool.get(1.0 - f64::EPSILON), Some(1.0));
+    assert_eq!(pool.values(), &[1.0, -1.0, 0.0, 1e-300, f64::INFINITY]);
+
+    // NaN is never registered.
+    assert!(pool.intern(f64::NAN).is_nan());
+    assert_eq!(pool.len(), 5);
+
+    // The closest canonical value wins.
+    let mut pool = InternPool::with_tolerance(0.1f32);
+    assert_eq!(pool.intern(1.0), 1.0);
+    assert_eq!(pool.intern(1.15), 1.15);
+    assert_eq!(pool.intern(1.09), 1.15);
+    assert_eq!(pool.intern(1.06), 1.0);
+    assert_eq!(pool.tolerance(), 0.1);
+}
+
+#[test]
+fn test_intern_pool_consistent() {
+    // Interning the same values again, in any order, gives the same results.
+    let values: Vec<f64> = (0..1000).map(|i| (i as f64 * 0.1).sin()).collect();
+    let mut pool = InternPool::new();
+    let first: Vec<f64> = values.iter().map(|&v| pool.intern(v)).collect();
+    let len = pool.len();
+    let again: Vec<f64> = values
+        .iter()
+        .rev()
+        .map(|&v| pool.intern(v * (1.0 + 1e-15)))
+        .collect();
+    assert_eq!(pool.len(), len);
+    assert!(first.iter().eq(again.iter().rev()));
+}
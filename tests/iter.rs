@@ -0,0 +1,11 @@
+#[test]
+fn test_equal_iter() {
+    assert!(almost::equal_iter(Vec::<f32>::new(), []));
+    assert!(almost::equal_iter(
+        (0..10).map(|i| i as f32 / 10.0),
+        (0..10).map(|i| i as f32 * 0.1)
+    ));
+    assert!(!almost::equal_iter([f32::NAN], [f32::NAN]));
+    assert!(!almost::equal_iter([1.0f32, 2.0, 3.0], [1.0, 2.0]));
+    assert!(!almost::equal_iter_with([1.0f32, 2.0], [1.0, 2.5], 0.1));
+}
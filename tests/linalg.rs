@@ -0,0 +1,278 @@
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn test_direction_equal() {
+    use almost::{direction_equal, direction_equal_with, DirectionSign};
+    let a = [3.0f64, -4.0, 12.0];
+    let scaled = a.map(|x| x * 1e-250);
+    assert!(direction_equal(&a, &scaled, DirectionSign::Same));
+    let huge = a.map(|x| x * 1e300);
+    assert!(direction_equal(&huge, &scaled, DirectionSign::Same));
+    let flipped = a.map(|x| -x * 7.0);
+    assert!(!direction_equal(&a, &flipped, DirectionSign::Same));
+    assert!(direction_equal(&a, &flipped, DirectionSign::Either));
+
+    // A rotation by a tiny angle is still detected, which it wouldn't be if
+    // only the cosine were compared to 1.
+    let angle = 1e-7f64;
+    let b = [angle.cos(), angle.sin()];
+    assert!(!direction_equal_with(
+        &[1.0, 0.0],
+        &b,
+        DirectionSign::Same,
+        1e-8
+    ));
+    assert!(direction_equal_with(
+        &[1.0, 0.0],
+        &b,
+        DirectionSign::Same,
+        1.01e-7
+    ));
+    assert!(!direction_equal_with(
+        &[1.0, 0.0],
+        &b,
+        DirectionSign::Same,
+        0.99e-7
+    ));
+
+    let embedding: Vec<f32> = (0..768).map(|i| ((i * 37) % 101) as f32 - 50.0).collect();
+    let noisy: Vec<f32> = embedding.iter().map(|x| x * 2.5 + 1e-4).collect();
+    assert!(direction_equal_with(
+        &embedding,
+        &noisy,
+        DirectionSign::Same,
+        1e-4
+    ));
+    assert!(!direction_equal_with(
+        &embedding,
+        &noisy,
+        DirectionSign::Same,
+        2e-7
+    ));
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn test_direction_equal_degenerate() {
+    use almost::{direction_equal, DirectionSign::Either};
+    let a = [1.0f64, 2.0];
+    assert!(!direction_equal(&a, &[0.0, 0.0], Either));
+    assert!(!direction_equal(&[0.0f64, 0.0], &[0.0, 0.0], Either));
+    assert!(!direction_equal(&a, &[1.0], Either));
+    assert!(!direction_equal(&a, &[1.0, f64::NAN], Either));
+    assert!(!direction_equal(&a, &[1.0, f64::INFINITY], Either));
+    assert!(!direction_equal::<f64>(&[], &[], Either));
+    assert!(direction_equal(&[f32::MIN_POSITIVE / 8.0], &[1e30], Either));
+}
+
+#[test]
+fn test_unit_length() {
+    use almost::{unit_length, unit_length_with};
+    assert!(unit_length(&[1.0f64]));
+    assert!(unit_length(&[-1.0f32]));
+    assert!(unit_length(&[0.0f64, 0.6, 0.0, -0.8]));
+    assert!(!unit_length(&[0.6f64, 0.8 + 1e-4]));
+    assert!(unit_length_with(&[0.6f64, 0.8 + 1e-4], 1e-3));
+    assert!(!unit_length::<f64>(&[]));
+    assert!(!unit_length(&[0.0f64; 3]));
+    assert!(!unit_length(&[1.0, f64::NAN]));
+    assert!(!unit_length(&[f64::INFINITY]));
+    // The tolerance grows for long vectors, which accumulate more rounding.
+    let n = 1 << 16;
+    let x = (1.0f32 / n as f32).sqrt();
+    let v = vec![x * 1.005; n];
+    assert!(unit_length(&v));
+    assert!(!unit_length_with(&v, almost::F32_TOLERANCE));
+    assert!(!unit_length(&vec![x * 1.01; n]));
+}
+
+#[test]
+fn test_orthogonal() {
+    use almost::{orthogonal, orthogonal_with};
+    assert!(orthogonal(&[1.0f64, 0.0], &[0.0, 5.0]));
+    assert!(orthogonal(&[3.0f32, 4.0], &[-4e20, 3e20]));
+    assert!(!orthogonal(&[1.0f64, 0.0], &[1e-4, 1.0]));
+    assert!(orthogonal_with(&[1.0f64, 0.0], &[1e-7, 1.0], 1.1e-7));
+    // Scale doesn't matter, in either direction.
+    for &s in &[1e-300, 1e-150, 1.0, 1e150, 1e300] {
+        let u = [s, 2.0 * s, -s];
+        let v = [s, 0.0, s * (1.0 + 1e-12)];
+        assert!(orthogonal(&u, &v), "{}", s);
+        let w = [s, 0.0, s * 0.5];
+        assert!(!orthogonal(&u, &w), "{}", s);
+    }
+    assert!(orthogonal(&[0.0f64, 0.0], &[1.0, 1.0]));
+    assert!(orthogonal::<f64>(&[], &[]));
+    assert!(!orthogonal(&[1.0f64, 0.0], &[0.0]));
+    assert!(!orthogonal(&[1.0f64, f64::NAN], &[0.0, 1.0]));
+    assert!(!orthogonal(&[1.0f64, 0.0], &[0.0, f64::INFINITY]));
+}
+
+#[test]
+fn test_matrix_properties() {
+    use almost::{almost_identity, almost_orthogonal, almost_symmetric, almost_symmetric_with};
+    let m = [2.0f64, -1.0, 0.0, -1.0, 2.0, -1.0, 0.0, -1.0, 2.0];
+    assert!(almost_symmetric(&m, 3));
+    assert!(!almost_identity(&m, 3));
+    assert!(!almost_orthogonal(&m, 3));
+    let mut skewed = m;
+    skewed[1] = -1.01;
+    assert!(!almost_symmetric(&skewed, 3));
+    assert!(almost_symmetric_with(&skewed, 3, 0.01));
+    // A tiny asymmetry relative to the whole matrix is fine.
+    let big = m.map(|x| x * 1e200);
+    let mut nearly = big;
+    nearly[2] = 1e180;
+    assert!(almost_symmetric(&nearly, 3));
+
+    let id = [1.0f32, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+    assert!(almost_identity(&id, 3));
+    assert!(almost_symmetric(&id, 3));
+    assert!(almost_orthogonal(&id, 3));
+    assert!(!almost_identity(&id.map(|x| x * 1.01), 3));
+
+    // A rotation composed many times drifts from orthogonal, but only by
+    // rounding error.
+    let (s, c) = (0.1f64.sin(), 0.1f64.cos());
+    let step = [c, -s, s, c];
+    let mut r = [1.0, 0.0, 0.0, 1.0];
+    for _ in 0..1000 {
+        r = [
+            r[0] * step[0] + r[1] * step[2],
+            r[0] * step[1] + r[1] * step[3],
+            r[2] * step[0] + r[3] * step[2],
+            r[2] * step[1] + r[3] * step[3],
+        ];
+    }
+    assert!(almost_orthogonal(&r, 2));
+    assert!(!almost_symmetric(&r, 2));
+    assert!(!almost_orthogonal(&[1.0, 0.1, 0.0, 1.0], 2));
+
+    for f in [almost_identity, almost_orthogonal, almost_symmetric] {
+        assert!(f(&[], 0));
+        assert!(!f(&[1.0, 0.0, 0.0, f64::NAN], 2));
+        assert!(!f(&[1.0, 0.0, 0.0, f64::INFINITY], 2));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_matrix_not_square() {
+    almost::almost_identity(&[1.0f64, 0.0, 1.0], 2);
+}
+
+#[test]
+fn test_probabilities() {
+    use almost::{almost_normalized, almost_normalized_with, almost_row_stochastic};
+    let softmax = |x: &[f32]| -> Vec<f32> {
+        let e: Vec<f32> = x.iter().map(|v| v.exp()).collect();
+        let sum: f32 = e.iter().sum();
+        e.iter().map(|v| v / sum).collect()
+    };
+    let logits: Vec<f32> = (0..50_000)
+        .map(|i| ((i * 7919) % 1000) as f32 / 100.0)
+        .collect();
+    assert!(almost_normalized(&softmax(&logits)));
+    assert!(almost_normalized(&[1.0f64]));
+    assert!(almost_normalized(&[1.0 + 1e-12, -1e-12]));
+    assert!(!almost_normalized(&[1.0 + 1e-3, -1e-3]));
+    assert!(!almost_normalized(&[0.5f64, 0.5 + 1e-4]));
+    assert!(almost_normalized_with(&[0.5f64, 0.5 + 1e-4], 1e-3));
+    assert!(!almost_normalized::<f64>(&[]));
+    assert!(!almost_normalized(&[1.0, f64::NAN]));
+    assert!(!almost_normalized(&[f64::INFINITY, f64::NEG_INFINITY]));
+
+    let m = [0.5f64, 0.5, 0.25, 0.75, 1.0, 0.0];
+    assert!(almost_row_stochastic(&m, 2));
+    assert!(!almost_row_stochastic(&m, 3));
+    assert!(!almost_row_stochastic(&m, 6));
+    assert!(almost_row_stochastic::<f64>(&[], 0));
+}
+
+#[test]
+#[should_panic]
+fn test_row_stochastic_partial_row() {
+    almost::almost_row_stochastic(&[0.5f64, 0.5, 1.0], 2);
+}
+
+#[test]
+fn test_weighted_equal() {
+    use almost::weighted_equal;
+    let w = [4.0f64, 1.0, 0.0];
+    // Distance sqrt(4 * 0.25 + 1) = sqrt(2).
+    assert!(weighted_equal(
+        &[0.5, 1.0, 100.0],
+        &[0.0, 0.0, -5.0],
+        &w,
+        1.5
+    ));
+    assert!(!weighted_equal(
+        &[0.5, 1.0, 100.0],
+        &[0.0, 0.0, -5.0],
+        &w,
+        1.4
+    ));
+    // Tiny and huge differences don't underflow or overflow.
+    assert!(weighted_equal(
+        &[1e-200, 0.0],
+        &[0.0, 1e-200],
+        &[1.0, 1.0],
+        2e-200
+    ));
+    assert!(!weighted_equal(
+        &[1e200, 0.0],
+        &[0.0, 1e200],
+        &[1.0, 1.0],
+        1e200
+    ));
+    assert!(weighted_equal(
+        &[1e200, 0.0],
+        &[0.0, 1e200],
+        &[1.0, 1.0],
+        1.5e200
+    ));
+
+    assert!(!weighted_equal(&[1.0, 2.0], &[1.0], &[1.0, 1.0], 1.0));
+    assert!(!weighted_equal(&[1.0], &[1.0], &[1.0, 1.0], 1.0));
+    assert!(!weighted_equal(&[1.0, 2.0], &[1.0, 2.0], &[1.0, -1.0], 1.0));
+    assert!(!weighted_equal(&[1.0], &[1.0], &[f64::NAN], 1.0));
+    let inf = f64::INFINITY;
+    assert!(weighted_equal(&[inf, 1.0], &[inf, 1.0], &[1.0, 1.0], 1.0));
+    assert!(!weighted_equal(&[inf, 1.0], &[inf, 1.5], &[1.0, 1.0], 1.0));
+    assert!(!weighted_equal(&[f64::NAN], &[f64::NAN], &[1.0], 1.0));
+    assert!(weighted_equal::<f32>(&[], &[], &[], 1.0));
+}
+
+#[test]
+fn test_metric_equal() {
+    use almost::{metric_equal, weighted_equal};
+    let a = [1.0f64, 2.0, 3.0];
+    let b = [1.1, 1.8, 3.0];
+    // A diagonal metric is the same as the weighted norm.
+    let diag = [4.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 9.0];
+    for &d in &[0.28, 0.3] {
+        assert_eq!(
+            metric_equal(&a, &b, &diag, d),
+            weighted_equal(&a, &b, &[4.0, 1.0, 9.0], d)
+        );
+    }
+    assert!(metric_equal(&a, &b, &diag, 0.3));
+    assert!(!metric_equal(&a, &b, &diag, 0.28));
+
+    // A metric which isn't positive semidefinite.
+    let indefinite = [1.0, 2.0, 2.0, 1.0];
+    assert!(!metric_equal(&[1.0, 0.0], &[0.0, 1.0], &indefinite, 1e3));
+    assert!(metric_equal(&[1.0, 0.0], &[1.0, 0.0], &indefinite, 1.0));
+    assert!(!metric_equal(&[1.0, 0.0], &[1.0], &indefinite, 1.0));
+    assert!(!metric_equal(
+        &[1.0, f64::NAN],
+        &[1.0, 0.0],
+        &[1.0, 0.0, 0.0, 1.0],
+        1.0
+    ));
+}
+
+#[test]
+#[should_panic]
+fn test_metric_wrong_size() {
+    almost::metric_equal(&[1.0f64, 2.0], &[1.0, 2.0], &[1.0, 0.0, 1.0], 1.0);
+}
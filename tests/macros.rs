@@ -0,0 +1,207 @@
+use almost::almost_matches;
+
+#[test]
+fn test_almost_matches() {
+    let third = 1.0f32 / 3.0;
+    assert!(almost_matches!(third * 3.0, 1.0));
+    assert!(almost_matches!(third * 3.0, 2.0 | 1.0,));
+    assert!(!almost_matches!(third, 0.3 | 0.34));
+    assert!(almost_matches!(1e-5f32, 0.0 | 1.0));
+    assert!(!almost_matches!(f32::NAN, f32::NAN | 0.0));
+    assert!(almost_matches!(-2.0f32, -1.0 - 1.0));
+    let mut evaluated = 0;
+    assert!(almost_matches!(
+        {
+            evaluated += 1;
+            2.0f64
+        },
+        1.0 | 2.0 | 3.0
+    ));
+    assert_eq!(evaluated, 1);
+}
+
+const SQRT_3: f64 = 1.7320508075688772;
+almost::const_assert_almost_eq!(SQRT_3 * SQRT_3, 3.0);
+almost::const_assert_almost_eq!(1.0f32 / 3.0, 0.33333334,);
+almost::const_assert_almost_eq!(SQRT_3, 1.7321, 1e-4);
+
+#[test]
+fn test_const_assert_almost_eq() {
+    const X: f32 = 0.1;
+    almost::const_assert_almost_eq!(X * 3.0, 0.3);
+}
+
+#[test]
+fn test_const_equal() {
+    use almost::testutil::{anchors, just_equal, just_unequal};
+    for &x in anchors::<f64>().iter() {
+        for y in [Some(x), just_equal(x), just_unequal(x), Some(-x)]
+            .iter()
+            .flatten()
+        {
+            assert_eq!(
+                almost::const_equal_f64(x, *y),
+                almost::equal(x, *y),
+                "{} {}",
+                x,
+                y
+            );
+            assert_eq!(
+                almost::const_equal_with_f64(x, *y, 1e-3),
+                almost::equal_with(x, *y, 1e-3),
+                "{} {}",
+                x,
+                y
+            );
+        }
+    }
+    for &x in anchors::<f32>().iter() {
+        for y in [Some(x), just_equal(x), just_unequal(x), Some(-x)]
+            .iter()
+            .flatten()
+        {
+            assert_eq!(
+                almost::const_equal_f32(x, *y),
+                almost::equal(x, *y),
+                "{} {}",
+                x,
+                y
+            );
+        }
+    }
+}
+
+#[test]
+fn test_assert_approx_eq() {
+    use almost::assert_approx_eq;
+    assert_approx_eq!(0.1 + 0.2, 0.3);
+    assert_approx_eq!(1e-200 * 3.0, 3e-200);
+    assert_approx_eq!(1e300 / 3.0, 3.333_333_333_333_333e299);
+    assert_approx_eq!(1.0f32, 1.0004, 1e-3);
+    assert_approx_eq!(1e-9, 0.0, 1e-6);
+    // Evaluated once.
+    let mut calls = 0;
+    assert_approx_eq!(
+        {
+            calls += 1;
+            1.0
+        },
+        1.0,
+    );
+    assert_eq!(calls, 1);
+}
+
+#[test]
+#[should_panic(expected = "almost::zero")]
+fn test_assert_approx_eq_zero() {
+    almost::assert_approx_eq!(1e-9, 0.0);
+}
+
+#[test]
+#[should_panic(expected = "expect diff: `0.001`")]
+fn test_assert_approx_eq_eps() {
+    almost::assert_approx_eq!(1.0, 1.01, 1e-3);
+}
+
+#[test]
+#[should_panic(expected = "left: `1.0`, right: `1.1`")]
+fn test_assert_approx_eq_fail() {
+    almost::assert_approx_eq!(1.0f32, 1.1);
+}
+
+#[test]
+fn test_assert_almost_eq() {
+    almost::assert_almost_eq!(0.1 + 0.2, 0.3);
+    almost::assert_almost_eq!(1.0f32, 1.0004, 1e-3,);
+    almost::assert_almost_eq!(f64::INFINITY, f64::INFINITY);
+    almost::assert_almost_ne!(1.0, 1.1);
+    almost::assert_almost_ne!(f64::NAN, f64::NAN);
+    almost::assert_almost_ne!(1.0f32, 1.01, 1e-3, "{}", "message");
+    almost::debug_assert_almost_eq!(0.1 + 0.2, 0.3, 1e-9);
+    almost::debug_assert_almost_ne!(1.0, -1.0);
+    let mut evaluated = 0;
+    almost::assert_almost_eq!(
+        {
+            evaluated += 1;
+            2.0
+        },
+        2.0
+    );
+    assert_eq!(evaluated, 1);
+}
+
+#[test]
+fn test_assert_almost_eq_message() {
+    let message = std::panic::catch_unwind(|| {
+        almost::assert_almost_eq!(1.0, 2.0, 1e-3, "step {}", 3);
+    })
+    .unwrap_err();
+    assert_eq!(
+        message.downcast_ref::<String>().unwrap(),
+        "assertion `left ~= right` failed: step 3
+     left: 1.0
+    right: 2.0
+ abs diff: 1.0
+ rel diff: 0.5
+tolerance: 0.001"
+    );
+}
+
+#[test]
+#[should_panic(expected = "hint: ")]
+fn test_assert_almost_eq_zero() {
+    almost::assert_almost_eq!(1e-9, 0.0);
+}
+
+#[test]
+#[should_panic(expected = "assertion `left !~= right` failed\n     left: 0.30000000000000004")]
+fn test_assert_almost_ne_fail() {
+    almost::assert_almost_ne!(0.1 + 0.2, 0.3);
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic(expected = "left ~= right"))]
+fn test_debug_assert_almost_eq() {
+    almost::debug_assert_almost_eq!(1.0f32, 2.0);
+}
+
+#[test]
+#[should_panic(expected = "rel diff: 0.0909090909090909")]
+fn test_assert_almost_eq_fail() {
+    almost::assert_almost_eq!(1.0, 1.1);
+}
+
+#[test]
+fn test_almost() {
+    use almost::almost;
+    let (a, b) = (0.1 + 0.2, 0.3);
+    assert!(almost!(a ~= b));
+    assert!(almost!(a ~= b,));
+    assert!(!almost!(1.0 ~= 1.0001));
+    assert!(almost!(1.0 ~= 1.0001, rel = 1e-3));
+    assert!(almost!(a * 2.0 - b ~= b + b - b));
+    assert!(almost!([1.0f64, 2.0].iter().sum::<f64>() ~= 3.0));
+    assert!(almost!(f64::max(1.0, 2.0) ~= 2.0));
+    assert!(!almost!(1e-17 ~= 1e-30, rel = 1e-9, abs = 1e-20));
+    assert!(almost!(1e-17 ~= 0.0f64 + 1e-30, abs = 1e-12, rel = 1e-9));
+
+    // Zero uses an absolute tolerance.
+    let x = a - b;
+    assert!(!almost!(x ~= x * 0.5));
+    assert!(almost!(x ~= 0));
+    assert!(almost!(x ~= 0.0));
+    assert!(almost!(1e-7f32 ~= 0f32));
+    assert!(almost!(1e-7f32 ~= 0.0f32));
+    assert!(almost!(x ~= 0f64, abs = 1e-15));
+    assert!(!almost!(x ~= 0.0f64, abs = 1e-17));
+    assert!(almost!(-x ~= 0, abs = 1e-15,));
+
+    // Evaluated once.
+    let mut calls = 0;
+    let mut f = || {
+        calls += 1;
+        1.0
+    };
+    assert!(almost!(f() ~= 1.0));
+    assert_eq!(calls, 1);
+}
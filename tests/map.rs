@@ -0,0 +1,91 @@
+#![cfg(feature = "alloc")]
+
+use almost::TolerantMap;
+use ieee754::Ieee754;
+
+fn check_neighborhood<F>(map: &TolerantMap<f64, usize>, key: f64, id: usize, tol: f64, next: F)
+where
+    F: Fn(f64) -> f64,
+{
+    // Walk away from `key` until we leave the tolerance, checking that every
+    // value almost equal to it finds it.
+    let mut v = key;
+    for _ in 0..64 {
+        if !almost::equal_with(v, key, tol) {
+            break;
+        }
+        assert_eq!(map.get(v), Some(&id), "{:e} should find {:e}", v, key);
+        v = next(v);
+    }
+}
+
+#[test]
+fn test_map_boundaries() {
+    for &tol in &[
+        f64::EPSILON,
+        f64::EPSILON * 7.0,
+        almost::F64_TOLERANCE,
+        0.25,
+        0.49,
+    ] {
+        let keys = [
+            1.0,
+            -1.0,
+            2.0,
+            3.0,
+            1e300,
+            f64::MAX,
+            f64::INFINITY,
+            -f64::INFINITY,
+            f64::MIN_POSITIVE,
+            0.0,
+            1e-310,
+        ];
+        for &key in &keys {
+            let mut map = TolerantMap::with_tolerance(tol);
+            assert_eq!(map.insert(key, 1), None);
+            assert_eq!(map.get(key), Some(&1));
+            // Scan outwards at one ulp per step, and then with larger steps.
+            check_neighborhood(&map, key, 1, tol, |v| v.next());
+            check_neighborhood(&map, key, 1, tol, |v| v.prev());
+            let step = (key * tol / 16.0).abs();
+            if step.is_finite() && step > 0.0 {
+                check_neighborhood(&map, key, 1, tol, |v| v + step);
+                check_neighborhood(&map, key, 1, tol, |v| v - step);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_map_basics() {
+    let mut map = TolerantMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(0.3, 'a'), None);
+    assert_eq!(map.insert(0.1 + 0.2, 'b'), Some('a'));
+    assert_eq!(map.get_key_value(0.1 + 0.2), Some((0.3, &'b')));
+    assert_eq!(map.insert(-0.3, 'c'), None);
+    assert_eq!(map.insert(f64::NAN, 'd'), None);
+    assert_eq!(map.get(f64::NAN), None);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.iter().count(), 3);
+    assert_eq!(map.remove(0.3), Some('b'));
+    assert_eq!(map.remove(0.3), None);
+    assert_eq!(map.get(-0.3), Some(&'c'));
+    assert_eq!(map.len(), 2);
+    map.clear();
+    assert!(map.is_empty());
+
+    // Closest wins when several keys are in range.
+    let mut map = TolerantMap::with_tolerance(0.1f32);
+    map.insert(1.0, 1);
+    map.insert(1.15, 2);
+    assert_eq!(map.get(1.06), Some(&1));
+    assert_eq!(map.get(1.09), Some(&2));
+}
+
+#[test]
+#[should_panic]
+fn test_map_bad_tolerance() {
+    let _ = TolerantMap::<f64, ()>::with_tolerance(0.5);
+}
@@ -0,0 +1,29 @@
+use almost::{
+    almost_decreasing, almost_increasing, almost_increasing_with, strictly_decreasing,
+    strictly_increasing,
+};
+
+#[test]
+fn test_almost_monotonic() {
+    assert_eq!(almost_increasing::<f64>(&[]), Ok(()));
+    assert_eq!(almost_increasing(&[1.0]), Ok(()));
+    assert_eq!(almost_increasing(&[1.0, f64::NAN]), Err(1));
+    assert_eq!(almost_increasing(&[f64::NAN, 1.0]), Err(0));
+    // The first element which breaks the ordering, even if a later one is NaN.
+    assert_eq!(almost_increasing(&[2.0, 1.0, f64::NAN]), Err(1));
+    assert_eq!(almost_increasing(&[1.0, 1.0, 1.0]), Ok(()));
+    assert_eq!(almost_decreasing(&[1.0, 2.0]), Err(1));
+    assert_eq!(almost_decreasing(&[-1.0, -2.0, f64::NEG_INFINITY]), Ok(()));
+    // Drift is caught, even though each step is within tolerance.
+    let v: Vec<f32> = (0..100).map(|i| 1.0 - i as f32 * 1e-4).collect();
+    assert_eq!(almost_increasing_with(&v, 1e-3), Err(11));
+}
+
+#[test]
+fn test_strictly_monotonic() {
+    assert_eq!(strictly_increasing::<f32>(&[]), Ok(()));
+    assert_eq!(strictly_increasing(&[0.0, 1.0, f32::INFINITY]), Ok(()));
+    assert_eq!(strictly_increasing(&[1.0, 1.0]), Err(1));
+    assert_eq!(strictly_increasing(&[2.0, 1.0]), Err(1));
+    assert_eq!(strictly_decreasing(&[2.0, 1.0, f32::NAN]), Err(2));
+}
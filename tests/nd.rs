@@ -0,0 +1,60 @@
+#![cfg(feature = "ndarray")]
+
+use almost::NdMismatch;
+use ndarray::{array, Array3};
+
+#[test]
+fn test_ndarray_broadcasting() {
+    let a = array![[1.0, 2.0, 3.0], [1.0, 2.0, 3.0]];
+    assert!(almost::ndarray_equal(&a, &array![1.0, 2.0, 3.0]).is_ok());
+    assert!(almost::ndarray_equal(&array![[1.0], [1.0]], &array![[1.0, 1.0]]).is_ok());
+    assert!(almost::ndarray_equal(&a, &array![[1.0, 2.0, 3.0]]).is_ok());
+    assert!(almost::ndarray_equal(&a, &ndarray::arr0(1.0)).is_err());
+
+    match almost::ndarray_equal(&a, &array![1.0, 2.0]) {
+        Err(NdMismatch::Shape { lhs, rhs }) => {
+            assert_eq!(lhs, [2, 3]);
+            assert_eq!(rhs, [2]);
+        }
+        other => panic!("{:?}", other),
+    }
+
+    let mut c = Array3::<f32>::ones((2, 3, 4));
+    c[[1, 2, 0]] = 1.5;
+    c[[1, 2, 3]] = 3.0;
+    match almost::ndarray_equal(&c, &array![1.0f32]) {
+        Err(NdMismatch::Values { shape, report }) => {
+            assert_eq!(shape, [2, 3, 4]);
+            assert_eq!(report.total, 24);
+            assert_eq!(report.mismatches, 2);
+            assert_eq!(report.first_mismatch.unwrap().index, [1, 2, 0]);
+            let worst = report.worst.unwrap();
+            assert_eq!(worst.index, [1, 2, 3]);
+            assert_eq!(worst.abs_error, 2.0);
+        }
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_ndarray_axis_tolerances() {
+    use ndarray::Axis;
+    let a = array![[1.0, 1.0, 1.0], [1.0, 1.0, 1.0]];
+    let b = array![1.0 + 1e-6, 1.0 + 1e-3, 1.0];
+    let tols = [1e-5, 1e-2, 1e-12];
+    assert!(almost::ndarray_equal_along(&a, &b, Axis(1), &tols).is_ok());
+    assert!(almost::ndarray_equal_along(&a, &b, Axis(1), &[1e-2]).is_ok());
+    let report = match almost::ndarray_equal_along(&a, &b, Axis(1), &[1e-5, 1e-5, 1e-5]) {
+        Err(NdMismatch::Values { report, .. }) => report,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(report.mismatches, 2);
+    assert_eq!(report.first_mismatch.unwrap().index, [0, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_ndarray_axis_tolerances_wrong_len() {
+    let a = array![[1.0, 1.0, 1.0]];
+    let _ = almost::ndarray_equal_along(&a, &a, ndarray::Axis(1), &[1e-5, 1e-5]);
+}
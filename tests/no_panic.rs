@@ -0,0 +1,62 @@
+#![cfg(feature = "no-panic")]
+
+// These would trip the debug assertions without the `no-panic` feature.
+//
+// In release builds, `cargo test --release --features no-panic --tests` also
+// checks that the scalar comparisons can't panic, by failing to link if they
+// could, and CI runs it. (Doctests aren't optimized, so the check can't pass
+// for them.)
+
+#[test]
+fn test_clamped_tolerance() {
+    // Clamped to just below 1.0, so everything with the same sign is equal.
+    assert!(almost::equal_with(1.0, 1.5, 2.0));
+    assert!(almost::equal_with(1.0f32, 100.0, f32::INFINITY));
+    assert!(!almost::equal_with(1.0, -1.0, 2.0));
+    // Clamped to the machine epsilon.
+    assert!(almost::equal_with(1.0, 1.0, 0.0));
+    assert!(!almost::equal_with(1.0, 1.0 + 1e-14, -1.0));
+    assert!(!almost::equal_with(1.0f32, 1.0001, f32::EPSILON / 4.0));
+    // NaN tolerances reject everything except equal infinities.
+    assert!(!almost::equal_with(1.0, 1.0, f64::NAN));
+    assert!(almost::equal_with(f64::INFINITY, f64::INFINITY, f64::NAN));
+    // And order everything but identical values.
+    use core::cmp::Ordering;
+    assert_eq!(
+        almost::total_cmp_with(1.0, 1.0 + 1e-15, f64::NAN),
+        Ordering::Less
+    );
+    assert_eq!(
+        almost::total_cmp_with(1.0f32, 1.0, f32::NAN),
+        Ordering::Equal
+    );
+    assert_eq!(almost::total_cmp_with(-1.0, 1.0, f64::NAN), Ordering::Less);
+}
+
+#[test]
+fn test_nonpositive_zero_tolerance() {
+    assert!(!almost::zero_with(0.0, 0.0));
+    assert!(!almost::zero_with(0.0f32, -1.0));
+    assert!(!almost::zero_with(0.0, f64::NAN));
+    // No distance is less than a negative one, even for identical vectors.
+    assert!(!almost::weighted_equal(&[1.0], &[1.1], &[1.0], -3.0));
+    assert!(!almost::weighted_equal(&[1.0], &[1.0], &[1.0], 0.0));
+    assert!(!almost::metric_equal(&[1.0], &[1.1], &[1.0], -3.0));
+    assert!(!almost::metric_equal(&[1.0f32], &[1.0], &[1.0], f32::NAN));
+}
+
+#[test]
+fn test_other_tolerances() {
+    assert!(almost::frobenius_equal_with(&[1.0, 2.0], &[1.1, 2.1], 5.0));
+    #[cfg(feature = "alloc")]
+    assert!(almost::roots_equal_with(&[1.0], &[1.0], 0.0));
+    assert!(almost::complex_equal_with(
+        (1.0, 1.0),
+        (1.0, 1.0),
+        almost::ComplexMetric::Magnitude,
+        0.0
+    ));
+    // Clamped to the machine epsilon, with no upper limit.
+    assert!(!almost::equal_ratio_with(1.0, 1.0 + 1e-14, 0.0));
+    assert!(almost::equal_ratio_with(1.0, 1e300, f64::INFINITY));
+}
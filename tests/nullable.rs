@@ -0,0 +1,35 @@
+use almost::{nullable_equal, nullable_iter_equal};
+
+#[test]
+fn test_nullable_equal() {
+    assert!(nullable_equal::<f64>(&[], &[]));
+    assert!(nullable_equal(&[None, None], &[None::<f64>, None]));
+    assert!(!nullable_equal(&[Some(1.0)], &[Some(1.0), None]));
+    assert!(!nullable_equal(&[Some(1.0)], &[Some(1.1)]));
+    assert!(!nullable_equal(&[Some(f64::NAN)], &[Some(f64::NAN)]));
+    assert!(!nullable_iter_equal(vec![None], vec![None::<f32>, None]));
+    assert!(!nullable_iter_equal(vec![Some(0.0)], vec![None::<f32>]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_compare_nullable() {
+    let a = [None, Some(1.0f32), None, Some(2.0), Some(3.0)];
+    let b = [Some(0.0), Some(1.0), None, None, Some(3.0)];
+    let report = almost::compare_nullable(&a, &b);
+    assert_eq!(report.null_mismatches, [0, 3]);
+    assert_eq!(report.values.total, 2);
+    assert!(report.values.passed());
+    assert!(!report.passed());
+    assert!(report
+        .to_string()
+        .contains("nullness differs at 2 positions, first at 0"));
+    assert!(almost::compare_nullable(&a, &a).passed());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[should_panic]
+fn test_compare_nullable_length() {
+    almost::compare_nullable(&[Some(1.0)], &[]);
+}
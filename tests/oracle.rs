@@ -0,0 +1,76 @@
+#![cfg(feature = "oracle")]
+
+use almost::oracle::verify;
+use rand::{prelude::*, rngs::SmallRng};
+
+#[test]
+fn test_oracle_edge_cases() {
+    let tol = almost::F64_TOLERANCE;
+    let special = [
+        0.0,
+        -0.0,
+        5e-324,
+        -5e-324,
+        1e-323,
+        f64::MIN_POSITIVE,
+        f64::MIN_POSITIVE * (1.0 - tol),
+        f64::MIN_POSITIVE * (1.0 - 2.0 * tol),
+        f64::MIN_POSITIVE * (1.0 - 3.67e-11),
+        1.0,
+        1.0 + tol,
+        1.0 - tol / 2.0,
+        f64::MAX,
+        f64::MAX * (1.0 - tol / 2.0),
+        f64::MAX * (1.0 - 2.0 * tol),
+        f64::INFINITY,
+        -f64::INFINITY,
+        f64::NAN,
+    ];
+    for &a in &special {
+        for &b in &special {
+            for &tol in &[tol, 3.67e-11, 1e-3, 2.0 * f64::EPSILON, 0.5] {
+                let v = verify(a, b, tol);
+                assert!(v.agrees(), "{:e} vs {:e} with {:e}: {:?}", a, b, tol, v);
+                let v = verify(a as f32, b as f32, (tol as f32).max(2.0 * f32::EPSILON));
+                assert!(v.agrees(), "{:e} vs {:e} with {:e}: {:?}", a, b, tol, v);
+            }
+        }
+    }
+    let v = verify(f64::MAX, f64::INFINITY, 1e-10);
+    assert!(v.exact && v.rel_err < 1e-15);
+    assert!(!verify(f64::NAN, f64::NAN, 1e-10).exact);
+    assert!(!verify(f64::INFINITY, -f64::INFINITY, 0.5).exact);
+
+    // On the boundary, the exact comparison is strict.
+    let v = verify(1.0, 1.0 - 2.0f64.powi(-20), 2.0f64.powi(-20));
+    assert!(!v.exact && v.borderline && v.agrees());
+    assert_eq!(v.rel_err, 2.0f64.powi(-20));
+
+    // Every bit of the significand counts, including the low ones.
+    let v = verify(1.0 + f64::EPSILON, 1.0, 2.0 * f64::EPSILON);
+    assert!(v.exact && v.agrees());
+    assert_eq!(v.rel_err, f64::EPSILON / (1.0 + f64::EPSILON));
+    let v = verify(
+        f64::MAX,
+        f64::from_bits(f64::MAX.to_bits() - 1),
+        f64::EPSILON,
+    );
+    assert_eq!(v.rel_err, 2.0f64.powi(971) / f64::MAX);
+}
+
+#[test]
+fn test_oracle_random() {
+    let mut rng = SmallRng::seed_from_u64(0x0ac1e);
+    let tolerances = [almost::F64_TOLERANCE, 1e-3, f64::EPSILON * 2.0, 0.5];
+    for _ in 0..20_000 {
+        let a = f64::from_bits(rng.gen());
+        let b = match rng.gen_range(0, 3) {
+            0 => f64::from_bits(rng.gen()),
+            1 => a * (1.0 + rng.gen_range(-1e-2, 1e-2)),
+            _ => f64::from_bits(a.to_bits().wrapping_add(rng.gen_range(0, 1 << 30))),
+        };
+        let tol = tolerances[rng.gen_range(0, tolerances.len())];
+        let v = verify(a, b, tol);
+        assert!(v.agrees(), "{:e} vs {:e} with {:e}: {:?}", a, b, tol, v);
+    }
+}
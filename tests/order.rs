@@ -0,0 +1,192 @@
+use almost::ApproxOrdering::{self, AlmostEqual, Greater, Less};
+use core::cmp::Ordering;
+
+#[test]
+fn test_compare() {
+    assert_eq!(almost::compare(1.0, 1.0), Some(AlmostEqual));
+    assert_eq!(almost::compare(0.1 + 0.2, 0.3), Some(AlmostEqual));
+    assert_eq!(almost::compare(1.0, 1.001), Some(Less));
+    assert_eq!(almost::compare(1.01f32, 1.0), Some(Greater));
+    assert_eq!(almost::compare(-0.0, 0.0), Some(AlmostEqual));
+    assert_eq!(almost::compare(-1.0, 1.0), Some(Less));
+    assert_eq!(
+        almost::compare(f64::INFINITY, f64::INFINITY),
+        Some(AlmostEqual)
+    );
+    assert_eq!(almost::compare(f64::MAX, f64::INFINITY), Some(AlmostEqual));
+    assert_eq!(almost::compare(1.0, f64::INFINITY), Some(Less));
+    assert_eq!(almost::compare(f32::NEG_INFINITY, -1.0), Some(Less));
+    // Subnormals are all almost equal to each other, and to zero.
+    assert_eq!(almost::compare(5e-324, 0.0), Some(AlmostEqual));
+    assert_eq!(almost::compare(f64::NAN, 1.0), None);
+    assert_eq!(almost::compare(1.0, f64::NAN), None);
+    assert_eq!(almost::compare(f32::NAN, f32::NAN), None);
+}
+
+#[test]
+fn test_compare_with() {
+    assert_eq!(almost::compare_with(1.0, 1.05, 0.1), Some(AlmostEqual));
+    assert_eq!(almost::compare_with(1.0, 1.05, 0.01), Some(Less));
+    assert_eq!(almost::compare_with(1.05, 1.0, 0.01), Some(Greater));
+    // Agrees with `equal_with` and `<`.
+    let values = [
+        -2.0f32,
+        -1.0,
+        -1.0001,
+        0.0,
+        1e-30,
+        1.0,
+        1.0001,
+        3.0,
+        f32::MAX,
+    ];
+    for &a in &values {
+        for &b in &values {
+            let ord = almost::compare_with(a, b, 1e-3).unwrap();
+            assert_eq!(ord == AlmostEqual, almost::equal_with(a, b, 1e-3));
+            if ord == Less {
+                assert!(a < b);
+            }
+            assert_eq!(almost::compare_with(b, a, 1e-3), Some(ord.reverse()));
+        }
+    }
+}
+
+#[test]
+fn test_approx_ordering() {
+    assert_eq!(Less.reverse(), Greater);
+    assert_eq!(AlmostEqual.reverse(), AlmostEqual);
+    assert_eq!(Ordering::from(Less), Ordering::Less);
+    assert_eq!(Ordering::from(AlmostEqual), Ordering::Equal);
+    assert_eq!(Ordering::from(ApproxOrdering::Greater), Ordering::Greater);
+}
+
+#[test]
+fn test_inequalities() {
+    assert!(almost::le(0.1 + 0.2, 0.3));
+    assert!(almost::ge(0.3, 0.1 + 0.2));
+    assert!(almost::le(1.0, 2.0));
+    assert!(!almost::le(2.0, 1.0));
+    assert!(almost::ge(2.0f32, 1.0));
+    assert!(!almost::ge(1.0f32, 2.0));
+    assert!(!almost::definitely_less(0.3, 0.1 + 0.2));
+    assert!(!almost::definitely_greater(0.1 + 0.2, 0.3));
+    assert!(almost::definitely_less(1.0, 2.0));
+    assert!(almost::definitely_greater(2.0, 1.0));
+    assert!(almost::le(f64::INFINITY, f64::INFINITY));
+    assert!(almost::definitely_less(f64::NEG_INFINITY, f64::INFINITY));
+    for f in [
+        almost::le::<f64>,
+        almost::ge,
+        almost::definitely_less,
+        almost::definitely_greater,
+    ] {
+        assert!(!f(f64::NAN, 1.0));
+        assert!(!f(1.0, f64::NAN));
+        assert!(!f(f64::NAN, f64::NAN));
+    }
+}
+
+#[test]
+fn test_inequalities_with() {
+    let values = [-1.0f64, -0.999, 0.0, 1e-300, 0.5, 0.5005, 1.0, 2.0];
+    for &a in &values {
+        for &b in &values {
+            let tol = 1e-2;
+            // The strict forms are the negations of the others.
+            assert_eq!(
+                almost::definitely_less_with(a, b, tol),
+                !almost::ge_with(a, b, tol)
+            );
+            assert_eq!(
+                almost::definitely_greater_with(a, b, tol),
+                !almost::le_with(a, b, tol)
+            );
+            assert_eq!(
+                almost::le_with(a, b, tol) && almost::ge_with(a, b, tol),
+                almost::equal_with(a, b, tol)
+            );
+            if a <= b {
+                assert!(almost::le_with(a, b, tol));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_total_cmp() {
+    assert_eq!(almost::total_cmp(0.1 + 0.2, 0.3), Ordering::Equal);
+    assert_eq!(almost::total_cmp(1.0, 1.001), Ordering::Less);
+    assert_eq!(almost::total_cmp(-0.0, 0.0), Ordering::Equal);
+    assert_eq!(almost::total_cmp(-1.0, 1.0), Ordering::Less);
+    assert_eq!(almost::total_cmp(f64::NAN, -f64::NAN), Ordering::Equal);
+    assert_eq!(
+        almost::total_cmp(f64::NAN, f64::INFINITY),
+        Ordering::Greater
+    );
+    assert_eq!(
+        almost::total_cmp(f32::NEG_INFINITY, f32::NAN),
+        Ordering::Less
+    );
+    // Neighbours on either side of a bucket boundary aren't equal.
+    // With a tolerance of `2^-26`, buckets are `2^26` ulps wide.
+    let tol = 1.0 / (1 << 26) as f64;
+    let boundary = f64::from_bits(1.0f64.to_bits() + (1 << 26));
+    let below = f64::from_bits(boundary.to_bits() - 1);
+    assert!(almost::equal_with(below, boundary, tol));
+    assert_eq!(almost::total_cmp_with(below, boundary, tol), Ordering::Less);
+    assert_eq!(almost::total_cmp_with(1.0, below, tol), Ordering::Equal);
+}
+
+#[test]
+fn test_total_cmp_is_total() {
+    // A chain of values in which each is almost equal to the next, but not
+    // to ones much further along.
+    let mut values = vec![f64::NAN, -0.0, 0.0, 1e-320, -1e-320, f64::INFINITY];
+    for i in -10..10 {
+        let x = 1.0 + i as f64 * 1e-9;
+        values.extend_from_slice(&[x, -x, x * 1e300, x * 1e-300]);
+    }
+    let tols = [f64::EPSILON, 1e-9, almost::F64_TOLERANCE, 0.5];
+    for &tol in &tols {
+        let cmp = |a: &f64, b: &f64| almost::total_cmp_with(*a, *b, tol);
+        for a in &values {
+            for b in &values {
+                assert_eq!(cmp(a, b), cmp(b, a).reverse(), "{} {}", a, b);
+                if cmp(a, b) == Ordering::Equal && !a.is_nan() {
+                    assert!(almost::equal_with(*a, *b, tol), "{} {} {}", a, b, tol);
+                }
+                if a < b {
+                    assert_ne!(cmp(a, b), Ordering::Greater, "{} {}", a, b);
+                }
+                for c in &values {
+                    if cmp(a, b) != Ordering::Greater && cmp(b, c) != Ordering::Greater {
+                        assert_ne!(cmp(a, c), Ordering::Greater, "{} {} {}", a, b, c);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_sort_key() {
+    use almost::SortKey;
+    use std::collections::BTreeMap;
+    let mut counts = BTreeMap::new();
+    for &x in &[
+        0.3,
+        0.1 + 0.2,
+        0.1,
+        0.7,
+        0.3 * 3.0 - 0.2,
+        f64::NAN,
+        f64::NAN,
+    ] {
+        *counts.entry(SortKey(x)).or_insert(0) += 1;
+    }
+    let counts: Vec<_> = counts.into_iter().map(|(k, n)| (k.0, n)).collect();
+    assert_eq!(counts.len(), 4);
+    assert_eq!(counts[..3], [(0.1, 1), (0.3, 2), (0.7, 2)]);
+    assert!(counts[3].0.is_nan() && counts[3].1 == 2);
+}
@@ -0,0 +1,52 @@
+use almost::{minimum_image, periodic_equal, periodic_equal_with};
+
+#[test]
+fn test_minimum_image() {
+    assert_eq!(minimum_image(1.0, 1.0, 4.0), 0.0);
+    assert_eq!(minimum_image(3.5, 0.5, 4.0), -1.0);
+    assert_eq!(minimum_image(-0.5, 3.5, 4.0), 0.0);
+    assert_eq!(minimum_image(0.0f64, 2.0, 4.0).abs(), 2.0);
+    // Coordinates which have wrapped around several times.
+    assert_eq!(minimum_image(41.0, -2.5, 4.0), -0.5);
+    assert!(almost::equal(minimum_image(-3.9, 3.9, 4.0), 0.2));
+    assert!(almost::equal(minimum_image(3.9, -3.9, 4.0), -0.2));
+    assert_eq!(minimum_image(1.0f32, 5.0, f32::INFINITY), -4.0);
+    assert!(minimum_image(1.0f64, 2.0, 0.0).is_nan());
+    assert!(minimum_image(1.0f64, 2.0, -1.0).is_nan());
+    assert!(minimum_image(f64::NAN, 2.0, 3.0).is_nan());
+    assert!(minimum_image(1.0, 2.0, f64::NAN).is_nan());
+}
+
+#[test]
+fn test_periodic_equal() {
+    let box_ = [10.0, 20.0];
+    assert!(periodic_equal(&[1.0, 2.0], &[1.0, 2.0], &box_));
+    assert!(periodic_equal(&[10.0, 2.0], &[0.0, 2.0], &box_));
+    assert!(periodic_equal(&[11.0, -18.0], &[1.0, 2.0], &box_));
+    assert!(periodic_equal(&[1e-12, 20.0 - 1e-12], &[0.0, 0.0], &box_));
+    assert!(!periodic_equal(&[5.0, 2.0], &[5.0, 12.0], &box_));
+    assert!(!periodic_equal(&[0.1, 2.0], &[9.9, 2.0], &box_));
+    assert!(!periodic_equal(&[f64::NAN, 2.0], &[0.0, 2.0], &box_));
+    assert!(!periodic_equal(&[1.0, 2.0], &[1.0], &box_));
+
+    // A mix of periodic and open axes.
+    let slab = [1.0f32, f32::INFINITY];
+    assert!(periodic_equal_with(
+        &[0.99, 1e6],
+        &[0.0, 1.001e6],
+        &slab,
+        0.02
+    ));
+    assert!(!periodic_equal_with(
+        &[0.99, 1e6],
+        &[0.0, 1.1e6],
+        &slab,
+        0.02
+    ));
+}
+
+#[test]
+#[should_panic]
+fn test_periodic_equal_periods_len() {
+    periodic_equal(&[1.0, 2.0], &[1.0, 2.0], &[1.0]);
+}
@@ -0,0 +1,80 @@
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use almost::{pose_equal, Pose, PoseTolerance};
+
+/// A rotation by `angle` radians about the unit vector `axis`, as `[x, y, z, w]`.
+fn rotation(axis: [f64; 3], angle: f64) -> [f64; 4] {
+    let (s, c) = (angle / 2.0).sin_cos();
+    [axis[0] * s, axis[1] * s, axis[2] * s, c]
+}
+
+#[test]
+fn test_pose_rotation() {
+    let tol = PoseTolerance::new(1.0, 1e-6, 1e-3);
+    let a = Pose::new([0.0; 3], rotation([0.0, 0.0, 1.0], 1.0));
+    for &(delta, ok) in &[
+        (0.0, true),
+        (0.9e-3, true),
+        (-0.9e-3, true),
+        (1.1e-3, false),
+    ] {
+        let b = Pose::new([0.0; 3], rotation([0.0, 0.0, 1.0], 1.0 + delta));
+        assert_eq!(pose_equal(&a, &b, tol), ok, "{}", delta);
+    }
+    // About a different axis.
+    let b = Pose::new([0.0; 3], rotation([0.6, 0.8, 0.0], 0.9e-3));
+    let id = Pose::new([0.0; 3], [0.0, 0.0, 0.0, 1.0]);
+    assert!(pose_equal(&id, &b, tol));
+    assert!(!pose_equal(&a, &b, tol));
+    // Larger angles, and unnormalized quaternions.
+    let tol = PoseTolerance::new(1.0, 1e-6, 0.5);
+    let b = Pose::new([0.0; 3], rotation([0.0, 0.0, 1.0], 1.45));
+    let mut scaled = b;
+    scaled.rotation.iter_mut().for_each(|q| *q *= -3.0);
+    assert!(pose_equal(&a, &b, tol) && pose_equal(&a, &scaled, tol));
+    let b = Pose::new([0.0; 3], rotation([0.0, 0.0, 1.0], 1.55));
+    assert!(!pose_equal(&a, &b, tol));
+}
+
+#[test]
+fn test_pose_translation() {
+    let q = [0.0, 0.0, 0.0, 1.0];
+    let tol = PoseTolerance::new(10.0, 1e-4, 1e-3);
+    let a = Pose::new([0.0, 0.0, 0.0], q);
+    assert!(pose_equal(&a, &Pose::new([0.0006, 0.0, 0.0008], q), tol));
+    assert!(!pose_equal(&a, &Pose::new([0.0006, 0.0, 0.00081], q), tol));
+    // Far from the origin, the same absolute tolerance applies.
+    let a = Pose::new([1e3, -2e3, 0.0], q);
+    assert!(pose_equal(
+        &a,
+        &Pose::new([1e3 + 0.0009, -2e3, 0.0], q),
+        tol
+    ));
+    assert!(!pose_equal(
+        &a,
+        &Pose::new([1e3 + 0.0011, -2e3, 0.0], q),
+        tol
+    ));
+}
+
+#[test]
+fn test_pose_special() {
+    let tol = PoseTolerance::new(1.0, 1e-3, 1e-3);
+    let q = [0.0, 0.0, 0.0, 1.0];
+    let a = Pose::new([1.0, 2.0, 3.0], q);
+    assert!(!pose_equal(&a, &Pose::new([1.0, 2.0, 3.0], [0.0; 4]), tol));
+    assert!(!pose_equal(&a, &Pose::new([1.0, f64::NAN, 3.0], q), tol));
+    assert!(!pose_equal(
+        &a,
+        &Pose::new([1.0, f64::INFINITY, 3.0], q),
+        tol
+    ));
+    assert!(!pose_equal(
+        &a,
+        &Pose::new([1.0, 2.0, 3.0], [0.0, f64::NAN, 0.0, 1.0]),
+        tol
+    ));
+    let far = Pose::new([f64::MAX, 0.0, 0.0], q);
+    assert!(pose_equal(&far, &far, tol));
+    assert!(pose_equal(&a, &a, tol));
+}
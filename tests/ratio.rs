@@ -0,0 +1,45 @@
+use almost::{equal_ratio, equal_ratio_with};
+
+#[test]
+fn test_equal_ratio() {
+    assert!(equal_ratio(1.0f64, 1.0 + 1e-15));
+    assert!(equal_ratio(-2.0f32, -2.0 * (1.0 + f32::EPSILON)));
+    assert!(!equal_ratio(1.0f64, 1.001));
+    // Symmetric, unlike `|a / b - 1|`.
+    for &(a, b) in &[(1.0f64, 1.5), (1.5, 1.0), (-3.0, -2.0), (-2.0, -3.0)] {
+        assert!(equal_ratio_with(a, b, 0.51));
+        assert!(!equal_ratio_with(a, b, 0.49));
+    }
+    // Large tolerances are fine.
+    assert!(equal_ratio_with(1.0f64, 99.0, 99.0));
+    assert!(!equal_ratio_with(1.0f64, 101.0, 99.0));
+}
+
+#[test]
+fn test_equal_ratio_special() {
+    let inf = f64::INFINITY;
+    assert!(equal_ratio(0.0f64, 0.0));
+    assert!(equal_ratio(0.0f64, -0.0));
+    assert!(!equal_ratio_with(0.0f64, 1e-300, 1e10));
+    assert!(!equal_ratio_with(-1.0f64, 1.0, 1e10));
+    assert!(!equal_ratio_with(-1e-300f64, 0.0, 1e10));
+    assert!(equal_ratio(inf, inf));
+    assert!(!equal_ratio(inf, -inf));
+    assert!(!equal_ratio_with(inf, f64::MAX, 1e10));
+    assert!(!equal_ratio(f64::NAN, f64::NAN));
+    assert!(!equal_ratio_with(f64::NAN, 1.0, 1e10));
+    // Huge and tiny magnitudes don't overflow or underflow.
+    assert!(equal_ratio_with(f64::MAX, f64::MAX / 1.5, 0.6));
+    assert!(!equal_ratio_with(f64::MAX, 1e-300, 1e10));
+    assert!(!equal_ratio_with(1e-300, f64::MAX, 1e10));
+    // Subnormals are compared as they are, not rescaled.
+    let tiny = f64::from_bits(1);
+    assert!(equal_ratio_with(tiny * 100.0, tiny * 101.0, 0.02));
+    assert!(!equal_ratio_with(tiny * 100.0, tiny * 103.0, 0.02));
+    assert!(!equal_ratio_with(tiny, tiny * 2.0, 0.5));
+    assert!(equal_ratio_with(
+        f64::MIN_POSITIVE,
+        f64::MIN_POSITIVE * 1.01,
+        0.02
+    ));
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "alloc")]
+
+#[test]
+fn test_roots_clusters() {
+    // A triple root perturbed by about tol^(1/3).
+    let tol = 1e-12;
+    let d = 5e-5;
+    let found = [2.0, 1.0 + d, 1.0 - d / 2.0, 1.0 - d / 2.0];
+    let expected = [1.0, 1.0, 1.0, 2.0];
+    assert!(almost::roots_equal_with(&found, &expected, tol));
+    // The same spread isn't acceptable for a simple root.
+    assert!(!almost::roots_equal_with(&[1.0 + d], &[1.0], tol));
+    // Multiplicity must be honored.
+    assert!(!almost::roots_equal_with(
+        &[1.0, 1.0, 2.0],
+        &[1.0, 2.0, 2.0],
+        tol
+    ));
+    assert!(!almost::roots_equal_with(
+        &[1.0, 2.0],
+        &[1.0, 2.0, 2.0],
+        tol
+    ));
+}
+
+#[test]
+fn test_roots_special_values() {
+    assert!(almost::roots_equal::<f64>(&[], &[]));
+    assert!(almost::roots_equal(&[0.0, 0.0], &[0.0, -0.0]));
+    assert!(!almost::roots_equal(&[f64::NAN], &[f64::NAN]));
+    assert!(almost::roots_equal(
+        &[f64::INFINITY, 1.0],
+        &[1.0, f64::INFINITY]
+    ));
+    assert!(!almost::roots_equal(&[f64::INFINITY], &[-f64::INFINITY]));
+    // Small values are compared relative to the largest.
+    assert!(almost::roots_equal(&[1e-20, 1.0], &[1.0, -1e-20]));
+    assert!(almost::complex_roots_equal(
+        &[(0.5f32, 3e-8), (0.5, -3e-8)],
+        &[(0.5, 0.0), (0.5, 0.0)]
+    ));
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "alloc")]
+use almost::{shrink_failure, shrink_failure_with};
+
+#[test]
+fn test_shrink_failure() {
+    let expected: Vec<f64> = (0..1000).map(|i| i as f64 * 0.25).collect();
+    let mut actual = expected.clone();
+    assert!(shrink_failure(&actual, &expected).is_none());
+    assert!(shrink_failure::<f32>(&[], &[]).is_none());
+    actual[17] = 1.0;
+    actual[400] = f64::NAN;
+    actual[401] = expected[401] * (1.0 + 1e-15);
+    actual[999] = -f64::INFINITY;
+    let repro = shrink_failure(&actual, &expected).unwrap();
+    assert_eq!(repro.len, 1000);
+    assert_eq!(repro.window, 17..1000);
+    let indices: Vec<usize> = repro.violations.iter().map(|d| d.index).collect();
+    assert_eq!(indices, [17, 400, 999]);
+    assert_eq!(repro.rhs(), [4.25, 100.0, 249.75]);
+    assert_eq!(repro.violations[0].abs_error, 3.25);
+    assert!(repro.lhs()[1].is_nan());
+}
+
+#[test]
+fn test_shrink_failure_display() {
+    let repro = shrink_failure_with(
+        &[1.0f32, 0.1, 3.0, 5.0],
+        &[1.0, 0.2, 3.0, f32::INFINITY],
+        0.01,
+    )
+    .unwrap();
+    assert_eq!(repro.window, 1..4);
+    assert_eq!(
+        repro.to_string(),
+        "\
+#[test]
+fn reproduce_mismatch() {
+    // 2 of 4 elements differ, in 1..4, at indices [1, 3].
+    let lhs: [f32; 2] = [0.1, 5.0];
+    let rhs: [f32; 2] = [0.2, f32::INFINITY];
+    for (a, b) in lhs.iter().zip(&rhs) {
+        assert!(almost::equal_with(*a, *b, 0.01), \"{} vs {}\", a, b);
+    }
+}"
+    );
+    // Values round-trip exactly, so single-ulp differences are reproduced.
+    let a = 1.0f64 + 4.0 * f64::EPSILON;
+    let repro = shrink_failure_with(&[a], &[1.0], f64::EPSILON).unwrap();
+    assert!(repro.to_string().contains("[1.0000000000000009]"));
+}
+
+#[test]
+#[should_panic]
+fn test_shrink_failure_lengths() {
+    shrink_failure(&[1.0, 2.0], &[1.0]);
+}
@@ -0,0 +1,32 @@
+#![cfg(any(feature = "std", feature = "libm"))]
+use almost::{equal_snr, snr_db};
+
+#[test]
+fn test_snr_db() {
+    let reference: Vec<f64> = (0..100).map(|i| (i as f64 * 0.1).sin()).collect();
+    // An error of 1% of the signal everywhere is exactly 40 dB.
+    let scaled: Vec<f64> = reference.iter().map(|x| x * 1.01).collect();
+    assert!(almost::equal_with(snr_db(&reference, &scaled), 40.0, 1e-9));
+    assert!(equal_snr(&reference, &scaled, 39.9));
+    assert!(!equal_snr(&reference, &scaled, 40.1));
+    // Huge and tiny signals don't overflow or underflow.
+    let huge: Vec<f64> = reference.iter().map(|x| x * 1e300).collect();
+    let huge_scaled: Vec<f64> = scaled.iter().map(|x| x * 1e300).collect();
+    assert!(almost::equal_with(snr_db(&huge, &huge_scaled), 40.0, 1e-9));
+    let tiny: Vec<f32> = reference.iter().map(|&x| x as f32 * 1e-40).collect();
+    let tiny_scaled: Vec<f32> = tiny.iter().map(|x| x * 1.01).collect();
+    assert!(equal_snr(&tiny, &tiny_scaled, 30.0));
+}
+
+#[test]
+fn test_snr_db_degenerate() {
+    let a = [1.0f64, -1.0, 0.5];
+    assert_eq!(snr_db(&a, &a), f64::INFINITY);
+    assert_eq!(snr_db(&[0.0f64; 3], &[0.0; 3]), f64::INFINITY);
+    assert_eq!(snr_db(&[0.0f64; 3], &a), f64::NEG_INFINITY);
+    assert!(snr_db(&a, &a[..2]).is_nan());
+    assert!(snr_db(&a, &[1.0, f64::NAN, 0.5]).is_nan());
+    assert!(snr_db(&a, &[1.0, f64::INFINITY, 0.5]).is_nan());
+    assert!(!equal_snr(&a, &a[..2], 0.0));
+    assert!(equal_snr(&a, &a, 1000.0));
+}
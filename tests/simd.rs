@@ -0,0 +1,51 @@
+#![cfg(almost_portable_simd)]
+#![feature(portable_simd)]
+
+use almost::{simd_equal_lanes_f32, simd_equal_lanes_f64, simd_zero_lanes_f64};
+use std::simd::{f32x4, f64x2, f64x4, Mask};
+
+#[test]
+fn test_simd() {
+    let a = f32x4::from_array([1.0, 0.1 + 0.2, 1e30, -5.0]);
+    let b = f32x4::from_array([1.0, 0.3, 1e30 * (1.0 + 1e-7), -5.0]);
+    assert!(almost::equal(a, b));
+    let c = f32x4::from_array([1.0, 0.3, 1e29, -5.0]);
+    assert!(!almost::equal(a, c));
+    assert_eq!(
+        simd_equal_lanes_f32(a, c, almost::F32_TOLERANCE),
+        Mask::from_array([true, true, false, true])
+    );
+
+    let inf = f64x2::from_array([f64::INFINITY, f64::NAN]);
+    assert_eq!(
+        simd_equal_lanes_f64(inf, inf, 1e-6),
+        Mask::from_array([true, false])
+    );
+    assert!(almost::zero(f64x2::from_array([1e-20, -0.0])));
+    assert_eq!(
+        simd_zero_lanes_f64(f64x2::from_array([1e-20, 1.0]), 1e-10),
+        Mask::from_array([true, false])
+    );
+}
+
+#[test]
+fn test_simd_small_scale() {
+    // Tiny values are rescaled, so they agree with the scalar comparison.
+    let a = [f64::MIN_POSITIVE, 1e-300, 0.0, 5e-324];
+    let b = [
+        2.225071633433343e-308,
+        1e-300 * (1.0 + 1e-7),
+        1e-310,
+        1e-323,
+    ];
+    let lanes = simd_equal_lanes_f64(f64x4::from_array(a), f64x4::from_array(b), 1e-6);
+    for i in 0..4 {
+        assert_eq!(
+            lanes.test(i),
+            almost::equal_with(a[i], b[i], 1e-6),
+            "lane {}",
+            i
+        );
+    }
+    assert_eq!(lanes, Mask::from_array([true, true, false, true]));
+}
@@ -0,0 +1,135 @@
+use almost::{all_almost_equal, all_almost_equal_with, Agreement};
+
+#[test]
+fn test_all_almost_equal() {
+    for &a in &[Agreement::Pairwise, Agreement::WithMean] {
+        assert!(all_almost_equal::<f64>(&[], a));
+        assert!(all_almost_equal(&[5.0], a));
+        assert!(!all_almost_equal(&[f64::NAN], a));
+        assert!(!all_almost_equal(&[1.0, f64::NAN, 1.0], a));
+        assert!(!all_almost_equal(&[-1.0, 1.0], a));
+        assert!(all_almost_equal(&[0.0, -0.0, 0.0], a));
+        assert!(all_almost_equal(&[f64::INFINITY; 3], a));
+        assert!(all_almost_equal(&[f64::MAX, f64::MAX, f64::MAX], a));
+        assert!(all_almost_equal(&[-3.0, -3.0 - 1e-12, -3.0 + 1e-12], a));
+    }
+    // 1.0 and 1.18 are each within 10% of the mean, but not of each other.
+    let v = [1.0f32, 1.18];
+    assert!(all_almost_equal_with(&v, Agreement::WithMean, 0.1));
+    assert!(!all_almost_equal_with(&v, Agreement::Pairwise, 0.1));
+}
+
+#[test]
+fn test_almost_constant() {
+    assert_eq!(almost::almost_constant::<f64>(&[]), None);
+    let s = almost::almost_constant(&[2.0]).unwrap();
+    assert!(s.is_constant && s.relative == 0.0);
+    let s = almost::almost_constant(&[f64::NAN, 1.0, 3.0]).unwrap();
+    assert!(!s.is_constant && s.relative.is_nan());
+    assert_eq!((s.min, s.max), (1.0, 3.0));
+    let s = almost::almost_constant(&[-1.0, 1.0]).unwrap();
+    assert!(!s.is_constant);
+    assert_eq!(s.relative, 2.0);
+}
+
+#[test]
+fn test_zero_scans() {
+    use almost::{all_almost_zero, all_almost_zero_with, any_almost_zero, any_almost_zero_with};
+    let mut v = vec![0.0f64; 100];
+    for (i, x) in v.iter_mut().enumerate() {
+        *x = if i % 2 == 0 { 1e-20 } else { -1e-30 };
+    }
+    assert_eq!(all_almost_zero(&v), Ok(()));
+    assert_eq!(any_almost_zero(&v), Some(0));
+    assert_eq!(all_almost_zero::<f64>(&[]), Ok(()));
+    assert_eq!(any_almost_zero::<f64>(&[]), None);
+    // Every position, including the remainder after the last full chunk.
+    for i in 0..v.len() {
+        let mut w = v.clone();
+        w[i] = 1.0;
+        assert_eq!(all_almost_zero(&w), Err(i));
+        w[i] = f64::NAN;
+        assert_eq!(all_almost_zero(&w), Err(i));
+        let mut w = vec![1.0f32; v.len()];
+        w[i] = -0.0;
+        assert_eq!(any_almost_zero(&w), Some(i));
+        w[i] = f32::NEG_INFINITY;
+        assert_eq!(any_almost_zero(&w), None);
+    }
+    assert_eq!(all_almost_zero_with(&[0.5, -0.5], 0.6), Ok(()));
+    assert_eq!(all_almost_zero_with(&[0.5, -0.7], 0.6), Err(1));
+    assert_eq!(any_almost_zero_with(&[1.0f32, 0.5], 0.5), None);
+    assert_eq!(all_almost_zero_with(&[1e300, 1.0], f64::INFINITY), Ok(()));
+    assert_eq!(
+        all_almost_zero_with(&[1.0, f64::INFINITY], f64::INFINITY),
+        Err(1)
+    );
+}
+
+#[test]
+fn test_equal_slices() {
+    assert!(almost::equal_slices::<f64>(&[], &[]));
+    assert!(!almost::equal_slices(&[1.0f32], &[]));
+    assert!(almost::equal_slices(&[0.1 + 0.2; 40], &[0.3; 40]));
+    assert!(!almost::equal_slices(&[f64::NAN; 20], &[f64::NAN; 20]));
+    // Agrees with the scalar comparison, wherever a pair is in the slices.
+    let pairs = [
+        (1.0, 1.0 + 1e-12),
+        (1.0, 1.001),
+        (0.0, -0.0),
+        (0.0, 1e-300),
+        (5e-324, 1e-310),
+        (1e-300, 1.00000001e-300),
+        (f64::MAX, f64::INFINITY),
+        (f64::INFINITY, f64::INFINITY),
+        (f64::NEG_INFINITY, f64::INFINITY),
+        (f64::MAX, -f64::MAX),
+        (1.0, f64::NAN),
+        (-2.0, 2.0),
+    ];
+    for &len in &[1, 15, 16, 17, 40, 64] {
+        for at in 0..len {
+            for &(a, b) in &pairs {
+                let mut lhs: Vec<f64> = (0..len).map(|i| i as f64 * 1.5 - 7.0).collect();
+                let mut rhs = lhs.clone();
+                lhs[at] = a;
+                rhs[at] = b;
+                assert_eq!(
+                    almost::equal_slices(&lhs, &rhs),
+                    almost::equal(a, b),
+                    "{:?} vs {:?} at {} of {}",
+                    a,
+                    b,
+                    at,
+                    len
+                );
+                assert_eq!(
+                    almost::equal_slices_with(&rhs, &lhs, 1e-3),
+                    almost::equal_with(b, a, 1e-3)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_equal_slices_f32() {
+    let lhs: Vec<f32> = (0..1000).map(|i| (i as f32).sqrt()).collect();
+    let mut rhs: Vec<f32> = lhs.iter().map(|v| v * (1.0 + 1e-6)).collect();
+    assert!(almost::equal_slices(&lhs, &rhs));
+    assert!(!almost::equal_slices_with(&lhs, &rhs, f32::EPSILON));
+    // Subnormals are compared relative to `MIN_POSITIVE`.
+    rhs[500] = 1e-40;
+    rhs[501] = -1e-40;
+    let mut lhs = lhs;
+    lhs[500] = 0.0;
+    lhs[501] = 1e-42;
+    assert!(!almost::equal_slices(&lhs, &rhs));
+    assert!(almost::equal_slices_with(&lhs, &rhs, 0.01));
+    rhs[500] = 0.0;
+    rhs[501] = 1e-42;
+    rhs[999] = f32::INFINITY;
+    assert!(!almost::equal_slices(&lhs, &rhs));
+    lhs[999] = f32::INFINITY;
+    assert!(almost::equal_slices(&lhs, &rhs));
+}
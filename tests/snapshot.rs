@@ -0,0 +1,82 @@
+#![cfg(feature = "insta")]
+use almost::{snapshot_redaction, snapshot_redaction_with, SnapshotComparator};
+use std::collections::BTreeMap;
+
+#[test]
+fn test_snapshot_redaction() {
+    let mut settings = insta::Settings::new();
+    settings.add_redaction(".**", snapshot_redaction());
+    let mut map = BTreeMap::new();
+    map.insert("mass", vec![Some(0.1f64 + 0.2), None]);
+    map.insert("tiny", vec![Some(-1e-20)]);
+    let value = (map, [0.1f32 + 0.2, 5.0], f64::INFINITY, 7u32);
+    settings.bind(|| {
+        insta::assert_yaml_snapshot!(value, @r"
+        - mass:
+            - 0.3
+            - ~
+          tiny:
+            - 0
+        - - 0.3
+          - 5
+        - inf
+        - 7
+        ");
+    });
+}
+
+#[test]
+fn test_snapshot_redaction_with() {
+    let mut settings = insta::Settings::new();
+    settings.add_redaction(".values", snapshot_redaction_with(1e-2, 1e-3));
+    let value = BTreeMap::from([
+        ("values", [1.234f64, -0.0005, 88.88]),
+        ("raw", [1.234, 0.0, 0.0]),
+    ]);
+    settings.bind(|| {
+        insta::assert_yaml_snapshot!(value, @r"
+        raw:
+          - 1.234
+          - 0
+          - 0
+        values:
+          - 1.2
+          - 0
+          - 89
+        ");
+    });
+}
+
+#[test]
+fn test_snapshot_comparator_text() {
+    let c = SnapshotComparator::with_tolerance(1e-6, 1e-9);
+    assert!(c.text_matches("x = 1.0, y = -2.5e3", "x = 1.0000001, y = -2500.0000001"));
+    assert!(c.text_matches("[0.0, 1e-12]", "[-3e-10, 0]"));
+    assert!(c.text_matches("", ""));
+    assert!(!c.text_matches("x = 1.0", "x = 1.01"));
+    assert!(!c.text_matches("x = 1.0", "y = 1.0"));
+    assert!(!c.text_matches("x = 1.0", "x = 1.0 "));
+    assert!(!c.text_matches("1.0", "-1.0"));
+    // Digits in identifiers aren't numbers.
+    assert!(!c.text_matches("v2", "v2.0000001"));
+    assert!(c.text_matches("v2 = 3", "v2 = 3.0000001"));
+    assert!(!c.text_matches("v1 = 3", "v2 = 3"));
+    // Neither are `e`s which aren't followed by an exponent.
+    assert!(c.text_matches("5e", "5.0000001e"));
+    assert!(c.text_matches("1.5.3", "1.5000001.3"));
+    assert!(!c.text_matches("NaN", "nan"));
+}
+
+#[test]
+fn test_snapshot_comparator() {
+    let mut settings = insta::Settings::new();
+    settings.set_comparator(Box::new(SnapshotComparator::new()));
+    settings.bind(|| {
+        let total: f64 = (0..10).map(|_| 0.1).sum();
+        insta::assert_snapshot!(format!("total = {}", total), @"total = 1.0");
+        insta::assert_yaml_snapshot!([total, 1e-17], @r"
+        - 1
+        - 0
+        ");
+    });
+}
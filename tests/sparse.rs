@@ -0,0 +1,122 @@
+#[test]
+fn test_sparse_equal() {
+    let a = [(1, 1.0), (4, 0.1 + 0.2)];
+    assert!(almost::sparse_equal(&a, &[(1, 1.0), (4, 0.3)]));
+    assert!(almost::sparse_equal(&a, &[(1, 1.0), (2, 1e-30), (4, 0.3)]));
+    assert!(!almost::sparse_equal(&a, &[(1, 1.0), (2, 1e-3), (4, 0.3)]));
+    assert!(!almost::sparse_equal(&a, &[(1, 1.0), (3, 0.3)]));
+    assert!(!almost::sparse_equal(&a, &[(1, 1.0)]));
+    assert!(almost::sparse_equal::<i32, f64>(&[], &[]));
+    assert!(almost::sparse_equal(&[], &[(0, 0.0f32), (9, -1e-20)]));
+}
+
+#[test]
+fn test_sparse_unsorted() {
+    let sorted = [(0, 1.0), (2, 2.0), (5, 3.0)];
+    let unsorted = [(0, 1.0), (5, 3.0), (2, 2.0)];
+    assert!(!almost::sparse_equal(&sorted, &unsorted));
+    assert!(!almost::sparse_equal(&unsorted, &sorted));
+    assert!(!almost::sparse_equal(&unsorted, &unsorted));
+    // Duplicates are rejected too, even if they'd sum to the other side.
+    let dup = [(0, 1.0), (2, 1.0), (2, 1.0), (5, 3.0)];
+    assert!(!almost::sparse_equal(&dup, &sorted));
+    assert!(!almost::sparse_equal(&sorted, &dup));
+}
+
+#[test]
+fn test_sparse_nan() {
+    let a = [(0, 1.0), (3, f64::NAN)];
+    assert!(!almost::sparse_equal(&a, &a));
+    assert!(!almost::sparse_equal(&a, &[(0, 1.0)]));
+    assert!(!almost::sparse_equal(&[(0, 1.0)], &a));
+    assert!(!almost::sparse_equal_with(&a, &[(0, 1.0)], 0.5, 1e10));
+}
+
+#[cfg(feature = "sprs")]
+#[test]
+fn test_sprs() {
+    let a = sprs::CsVec::new(4, vec![1, 3], vec![1.0, f64::NAN]);
+    assert!(!almost::sprs_vec_equal(a.view(), a.view()));
+    let b = sprs::CsVec::new(5, vec![1], vec![1.0]);
+    let c = sprs::CsVec::new(4, vec![1], vec![1.0]);
+    assert!(!almost::sprs_vec_equal(b.view(), c.view()));
+
+    let mut t = sprs::TriMat::new((2, 3));
+    t.add_triplet(0, 1, 0.1 + 0.2);
+    t.add_triplet(1, 2, 1e-20);
+    let (csr, csc) = (t.to_csr::<usize>(), t.to_csc::<usize>());
+    let mut t = sprs::TriMat::new((2, 3));
+    t.add_triplet(0, 1, 0.3);
+    assert!(almost::sprs_mat_equal(
+        csr.view(),
+        t.to_csr::<usize>().view()
+    ));
+    assert!(almost::sprs_mat_equal(
+        csc.view(),
+        t.to_csr::<usize>().view()
+    ));
+    assert!(!almost::sprs_mat_equal_with(
+        csc.view(),
+        t.to_csr().view(),
+        1e-3,
+        1e-30
+    ));
+    let other = sprs::CsMat::<f64>::zero((3, 2));
+    assert!(!almost::sprs_mat_equal(csr.view(), other.view()));
+}
+
+#[cfg(feature = "nalgebra-sparse")]
+#[test]
+fn test_nalgebra_sparse() {
+    use nalgebra_sparse::{CooMatrix, CscMatrix, CsrMatrix};
+    let mut a = CooMatrix::new(2, 3);
+    a.push(0, 1, 0.1 + 0.2);
+    a.push(1, 0, 2.0);
+    a.push(1, 2, 1e-20);
+    let mut b = CooMatrix::new(2, 3);
+    b.push(0, 1, 0.3);
+    b.push(1, 0, 2.0);
+    assert!(almost::csr_equal(
+        &CsrMatrix::from(&a),
+        &CsrMatrix::from(&b)
+    ));
+    assert!(almost::csc_equal(
+        &CscMatrix::from(&a),
+        &CscMatrix::from(&b)
+    ));
+    b.push(1, 1, 1e-3);
+    assert!(!almost::csr_equal(
+        &CsrMatrix::from(&a),
+        &CsrMatrix::from(&b)
+    ));
+    assert!(!almost::csc_equal(
+        &CscMatrix::from(&a),
+        &CscMatrix::from(&b)
+    ));
+    let (csr, csc) = (CsrMatrix::from(&b), CscMatrix::from(&b));
+    assert!(almost::csr_equal_with(
+        &CsrMatrix::from(&a),
+        &csr,
+        1e-6,
+        1e-2
+    ));
+    assert!(almost::csc_equal_with(
+        &CscMatrix::from(&a),
+        &csc,
+        1e-6,
+        1e-2
+    ));
+
+    a.push(0, 0, f64::NAN);
+    assert!(!almost::csr_equal(
+        &CsrMatrix::from(&a),
+        &CsrMatrix::from(&a)
+    ));
+    assert!(!almost::csc_equal(
+        &CscMatrix::from(&a),
+        &CscMatrix::from(&a)
+    ));
+    let (tall, wide) = (CsrMatrix::<f32>::zeros(3, 2), CsrMatrix::zeros(2, 3));
+    assert!(!almost::csr_equal(&tall, &wide));
+    assert!(almost::csr_equal(&tall, &tall));
+}
@@ -0,0 +1,51 @@
+#![cfg(any(feature = "std", feature = "libm"))]
+use almost::{means_equal, means_equal_with, SampleStats};
+
+fn stats(mean: f64, variance: f64, count: usize) -> SampleStats<f64> {
+    SampleStats {
+        mean,
+        variance,
+        count,
+    }
+}
+
+#[test]
+fn test_from_samples() {
+    let s = SampleStats::from_samples(&[2.0f32, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+    assert_eq!(s.count, 8);
+    assert_eq!(s.mean, 5.0);
+    assert!(almost::equal(s.variance, 32.0 / 7.0));
+    let s = SampleStats::from_samples(&[3.0f64]);
+    assert_eq!(s.mean, 3.0);
+    assert!(s.variance.is_nan());
+    assert_eq!(SampleStats::<f64>::from_samples(&[]).count, 0);
+}
+
+#[test]
+fn test_means_equal() {
+    // Standard error of the difference is sqrt(1 * (1/100 + 1/100)) ~= 0.1414.
+    let a = stats(10.0, 1.0, 100);
+    assert!(means_equal(a, stats(10.4, 1.0, 100)));
+    assert!(!means_equal(a, stats(10.5, 1.0, 100)));
+    assert!(!means_equal_with(a, stats(10.2, 1.0, 100), 1.0));
+    assert!(means_equal_with(a, stats(10.2, 1.0, 100), 1.5));
+    // Larger samples give tighter bounds.
+    assert!(!means_equal(
+        stats(10.0, 1.0, 10_000),
+        stats(10.1, 1.0, 10_000)
+    ));
+}
+
+#[test]
+fn test_means_equal_degenerate() {
+    assert!(means_equal(stats(1.0, 0.0, 5), stats(1.0, 0.0, 5)));
+    assert!(!means_equal(stats(1.0, 0.0, 5), stats(1.1, 0.0, 5)));
+    assert!(!means_equal(stats(1.0, 1.0, 0), stats(1.0, 1.0, 100)));
+    assert!(!means_equal(
+        stats(1.0, f64::NAN, 1),
+        stats(1.0, f64::NAN, 1)
+    ));
+    // A single value can be compared against a larger sample.
+    assert!(means_equal(stats(1.0, f64::NAN, 1), stats(1.0, 0.5, 10)));
+    assert!(!means_equal(stats(1.0, 1.0, 10), stats(f64::NAN, 1.0, 10)));
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "std")]
+use almost::{compare_tables, compare_tables_with, TableError, TableSide};
+
+const GOLDEN: &str = "\
+# time step output
+t, x, y
+
+0.0, 1.0, 2.0
+0.5, 1.5, 2.5
+1.0, 2.0, 0.001
+";
+
+#[test]
+fn test_compare_tables() {
+    let r = compare_tables(GOLDEN, GOLDEN).unwrap();
+    assert!(r.passed());
+    assert_eq!(r.values.total, 9);
+    assert_eq!(r.column_name(2), Some("y"));
+    assert_eq!(r.column_name(3), None);
+
+    let output = "t,x,y\n0,1,2\n0.5,1.5,2.5000000000001\n1,2.1,0.001\n";
+    let r = compare_tables(output, GOLDEN).unwrap();
+    assert_eq!(r.values.mismatches, 1);
+    assert_eq!(r.values.first_mismatch.as_ref().unwrap().index, (2, 1));
+    let text = r.to_string();
+    assert!(text.contains("first mismatch in column \"x\""), "{}", text);
+    assert!(compare_tables_with(output, GOLDEN, 0.1).unwrap().passed());
+}
+
+#[test]
+fn test_compare_tables_whitespace() {
+    let lhs = "1.0 2.0\t3.0\n  4.0   5.0 6.0\n";
+    let rhs = "1.0,2.0,3.0\n4.0,5.0,6.0\n";
+    let r = compare_tables(lhs, rhs).unwrap();
+    assert!(r.passed());
+    assert_eq!(r.headers, None);
+    assert_eq!(r.column_name(0), None);
+    let r = compare_tables("1 nan inf\n", "1 NaN inf\n").unwrap();
+    assert_eq!(r.values.mismatches, 1);
+    assert_eq!(r.values.first_mismatch.unwrap().index, (0, 1));
+}
+
+#[test]
+fn test_compare_tables_errors() {
+    let e = compare_tables("1 2\n3 four\n", "1 2\n3 4\n").unwrap_err();
+    assert_eq!(
+        e,
+        TableError::InvalidNumber {
+            side: TableSide::Lhs,
+            line: 2,
+            column: 1,
+            text: "four".into(),
+        }
+    );
+    assert!(e.to_string().contains("line 2, column 1"), "{}", e);
+    assert!(matches!(
+        compare_tables("a,b\n1,2\n", "1,2\n"),
+        Err(TableError::HeaderMismatch { rhs: None, .. })
+    ));
+    assert!(matches!(
+        compare_tables("a,b\n1,2\n", "a,c\n1,2\n"),
+        Err(TableError::HeaderMismatch { .. })
+    ));
+    assert_eq!(
+        compare_tables("1\n2\n", "1\n").unwrap_err(),
+        TableError::RowCount { lhs: 2, rhs: 1 }
+    );
+    assert_eq!(
+        compare_tables("1 2\n3 4\n", "1 2\n3\n").unwrap_err(),
+        TableError::ColumnCount {
+            row: 1,
+            lhs: 2,
+            rhs: 1
+        }
+    );
+}
+
+#[test]
+fn test_compare_table_files() {
+    let dir = std::env::temp_dir().join(format!("almost-table-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (pa, pb) = (dir.join("a.csv"), dir.join("b.csv"));
+    std::fs::write(&pa, GOLDEN).unwrap();
+    std::fs::write(&pb, "t,x,y\n0,1,2\n").unwrap();
+    assert!(almost::compare_table_files(&pa, &pa).unwrap().passed());
+    let e = almost::compare_table_files(&pa, &pb).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidData);
+    assert!(e.to_string().contains("3 vs 1"), "{}", e);
+    let e = almost::compare_table_files(&pa, dir.join("missing.csv")).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::NotFound);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
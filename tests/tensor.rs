@@ -0,0 +1,61 @@
+#![cfg(feature = "candle")]
+
+use almost::{tensor_equal, tensor_equal_with, tensor_tolerance, TensorMismatch};
+use candle_core::{DType, Device, Tensor};
+
+fn tensor(values: &[f32], shape: &[usize]) -> Tensor {
+    Tensor::from_slice(values, shape, &Device::Cpu).unwrap()
+}
+
+#[test]
+fn test_tensor_equal() {
+    let a = tensor(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]);
+    assert!(tensor_equal(&a, &a).is_ok());
+    let b = tensor(&[1.0, 2.0, 3.0, 4.0, 5.5, 6.1], &[2, 3]);
+    match tensor_equal(&a, &b).unwrap_err() {
+        TensorMismatch::Values { tolerance, report } => {
+            assert_eq!(tolerance, almost::F32_TOLERANCE as f64);
+            assert_eq!(report.total, 6);
+            assert_eq!(report.mismatches, 2);
+            assert_eq!(report.first_mismatch.unwrap().index, [1, 1]);
+            assert_eq!(report.worst.unwrap().index, [1, 1]);
+        }
+        e => panic!("{:?}", e),
+    }
+    assert!(tensor_equal_with(&a, &b, 0.1).is_ok());
+    let text = tensor_equal(&a, &b).unwrap_err().to_string();
+    assert!(text.contains("2 of 6 elements differ"), "{}", text);
+}
+
+#[test]
+fn test_tensor_dtypes() {
+    let a = tensor(&[0.1, 0.2, 0.3], &[3]);
+    let half = a.affine(1.005, 0.0).unwrap().to_dtype(DType::F16).unwrap();
+    let bf16 = a.affine(1.02, 0.0).unwrap().to_dtype(DType::BF16).unwrap();
+    assert!(tensor_equal(&half, &a).is_ok());
+    assert!(tensor_equal(&a, &half).is_ok());
+    assert!(tensor_equal(&half, &half.affine(1.2, 0.0).unwrap()).is_err());
+    assert!(tensor_equal(&bf16, &a).is_ok());
+    assert!(tensor_equal(&a.to_dtype(DType::F64).unwrap(), &a).is_ok());
+    assert!(tensor_tolerance(DType::F16).unwrap() > tensor_tolerance(DType::F32).unwrap());
+
+    let ints = a.to_dtype(DType::U32).unwrap();
+    assert!(matches!(
+        tensor_equal(&ints, &a),
+        Err(TensorMismatch::UnsupportedDType(DType::U32))
+    ));
+}
+
+#[test]
+fn test_tensor_shapes() {
+    let a = tensor(&[1.0, 2.0, 3.0, 4.0], &[2, 2]);
+    let b = tensor(&[1.0, 2.0, 3.0, 4.0], &[4]);
+    match tensor_equal(&a, &b).unwrap_err() {
+        TensorMismatch::Shape { lhs, rhs } => assert_eq!((lhs, rhs), (vec![2, 2], vec![4])),
+        e => panic!("{:?}", e),
+    }
+    let empty = tensor(&[], &[0, 3]);
+    assert!(tensor_equal(&empty, &empty).is_ok());
+    let nan = tensor(&[f32::NAN], &[1]);
+    assert!(tensor_equal(&nan, &nan).is_err());
+}
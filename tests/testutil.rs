@@ -0,0 +1,171 @@
+use almost::testutil::{anchors, just_equal, just_equal_with, just_unequal, just_unequal_with};
+use almost::Float;
+
+fn check_boundaries<T: Float + core::fmt::Display>(tol: T) {
+    for &x in anchors::<T>().iter() {
+        let inside = just_equal_with(x, tol);
+        let outside = just_unequal_with(x, tol);
+        if !x.is_finite() {
+            assert!(inside.is_none() && outside.is_none(), "{}", x);
+            continue;
+        }
+        if let Some(y) = inside {
+            assert!(y > x, "{} {}", x, y);
+            assert!(almost::equal_with(x, y, tol), "{} {}", x, y);
+            assert!(almost::equal_with(y, x, tol), "{} {}", x, y);
+        }
+        match outside {
+            Some(y) => {
+                assert!(y > x, "{} {}", x, y);
+                assert!(!almost::equal_with(x, y, tol), "{} {}", x, y);
+                assert!(!almost::equal_with(y, x, tol), "{} {}", x, y);
+            }
+            // Only possible if everything up to infinity is equal.
+            None => assert!(!inside.unwrap().is_finite(), "{}", x),
+        }
+    }
+}
+
+#[test]
+fn test_boundaries() {
+    check_boundaries(almost::F64_TOLERANCE);
+    check_boundaries(1e-3f64);
+    check_boundaries(0.5f64);
+    check_boundaries(f64::EPSILON);
+    check_boundaries(almost::F32_TOLERANCE);
+    check_boundaries(1e-2f32);
+    check_boundaries(f32::EPSILON);
+}
+
+#[test]
+fn test_adjacent() {
+    for &x in anchors::<f64>().iter() {
+        if let (Some(a), Some(b)) = (just_equal(x), just_unequal(x)) {
+            if x >= 0.0 {
+                assert_eq!(a.to_bits() + 1, b.to_bits(), "{}", x);
+            }
+        }
+    }
+    let a = just_equal(1.0f64).unwrap();
+    let b = just_unequal(1.0f64).unwrap();
+    assert_eq!(a.to_bits() + 1, b.to_bits());
+    // Below zero, moving up moves towards zero.
+    let a = just_equal(-1.0f32).unwrap();
+    let b = just_unequal(-1.0f32).unwrap();
+    assert_eq!(a.to_bits(), b.to_bits() + 1);
+}
+
+#[test]
+fn test_specials() {
+    assert_eq!(just_equal(f64::NAN), None);
+    assert_eq!(just_unequal(f32::INFINITY), None);
+    // Subnormals are compared against `MIN_POSITIVE`, so they're all almost
+    // equal to zero.
+    assert!(just_equal(0.0f64).unwrap() < f64::MIN_POSITIVE);
+    assert!(almost::equal(0.0, f64::from_bits(1)));
+    assert_eq!(just_equal(-0.0f64), just_equal(0.0f64));
+}
+
+#[test]
+fn test_pair_at_ulps() {
+    use almost::testutil::pair_at_ulps;
+    assert_eq!(pair_at_ulps(1.0, 1).1, 1.0 + f64::EPSILON);
+    assert_eq!(pair_at_ulps(1.0, -2).1, 1.0 - f64::EPSILON);
+    assert_eq!(pair_at_ulps(2.0f32, -1).1, 2.0 - f32::EPSILON);
+    assert_eq!(pair_at_ulps(0.0, -1).1, -f64::from_bits(1));
+    assert_eq!(pair_at_ulps(-0.0, 0).1, 0.0);
+    assert_eq!(pair_at_ulps(f32::MAX, 1).1, f32::INFINITY);
+    assert!(pair_at_ulps(f32::INFINITY, 1).1.is_nan());
+    assert!(pair_at_ulps(f64::NAN, 0).1.is_nan());
+    assert!(pair_at_ulps(1.0f64, i64::MAX).1.is_nan());
+    assert_eq!(pair_at_ulps(f64::INFINITY, -1).1, f64::MAX);
+}
+
+#[test]
+fn test_pair_at_relative_error() {
+    use almost::testutil::pair_at_relative_error;
+    for &x in [1.0, -3.5, 1e-300, 2.0f64.powi(-1020), 1e300, f64::MAX].iter() {
+        for &rel in [1e-9, 0.01, 0.5].iter() {
+            let (a, b) = pair_at_relative_error(x, rel);
+            assert_eq!(a, x);
+            assert!(
+                b.abs() < x.abs() && b.signum() == x.signum(),
+                "{} {}",
+                x,
+                rel
+            );
+            assert!(
+                almost::equal_with(a, b, rel * (1.0 + 1e-6)),
+                "{} {} {}",
+                x,
+                rel,
+                b
+            );
+            assert!(
+                !almost::equal_with(a, b, rel * (1.0 - 1e-6)),
+                "{} {} {}",
+                x,
+                rel,
+                b
+            );
+        }
+    }
+    // Only a few ULPs, so this can't be as precise.
+    let (a, b) = pair_at_relative_error(1.0, 1e-15);
+    assert!(almost::equal_with(a, b, 1.1e-15));
+    assert!(!almost::equal_with(a, b, 0.9e-15));
+    // Exactly representable.
+    assert_eq!(pair_at_relative_error(1.0f32, 0.25), (1.0, 0.75));
+    assert_eq!(pair_at_relative_error(0.0, 0.5), (0.0, 0.0));
+    assert!(pair_at_relative_error(f64::INFINITY, 0.5).1.is_infinite());
+}
+
+#[test]
+fn test_ulp_range() {
+    use almost::testutil::ulp_range;
+    assert_eq!(ulp_range(1.0f32, 2.0).count(), (1 << 23) + 1);
+    assert_eq!(ulp_range(1.0f32, 2.0).size_hint().0, (1 << 23) + 1);
+    let v: Vec<f64> = ulp_range(1.0, 1.0).collect();
+    assert_eq!(v, [1.0]);
+    assert_eq!(ulp_range(2.0f64, 1.0).next(), None);
+    assert_eq!(ulp_range(f64::NAN, 1.0).next(), None);
+    assert_eq!(ulp_range(0.0, f64::NAN).next(), None);
+    assert_eq!(ulp_range(-0.0f64, 0.0).count(), 1);
+
+    // Across binades and zero, from both ends.
+    let below = 1.0 - f64::EPSILON / 2.0;
+    let mut r = ulp_range(below, 1.0 + f64::EPSILON);
+    assert_eq!(r.size_hint(), (3, Some(3)));
+    assert_eq!(r.next_back(), Some(1.0 + f64::EPSILON));
+    assert_eq!(r.next(), Some(below));
+    assert_eq!(r.next(), Some(1.0));
+    assert_eq!(r.next(), None);
+    assert_eq!(r.next_back(), None);
+
+    let tiny = f32::from_bits(1);
+    let v: Vec<f32> = ulp_range(-tiny * 4.0, tiny * 4.0)
+        .step_ulps(3)
+        .rev()
+        .collect();
+    assert_eq!(v, [tiny * 2.0, -tiny, -tiny * 4.0]);
+
+    // The extremes don't overflow.
+    let v: Vec<f64> = ulp_range(f64::MAX, f64::INFINITY)
+        .step_ulps(u64::MAX)
+        .collect();
+    assert_eq!(v, [f64::MAX]);
+    let v: Vec<f64> = ulp_range(f64::NEG_INFINITY, f64::INFINITY)
+        .step_ulps(u64::MAX / 2)
+        .collect();
+    assert_eq!(v.len(), 2);
+    assert_eq!(v[0], f64::NEG_INFINITY);
+    let mut inf = ulp_range(f64::MAX, f64::INFINITY);
+    assert_eq!(inf.nth(1), Some(f64::INFINITY));
+    assert_eq!(inf.next(), None);
+}
+
+#[test]
+#[should_panic]
+fn test_ulp_range_zero_step() {
+    almost::testutil::ulp_range(1.0f64, 2.0).step_ulps(0);
+}
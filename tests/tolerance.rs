@@ -0,0 +1,194 @@
+use almost::{Tolerance, ToleranceError};
+use std::convert::TryFrom;
+
+#[test]
+fn test_tolerance() {
+    assert_eq!(Tolerance::new(f64::NAN), Err(ToleranceError::NotFinite));
+    assert_eq!(
+        Tolerance::new(f32::INFINITY),
+        Err(ToleranceError::NotFinite)
+    );
+    assert_eq!(Tolerance::new(0.0f32), Err(ToleranceError::TooSmall));
+    assert_eq!(Tolerance::new(-0.1f32), Err(ToleranceError::TooSmall));
+    assert_eq!(Tolerance::new(1.0f32), Err(ToleranceError::TooLarge));
+    assert_eq!(
+        Tolerance::new(f32::EPSILON).map(Tolerance::get),
+        Ok(f32::EPSILON)
+    );
+    assert_eq!(Tolerance::<f64>::default().get(), almost::F64_TOLERANCE);
+    assert_eq!(Tolerance::<f32>::default().get(), almost::F32_TOLERANCE);
+    let t = Tolerance::try_from(0.5f64).unwrap();
+    assert_eq!(f64::from(t), 0.5);
+    assert!(t.equal(1.0, 1.9));
+    assert!(!t.equal(1.0, 2.1));
+    assert!(!t.equal(f64::NAN, f64::NAN));
+    assert_eq!(
+        ToleranceError::TooLarge.to_string(),
+        "tolerance must be less than 1.0"
+    );
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+#[test]
+fn test_from_accurate_fraction() {
+    assert_eq!(
+        Tolerance::<f64>::from_accurate_fraction(0.5).unwrap().get(),
+        f64::EPSILON.sqrt()
+    );
+    assert_eq!(
+        Tolerance::<f32>::from_accurate_fraction(1.0).unwrap().get(),
+        f32::EPSILON
+    );
+    assert!(
+        Tolerance::<f64>::from_accurate_fraction(0.01)
+            .unwrap()
+            .get()
+            < 1.0
+    );
+    assert_eq!(
+        Tolerance::<f64>::from_accurate_fraction(0.0),
+        Err(ToleranceError::TooLarge)
+    );
+    assert_eq!(
+        Tolerance::<f64>::from_accurate_fraction(-1.0),
+        Err(ToleranceError::TooLarge)
+    );
+    assert_eq!(
+        Tolerance::<f32>::from_accurate_fraction(1.5),
+        Err(ToleranceError::TooSmall)
+    );
+    assert_eq!(
+        Tolerance::<f32>::from_accurate_fraction(f32::NAN),
+        Err(ToleranceError::NotFinite)
+    );
+}
+
+#[test]
+fn test_derived_tolerances() {
+    let t = |v: f64| Tolerance::new(v).unwrap();
+    let half_eps = f64::EPSILON / 2.0;
+    let sum = Tolerance::for_sum(t(1e-3), t(1e-5), (1.0, 1.0)).unwrap();
+    assert!(almost::equal(sum.get(), (1e-3 + 1e-5) / 2.0 + half_eps));
+    // Huge and tiny scales don't overflow or underflow.
+    for &s in &[1e300, 1e-300, f64::MIN_POSITIVE / 8.0] {
+        let scaled = Tolerance::for_sum(t(1e-3), t(1e-5), (s, s)).unwrap();
+        assert!(almost::equal(scaled.get(), sum.get()));
+    }
+    assert_eq!(
+        Tolerance::for_sum(t(1e-3), t(1e-3), (2.0, -2.0)),
+        Err(ToleranceError::TooLarge)
+    );
+    assert_eq!(
+        Tolerance::for_sum(t(1e-3), t(1e-3), (0.0, 0.0)),
+        Err(ToleranceError::TooLarge)
+    );
+    assert_eq!(
+        Tolerance::for_sum(t(1e-3), t(1e-3), (1.0, 1.01)).map(|t| almost::equal(t.get(), 1e-3)),
+        Ok(true)
+    );
+    assert_eq!(
+        Tolerance::for_sum(t(1e-3), t(1e-3), (f64::INFINITY, 1.0)),
+        Err(ToleranceError::NotFinite)
+    );
+    assert_eq!(
+        Tolerance::for_sum(t(1e-3), t(1e-3), (f64::NAN, 1.0)),
+        Err(ToleranceError::NotFinite)
+    );
+
+    let eps = Tolerance::new(f32::EPSILON).unwrap();
+    let product = Tolerance::for_product(eps, eps).unwrap();
+    assert!(product.get() > 2.0 * f32::EPSILON);
+    assert_eq!(
+        Tolerance::for_product(t(0.6), t(0.6)),
+        Err(ToleranceError::TooLarge)
+    );
+    assert!(almost::equal(
+        Tolerance::for_quotient(t(0.25), t(0.2)).unwrap().get(),
+        0.45 / 0.8 + half_eps
+    ));
+    assert_eq!(
+        Tolerance::for_quotient(t(0.1), t(0.5)),
+        Err(ToleranceError::TooLarge)
+    );
+}
+
+#[test]
+fn test_tolerance_error() {
+    fn parse(s: &str) -> Result<Tolerance<f64>, Box<dyn core::error::Error>> {
+        Ok(Tolerance::new(s.parse()?)?)
+    }
+    assert!(parse("1e-6").is_ok());
+    let e = parse("2").unwrap_err();
+    assert_eq!(e.to_string(), "tolerance must be less than 1.0");
+    assert!(parse("x").is_err());
+}
+
+#[test]
+fn test_parse_tolerance() {
+    use almost::ParseToleranceError;
+    let parse = |s: &str| s.parse::<Tolerance<f64>>().map(Tolerance::get);
+    assert_eq!(parse("1e-9"), Ok(1e-9));
+    assert_eq!(parse(" 0.25 "), Ok(0.25));
+    assert_eq!(parse("6digits"), Ok(1e-6));
+    assert_eq!(parse("1 digit"), Ok(0.1));
+    assert_eq!(parse("12digits"), Ok(1e-12));
+    assert_eq!(parse("4ulps"), Ok(4.0 * f64::EPSILON));
+    assert_eq!(parse("1ulp"), Ok(f64::EPSILON));
+    assert_eq!(parse("0.1%"), Ok(1e-3));
+    assert_eq!(parse("3 %"), Ok(0.03));
+    assert_eq!(parse("1e-4%"), Ok(1e-6));
+    assert_eq!(
+        "2.5%".parse::<Tolerance<f32>>().map(Tolerance::get),
+        Ok(0.025)
+    );
+
+    let invalid = |e| Err(ParseToleranceError::Invalid(e));
+    assert_eq!(parse("0digits"), invalid(ToleranceError::TooLarge));
+    assert_eq!(parse("20digits"), invalid(ToleranceError::TooSmall));
+    assert_eq!(parse("0ulps"), invalid(ToleranceError::TooSmall));
+    assert_eq!(parse("100%"), invalid(ToleranceError::TooLarge));
+    assert_eq!(parse("-1e-6"), invalid(ToleranceError::TooSmall));
+    assert_eq!(parse("nan"), invalid(ToleranceError::NotFinite));
+    for s in &[
+        "",
+        "%",
+        "digits",
+        "1.5digits",
+        "-4ulps",
+        "1e-6 ulps",
+        "1x",
+        "1e%",
+    ] {
+        assert_eq!(parse(s), Err(ParseToleranceError::InvalidSyntax), "{:?}", s);
+    }
+    assert!(parse("2")
+        .unwrap_err()
+        .to_string()
+        .contains("less than 1.0"));
+}
+
+#[test]
+fn test_display_tolerance() {
+    let t = Tolerance::new(1e-6f64).unwrap();
+    assert_eq!(t.to_string(), "1e-6");
+    assert_eq!(
+        format!("{:.2}", Tolerance::new(0.123f32).unwrap()),
+        "1.23e-1"
+    );
+    for t in &[
+        Tolerance::<f64>::default(),
+        Tolerance::new(f64::EPSILON).unwrap(),
+    ] {
+        assert_eq!(t.to_string().parse::<Tolerance<f64>>(), Ok(*t));
+    }
+}
+
+#[test]
+fn test_runtime_tolerances() {
+    use almost::AlmostEqual;
+    fn derived<T: AlmostEqual<Float = T> + Copy + std::ops::Mul<Output = T>>(v: T) -> T {
+        v.machine_epsilon() * v.default_tolerance()
+    }
+    assert_eq!(derived(2.0f64), f64::EPSILON * almost::F64_TOLERANCE);
+    assert_eq!(derived(2.0f32), f32::EPSILON * almost::F32_TOLERANCE);
+}
@@ -0,0 +1,52 @@
+use almost::testutil::{anchors, pair_at_ulps};
+use almost::{ulp_distance, ulp_equal, Float};
+
+#[test]
+fn test_ulp_distance_f64() {
+    for &x in anchors::<f64>().iter() {
+        if x.is_nan() {
+            assert_eq!(ulp_distance(x, x), None);
+            continue;
+        }
+        assert_eq!(ulp_distance(x, x), Some(0));
+        for n in [-3, -1, 1, 2, 1000] {
+            let (a, b) = pair_at_ulps(x, n);
+            if b.is_nan() {
+                continue;
+            }
+            assert_eq!(ulp_distance(a, b), Some(n.unsigned_abs()), "{} {}", a, b);
+            assert_eq!(ulp_distance(b, a), Some(n.unsigned_abs()), "{} {}", a, b);
+            assert!(ulp_equal(a, b, n.unsigned_abs()));
+            assert!(!ulp_equal(a, b, n.unsigned_abs() - 1));
+        }
+    }
+    assert_eq!(ulp_distance(0.0, -0.0), Some(0));
+    assert_eq!(ulp_distance(-f64::from_bits(3), f64::from_bits(4)), Some(7));
+    assert_eq!(ulp_distance(f64::MAX, f64::INFINITY), Some(1));
+    assert_eq!(ulp_distance(f64::INFINITY, f64::INFINITY), Some(0));
+    assert_eq!(
+        ulp_distance(f64::NEG_INFINITY, f64::INFINITY),
+        Some(2 * f64::INFINITY.to_bits())
+    );
+    assert_eq!(ulp_distance(f64::NAN, f64::NAN), None);
+    assert!(!ulp_equal(f64::NAN, f64::NAN, u64::MAX));
+    assert!(ulp_equal(f64::NEG_INFINITY, f64::INFINITY, u64::MAX));
+}
+
+#[test]
+fn test_ulp_distance_f32() {
+    for &x in anchors::<f32>().iter() {
+        assert!(x.ulp_equal(x, 0) || x.is_nan());
+        let (a, b) = pair_at_ulps(x, 5);
+        if !b.is_nan() {
+            assert_eq!(a.ulp_distance(b), Some(5));
+        }
+    }
+    assert_eq!(ulp_distance(1.0f32, 2.0), Some(1 << 23));
+    assert_eq!(
+        ulp_distance(-1.0f32, 1.0),
+        Some(2 * 1.0f32.to_bits() as u64)
+    );
+    assert_eq!(ulp_distance(f32::NEG_INFINITY, f32::MIN), Some(1));
+    assert!(!ulp_equal(1.0f32, f32::NAN, 10));
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "ultraviolet")]
+
+use ultraviolet::{
+    f32x4, DMat3, DRotor3, DVec3, DVec3x2, Mat4, Mat4x4, Rotor3, Rotor3x4, Vec3, Vec3x4, Vec3x8,
+};
+
+#[test]
+fn test_ultraviolet_scalar() {
+    let a = DVec3::new(1.0, 0.0, -2.0);
+    assert!(almost::equal(a, DVec3::new(1.0 + 1e-12, 1e-17, -2.0)));
+    assert!(!almost::equal(a, DVec3::new(1.0, 1e-3, -2.0)));
+    assert!(almost::equal(
+        Vec3::new(0.1 + 0.2, 3.0, 0.0),
+        Vec3::new(0.3, 3.0, 0.0)
+    ));
+    assert!(!almost::equal(
+        DVec3::new(f64::NAN, 0.0, 0.0),
+        DVec3::new(f64::NAN, 0.0, 0.0)
+    ));
+    assert!(almost::zero(DVec3::new(1e-17, 0.0, -1e-20)));
+
+    let m = Mat4::from_rotation_z(0.1);
+    let composed = Mat4::from_rotation_z(0.03) * Mat4::from_rotation_z(0.07);
+    assert!(almost::equal(m, composed));
+    assert!(!almost::equal(m, Mat4::from_rotation_z(0.1 + 1e-3)));
+    assert!(almost::equal(DMat3::identity(), DMat3::identity()));
+
+    let r = DRotor3::from_rotation_xy(0.1);
+    let s = DRotor3::from_rotation_xy(0.03) * DRotor3::from_rotation_xy(0.07);
+    assert!(almost::equal(r, s));
+    assert!(!almost::equal(r, DRotor3::from_rotation_xy(0.1 + 1e-6)));
+    assert!(almost::zero(Rotor3::new(0.0, ultraviolet::Bivec3::zero())));
+}
+
+#[test]
+fn test_ultraviolet_wide_lanes() {
+    let a = Vec3x4::from([Vec3::new(1.0, 2.0, 3.0); 4]);
+    let b = Vec3x4::from([
+        Vec3::new(1.0, 2.0, 3.0),
+        Vec3::new(1.0, 2.0, 3.1),
+        Vec3::new(f32::NAN, 2.0, 3.0),
+        Vec3::new(1.0, 2.000001, 3.0),
+    ]);
+    let lanes = almost::ultraviolet_equal_lanes(a, b, almost::F32_TOLERANCE);
+    assert_eq!(lanes.move_mask(), 0b1001);
+    assert!(!almost::equal(a, b));
+    assert!(almost::equal(a, a));
+
+    let small = Vec3::new(1e-9, 0.0, 0.0);
+    let v = Vec3x8::from([small, small, Vec3::one(), small, small, small, small, small]);
+    let zero = almost::ultraviolet_zero_lanes(v, almost::F32_TOLERANCE);
+    assert_eq!(zero.move_mask(), 0b1111_1011);
+    assert!(!almost::zero(v));
+
+    let d = DVec3x2::from([DVec3::new(1.0, 0.0, 0.0), DVec3::new(0.0, 1.0, 0.0)]);
+    let e = DVec3x2::from([DVec3::new(1.0, 1e-17, 0.0), DVec3::new(1e-3, 1.0, 0.0)]);
+    assert_eq!(
+        almost::ultraviolet_equal_lanes(d, e, 1e-9).move_mask(),
+        0b01
+    );
+    assert_eq!(
+        almost::ultraviolet_equal_lanes(d, e, 1e-2).move_mask(),
+        0b11
+    );
+
+    let angle = |a: f32| f32x4::splat(a);
+    let m = Mat4x4::from_rotation_z(angle(0.1));
+    let n = Mat4x4::from_rotation_z(angle(0.03)) * Mat4x4::from_rotation_z(angle(0.07));
+    assert!(almost::equal(m, n));
+    let r = Rotor3x4::from_rotation_xy(angle(0.1));
+    let s = Rotor3x4::from_rotation_xy(f32x4::from([0.1, 0.2, 0.1, 0.1]));
+    assert_eq!(
+        almost::ultraviolet_equal_lanes(r, s, 1e-6).move_mask(),
+        0b1101
+    );
+}
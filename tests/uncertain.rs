@@ -0,0 +1,108 @@
+use almost::Uncertain;
+
+#[test]
+fn test_uncertain_display() {
+    let cases: &[(f64, f64, &str)] = &[
+        (1.23456, 0.00123, "1.2346(12)"),
+        (1.23456, 0.0996, "1.23(10)"),
+        (-0.000123456, 0.000000071, "-0.000123456(71)"),
+        (6.02214076e23, 1.2e15, "6.022140760(12)e23"),
+        (1234.4, 56.0, "1234(56)"),
+        (12344.0, 567.0, "1.234(57)e4"),
+        (5.0, 1234.0, "0.0(12)e3"),
+        (0.0, 0.5, "0.00(50)"),
+        (1.6e-19, 3.2e-25, "1.6000000(32)e-19"),
+        (9.7, 1.0, "9.7(10)"),
+        (1.0, f64::INFINITY, "1 ± inf"),
+        (f64::NAN, 0.1, "NaN ± 0.1"),
+        (2.5, 0.0, "2.5 ± 0"),
+    ];
+    for &(v, u, expected) in cases {
+        assert_eq!(Uncertain::new(v, u).to_string(), expected, "{} {}", v, u);
+    }
+    assert_eq!(Uncertain::new(1.0, -0.5).uncertainty, 0.5);
+    assert_eq!(Uncertain::new(3.24159f32, 0.0021).to_string(), "3.2416(21)");
+    assert_eq!(
+        format!("{:.3}", Uncertain::new(3.24159, 0.002134)),
+        "3.24159(213)"
+    );
+    assert_eq!(
+        format!("{:#}", Uncertain::new(3.24159, 0.002134)),
+        "3.2416 ± 0.0021"
+    );
+    assert_eq!(format!("{:#}", Uncertain::new(1234.4, 56.0)), "1234 ± 56");
+    assert_eq!(
+        format!("{:#}", Uncertain::new(12344.0, 567.0)),
+        "(1.234 ± 0.057)e4"
+    );
+    assert_eq!(Uncertain::relative(-2.0, 0.01).to_string(), "-2.000(20)");
+}
+
+#[test]
+fn test_uncertain_parse() {
+    use almost::ParseUncertainError;
+    let parse = |s: &str| s.parse::<Uncertain<f64>>();
+    let cases: &[(&str, f64, f64)] = &[
+        ("1.234(5)", 1.234, 0.005),
+        ("  1.234 ± 0.005 ", 1.234, 0.005),
+        ("1.234+-0.005", 1.234, 0.005),
+        ("-0.000123456(71)", -0.000123456, 7.1e-8),
+        ("1234(56)", 1234.0, 56.0),
+        ("9.7(1.0)", 9.7, 1.0),
+        ("6.02214076(12)e23", 6.02214076e23, 1.2e16),
+        ("1.054571817(13)E-34", 1.054571817e-34, 1.3e-42),
+        ("1e3 ± 2e1", 1000.0, 20.0),
+        ("nan ± 1", f64::NAN, 1.0),
+    ];
+    for &(s, value, unc) in cases {
+        let u = parse(s).unwrap();
+        assert!(
+            (u.value == value || value.is_nan() && u.value.is_nan()) && u.uncertainty == unc,
+            "{}: {:?}",
+            s,
+            u
+        );
+    }
+    let u: Uncertain<f32> = "0.1(2)".parse().unwrap();
+    assert_eq!((u.value, u.uncertainty), (0.1, 0.2));
+
+    assert_eq!(parse("1.234"), Err(ParseUncertainError::MissingUncertainty));
+    assert_eq!(parse("x ± 1"), Err(ParseUncertainError::InvalidValue));
+    assert_eq!(
+        parse("1 ± -1"),
+        Err(ParseUncertainError::InvalidUncertainty)
+    );
+    assert_eq!(
+        parse("1 ± nan"),
+        Err(ParseUncertainError::InvalidUncertainty)
+    );
+    assert_eq!(
+        parse("1.2(x)"),
+        Err(ParseUncertainError::InvalidUncertainty)
+    );
+    assert_eq!(parse("1.2(3"), Err(ParseUncertainError::InvalidUncertainty));
+    assert_eq!(parse("1.2()"), Err(ParseUncertainError::InvalidUncertainty));
+    assert_eq!(parse("1.2(3)x"), Err(ParseUncertainError::InvalidValue));
+
+    // What `Display` writes can be parsed back.
+    for &(v, u) in &[(1.23456, 0.00123), (6.02214076e23, 1.2e15), (-5.0, 1234.0)] {
+        let written = Uncertain::new(v, u).to_string();
+        let parsed = parse(&written).unwrap();
+        assert!(parsed.contains(v), "{}", written);
+        assert!(
+            almost::equal_with(parsed.uncertainty, u, 0.1),
+            "{}",
+            written
+        );
+    }
+}
+
+#[test]
+fn test_uncertain_contains() {
+    let u = Uncertain::new(2.0, 0.5);
+    assert!(u.contains(2.5));
+    assert!(u.contains(1.5));
+    assert!(!u.contains(2.51));
+    assert!(!u.contains(f64::NAN));
+    assert!(Uncertain::new(1.0, f64::INFINITY).contains(-1e300));
+}
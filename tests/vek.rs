@@ -0,0 +1,42 @@
+#![cfg(feature = "vek")]
+
+use vek::{Mat4, Quaternion, Vec2, Vec3, Vec4};
+
+#[test]
+fn test_vek_vectors() {
+    let a = Vec3::new(1.0, 0.0, -2.0);
+    assert!(almost::equal(a, Vec3::new(1.0 + 1e-12, 1e-17, -2.0)));
+    assert!(!almost::equal(a, Vec3::new(1.0, 1e-3, -2.0)));
+    assert!(almost::equal_with(a, Vec3::new(1.0, 1e-3, -2.0), 1e-2));
+    assert!(almost::equal(
+        Vec2::new(0.1f32 + 0.2, 3.0),
+        Vec2::new(0.3, 3.0)
+    ));
+    assert!(!almost::equal(
+        Vec4::new(1.0, 2.0, 3.0, 4.0),
+        Vec4::new(1.0, 2.0, 3.0, -4.0)
+    ));
+    assert!(!almost::equal(
+        Vec3::new(f64::NAN, 0.0, 0.0),
+        Vec3::new(f64::NAN, 0.0, 0.0)
+    ));
+    assert!(almost::zero(Vec3::new(1e-17, 0.0, -1e-20)));
+    assert!(!almost::zero(Vec3::new(1e-17, 0.1, 0.0)));
+}
+
+#[test]
+fn test_vek_mat4_quaternion() {
+    let angle = 0.1f64;
+    let rotation = Mat4::rotation_z(angle);
+    let composed = Mat4::rotation_z(0.03) * Mat4::rotation_z(0.07);
+    assert!(almost::equal(rotation, composed));
+    assert!(!almost::equal(rotation, Mat4::rotation_z(angle + 1e-6)));
+    assert!(almost::equal(Mat4::<f64>::identity(), Mat4::identity()));
+    assert!(almost::zero(rotation - composed));
+
+    let q = Quaternion::rotation_z(angle);
+    let r = Quaternion::rotation_z(0.03) * Quaternion::rotation_z(0.07);
+    assert!(almost::equal(q, r));
+    assert!(!almost::equal(q, -q));
+    assert!(!almost::zero(q));
+}
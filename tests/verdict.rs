@@ -0,0 +1,15 @@
+use almost::stable_verdict;
+
+#[test]
+fn test_stable_verdict() {
+    assert!(stable_verdict(1.0, 1.0, 1e-3).stable);
+    assert!(stable_verdict(1.0, 2.0, 1e-3).stable);
+    let v = stable_verdict(f64::NAN, f64::NAN, 1e-3);
+    assert!(!v.equal && v.stable);
+    let v = stable_verdict(f64::INFINITY, f64::INFINITY, 1e-3);
+    assert!(v.equal && v.stable);
+    // Just inside the boundary.
+    let b = 1.0 - 0.25 + f32::EPSILON;
+    let v = stable_verdict(1.0f32, b, 0.25);
+    assert!(v.equal && !v.stable);
+}
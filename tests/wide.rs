@@ -0,0 +1,53 @@
+#![cfg(feature = "simba")]
+
+use almost::{simba_equal_lanes, simba_zero_lanes, AlmostEqual};
+use simba::simd::{SimdBool, SimdValue, WideF32x4, WideF32x8, WideF64x4};
+
+#[test]
+fn test_simba_equal_lanes() {
+    let a = WideF64x4::from([1.0, 0.0, -3.0, 1e300]);
+    let b = WideF64x4::from([1.0 + 1e-12, 0.0, -3.0001, 1e300]);
+    assert_eq!(simba_equal_lanes(a, b, 1e-10).bitmask(), 0b1011);
+    assert!(!almost::equal(a, b));
+    assert!(almost::equal_with(a, b, 1e-4));
+    assert!(almost::equal(a, a));
+
+    let inf = f32::INFINITY;
+    let a = WideF32x8::from([inf, -inf, f32::NAN, 1.0, inf, 0.0, 2.0, f32::MAX]);
+    let b = WideF32x8::from([inf, inf, f32::NAN, 1.0, 1.0, -0.0, 2.0, f32::MAX]);
+    assert_eq!(
+        simba_equal_lanes(a, b, almost::F32_TOLERANCE).bitmask(),
+        0b1110_1001
+    );
+}
+
+#[test]
+fn test_simba_small_scale() {
+    // Tiny values are rescaled, so they agree with the scalar comparison.
+    let a = [f64::MIN_POSITIVE, 1e-300, 0.0, 5e-324];
+    let b = [
+        2.225071633433343e-308,
+        1e-300 * (1.0 + 1e-7),
+        1e-310,
+        1e-323,
+    ];
+    let lanes = simba_equal_lanes(WideF64x4::from(a), WideF64x4::from(b), 1e-6);
+    for i in 0..4 {
+        assert_eq!(
+            lanes.extract(i),
+            almost::equal_with(a[i], b[i], 1e-6),
+            "lane {}",
+            i
+        );
+    }
+    assert_eq!(lanes.bitmask(), 0b1011);
+}
+
+#[test]
+fn test_simba_zero_lanes() {
+    let v = WideF32x4::from([0.0, 1e-7, -1e-7, 0.1]);
+    assert_eq!(simba_zero_lanes(v, 1e-6).bitmask(), 0b0111);
+    assert!(!v.almost_zero());
+    assert!(WideF32x4::from([0.0, 1e-7, -1e-7, 0.0]).almost_zero());
+    assert!(!WideF32x4::from([f32::NAN; 4]).almost_zero());
+}
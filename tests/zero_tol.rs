@@ -0,0 +1,19 @@
+#[test]
+fn test_equal_or_both_tiny() {
+    assert!(almost::equal_or_both_tiny(0.0, 0.0, 1e-10));
+    assert!(almost::equal_or_both_tiny(1e-11, -1e-11, 1e-10));
+    assert!(!almost::equal_or_both_tiny(1e-11, 1e-9, 1e-10));
+    assert!(almost::equal_or_both_tiny(1e-9, 1e-9 + 1e-20, 1e-10));
+    assert!(almost::equal_or_both_tiny(1.0, 1.0 + 1e-12, 1e-10));
+    assert!(!almost::equal_or_both_tiny(1.0, 1.1, 1e-10));
+    assert!(!almost::equal_or_both_tiny(f64::NAN, f64::NAN, 1e-10));
+}
+
+#[test]
+fn test_equal_with_zero_tol() {
+    assert!(almost::equal_with_zero_tol(0.0f32, -0.0, 1e-3, 1e-6));
+    assert!(!almost::equal_with_zero_tol(0.0f32, 1e-6, 1e-3, 1e-6));
+    assert!(almost::equal_with_zero_tol(1e-3f32, 1.0005e-3, 1e-3, 1e-6));
+    assert!(!almost::equal_with_zero_tol(1e-3f32, 1.01e-3, 1e-3, 1e-6));
+    assert!(!almost::equal_with_zero_tol(f32::NAN, 0.0, 1e-3, 1e-6));
+}